@@ -26,6 +26,7 @@ use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiSe
 use sc_rpc::{
 	dev::{Dev, DevApiServer},
 	statement::{StatementApiServer, StatementStore},
+	SubscriptionTaskExecutor,
 };
 use sp_runtime::traits::Block as BlockT;
 use std::{marker::PhantomData, sync::Arc};
@@ -41,6 +42,7 @@ pub(crate) trait BuildRpcExtensions<Client, Backend, Pool, StatementStore> {
 		backend: Arc<Backend>,
 		pool: Arc<Pool>,
 		statement_store: Option<Arc<StatementStore>>,
+		executor: SubscriptionTaskExecutor,
 	) -> sc_service::error::Result<RpcExtension>;
 }
 
@@ -66,6 +68,7 @@ where
 			sc_transaction_pool::TransactionPoolHandle<Block, ParachainClient<Block, RuntimeApi>>,
 		>,
 		statement_store: Option<Arc<sc_statement_store::Store>>,
+		executor: SubscriptionTaskExecutor,
 	) -> sc_service::error::Result<RpcExtension> {
 		let build = || -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>> {
 			let mut module = RpcExtension::new(());
@@ -74,7 +77,7 @@ where
 			module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
 			module.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
 			if let Some(statement_store) = statement_store {
-				module.merge(StatementStore::new(statement_store).into_rpc())?;
+				module.merge(StatementStore::new(statement_store, executor).into_rpc())?;
 			}
 			module.merge(Dev::new(client).into_rpc())?;
 
@@ -439,12 +439,13 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 				let backend_for_rpc = backend.clone();
 				let statement_store = statement_store.clone();
 
-				Box::new(move |_| {
+				Box::new(move |executor| {
 					Self::BuildRpcExtensions::build_rpc_extensions(
 						client.clone(),
 						backend_for_rpc.clone(),
 						transaction_pool.clone(),
 						statement_store.clone(),
+						executor,
 					)
 				})
 			};
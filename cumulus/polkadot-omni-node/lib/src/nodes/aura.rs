@@ -356,12 +356,13 @@ where
 			let transaction_pool = transaction_pool.clone();
 			let backend_for_rpc = backend.clone();
 
-			Box::new(move |_| {
+			Box::new(move |executor| {
 				let module = Self::BuildRpcExtensions::build_rpc_extensions(
 					client.clone(),
 					backend_for_rpc.clone(),
 					transaction_pool.clone(),
 					None,
+					executor,
 				)?;
 				Ok(module)
 			})
@@ -108,4 +108,19 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Convert the RocksDB database to ParityDB. ParityDB databases can't be converted to RocksDB.
+	ConvertDb(sc_cli::ConvertDbCmd),
+
+	/// Prune the state of an archive database.
+	PruneDb(sc_cli::PruneDbCmd),
+
+	/// Check the integrity of the database.
+	CheckDb(sc_cli::CheckDbCmd),
+
+	/// Export a snapshot of the state of a finalized block.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
+	/// Start the database from a snapshot of the state of a finalized block.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
 }
@@ -0,0 +1,323 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Extrinsic and event indexer of the kitchensink runtime.
+//!
+//! Populates the index queried by the `archive_v1_itemsByAccount` and `archive_v1_eventsByName`
+//! RPC methods.
+
+use polkadot_sdk::*;
+
+use codec::{Compact, Decode, Encode};
+use kitchensink_runtime::{RuntimeEvent, UncheckedExtrinsic};
+use node_primitives::{Block, Hash};
+use sc_client_api::{BlockIndexer, IndexEntry, IndexKey, IndexedItem, StorageCollection};
+use scale_info::{MetaType, PortableRegistry, Registry, TypeDef, TypeDefPrimitive};
+use sp_runtime::{generic::Preamble, traits::Block as BlockT, MultiAddress};
+use std::collections::{HashMap, HashSet};
+
+type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
+
+/// Indexes the extrinsics and events of kitchensink blocks.
+///
+/// Signed extrinsics are indexed under their signer. Events are indexed under their pallet and
+/// event name, every account they contain and the signer of the extrinsic emitting them.
+///
+/// Extrinsics and events are decoded with the types of the kitchensink runtime this node is built
+/// with. Blocks with extrinsics or events which fail to decode, such as the ones of another
+/// runtime version, are reported as not completely indexed.
+pub struct Indexer {
+	/// Type information of [`RuntimeEvent`].
+	registry: PortableRegistry,
+	/// Id of [`RuntimeEvent`] in the registry.
+	event_type: u32,
+	/// Ids of the account id types in the registry.
+	account_types: HashSet<u32>,
+	/// Pallet and event names by pallet and event index.
+	event_names: HashMap<(u8, u8), (String, String)>,
+	/// Storage key of the events of a block.
+	events_key: Vec<u8>,
+}
+
+impl Indexer {
+	/// Create a new indexer.
+	pub fn new() -> Self {
+		let mut registry = Registry::new();
+		let event_type = registry.register_type(&MetaType::new::<RuntimeEvent>()).id;
+		let registry = PortableRegistry::from(registry);
+
+		let account_types = registry
+			.types
+			.iter()
+			.filter(|ty| ty.ty.path.segments.last().map_or(false, |name| name == "AccountId32"))
+			.map(|ty| ty.id)
+			.collect();
+
+		let mut event_names = HashMap::new();
+		if let Some(TypeDef::Variant(pallets)) = registry.resolve(event_type).map(|ty| &ty.type_def)
+		{
+			for pallet in &pallets.variants {
+				let Some(field) = pallet.fields.first() else { continue };
+				let Some(TypeDef::Variant(events)) =
+					registry.resolve(field.ty.id).map(|ty| &ty.type_def)
+				else {
+					continue
+				};
+				for event in &events.variants {
+					event_names.insert(
+						(pallet.index, event.index),
+						(pallet.name.clone(), event.name.clone()),
+					);
+				}
+			}
+		}
+
+		let events_key = [sp_core::twox_128(b"System"), sp_core::twox_128(b"Events")].concat();
+
+		Self { registry, event_type, account_types, event_names, events_key }
+	}
+
+	/// Collect the accounts contained in an encoded value of type `ty`.
+	fn collect_accounts(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<Vec<u8>>,
+	) -> Result<(), codec::Error> {
+		if self.account_types.contains(&ty) {
+			accounts.push(<[u8; 32]>::decode(input)?.to_vec());
+			return Ok(())
+		}
+
+		match &self.registry.resolve(ty).ok_or("Unknown type")?.type_def {
+			TypeDef::Composite(composite) =>
+				for field in &composite.fields {
+					self.collect_accounts(field.ty.id, input, accounts)?;
+				},
+			TypeDef::Variant(variant) => {
+				let index = u8::decode(input)?;
+				let variant = variant
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Unknown variant")?;
+				for field in &variant.fields {
+					self.collect_accounts(field.ty.id, input, accounts)?;
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.collect_accounts_repeated(sequence.type_param.id, len, input, accounts)?;
+			},
+			TypeDef::Array(array) =>
+				self.collect_accounts_repeated(array.type_param.id, array.len, input, accounts)?,
+			TypeDef::Tuple(tuple) =>
+				for field in &tuple.fields {
+					self.collect_accounts(field.id, input, accounts)?;
+				},
+			TypeDef::Primitive(primitive) => {
+				let len = match primitive {
+					TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+					TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+					TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+					TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+					TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+					TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+					TypeDefPrimitive::Str => Compact::<u32>::decode(input)?.0,
+				};
+				skip(input, len)?;
+			},
+			TypeDef::Compact(_) => {
+				Compact::<u128>::decode(input)?;
+			},
+			TypeDef::BitSequence(_) => return Err("Bit sequences are not supported".into()),
+		}
+		Ok(())
+	}
+
+	/// Collect the accounts contained in `len` encoded values of type `ty`.
+	fn collect_accounts_repeated(
+		&self,
+		ty: u32,
+		len: u32,
+		input: &mut &[u8],
+		accounts: &mut Vec<Vec<u8>>,
+	) -> Result<(), codec::Error> {
+		let ty_def = self.registry.resolve(ty).map(|ty| &ty.type_def);
+		if let Some(TypeDef::Primitive(TypeDefPrimitive::U8)) = ty_def {
+			return skip(input, len)
+		}
+
+		for _ in 0..len {
+			self.collect_accounts(ty, input, accounts)?;
+		}
+		Ok(())
+	}
+}
+
+fn skip(input: &mut &[u8], len: u32) -> Result<(), codec::Error> {
+	let len = len as usize;
+	if input.len() < len {
+		return Err("Not enough data to skip".into())
+	}
+	*input = &input[len..];
+	Ok(())
+}
+
+impl Default for Indexer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl BlockIndexer<Block> for Indexer {
+	fn index_block(
+		&self,
+		_header: &<Block as BlockT>::Header,
+		body: &[<Block as BlockT>::Extrinsic],
+		storage_changes: Option<&StorageCollection>,
+	) -> (Vec<IndexEntry>, bool) {
+		let mut entries = Vec::new();
+		let mut complete = true;
+
+		let mut signers = HashMap::new();
+		for (index, extrinsic) in body.iter().enumerate() {
+			let Ok(extrinsic) = UncheckedExtrinsic::decode(&mut &extrinsic.encode()[..]) else {
+				complete = false;
+				continue
+			};
+			if let Preamble::Signed(MultiAddress::Id(signer), ..) = extrinsic.0.preamble {
+				let signer = signer.encode();
+				entries.push((
+					IndexKey::Account(signer.clone()),
+					IndexedItem::Extrinsic(index as u32),
+				));
+				signers.insert(index as u32, signer);
+			}
+		}
+
+		let events = storage_changes
+			.into_iter()
+			.flatten()
+			.find(|(key, _)| *key == self.events_key)
+			.and_then(|(_, value)| value.as_ref())
+			.map_or(Ok(Vec::new()), |value| Vec::<EventRecord>::decode(&mut &value[..]));
+		let Ok(events) = events else { return (entries, false) };
+
+		for (index, record) in events.into_iter().enumerate() {
+			let item = IndexedItem::Event(index as u32);
+			let event = record.event.encode();
+			let names = match event[..] {
+				[pallet_index, event_index, ..] =>
+					self.event_names.get(&(pallet_index, event_index)),
+				_ => None,
+			};
+			if let Some((pallet, name)) = names {
+				let key = IndexKey::Event { pallet: pallet.clone(), name: name.clone() };
+				entries.push((key, item));
+			}
+
+			// Events which can't be walked are still indexed under the accounts found so far.
+			let mut accounts = Vec::new();
+			let _ = self.collect_accounts(self.event_type, &mut &event[..], &mut accounts);
+			if let frame_system::Phase::ApplyExtrinsic(extrinsic) = record.phase {
+				accounts.extend(signers.get(&extrinsic).cloned());
+			}
+			accounts.sort();
+			accounts.dedup();
+			entries.extend(accounts.into_iter().map(|account| (IndexKey::Account(account), item)));
+		}
+
+		(entries, complete)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use kitchensink_runtime::Runtime;
+	use node_primitives::Header;
+	use sp_keyring::Sr25519Keyring;
+	use sp_runtime::traits::Header as _;
+	use IndexedItem::Event;
+
+	fn header() -> Header {
+		Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		)
+	}
+
+	#[test]
+	fn events_are_indexed_by_name_and_accounts() {
+		let indexer = Indexer::new();
+		let alice = Sr25519Keyring::Alice.to_account_id();
+		let bob = Sr25519Keyring::Bob.to_account_id();
+
+		let events = vec![
+			EventRecord {
+				phase: frame_system::Phase::Initialization,
+				event: RuntimeEvent::Balances(pallet_balances::Event::<Runtime>::Transfer {
+					from: alice.clone(),
+					to: bob.clone(),
+					amount: 42,
+				}),
+				topics: vec![],
+			},
+			EventRecord {
+				phase: frame_system::Phase::Finalization,
+				event: RuntimeEvent::System(frame_system::Event::<Runtime>::Remarked {
+					sender: bob.clone(),
+					hash: Default::default(),
+				}),
+				topics: vec![],
+			},
+		];
+		let storage_changes = vec![(indexer.events_key.clone(), Some(events.encode()))];
+
+		let (mut entries, complete) = indexer.index_block(&header(), &[], Some(&storage_changes));
+		assert!(complete);
+		entries.sort();
+		let mut expected = vec![
+			(IndexKey::Event { pallet: "Balances".into(), name: "Transfer".into() }, Event(0)),
+			(IndexKey::Account(alice.encode()), Event(0)),
+			(IndexKey::Account(bob.encode()), Event(0)),
+			(IndexKey::Event { pallet: "System".into(), name: "Remarked".into() }, Event(1)),
+			(IndexKey::Account(bob.encode()), Event(1)),
+		];
+		expected.sort();
+		assert_eq!(entries, expected);
+	}
+
+	#[test]
+	fn blocks_with_undecodable_events_are_incomplete() {
+		let indexer = Indexer::new();
+		let storage_changes = vec![(indexer.events_key.clone(), Some(vec![4, 0xff]))];
+		let (entries, complete) = indexer.index_block(&header(), &[], Some(&storage_changes));
+		assert!(entries.is_empty());
+		assert!(!complete);
+
+		// Blocks without events are complete.
+		let (entries, complete) = indexer.index_block(&header(), &[], Some(&Vec::new()));
+		assert!(entries.is_empty());
+		assert!(complete);
+	}
+}
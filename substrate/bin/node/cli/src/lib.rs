@@ -37,6 +37,7 @@ pub mod chain_spec;
 mod cli;
 #[cfg(feature = "cli")]
 mod command;
+pub mod indexer;
 pub mod service;
 
 #[cfg(feature = "cli")]
@@ -153,6 +153,7 @@ where
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use sc_rpc::{
 		dev::{Dev, DevApiServer},
+		mempool::{Mempool, MempoolApiServer},
 		mixnet::MixnetApiServer,
 		statement::StatementApiServer,
 	};
@@ -171,6 +172,7 @@ where
 		finality_provider,
 	} = grandpa;
 
+	io.merge(Mempool::<_, AccountId, Nonce>::new(pool.clone()).into_rpc())?;
 	io.merge(System::new(client.clone(), pool).into_rpc())?;
 	// Making synchronous calls in light client freezes the browser currently,
 	// more context: https://github.com/paritytech/substrate/pull/3480
@@ -190,7 +192,7 @@ where
 	)?;
 	io.merge(
 		Grandpa::new(
-			subscription_executor,
+			subscription_executor.clone(),
 			shared_authority_set.clone(),
 			shared_voter_state,
 			justification_stream,
@@ -206,7 +208,8 @@ where
 
 	io.merge(StateMigration::new(client.clone(), backend).into_rpc())?;
 	io.merge(Dev::new(client).into_rpc())?;
-	let statement_store = sc_rpc::statement::StatementStore::new(statement_store).into_rpc();
+	let statement_store =
+		sc_rpc::statement::StatementStore::new(statement_store, subscription_executor).into_rpc();
 	io.merge(statement_store)?;
 
 	if let Some(mixnet_api) = mixnet_api {
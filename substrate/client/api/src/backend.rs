@@ -36,7 +36,9 @@ use sp_state_machine::{
 use sp_storage::{ChildInfo, StorageData, StorageKey};
 pub use sp_trie::MerkleValue;
 
-use crate::{blockchain::Backend as BlockchainBackend, UsageInfo};
+use crate::{
+	blockchain::Backend as BlockchainBackend, IndexEntry, IndexKey, IndexedItems, UsageInfo,
+};
 
 pub use sp_state_machine::{Backend as StateBackend, BackendTransaction, KeyValueStates};
 
@@ -246,6 +248,18 @@ pub trait BlockImportOperation<Block: BlockT> {
 
 	/// Configure whether to create a block gap if newly imported block is missing parent
 	fn set_create_gap(&mut self, create_gap: bool);
+
+	/// Set the index entries of the imported block.
+	///
+	/// `complete` tells whether the block was imported with its body and executed, so that its
+	/// events could be indexed. Backends without an index ignore the entries.
+	fn set_index_entries(
+		&mut self,
+		_entries: Vec<IndexEntry>,
+		_complete: bool,
+	) -> sp_blockchain::Result<()> {
+		Ok(())
+	}
 }
 
 /// Interface for performing operations on the backend.
@@ -678,6 +692,22 @@ pub trait Backend<Block: BlockT>: AuxStore + Send + Sync {
 
 	/// Tells whether the backend requires full-sync mode.
 	fn requires_full_sync(&self) -> bool;
+
+	/// Returns the locations of the items indexed under `key` in the canonical blocks
+	/// `from..=to`, in ascending order.
+	///
+	/// At most `max_blocks` blocks are looked up, the returned page tells where to resume the
+	/// query from. Fails if some canonical blocks of the range were not completely indexed.
+	/// Returns `None` if the backend doesn't maintain an index.
+	fn indexed_items(
+		&self,
+		_key: &IndexKey,
+		_from: NumberFor<Block>,
+		_to: NumberFor<Block>,
+		_max_blocks: usize,
+	) -> sp_blockchain::Result<Option<IndexedItems<Block>>> {
+		Ok(None)
+	}
 }
 
 /// Mark for all Backend implementations, that are making use of state data, stored locally.
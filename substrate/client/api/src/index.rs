@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Index of extrinsics and events of imported blocks.
+//!
+//! The client itself knows nothing about the format of extrinsics and events. When the node
+//! provides a [`BlockIndexer`], the client passes every imported block to it and stores the
+//! returned [`IndexEntry`]s together with the block. The backend can then be queried for the
+//! locations of all items indexed under a given [`IndexKey`].
+//!
+//! The events of blocks imported without being executed, such as the blocks downloaded by warp or
+//! state sync, are not known to the indexer. Neither are the items which the indexer fails to
+//! decode. Queries over ranges containing such blocks fail rather than return partial results.
+
+use codec::{Decode, Encode};
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use sp_state_machine::StorageCollection;
+
+/// Key under which items of a block are indexed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Encode, Decode)]
+pub enum IndexKey {
+	/// Items touching an account, identified by its SCALE encoded id.
+	Account(Vec<u8>),
+	/// Events with the given pallet and event name.
+	Event {
+		/// Name of the pallet emitting the event.
+		pallet: String,
+		/// Name of the event.
+		name: String,
+	},
+}
+
+/// Item of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Encode, Decode)]
+pub enum IndexedItem {
+	/// Extrinsic with the given index in the block body.
+	Extrinsic(u32),
+	/// Event with the given index in the events of the block.
+	Event(u32),
+}
+
+/// Entry of the index, produced by a [`BlockIndexer`].
+pub type IndexEntry = (IndexKey, IndexedItem);
+
+/// Location of an indexed item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedLocation<Block: BlockT> {
+	/// Number of the block containing the item.
+	pub number: NumberFor<Block>,
+	/// Hash of the block containing the item.
+	pub hash: Block::Hash,
+	/// The item.
+	pub item: IndexedItem,
+}
+
+/// Page of the locations of the items indexed under a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedItems<Block: BlockT> {
+	/// Locations of the items, in ascending block order.
+	pub locations: Vec<IndexedLocation<Block>>,
+	/// Number of the block to resume the query from, `None` if the page is the last one.
+	pub next: Option<NumberFor<Block>>,
+}
+
+/// Extracts the index entries of imported blocks.
+pub trait BlockIndexer<Block: BlockT>: Send + Sync {
+	/// Returns the index entries of a block, and whether all the items of the block were indexed.
+	///
+	/// `storage_changes` are the changes of the top trie made by the block, `None` if the block
+	/// was imported without being executed.
+	fn index_block(
+		&self,
+		header: &Block::Header,
+		body: &[Block::Extrinsic],
+		storage_changes: Option<&StorageCollection>,
+	) -> (Vec<IndexEntry>, bool);
+}
@@ -24,6 +24,7 @@ pub mod call_executor;
 pub mod client;
 pub mod execution_extensions;
 pub mod in_mem;
+pub mod index;
 pub mod leaves;
 pub mod notifications;
 pub mod proof_provider;
@@ -31,12 +32,13 @@ pub mod proof_provider;
 pub use backend::*;
 pub use call_executor::*;
 pub use client::*;
+pub use index::{BlockIndexer, IndexEntry, IndexKey, IndexedItem, IndexedItems, IndexedLocation};
 pub use notifications::*;
 pub use proof_provider::*;
 pub use sp_blockchain as blockchain;
 pub use sp_blockchain::HeaderBackend;
 
-pub use sp_state_machine::{CompactProof, StorageProof};
+pub use sp_state_machine::{CompactProof, StorageCollection, StorageProof};
 pub use sp_storage::{ChildInfo, PrefixedStorageKey, StorageData, StorageKey};
 
 /// Usage Information Provider interface
@@ -41,7 +41,7 @@ use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
 	ExtrinsicInclusionMode, Percent, SaturatedConversion,
 };
-use std::{pin::Pin, sync::Arc, time};
+use std::{collections::HashSet, pin::Pin, sync::Arc, time};
 
 /// Default block size limit in bytes used by [`Proposer`].
 ///
@@ -396,104 +396,203 @@ where
 		debug!(target: LOG_TARGET, "Attempting to push transactions from the pool at {:?}.", self.parent_hash);
 		let mut transaction_pushed = false;
 
-		let end_reason = loop {
-			let pending_tx = if let Some(pending_tx) = pending_iterator.next() {
-				pending_tx
-			} else {
+		// Bundles go first, so that their transactions are not included one by one before. They
+		// count towards the transactions skipped before the soft deadline like any transaction.
+		let mut bundled = HashSet::new();
+		let mut failed_bundles = Vec::new();
+		let mut bundles_end_reason = None;
+		for bundle in self.transaction_pool.ready_bundles() {
+			let now = (self.now)();
+			if now > deadline {
+				debug!(target: LOG_TARGET, "Consensus deadline reached when pushing bundles.");
+				bundles_end_reason =
+					Some(limit_hit_reason.unwrap_or(EndProposingReason::HitDeadline));
+				break
+			}
+
+			if bundle.transactions.iter().any(|(hash, _)| bundled.contains(hash)) {
+				continue
+			}
+
+			let bundle_size: usize =
+				bundle.transactions.iter().map(|(_, xt)| xt.encoded_size()).sum();
+			let limit_hit = if block_builder.estimate_block_size() + bundle_size > block_size_limit
+			{
 				debug!(
 					target: LOG_TARGET,
-					"No more transactions, proceeding with proposing."
+					"[{:?}] Bundle would overflow the block size limit.", bundle.hash
 				);
-
-				break limit_hit_reason.unwrap_or(EndProposingReason::NoMoreTransactions)
+				EndProposingReason::HitBlockSizeLimit
+			} else {
+				let xts = bundle.transactions.iter().map(|(_, xt)| (**xt).clone()).collect();
+				trace!(target: LOG_TARGET, "[{:?}] Pushing bundle to the block.", bundle.hash);
+				match sc_block_builder::BlockBuilder::push_bundle(block_builder, xts) {
+					Ok(()) => {
+						transaction_pushed = true;
+						limit_hit_reason = None;
+						bundled.extend(bundle.transactions.iter().map(|(hash, _)| hash.clone()));
+						trace!(target: LOG_TARGET, "[{:?}] Pushed bundle to the block.", bundle.hash);
+						continue
+					},
+					Err((_, ApplyExtrinsicFailed(Validity(e)))) if e.exhausted_resources() => {
+						debug!(
+							target: LOG_TARGET,
+							"[{:?}] Bundle exhausts the block resources.", bundle.hash
+						);
+						EndProposingReason::HitBlockWeightLimit
+					},
+					Err((index, e)) => {
+						debug!(
+							target: LOG_TARGET,
+							"[{:?}] Invalid bundle, transaction {} failed: {} at: {}",
+							bundle.hash, index, e, self.parent_hash
+						);
+						failed_bundles.push(bundle.hash.clone());
+						continue
+					},
+				}
 			};
 
-			let now = (self.now)();
-			if now > deadline {
+			// A bundle which doesn't fit into a block without transactions never will.
+			if !transaction_pushed {
 				debug!(
 					target: LOG_TARGET,
-					"Consensus deadline reached when pushing block transactions, \
-				proceeding with proposing."
+					"[{:?}] Bundle doesn't fit into an empty block, dropping.", bundle.hash
 				);
-				break limit_hit_reason.unwrap_or(EndProposingReason::HitDeadline)
+				failed_bundles.push(bundle.hash.clone());
 			}
 
-			let pending_tx_data = (**pending_tx.data()).clone();
-			let pending_tx_hash = pending_tx.hash().clone();
+			limit_hit_reason = Some(limit_hit);
+			if skipped < MAX_SKIPPED_TRANSACTIONS {
+				skipped += 1;
+				debug!(
+					target: LOG_TARGET,
+					"Skipping bundle, will try {} more transactions before quitting.",
+					MAX_SKIPPED_TRANSACTIONS - skipped,
+				);
+			} else if now < soft_deadline {
+				debug!(
+					target: LOG_TARGET,
+					"Skipping bundle, we still have time before the soft deadline, so we will \
+					 try a bit more."
+				);
+			} else {
+				debug!(target: LOG_TARGET, "Block seems full, proceeding with proposing.");
+				bundles_end_reason = Some(limit_hit);
+				break
+			}
+		}
+		self.transaction_pool.remove_bundles(&failed_bundles);
 
-			let block_size = block_builder.estimate_block_size();
-			if block_size + pending_tx_data.encoded_size() > block_size_limit {
-				pending_iterator.report_invalid(&pending_tx);
-				limit_hit_reason = Some(EndProposingReason::HitBlockSizeLimit);
-				if skipped < MAX_SKIPPED_TRANSACTIONS {
-					skipped += 1;
+		let end_reason = if let Some(reason) = bundles_end_reason {
+			reason
+		} else {
+			loop {
+				let pending_tx = if let Some(pending_tx) = pending_iterator.next() {
+					pending_tx
+				} else {
 					debug!(
 						target: LOG_TARGET,
-						"Transaction would overflow the block size limit, \
-					 but will try {} more transactions before quitting.",
-						MAX_SKIPPED_TRANSACTIONS - skipped,
+						"No more transactions, proceeding with proposing."
 					);
-					continue
-				} else if now < soft_deadline {
+
+					break limit_hit_reason.unwrap_or(EndProposingReason::NoMoreTransactions)
+				};
+
+				let now = (self.now)();
+				if now > deadline {
 					debug!(
 						target: LOG_TARGET,
-						"Transaction would overflow the block size limit, \
-					 but we still have time before the soft deadline, so \
-					 we will try a bit more."
+						"Consensus deadline reached when pushing block transactions, \
+					proceeding with proposing."
 					);
-					continue
-				} else {
-					debug!(
+					break limit_hit_reason.unwrap_or(EndProposingReason::HitDeadline)
+				}
+
+				let pending_tx_hash = pending_tx.hash().clone();
+				if bundled.contains(&pending_tx_hash) {
+					trace!(
 						target: LOG_TARGET,
-						"Reached block size limit, proceeding with proposing."
+						"[{:?}] Already included as part of a bundle.", pending_tx_hash
 					);
-					break EndProposingReason::HitBlockSizeLimit
+					continue
 				}
-			}
+				let pending_tx_data = (**pending_tx.data()).clone();
 
-			trace!(target: LOG_TARGET, "[{:?}] Pushing to the block.", pending_tx_hash);
-			match sc_block_builder::BlockBuilder::push(block_builder, pending_tx_data) {
-				Ok(()) => {
-					transaction_pushed = true;
-					limit_hit_reason = None;
-					trace!(target: LOG_TARGET, "[{:?}] Pushed to the block.", pending_tx_hash);
-				},
-				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
+				let block_size = block_builder.estimate_block_size();
+				if block_size + pending_tx_data.encoded_size() > block_size_limit {
 					pending_iterator.report_invalid(&pending_tx);
-					limit_hit_reason = Some(EndProposingReason::HitBlockWeightLimit);
+					limit_hit_reason = Some(EndProposingReason::HitBlockSizeLimit);
 					if skipped < MAX_SKIPPED_TRANSACTIONS {
 						skipped += 1;
-						debug!(target: LOG_TARGET,
-							"Block seems full, but will try {} more transactions before quitting.",
+						debug!(
+							target: LOG_TARGET,
+							"Transaction would overflow the block size limit, \
+						 but will try {} more transactions before quitting.",
 							MAX_SKIPPED_TRANSACTIONS - skipped,
 						);
-					} else if (self.now)() < soft_deadline {
-						debug!(target: LOG_TARGET,
-							"Block seems full, but we still have time before the soft deadline, \
-							 so we will try a bit more before quitting."
+						continue
+					} else if now < soft_deadline {
+						debug!(
+							target: LOG_TARGET,
+							"Transaction would overflow the block size limit, \
+						 but we still have time before the soft deadline, so \
+						 we will try a bit more."
 						);
+						continue
 					} else {
 						debug!(
 							target: LOG_TARGET,
-							"Reached block weight limit, proceeding with proposing."
+							"Reached block size limit, proceeding with proposing."
 						);
-						break EndProposingReason::HitBlockWeightLimit
+						break EndProposingReason::HitBlockSizeLimit
 					}
-				},
-				Err(e) => {
-					pending_iterator.report_invalid(&pending_tx);
-					debug!(
-						target: LOG_TARGET,
-						"[{:?}] Invalid transaction: {} at: {}", pending_tx_hash, e, self.parent_hash
-					);
+				}
 
-					let error_to_report = match e {
-						ApplyExtrinsicFailed(Validity(e)) => Some(e),
-						_ => None,
-					};
+				trace!(target: LOG_TARGET, "[{:?}] Pushing to the block.", pending_tx_hash);
+				match sc_block_builder::BlockBuilder::push(block_builder, pending_tx_data) {
+					Ok(()) => {
+						transaction_pushed = true;
+						limit_hit_reason = None;
+						trace!(target: LOG_TARGET, "[{:?}] Pushed to the block.", pending_tx_hash);
+					},
+					Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
+						pending_iterator.report_invalid(&pending_tx);
+						limit_hit_reason = Some(EndProposingReason::HitBlockWeightLimit);
+						if skipped < MAX_SKIPPED_TRANSACTIONS {
+							skipped += 1;
+							debug!(target: LOG_TARGET,
+								"Block seems full, but will try {} more transactions before quitting.",
+								MAX_SKIPPED_TRANSACTIONS - skipped,
+							);
+						} else if (self.now)() < soft_deadline {
+							debug!(target: LOG_TARGET,
+								"Block seems full, but we still have time before the soft deadline, \
+								 so we will try a bit more before quitting."
+							);
+						} else {
+							debug!(
+								target: LOG_TARGET,
+								"Reached block weight limit, proceeding with proposing."
+							);
+							break EndProposingReason::HitBlockWeightLimit
+						}
+					},
+					Err(e) => {
+						pending_iterator.report_invalid(&pending_tx);
+						debug!(
+							target: LOG_TARGET,
+							"[{:?}] Invalid transaction: {} at: {}", pending_tx_hash, e, self.parent_hash
+						);
 
-					unqueue_invalid.insert(pending_tx_hash, error_to_report);
-				},
+						let error_to_report = match e {
+							ApplyExtrinsicFailed(Validity(e)) => Some(e),
+							_ => None,
+						};
+
+						unqueue_invalid.insert(pending_tx_hash, error_to_report);
+					},
+				}
 			}
 		};
 
@@ -766,6 +865,106 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_include_bundles_atomically() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		));
+
+		let genesis_hash = client.info().best_hash;
+		let good = vec![extrinsic(0), extrinsic(1)];
+		let bad = vec![extrinsic(2), extrinsic(5)];
+
+		block_on(txpool.submit_one(genesis_hash, SOURCE, extrinsic(0))).unwrap();
+		let good_hash = block_on(txpool.submit_bundle(genesis_hash, SOURCE, good.clone())).unwrap();
+		block_on(txpool.submit_bundle(genesis_hash, SOURCE, bad)).unwrap();
+
+		block_on(txpool.maintain(chain_event(
+			client.expect_header(genesis_hash).expect("there should be header"),
+		)));
+
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
+
+		let proposer = proposer_factory.init_with_now(
+			&client.expect_header(genesis_hash).unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		// when
+		let deadline = time::Duration::from_secs(9);
+		let block = block_on(
+			proposer.propose_block(ProposeArgs { max_duration: deadline, ..Default::default() }),
+		)
+		.map(|r| r.block)
+		.unwrap();
+
+		// then
+		// the first bundle is included once and in order, the second one is rolled back entirely
+		// and dropped from the pool.
+		assert_eq!(block.extrinsics(), &good[..]);
+		let bundles = txpool.ready_bundles();
+		assert_eq!(bundles.len(), 1);
+		assert_eq!(bundles[0].hash, good_hash);
+	}
+
+	#[test]
+	fn should_drop_bundles_exhausting_resources_of_an_empty_block() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		));
+
+		let genesis_hash = client.info().best_hash;
+		let huge = |nonce| {
+			ExtrinsicBuilder::new_fill_block(Perbill::from_parts(HUGE)).nonce(nonce).build()
+		};
+		let good = vec![extrinsic(0)];
+
+		block_on(txpool.submit_bundle(genesis_hash, SOURCE, vec![huge(0), huge(1)])).unwrap();
+		let good_hash = block_on(txpool.submit_bundle(genesis_hash, SOURCE, good.clone())).unwrap();
+
+		block_on(txpool.maintain(chain_event(
+			client.expect_header(genesis_hash).expect("there should be header"),
+		)));
+
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
+
+		let proposer = proposer_factory.init_with_now(
+			&client.expect_header(genesis_hash).unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		// when
+		let deadline = time::Duration::from_secs(9);
+		let block = block_on(
+			proposer.propose_block(ProposeArgs { max_duration: deadline, ..Default::default() }),
+		)
+		.map(|r| r.block)
+		.unwrap();
+
+		// then
+		// the first bundle never fits into a block and is dropped instead of being retried.
+		assert_eq!(block.extrinsics(), &good[..]);
+		let bundles = txpool.ready_bundles();
+		assert_eq!(bundles.len(), 1);
+		assert_eq!(bundles[0].hash, good_hash);
+	}
+
 	// This test ensures that if one transaction of a user was rejected, because for example
 	// the weight limit was hit, we don't mark the other transactions of the user as invalid because
 	// the nonce is not matching.
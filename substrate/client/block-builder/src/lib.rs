@@ -318,6 +318,45 @@ where
 		})
 	}
 
+	/// Push a bundle of extrinsics onto the block's list of extrinsics.
+	///
+	/// The extrinsics are applied in the given order and either all of them are included or none.
+	/// When one of them can not be applied, all changes made by the bundle are rolled back and the
+	/// index of the failing extrinsic is returned together with the error.
+	pub fn push_bundle(
+		&mut self,
+		xts: Vec<<Block as BlockT>::Extrinsic>,
+	) -> Result<(), (usize, Error)> {
+		let parent_hash = self.parent_hash;
+		let extrinsics = &mut self.extrinsics;
+		let version = self.version;
+
+		self.api.execute_in_transaction(|api| {
+			for (index, xt) in xts.iter().enumerate() {
+				let res = if version < 6 {
+					#[allow(deprecated)]
+					api.apply_extrinsic_before_version_6(parent_hash, xt.clone())
+						.map(legacy::byte_sized_error::convert_to_latest)
+				} else {
+					api.apply_extrinsic(parent_hash, xt.clone())
+				};
+
+				match res {
+					Ok(Ok(_)) => {},
+					Ok(Err(tx_validity)) =>
+						return TransactionOutcome::Rollback(Err((
+							index,
+							ApplyExtrinsicFailed::Validity(tx_validity).into(),
+						))),
+					Err(e) => return TransactionOutcome::Rollback(Err((index, Error::from(e)))),
+				}
+			}
+
+			extrinsics.extend(xts);
+			TransactionOutcome::Commit(Ok(()))
+		})
+	}
+
 	/// Consume the builder to build a valid `Block` containing all pushed extrinsics.
 	///
 	/// Returns the build `Block`, the changes to the storage and an optional `StorageProof`
@@ -473,4 +512,38 @@ mod tests {
 		assert!(proof_without_panic > proof_empty_block);
 		assert_eq!(proof_empty_block, proof_with_panic);
 	}
+
+	#[test]
+	fn failing_bundle_is_rolled_back_entirely() {
+		let builder = substrate_test_runtime_client::TestClientBuilder::new();
+		let client = builder.build();
+		let genesis_hash = client.info().best_hash;
+
+		let mut block_builder = BlockBuilderBuilder::new(&client)
+			.on_parent_block(genesis_hash)
+			.with_parent_block_number(0)
+			.build()
+			.unwrap();
+
+		let (index, _) = block_builder
+			.push_bundle(vec![
+				ExtrinsicBuilder::new_read(8).build(),
+				ExtrinsicBuilder::new_read_and_panic(8).build(),
+			])
+			.unwrap_err();
+		assert_eq!(index, 1);
+
+		block_builder
+			.push_bundle(vec![
+				ExtrinsicBuilder::new_read(4).build(),
+				ExtrinsicBuilder::new_read(8).build(),
+			])
+			.unwrap();
+
+		let block = block_builder.build().unwrap().block;
+		assert_eq!(
+			block.extrinsics(),
+			&[ExtrinsicBuilder::new_read(4).build(), ExtrinsicBuilder::new_read(8).build()]
+		);
+	}
 }
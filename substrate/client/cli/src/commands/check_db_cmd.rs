@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use clap::Parser;
+use sc_service::Configuration;
+use sp_runtime::traits::Block as BlockT;
+use std::fmt::Debug;
+
+/// The `check-db` command used to check the integrity of the database of the node.
+///
+/// The node must not be running. The same pruning options as the node must be given, as they
+/// define which block bodies and states are expected to be in the database.
+#[derive(Debug, Clone, Parser)]
+pub struct CheckDbCmd {
+	/// Maximum number of states whose trie nodes are checked, from the best block down.
+	///
+	/// All the states kept in the database are checked by default, which takes long for archive
+	/// databases.
+	#[arg(long, value_name = "COUNT")]
+	pub max_states: Option<u32>,
+
+	/// Revert the blocks above the last consistent block.
+	#[arg(long)]
+	pub repair: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CheckDbCmd {
+	/// Run the check-db command
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		let db_config = sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: config.state_pruning.clone(),
+			source: config.database.clone(),
+			blocks_pruning: config.blocks_pruning,
+			metrics_registry: None,
+		};
+		let report = sc_client_db::check_database::<B>(db_config, self.max_states, self.repair)?;
+		for inconsistency in &report.inconsistencies {
+			println!("{inconsistency}");
+		}
+		println!(
+			"Checked {} blocks and {} states, found {} inconsistencies",
+			report.checked_blocks,
+			report.checked_states,
+			report.inconsistencies.len(),
+		);
+		if report.inconsistencies.is_empty() {
+			return Ok(())
+		}
+
+		match report.last_consistent {
+			Some(number) if self.repair =>
+				println!("Reverted {} blocks, last consistent block: #{number}", report.reverted),
+			Some(number) => println!(
+				"Last consistent block: #{number}, run with `--repair` to revert the blocks above it"
+			),
+			None => println!("The genesis block is inconsistent"),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for CheckDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
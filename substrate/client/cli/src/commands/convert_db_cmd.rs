@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_service::DatabaseSource;
+use sp_runtime::traits::Block as BlockT;
+use std::{fmt::Debug, path::PathBuf};
+
+/// The `convert-db` command used to convert the RocksDB database of the node to ParityDB.
+///
+/// The node must not be running. An interrupted conversion is resumed by running the command
+/// again. ParityDB databases can't be converted to RocksDB, as ParityDB only keeps the hashes of
+/// most keys.
+#[derive(Debug, Clone, Parser)]
+pub struct ConvertDbCmd {
+	/// Path of the ParityDB database to create.
+	///
+	/// Defaults to the path used by the node when started with `--database paritydb`.
+	#[arg(long, value_name = "PATH")]
+	pub destination: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ConvertDbCmd {
+	/// Run the convert-db command
+	pub fn run<B: BlockT>(&self, database_config: DatabaseSource) -> error::Result<()> {
+		let source = match database_config {
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::Auto { rocksdb_path, cache_size, .. } =>
+				DatabaseSource::RocksDb { path: rocksdb_path, cache_size },
+			source => source,
+		};
+		if matches!(source, DatabaseSource::ParityDb { .. }) {
+			return Err(error::Error::Input(
+				"ParityDB databases can't be converted to RocksDB".into(),
+			))
+		}
+
+		let destination = match &self.destination {
+			Some(path) => path.clone(),
+			None => {
+				let path = source.path().ok_or_else(|| {
+					error::Error::Input("Cannot convert custom database implementation".into())
+				})?;
+				// The database lives in `<base>/db/<role>`, ParityDB in `<base>/paritydb/<role>`.
+				match (path.parent().and_then(|p| p.parent()), path.file_name()) {
+					(Some(base), Some(role)) => base.join("paritydb").join(role),
+					_ => return Err(error::Error::Input("Invalid database path".into())),
+				}
+			},
+		};
+
+		println!("Converting {source} database to ParityDB at {:?}", destination);
+		let summary = sc_client_db::convert_database::<B>(
+			&source,
+			&DatabaseSource::ParityDb { path: destination },
+		)?;
+		println!(
+			"Converted {} entries. Best block: #{} ({}), finalized block: #{} ({})",
+			summary.copied,
+			summary.best.1,
+			summary.best.0,
+			summary.finalized.1,
+			summary.finalized.0,
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ConvertDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
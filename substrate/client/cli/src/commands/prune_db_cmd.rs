@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use clap::Parser;
+use sc_service::{Configuration, DatabaseSource};
+use sp_runtime::traits::Block as BlockT;
+use std::fmt::Debug;
+
+/// The `prune-db` command used to prune the state of an archive database in place.
+///
+/// The state of the last canonical blocks given by `--state-pruning` is kept and, if
+/// `--blocks-pruning` is set to a number, the bodies of older finalized blocks are removed. The
+/// node must not be running. An interrupted pruning is resumed by running the command again.
+///
+/// Only RocksDB databases can be pruned. The trie nodes in use are written to a temporary
+/// database next to the pruned one, which needs as much disk space as their keys.
+#[derive(Debug, Clone, Parser)]
+pub struct PruneDbCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl PruneDbCmd {
+	/// Run the prune-db command
+	pub fn run<B: BlockT>(&self, config: &Configuration) -> error::Result<()> {
+		let source = match config.database.clone() {
+			#[cfg(feature = "rocksdb")]
+			DatabaseSource::Auto { rocksdb_path, cache_size, .. } =>
+				DatabaseSource::RocksDb { path: rocksdb_path, cache_size },
+			source => source,
+		};
+
+		println!("Pruning {source} database");
+		let summary = sc_client_db::prune_database::<B>(&sc_client_db::DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: config.state_pruning.clone(),
+			source,
+			blocks_pruning: config.blocks_pruning,
+			metrics_registry: None,
+		})?;
+		println!(
+			"Kept the state of blocks #{} to #{}: {} trie nodes kept, {} removed. \
+			Removed {} block bodies",
+			summary.window.0,
+			summary.window.1,
+			summary.kept_nodes,
+			summary.removed_nodes,
+			summary.removed_bodies,
+		);
+		Ok(())
+	}
+}
+
+impl CliConfiguration for PruneDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -28,7 +28,8 @@ use clap::Parser;
 use regex::Regex;
 use sc_service::{
 	config::{
-		BasePath, IpNetwork, PrometheusConfig, RpcBatchRequestConfig, TransactionPoolOptions,
+		BasePath, IpNetwork, PrometheusConfig, RpcBatchRequestConfig, RpcQuotaConfig,
+		TransactionPoolOptions,
 	},
 	ChainSpec, Role,
 };
@@ -53,6 +54,14 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub no_grandpa: bool,
 
+	/// Index the extrinsics and events of imported blocks by account and by event name.
+	///
+	/// The index is queried by the `archive_v1_itemsByAccount` and `archive_v1_eventsByName`
+	/// RPC methods, which are only available on archive nodes. Blocks imported while the index
+	/// is disabled, or without being executed, are not indexed and can not be queried.
+	#[arg(long)]
+	pub archive_index: bool,
+
 	/// The human-readable name for this node.
 	///
 	/// It's used as network node name.
@@ -282,6 +291,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.no_grandpa)
 	}
 
+	fn archive_index(&self) -> Result<bool> {
+		Ok(self.archive_index)
+	}
+
 	fn rpc_max_connections(&self) -> Result<u32> {
 		Ok(self.rpc_params.rpc_max_connections)
 	}
@@ -330,6 +343,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_params.rpc_rate_limit_trust_proxy_headers)
 	}
 
+	fn rpc_quotas(&self) -> Result<Option<RpcQuotaConfig>> {
+		self.rpc_params.rpc_quotas()
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
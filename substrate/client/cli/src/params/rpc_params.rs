@@ -18,7 +18,7 @@
 
 use crate::{
 	arg_enums::{Cors, RpcMethods},
-	params::{IpNetwork, RpcBatchRequestConfig},
+	params::{IpNetwork, RpcBatchRequestConfig, RpcQuotaConfig},
 	RPC_DEFAULT_MAX_CONNECTIONS, RPC_DEFAULT_MAX_REQUEST_SIZE_MB, RPC_DEFAULT_MAX_RESPONSE_SIZE_MB,
 	RPC_DEFAULT_MAX_SUBS_PER_CONN, RPC_DEFAULT_MESSAGE_CAPACITY_PER_CONN,
 };
@@ -26,6 +26,7 @@ use clap::Args;
 use std::{
 	net::{Ipv4Addr, Ipv6Addr, SocketAddr},
 	num::NonZeroU32,
+	path::PathBuf,
 };
 
 const RPC_LISTEN_ADDR: &str = "listen-addr";
@@ -100,6 +101,19 @@ pub struct RpcParams {
 	#[arg(long)]
 	pub rpc_rate_limit_trust_proxy_headers: bool,
 
+	/// Path to a JSON file with per-method RPC call costs and per-API-key quotas.
+	///
+	/// Each call is charged the cost of its method against the quota of the API key sent in the
+	/// `X-Api-Key` or `Authorization: Bearer` header, or against the anonymous quota of the client
+	/// IP address if no key is sent. Calls exceeding the quota are rejected. Addresses in
+	/// `--rpc-rate-limit-whitelisted-ips` are exempt from the anonymous quota, but their API keys
+	/// are still authenticated and charged.
+	///
+	/// The quotas are per process: they are shared by all RPC interfaces of the node, but not by
+	/// several nodes, and are reset when the node restarts.
+	#[arg(long, value_name = "PATH")]
+	pub rpc_quotas: Option<PathBuf>,
+
 	/// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
 	#[arg(long, default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB)]
 	pub rpc_max_request_size: u32,
@@ -295,6 +309,15 @@ impl RpcParams {
 
 		Ok(cfg)
 	}
+
+	/// Load the RPC quota configuration, if enabled.
+	pub fn rpc_quotas(&self) -> crate::Result<Option<RpcQuotaConfig>> {
+		self.rpc_quotas
+			.as_deref()
+			.map(RpcQuotaConfig::from_file)
+			.transpose()
+			.map_err(|e| crate::Error::Input(e.to_string()))
+	}
 }
 
 fn rpc_interface(
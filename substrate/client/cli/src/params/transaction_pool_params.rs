@@ -17,7 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use clap::{Args, ValueEnum};
-use sc_transaction_pool::TransactionPoolOptions;
+use sc_transaction_pool::{TransactionJournalOptions, TransactionPoolOptions};
+use std::{path::PathBuf, time::Duration};
 
 /// Type of transaction pool to be used
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -50,6 +51,19 @@ pub struct TransactionPoolParams {
 	#[arg(long, value_name = "COUNT", default_value_t = 20480)]
 	pub pool_kbytes: usize,
 
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	///
+	/// Only enforced by the fork-aware pool. No limit applies unless this or
+	/// `--pool-sender-kbytes` is given.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Maximum number of kilobytes of all transactions of a single sender in the transaction pool.
+	///
+	/// Only enforced by the fork-aware pool.
+	#[arg(long, value_name = "COUNT")]
+	pub pool_sender_kbytes: Option<usize>,
+
 	/// How long a transaction is banned for.
 	///
 	/// If it is considered invalid. Defaults to 1800s.
@@ -59,17 +73,48 @@ pub struct TransactionPoolParams {
 	/// The type of transaction pool to be instantiated.
 	#[arg(long, value_enum, default_value_t = TransactionPoolType::ForkAware)]
 	pub pool_type: TransactionPoolType,
+
+	/// Persist submitted transactions in an on-disk journal.
+	///
+	/// Journaled transactions are revalidated and resubmitted to the pool when the node restarts.
+	/// The journal is stored in the `txpool` directory of the chain data.
+	#[arg(long)]
+	pub pool_journal: bool,
+
+	/// Maximum number of kilobytes of the transaction pool journal.
+	#[arg(long, value_name = "COUNT", default_value_t = 20480)]
+	pub pool_journal_kbytes: usize,
+
+	/// Maximum age of journaled transactions resubmitted on startup.
+	#[arg(long, value_name = "SECONDS", default_value_t = 3600)]
+	pub pool_journal_max_age: u64,
 }
 
 impl TransactionPoolParams {
 	/// Fill the given `PoolConfiguration` by looking at the cli parameters.
 	pub fn transaction_pool(&self, is_dev: bool) -> TransactionPoolOptions {
-		TransactionPoolOptions::new_with_params(
+		let mut options = TransactionPoolOptions::new_with_params(
 			self.pool_limit,
 			self.pool_kbytes * 1024,
 			self.tx_ban_seconds,
 			self.pool_type.into(),
 			is_dev,
-		)
+		);
+		if self.pool_sender_limit.is_some() || self.pool_sender_kbytes.is_some() {
+			options = options.with_per_sender_limit(
+				self.pool_sender_limit.unwrap_or(self.pool_limit),
+				self.pool_sender_kbytes.unwrap_or(self.pool_kbytes) * 1024,
+			);
+		}
+		if !self.pool_journal {
+			return options
+		}
+
+		options.with_journal(TransactionJournalOptions {
+			max_count: self.pool_limit,
+			max_bytes: self.pool_journal_kbytes * 1024,
+			max_age: Duration::from_secs(self.pool_journal_max_age),
+			..TransactionJournalOptions::new(PathBuf::from("txpool").join("journal"))
+		})
 	}
 }
@@ -0,0 +1,514 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Integrity check of the database.
+//!
+//! The canonical chain is walked from the best block down to genesis, checking that every header
+//! is linked to the one below it and that the bodies and justifications kept by the blocks pruning
+//! mode can be read. The trie nodes of the canonicalized states kept along the way are checked to
+//! be in the database, and the blocks recorded in the state-db journals are checked against the
+//! chain.
+//!
+//! The blocks pinned by the backend are not checked: they are only held in memory by a running
+//! node, so an offline check never sees them, and none are pinned once it is stopped.
+//!
+//! A database is repaired by reverting the blocks above the last consistent one. Finalized blocks
+//! can only be reverted in the archive pruning modes.
+
+use std::fmt;
+
+use log::{info, warn};
+use sc_client_api::{backend::Backend as _, blockchain::Backend as _};
+use sc_state_db::LastCanonicalized;
+use sp_blockchain::{
+	BlockGapType, Error as ClientError, HeaderBackend, Info, Result as ClientResult,
+};
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor, One, Zero},
+	SaturatedConversion, Saturating,
+};
+
+use crate::{utils, Backend, BlocksPruning, DatabaseSettings, StateMetaDb};
+
+/// Number of blocks checked between two progress reports.
+const PROGRESS_INTERVAL: u64 = 100_000;
+
+/// Canonicalization delay of the backend opened to check a database. Blocks are never
+/// canonicalized while checking it.
+const CANONICALIZATION_DELAY: u64 = 4096;
+
+/// Inconsistency found in the database.
+#[derive(Debug, Clone)]
+pub enum Inconsistency<Block: BlockT> {
+	/// The header of the canonical block with the given number is missing.
+	MissingHeader(NumberFor<Block>),
+	/// The parent of the block is not the canonical block below it.
+	InvalidParent(Block::Hash, NumberFor<Block>),
+	/// The body of the block is missing or can't be read.
+	InvalidBody(Block::Hash, NumberFor<Block>, String),
+	/// The justifications of the block can't be read.
+	InvalidJustifications(Block::Hash, NumberFor<Block>, String),
+	/// Trie nodes of the state of the block are missing.
+	MissingState(Block::Hash, NumberFor<Block>, String),
+	/// The state-db journals record a block that is not in the chain.
+	InvalidJournal(Block::Hash, NumberFor<Block>),
+	/// The finalized block is not canonical.
+	InvalidFinalized(Block::Hash, NumberFor<Block>),
+}
+
+impl<Block: BlockT> Inconsistency<Block> {
+	/// Number of the block affected by the inconsistency, which is solved by reverting this block
+	/// and the ones above it.
+	pub fn number(&self) -> NumberFor<Block> {
+		match self {
+			Self::MissingHeader(number) |
+			Self::InvalidParent(_, number) |
+			Self::InvalidBody(_, number, _) |
+			Self::InvalidJustifications(_, number, _) |
+			Self::MissingState(_, number, _) |
+			Self::InvalidJournal(_, number) |
+			Self::InvalidFinalized(_, number) => *number,
+		}
+	}
+}
+
+impl<Block: BlockT> fmt::Display for Inconsistency<Block> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::MissingHeader(number) => write!(f, "Missing header of canonical block #{number}"),
+			Self::InvalidParent(hash, number) =>
+				write!(f, "Parent of block #{number} ({hash}) is not the canonical block below it"),
+			Self::InvalidBody(hash, number, err) =>
+				write!(f, "Invalid body of block #{number} ({hash}): {err}"),
+			Self::InvalidJustifications(hash, number, err) =>
+				write!(f, "Invalid justifications of block #{number} ({hash}): {err}"),
+			Self::MissingState(hash, number, err) =>
+				write!(f, "Missing state of block #{number} ({hash}): {err}"),
+			Self::InvalidJournal(hash, number) => write!(
+				f,
+				"State-db journal records block #{number} ({hash}), which is not in the chain"
+			),
+			Self::InvalidFinalized(hash, number) =>
+				write!(f, "Finalized block #{number} ({hash}) is not canonical"),
+		}
+	}
+}
+
+/// Result of an integrity check of the database.
+#[derive(Debug)]
+pub struct IntegrityReport<Block: BlockT> {
+	/// Number of canonical blocks checked.
+	pub checked_blocks: u64,
+	/// Number of states whose trie nodes were checked.
+	pub checked_states: u64,
+	/// Inconsistencies found.
+	pub inconsistencies: Vec<Inconsistency<Block>>,
+	/// Last block of the best chain which is consistent along with all of its ancestors, `None`
+	/// if the genesis block is inconsistent.
+	pub last_consistent: Option<NumberFor<Block>>,
+	/// Number of blocks reverted to repair the database.
+	pub reverted: NumberFor<Block>,
+}
+
+/// Check the integrity of the database of `settings`, reverting the blocks above the last
+/// consistent one when `repair` is set.
+///
+/// The trie nodes of at most `max_states` states are checked, see [`Backend::check_integrity`].
+pub fn check_database<Block: BlockT>(
+	settings: DatabaseSettings,
+	max_states: Option<u32>,
+	repair: bool,
+) -> ClientResult<IntegrityReport<Block>> {
+	let backend = Backend::<Block>::new(settings, CANONICALIZATION_DELAY)?;
+	let mut report = backend.check_integrity(max_states)?;
+	for inconsistency in &report.inconsistencies {
+		warn!(target: "db", "{inconsistency}");
+	}
+	if !repair || report.inconsistencies.is_empty() {
+		return Ok(report)
+	}
+
+	let last_consistent = report.last_consistent.ok_or_else(|| {
+		ClientError::Backend("Genesis block is inconsistent, the database can't be repaired".into())
+	})?;
+	let to_revert = backend.blockchain.info().best_number.saturating_sub(last_consistent);
+	let (reverted, _) = backend.revert(to_revert, true)?;
+	if reverted < to_revert {
+		warn!(target: "db", "Only {reverted} out of {to_revert} blocks could be reverted");
+	}
+	report.reverted = reverted;
+	info!(
+		target: "db",
+		"Database repaired, best block is #{}",
+		backend.blockchain.info().best_number,
+	);
+	Ok(report)
+}
+
+impl<Block: BlockT> Backend<Block> {
+	/// Check the integrity of the database.
+	///
+	/// The canonical chain is checked from the best block down to genesis, along with the trie
+	/// nodes of the states kept and the state-db journals. At most `max_states` states are checked,
+	/// from the best one down, or all the states kept if `None`.
+	pub fn check_integrity(&self, max_states: Option<u32>) -> ClientResult<IntegrityReport<Block>> {
+		let info = self.blockchain.info();
+		let mut report = IntegrityReport {
+			checked_blocks: 0,
+			checked_states: 0,
+			inconsistencies: Vec::new(),
+			last_consistent: None,
+			reverted: Zero::zero(),
+		};
+
+		if self.blockchain.hash(info.finalized_number)? != Some(info.finalized_hash) {
+			report
+				.inconsistencies
+				.push(Inconsistency::InvalidFinalized(info.finalized_hash, info.finalized_number));
+		}
+		self.check_chain(&info, max_states, &mut report)?;
+		self.check_journals(&mut report)?;
+
+		report.last_consistent =
+			match report.inconsistencies.iter().map(Inconsistency::number).min() {
+				Some(number) if number.is_zero() => None,
+				Some(number) => Some(number - One::one()),
+				None => Some(info.best_number),
+			};
+		info!(
+			target: "db",
+			"Checked {} blocks and {} states, found {} inconsistencies",
+			report.checked_blocks,
+			report.checked_states,
+			report.inconsistencies.len(),
+		);
+		Ok(report)
+	}
+
+	/// Check the canonical chain from the best block down to genesis, and the states kept.
+	fn check_chain(
+		&self,
+		info: &Info<Block>,
+		max_states: Option<u32>,
+		report: &mut IntegrityReport<Block>,
+	) -> ClientResult<()> {
+		let finalized_state = info.finalized_state.map(|(_, number)| number);
+		// The trie nodes of the states above the last canonicalized block are in the state-db
+		// overlay rather than in the database.
+		let last_canonicalized = self.storage.state_db.last_canonicalized();
+		let state_in_db = |number: NumberFor<Block>| match last_canonicalized {
+			LastCanonicalized::Block(last) => number.saturated_into::<u64>() <= last,
+			LastCanonicalized::NotCanonicalizing => true,
+			LastCanonicalized::None => false,
+		};
+		let mut states_left = max_states.map_or(u64::MAX, u64::from);
+		// Hash, number and parent hash of the canonical block above the current one.
+		let mut child: Option<(Block::Hash, NumberFor<Block>, Block::Hash)> = None;
+		let mut number = info.best_number;
+		loop {
+			let gap = info
+				.block_gap
+				.filter(|gap| gap.start <= number && number <= gap.end)
+				.map(|gap| gap.gap_type);
+			let header = match self.blockchain.hash(number)? {
+				Some(hash) => self.blockchain.header(hash)?.map(|header| (hash, header)),
+				None => None,
+			};
+
+			match header {
+				Some((hash, header)) => {
+					if let Some((child_hash, child_number, parent_hash)) = child {
+						if parent_hash != hash {
+							report
+								.inconsistencies
+								.push(Inconsistency::InvalidParent(child_hash, child_number));
+						}
+					}
+					child = Some((hash, number, *header.parent_hash()));
+
+					if gap.is_none() {
+						self.check_block_data(hash, number, info.finalized_number, report);
+					}
+
+					let state_kept = state_in_db(number) &&
+						(Some(number) == finalized_state || self.have_state_at(hash, number));
+					if states_left > 0 && state_kept {
+						if let Err(err) = utils::for_each_state_node::<HashingFor<Block>>(
+							&*self.storage.db,
+							*header.state_root(),
+							&mut |_, _| (),
+						) {
+							report.inconsistencies.push(Inconsistency::MissingState(
+								hash,
+								number,
+								err.to_string(),
+							));
+						}
+						report.checked_states += 1;
+						states_left -= 1;
+					} else if finalized_state.map_or(false, |finalized| number < finalized) {
+						// The states of the blocks below are pruned.
+						states_left = 0;
+					}
+				},
+				None => {
+					if gap != Some(BlockGapType::MissingHeaderAndBody) {
+						report.inconsistencies.push(Inconsistency::MissingHeader(number));
+					}
+					child = None;
+				},
+			}
+
+			report.checked_blocks += 1;
+			if report.checked_blocks.is_multiple_of(PROGRESS_INTERVAL) {
+				info!(target: "db", "Checked {} blocks, now at #{number}", report.checked_blocks);
+			}
+			if number.is_zero() {
+				return Ok(())
+			}
+			number -= One::one();
+		}
+	}
+
+	/// Check that the body kept by the blocks pruning mode and the justifications of a block can
+	/// be read.
+	fn check_block_data(
+		&self,
+		hash: Block::Hash,
+		number: NumberFor<Block>,
+		finalized: NumberFor<Block>,
+		report: &mut IntegrityReport<Block>,
+	) {
+		let body_kept = match self.blocks_pruning {
+			BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => true,
+			// Always keep the last finalized block
+			BlocksPruning::Some(blocks) => number.saturating_add(blocks.max(1).into()) > finalized,
+		};
+		if body_kept {
+			let err = match self.blockchain.body(hash) {
+				Ok(Some(_)) => None,
+				Ok(None) => Some("missing body".into()),
+				Err(err) => Some(err.to_string()),
+			};
+			if let Some(err) = err {
+				report.inconsistencies.push(Inconsistency::InvalidBody(hash, number, err));
+			}
+		}
+		if let Err(err) = self.blockchain.justifications(hash) {
+			report.inconsistencies.push(Inconsistency::InvalidJustifications(
+				hash,
+				number,
+				err.to_string(),
+			));
+		}
+	}
+
+	/// Check the blocks recorded in the state-db journals against the chain.
+	fn check_journals(&self, report: &mut IntegrityReport<Block>) -> ClientResult<()> {
+		let journaled = sc_state_db::journaled_blocks::<Block::Hash, Vec<u8>, _>(&StateMetaDb(
+			self.storage.db.clone(),
+		))
+		.map_err(ClientError::from_state_db)?;
+
+		let mut canonical = journaled.window;
+		if let Some(last_canonical) = journaled.last_canonical {
+			if !canonical.contains(&last_canonical) {
+				canonical.push(last_canonical);
+			}
+		}
+		for (hash, number) in canonical {
+			let number = number.saturated_into();
+			if self.blockchain.hash(number)? != Some(hash) {
+				report.inconsistencies.push(Inconsistency::InvalidJournal(hash, number));
+			}
+		}
+
+		for (hash, number, parent_hash) in journaled.non_canonical {
+			let number = number.saturated_into();
+			match self.blockchain.header(hash)? {
+				Some(header)
+					if *header.number() == number && *header.parent_hash() == parent_hash =>
+					(),
+				_ => report.inconsistencies.push(Inconsistency::InvalidJournal(hash, number)),
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		columns,
+		tests::{insert_block, Block},
+		utils::SharedKvdb,
+		DatabaseSource, DbHash, PruningMode,
+	};
+	use sc_client_api::backend::BlockImportOperation as _;
+	use sp_core::H256;
+	use sp_database::{Database, Transaction};
+	use sp_runtime::testing::{MockCallU64, TestXt};
+	use std::{collections::HashSet, sync::Arc};
+
+	type UncheckedXt = TestXt<MockCallU64, ()>;
+
+	fn settings(db: &SharedKvdb, create: bool) -> DatabaseSettings {
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::ArchiveAll),
+			source: DatabaseSource::Custom {
+				db: sp_database::as_database(db.clone()),
+				require_create_flag: create,
+			},
+			blocks_pruning: BlocksPruning::KeepAll,
+			metrics_registry: None,
+		}
+	}
+
+	/// Create an archive database with four finalized blocks on top of genesis and an unfinalized
+	/// block, returning the hashes of the blocks.
+	fn archive_database() -> (SharedKvdb, Vec<H256>) {
+		let db = SharedKvdb(Arc::new(kvdb_memorydb::create(utils::NUM_COLUMNS)));
+		let backend = Backend::<Block>::new(settings(&db, true), 8).unwrap();
+
+		let mut blocks = Vec::new();
+		let mut parent_hash = Default::default();
+		for number in 0..6 {
+			parent_hash = insert_block(
+				&backend,
+				number,
+				parent_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(number.into(), ())],
+				None,
+			)
+			.unwrap();
+			blocks.push(parent_hash);
+		}
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, blocks[4]).unwrap();
+		for hash in &blocks[1..5] {
+			op.mark_finalized(*hash, None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		(db, blocks)
+	}
+
+	#[test]
+	fn consistent_database_is_checked() {
+		let (db, _) = archive_database();
+		let report = check_database::<Block>(settings(&db, false), None, true).unwrap();
+		assert!(report.inconsistencies.is_empty());
+		assert_eq!(report.checked_blocks, 6);
+		assert_eq!(report.checked_states, 6);
+		assert_eq!(report.last_consistent, Some(5));
+		assert_eq!(report.reverted, 0);
+
+		let report = check_database::<Block>(settings(&db, false), Some(2), false).unwrap();
+		assert_eq!(report.checked_states, 2);
+	}
+
+	#[test]
+	fn states_in_the_overlay_are_skipped() {
+		let db = SharedKvdb(Arc::new(kvdb_memorydb::create(utils::NUM_COLUMNS)));
+		let pruned_settings = |create| DatabaseSettings {
+			state_pruning: Some(PruningMode::blocks_pruning(4)),
+			..settings(&db, create)
+		};
+		let backend = Backend::<Block>::new(pruned_settings(true), 8).unwrap();
+		let mut parent_hash = Default::default();
+		for number in 0..4 {
+			parent_hash = insert_block(
+				&backend,
+				number,
+				parent_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(number.into(), ())],
+				None,
+			)
+			.unwrap();
+		}
+		drop(backend);
+
+		// The states of the unfinalized blocks are only in the state-db journal.
+		let report = check_database::<Block>(pruned_settings(false), None, false).unwrap();
+		assert!(report.inconsistencies.is_empty());
+		assert_eq!(report.checked_blocks, 4);
+		assert_eq!(report.last_consistent, Some(3));
+	}
+
+	/// Keys of the trie nodes of the state with the given root.
+	fn state_nodes(db: &dyn Database<DbHash>, root: H256) -> HashSet<Vec<u8>> {
+		let mut nodes = HashSet::new();
+		utils::for_each_state_node::<HashingFor<Block>>(db, root, &mut |key, _| {
+			nodes.insert(key.to_vec());
+		})
+		.unwrap();
+		nodes
+	}
+
+	#[test]
+	fn inconsistent_database_is_repaired() {
+		let (db, blocks) = archive_database();
+		let database = sp_database::as_database::<_, DbHash>(db.clone());
+		let backend = Backend::<Block>::new(settings(&db, false), 8).unwrap();
+		let state_root =
+			|number: usize| *backend.blockchain.expect_header(blocks[number]).unwrap().state_root();
+		// A node added to the state by block 4, other than its root.
+		let parent_nodes = state_nodes(&*database, state_root(3));
+		let node = state_nodes(&*database, state_root(4))
+			.into_iter()
+			.find(|key| !parent_nodes.contains(key) && key[..] != state_root(4)[..])
+			.unwrap();
+		drop(backend);
+
+		// Lose the body of the best block and a trie node of the finalized state.
+		let mut transaction = Transaction::new();
+		let key = utils::number_and_hash_to_lookup_key(5u64, blocks[5]).unwrap();
+		transaction.remove(columns::BODY, &key);
+		transaction.remove(columns::BODY_INDEX, &key);
+		transaction.remove(columns::STATE, &node);
+		database.commit(transaction).unwrap();
+
+		let report = check_database::<Block>(settings(&db, false), None, false).unwrap();
+		assert!(matches!(
+			&report.inconsistencies[..],
+			[
+				Inconsistency::InvalidBody(body, 5, _),
+				Inconsistency::MissingState(_, 5, _),
+				Inconsistency::MissingState(state, 4, _),
+			] if *body == blocks[5] && *state == blocks[4],
+		));
+		assert_eq!(report.last_consistent, Some(3));
+		assert_eq!(report.reverted, 0);
+
+		let report = check_database::<Block>(settings(&db, false), None, true).unwrap();
+		assert_eq!(report.reverted, 2);
+
+		let backend = Backend::<Block>::new(settings(&db, false), 8).unwrap();
+		let info = backend.blockchain.info();
+		assert_eq!((info.best_hash, info.finalized_hash), (blocks[3], blocks[3]));
+		let report = backend.check_integrity(None).unwrap();
+		assert!(report.inconsistencies.is_empty());
+		assert_eq!(report.last_consistent, Some(3));
+	}
+}
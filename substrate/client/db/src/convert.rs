@@ -0,0 +1,476 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline conversion of a RocksDB database to ParityDB.
+//!
+//! The columns of the source database are streamed in key order into a new database. The
+//! conversion progress is committed together with each batch of entries, so that an interrupted
+//! conversion resumes where it stopped. Once all the columns are copied, the best and finalized
+//! blocks of both databases are compared and every node of the finalized state is checked to be
+//! present in the new database.
+//!
+//! RocksDB stores trie nodes under their hash prefixed with their position in the trie, while
+//! ParityDB stores them under their hash and counts references. Trie nodes, the state-db journals
+//! and the reference counted transactions are rewritten accordingly. ParityDB only keeps hashes of
+//! the keys of most columns, so the conversion can't go the other way.
+
+use codec::{Decode, Encode};
+use kvdb::KeyValueDB;
+use log::info;
+use parking_lot::Mutex;
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_database::{error::DatabaseError, Database, Transaction};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor},
+};
+
+use crate::{
+	columns,
+	utils::{self, meta_keys, DatabaseType, COLUMN_META, NUM_COLUMNS},
+	DatabaseSource, DbHash, DB_HASH_LEN,
+};
+
+/// Size of the entries committed at once to the new database.
+const BATCH_SIZE: usize = 64 * 1024 * 1024;
+
+/// Column being copied and key of the last entry copied in this column, if any.
+type Progress = (u32, Option<Vec<u8>>);
+
+/// Summary of a database conversion.
+#[derive(Debug)]
+pub struct ConversionSummary<Block: BlockT> {
+	/// Number of entries copied by this run of the conversion.
+	pub copied: u64,
+	/// Best block of the converted database.
+	pub best: (Block::Hash, NumberFor<Block>),
+	/// Finalized block of the converted database.
+	pub finalized: (Block::Hash, NumberFor<Block>),
+	/// Root of the latest finalized state, if the database holds one.
+	pub finalized_state_root: Option<Block::Hash>,
+}
+
+/// Read-only view of a key-value database.
+///
+/// Reads through [`Database::get`] can't fail, so the first read error is kept until
+/// [`KvdbReader::result`] returns it.
+struct KvdbReader<'a> {
+	db: &'a dyn KeyValueDB,
+	error: Mutex<Option<std::io::Error>>,
+}
+
+impl<'a> KvdbReader<'a> {
+	fn new(db: &'a dyn KeyValueDB) -> Self {
+		Self { db, error: Mutex::new(None) }
+	}
+
+	/// Returns the first error met reading the database, if any, or else `result`.
+	fn result<T>(&self, result: ClientResult<T>) -> ClientResult<T> {
+		match self.error.lock().take() {
+			Some(e) => Err(ClientError::Backend(format!("Error reading source database: {e}"))),
+			None => result,
+		}
+	}
+}
+
+impl Database<DbHash> for KvdbReader<'_> {
+	fn commit(&self, _transaction: Transaction<DbHash>) -> Result<(), DatabaseError> {
+		Err(DatabaseError(Box::new(std::io::Error::other("Read-only database"))))
+	}
+
+	fn get(&self, col: u32, key: &[u8]) -> Option<Vec<u8>> {
+		self.db.get(col, key).unwrap_or_else(|e| {
+			self.error.lock().get_or_insert(e);
+			None
+		})
+	}
+}
+
+impl sc_state_db::MetaDb for KvdbReader<'_> {
+	type Error = DatabaseError;
+
+	fn get_meta(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+		self.db.get(columns::STATE_META, key).map_err(|e| DatabaseError(Box::new(e)))
+	}
+}
+
+/// Convert the database at `source` to the database at `destination`.
+///
+/// Only RocksDB databases can be converted, to ParityDB. If `destination` holds a partially
+/// converted database, the conversion is resumed.
+pub fn convert_database<Block: BlockT>(
+	source: &DatabaseSource,
+	destination: &DatabaseSource,
+) -> ClientResult<ConversionSummary<Block>> {
+	let destination_path = match destination {
+		DatabaseSource::ParityDb { path } => path,
+		_ => return Err(ClientError::Backend("Databases can only be converted to ParityDB".into())),
+	};
+	let source_path = match source {
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::RocksDb { path, .. } => Some(path),
+		_ => None,
+	}
+	.ok_or_else(|| ClientError::Backend("Only RocksDB databases can be converted".into()))?;
+	let source = utils::open_raw_rocksdb::<Block>(source_path)?;
+	let destination =
+		crate::parity_db::open::<DbHash>(destination_path, DatabaseType::Full, true, false)
+			.map_err(|e| ClientError::Backend(format!("Error opening ParityDB: {e}")))?;
+
+	convert::<Block>(&*source, &*destination)
+}
+
+/// Copy all the columns of `source` to `destination`, then check the result.
+fn convert<Block: BlockT>(
+	source: &dyn KeyValueDB,
+	destination: &dyn Database<DbHash>,
+) -> ClientResult<ConversionSummary<Block>> {
+	let (first_column, mut resume_after) =
+		match destination.get(COLUMN_META, meta_keys::CONVERSION_PROGRESS) {
+			Some(progress) => {
+				let progress = Progress::decode(&mut &progress[..]).map_err(|e| {
+					ClientError::Backend(format!("Error decoding conversion progress: {e}"))
+				})?;
+				info!(target: "db", "Resuming database conversion at column {}", progress.0);
+				progress
+			},
+			None if destination.get(COLUMN_META, meta_keys::TYPE).is_some() =>
+				return Err(ClientError::Backend("Destination database is not empty".into())),
+			None => (0, None),
+		};
+
+	let mut copied = 0;
+	for column in first_column..NUM_COLUMNS {
+		copied += copy_column::<Block>(source, destination, column, resume_after.take())?;
+	}
+
+	let mut transaction = Transaction::new();
+	transaction.remove(COLUMN_META, meta_keys::CONVERSION_PROGRESS);
+	destination.commit(transaction)?;
+
+	let summary = check_conversion::<Block>(&KvdbReader::new(source), destination, copied)?;
+	info!(
+		target: "db",
+		"Database converted, best block #{} ({}), finalized block #{} ({})",
+		summary.best.1,
+		summary.best.0,
+		summary.finalized.1,
+		summary.finalized.0,
+	);
+	Ok(summary)
+}
+
+/// Copy the entries of a column following `resume_after`, returning the number of entries copied.
+fn copy_column<Block: BlockT>(
+	source: &dyn KeyValueDB,
+	destination: &dyn Database<DbHash>,
+	column: u32,
+	resume_after: Option<Vec<u8>>,
+) -> ClientResult<u64> {
+	// The pending deletions of the pruning window cancelled by later insertions must not be applied
+	// by ParityDB.
+	let pending_deletions = match column {
+		columns::STATE_META => {
+			let reader = KvdbReader::new(source);
+			sc_state_db::pending_deletions::<Block::Hash, _>(&reader).map_err(|e| {
+				ClientError::Backend(format!("Error reading pruning journal: {e:?}"))
+			})?
+		},
+		_ => Default::default(),
+	};
+
+	let mut transaction = Transaction::new();
+	let mut batch_size = 0;
+	let mut copied = 0;
+	for entry in source.iter(column) {
+		let (key, value) = entry
+			.map_err(|e| ClientError::Backend(format!("Error reading column {column}: {e}")))?;
+		if resume_after.as_ref().is_some_and(|last| key[..] <= last[..]) {
+			continue
+		}
+
+		batch_size += key.len() + value.len();
+		match column {
+			columns::STATE => {
+				let mut node_key = key.to_vec();
+				destination.sanitize_key(&mut node_key);
+				transaction.set_from_vec(column, &node_key, value);
+			},
+			columns::STATE_META => {
+				let value = sc_state_db::map_journal_keys::<Block::Hash>(
+					&key,
+					&value,
+					&pending_deletions,
+					|key| destination.sanitize_key(key),
+				)
+				.map_err(|e| ClientError::Backend(format!("Error decoding journal record: {e}")))?
+				.unwrap_or(value);
+				transaction.set_from_vec(column, &key, value);
+			},
+			// Reference counters are stored by RocksDB next to the values, under the key followed
+			// by a zero byte.
+			columns::TRANSACTION if key.len() != DB_HASH_LEN => continue,
+			columns::TRANSACTION => {
+				let hash = DbHash::from_slice(&key);
+				for _ in 0..transaction_references(source, &key)? {
+					transaction.store(column, hash, value.clone());
+				}
+			},
+			_ => transaction.set_from_vec(column, &key, value),
+		}
+		copied += 1;
+
+		if batch_size >= BATCH_SIZE {
+			let progress: Progress = (column, Some(key.to_vec()));
+			transaction.set_from_vec(
+				COLUMN_META,
+				meta_keys::CONVERSION_PROGRESS,
+				progress.encode(),
+			);
+			destination.commit(std::mem::take(&mut transaction))?;
+			batch_size = 0;
+			info!(target: "db", "Converting column {column}: {copied} entries copied");
+		}
+	}
+
+	let progress: Progress = (column + 1, None);
+	transaction.set_from_vec(COLUMN_META, meta_keys::CONVERSION_PROGRESS, progress.encode());
+	destination.commit(transaction)?;
+	info!(target: "db", "Converted column {column}: {copied} entries copied");
+	Ok(copied)
+}
+
+/// Read the reference counter of a transaction stored in RocksDB.
+fn transaction_references(source: &dyn KeyValueDB, key: &[u8]) -> ClientResult<u32> {
+	let counter_key = [key, &[0]].concat();
+	let counter = source
+		.get(columns::TRANSACTION, &counter_key)
+		.map_err(|e| ClientError::Backend(format!("Error reading transaction counter: {e}")))?;
+	match counter {
+		Some(counter) => counter
+			.try_into()
+			.map(u32::from_le_bytes)
+			.map_err(|_| ClientError::Backend("Invalid transaction reference counter".into())),
+		None => Ok(1),
+	}
+}
+
+/// Check that the converted database has the same best and finalized blocks as the source, and
+/// that the finalized state can be read from it.
+fn check_conversion<Block: BlockT>(
+	source: &KvdbReader,
+	destination: &dyn Database<DbHash>,
+	copied: u64,
+) -> ClientResult<ConversionSummary<Block>> {
+	let expected = source.result(utils::read_meta::<Block>(source, columns::HEADER))?;
+	let meta = utils::read_meta::<Block>(destination, columns::HEADER)?;
+	if (meta.genesis_hash, meta.best_hash, meta.finalized_hash) !=
+		(expected.genesis_hash, expected.best_hash, expected.finalized_hash) ||
+		meta.finalized_state != expected.finalized_state
+	{
+		return Err(ClientError::Backend(format!(
+			"Converted database doesn't match its source: expected best block {} and finalized \
+			 block {}, found {} and {}",
+			expected.best_hash, expected.finalized_hash, meta.best_hash, meta.finalized_hash,
+		)))
+	}
+
+	let finalized_state_root = match meta.finalized_state {
+		Some((hash, _)) => {
+			let read_header = |db: &dyn Database<DbHash>| {
+				utils::read_header::<Block>(
+					db,
+					columns::KEY_LOOKUP,
+					columns::HEADER,
+					BlockId::Hash(hash),
+				)
+			};
+			let header = read_header(destination)?
+				.ok_or_else(|| ClientError::UnknownBlock(format!("Missing header {hash}")))?;
+			if source.result(read_header(source))?.as_ref() != Some(&header) {
+				return Err(ClientError::Backend(format!(
+					"Converted header of block {hash} doesn't match its source"
+				)))
+			}
+
+			let root = *header.state_root();
+			utils::for_each_state_node::<HashingFor<Block>>(destination, root, &mut |_, _| ())
+				.map_err(|e| {
+					ClientError::Backend(format!("Converted state of block {hash} is invalid: {e}"))
+				})?;
+			Some(root)
+		},
+		None => None,
+	};
+
+	Ok(ConversionSummary {
+		copied,
+		best: (meta.best_hash, meta.best_number),
+		finalized: (meta.finalized_hash, meta.finalized_number),
+		finalized_state_root,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_block, Block},
+		utils::SharedKvdb,
+		Backend, BlocksPruning, DatabaseSettings, PruningMode,
+	};
+	use sc_client_api::{backend::Backend as _, blockchain::Backend as _, TrieCacheContext};
+	use sp_blockchain::HeaderBackend;
+	use sp_core::H256;
+	use sp_runtime::testing::{MockCallU64, TestXt};
+	use sp_state_machine::{Backend as _, IndexOperation};
+	use std::{path::Path, sync::Arc};
+
+	type UncheckedXt = TestXt<MockCallU64, ()>;
+
+	fn settings(source: DatabaseSource) -> DatabaseSettings {
+		DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(PruningMode::blocks_pruning(2)),
+			source,
+			blocks_pruning: BlocksPruning::Some(2),
+			metrics_registry: None,
+		}
+	}
+
+	/// Create a pruned database with a finalized block, an unfinalized block and an indexed
+	/// transaction, returning the hashes of the blocks and of the transaction.
+	fn source_database() -> (SharedKvdb, Vec<H256>, H256) {
+		let db = SharedKvdb(Arc::new(kvdb_memorydb::create(NUM_COLUMNS)));
+		let source = DatabaseSource::Custom {
+			db: sp_database::as_database(db.clone()),
+			require_create_flag: true,
+		};
+		let backend = Backend::<Block>::new(settings(source), 0).unwrap();
+
+		let extrinsic = UncheckedXt::new_transaction(0.into(), ()).encode();
+		let extrinsic_hash = <HashingFor<Block> as sp_core::Hasher>::hash(&extrinsic[1..]);
+		let mut blocks = Vec::new();
+		let mut parent_hash = Default::default();
+		for number in 0..5 {
+			let index = match number {
+				3 => vec![IndexOperation::Insert {
+					extrinsic: 0,
+					hash: extrinsic_hash.as_ref().to_vec(),
+					size: (extrinsic.len() - 1) as u32,
+				}],
+				4 => vec![IndexOperation::Renew {
+					extrinsic: 0,
+					hash: extrinsic_hash.as_ref().to_vec(),
+				}],
+				_ => Vec::new(),
+			};
+			parent_hash = insert_block(
+				&backend,
+				number,
+				parent_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(0.into(), ())],
+				Some(index),
+			)
+			.unwrap();
+			blocks.push(parent_hash);
+		}
+		backend.finalize_block(blocks[3], None).unwrap();
+
+		(db, blocks, extrinsic_hash)
+	}
+
+	/// Check that the converted database can be opened and holds the source blocks and states.
+	fn check_converted(path: &Path, blocks: &[H256], extrinsic_hash: H256) {
+		let source = DatabaseSource::ParityDb { path: path.to_path_buf() };
+		let backend = Backend::<Block>::new(settings(source), 0).unwrap();
+		let info = backend.blockchain().info();
+		assert_eq!((info.best_hash, info.best_number), (blocks[4], 4));
+		assert_eq!((info.finalized_hash, info.finalized_number), (blocks[3], 3));
+
+		// Every state has an entry keyed by the hash of the parent block.
+		for number in 2..5 {
+			let state = backend.state_at(blocks[number], TrieCacheContext::Untrusted).unwrap();
+			let parent_hash = blocks[number - 1].as_ref().to_vec();
+			assert_eq!(state.storage(&parent_hash).unwrap(), Some(parent_hash));
+		}
+		assert!(backend.blockchain().indexed_transaction(extrinsic_hash).unwrap().is_some());
+	}
+
+	#[test]
+	fn database_is_converted() {
+		let (source, blocks, extrinsic_hash) = source_database();
+		let dir = tempfile::tempdir().unwrap();
+		let destination =
+			crate::parity_db::open::<DbHash>(dir.path(), DatabaseType::Full, true, false).unwrap();
+
+		let summary = convert::<Block>(&source, &*destination).unwrap();
+		assert!(summary.copied > 0);
+		assert_eq!(summary.best, (blocks[4], 4));
+		assert_eq!(summary.finalized, (blocks[3], 3));
+		assert!(summary.finalized_state_root.is_some());
+		assert_eq!(destination.get(COLUMN_META, meta_keys::CONVERSION_PROGRESS), None);
+
+		// The destination is now a complete database.
+		assert!(convert::<Block>(&source, &*destination).is_err());
+
+		drop(destination);
+		check_converted(dir.path(), &blocks, extrinsic_hash);
+	}
+
+	#[test]
+	fn interrupted_conversion_is_resumed() {
+		let (source, blocks, extrinsic_hash) = source_database();
+		let dir = tempfile::tempdir().unwrap();
+		let destination =
+			crate::parity_db::open::<DbHash>(dir.path(), DatabaseType::Full, true, false).unwrap();
+
+		// Stop after the first header.
+		for column in 0..columns::HEADER {
+			copy_column::<Block>(&source, &*destination, column, None).unwrap();
+		}
+		let (key, value) = source.iter(columns::HEADER).next().unwrap().unwrap();
+		let mut transaction = Transaction::new();
+		transaction.set_from_vec(columns::HEADER, &key, value);
+		let progress: Progress = (columns::HEADER, Some(key.to_vec()));
+		transaction.set_from_vec(COLUMN_META, meta_keys::CONVERSION_PROGRESS, progress.encode());
+		destination.commit(transaction).unwrap();
+
+		// The partially converted database can't be opened.
+		let partial =
+			DatabaseSource::Custom { db: destination.clone(), require_create_flag: false };
+		assert!(Backend::<Block>::new(settings(partial), 0).is_err());
+
+		let summary = convert::<Block>(&source, &*destination).unwrap();
+		assert_eq!(summary.finalized, (blocks[3], 3));
+
+		drop(destination);
+		check_converted(dir.path(), &blocks, extrinsic_hash);
+	}
+
+	#[test]
+	fn only_rocksdb_is_converted_to_paritydb() {
+		let dir = tempfile::tempdir().unwrap();
+		let paritydb = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let other = DatabaseSource::ParityDb { path: dir.path().join("other") };
+		assert!(convert_database::<Block>(&paritydb, &other).is_err());
+		assert!(!dir.path().join("other").exists());
+	}
+}
@@ -0,0 +1,288 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Storage of the extrinsic and event index.
+//!
+//! The index is kept in the [`columns::INDEX`] column and written in the transaction importing
+//! the block. The items of a block are stored under their key followed by the number and hash of
+//! the block. The blocks with items under a key are listed by buckets of [`BUCKET_SIZE`] block
+//! numbers, so that a range of blocks is looked up without iterating the database. The keys
+//! written for a block are recorded as well, so that writing the block again replaces its entries.
+//! Entries of blocks which are not canonical are skipped when querying.
+//!
+//! Blocks which are not completely indexed are recorded as well, namely the blocks imported
+//! without being executed or with items the indexer could not decode, and the blocks imported
+//! before the index was enabled. Queries over ranges containing such canonical blocks fail. The
+//! last block indexed is recorded, so that enabling the index again after blocks were imported
+//! without it starts the index over from the next block.
+//!
+//! Buckets are read, modified and written back, which relies on blocks being committed one after
+//! the other.
+
+use std::collections::BTreeMap;
+
+use codec::{Decode, Encode};
+use sc_client_api::{IndexEntry, IndexKey, IndexedItem, IndexedItems, IndexedLocation};
+use sp_blockchain::{Error as ClientError, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::traits::{Block as BlockT, NumberFor, SaturatedConversion};
+
+use crate::{columns, DbHash};
+
+/// Number of block numbers covered by a bucket.
+const BUCKET_SIZE: u64 = 256;
+
+/// Prefix of the items of a block indexed under a key.
+const ITEMS_PREFIX: u8 = 0;
+/// Prefix of the buckets of blocks which are not completely indexed.
+const INCOMPLETE_PREFIX: u8 = 1;
+/// Key of the number of the first block indexed.
+const FIRST_BLOCK_KEY: &[u8] = &[2];
+/// Prefix of the buckets of blocks with items under a key.
+const BLOCKS_PREFIX: u8 = 3;
+/// Prefix of the keys written for a block.
+const BLOCK_KEYS_PREFIX: u8 = 4;
+/// Key of the highest number of the blocks indexed.
+const LAST_BLOCK_KEY: &[u8] = &[5];
+
+fn decode_error(err: codec::Error) -> ClientError {
+	ClientError::Backend(format!("Error decoding index entry: {err}"))
+}
+
+fn with_block<Block: BlockT>(mut db_key: Vec<u8>, number: u64, hash: &Block::Hash) -> Vec<u8> {
+	db_key.extend_from_slice(&number.to_be_bytes());
+	db_key.extend_from_slice(hash.as_ref());
+	db_key
+}
+
+/// Key of the items of a key in a block.
+fn items_key<Block: BlockT>(key: &IndexKey, number: u64, hash: &Block::Hash) -> Vec<u8> {
+	let mut db_key = vec![ITEMS_PREFIX];
+	key.encode_to(&mut db_key);
+	with_block::<Block>(db_key, number, hash)
+}
+
+/// Key of the keys written for a block.
+fn block_keys_key<Block: BlockT>(number: u64, hash: &Block::Hash) -> Vec<u8> {
+	with_block::<Block>(vec![BLOCK_KEYS_PREFIX], number, hash)
+}
+
+/// Key of the bucket of blocks with items under a key.
+fn blocks_key(key: &IndexKey, bucket: u64) -> Vec<u8> {
+	let mut db_key = vec![BLOCKS_PREFIX];
+	key.encode_to(&mut db_key);
+	db_key.extend_from_slice(&bucket.to_be_bytes());
+	db_key
+}
+
+/// Key of the bucket of incomplete blocks.
+fn incomplete_key(bucket: u64) -> Vec<u8> {
+	let mut db_key = vec![INCOMPLETE_PREFIX];
+	db_key.extend_from_slice(&bucket.to_be_bytes());
+	db_key
+}
+
+fn read<T: Decode + Default>(db: &dyn Database<DbHash>, db_key: &[u8]) -> ClientResult<T> {
+	db.get(columns::INDEX, db_key)
+		.map(|value| T::decode(&mut &value[..]).map_err(decode_error))
+		.transpose()
+		.map(Option::unwrap_or_default)
+}
+
+/// Add a block to or remove it from a bucket of blocks, kept ordered by number and hash.
+fn update_bucket<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	db: &dyn Database<DbHash>,
+	db_key: &[u8],
+	block: (u64, Block::Hash),
+	insert: bool,
+) -> ClientResult<()> {
+	let mut blocks = read::<Vec<(u64, Block::Hash)>>(db, db_key)?;
+	match (blocks.binary_search(&block), insert) {
+		(Err(position), true) => blocks.insert(position, block),
+		(Ok(position), false) => {
+			blocks.remove(position);
+		},
+		_ => return Ok(()),
+	}
+	if blocks.is_empty() {
+		transaction.remove(columns::INDEX, db_key);
+	} else {
+		transaction.set_from_vec(columns::INDEX, db_key, blocks.encode());
+	}
+	Ok(())
+}
+
+/// Record that the blocks from `first_block` on are indexed.
+///
+/// The first block indexed is kept if the index was already enabled and all the blocks below
+/// `first_block` were indexed. Otherwise blocks were imported while the index was disabled, and
+/// the index starts over from `first_block`.
+pub(crate) fn enable(
+	transaction: &mut Transaction<DbHash>,
+	db: &dyn Database<DbHash>,
+	first_block: u64,
+) -> ClientResult<()> {
+	let decode = |value: Vec<u8>| u64::decode(&mut &value[..]).map_err(decode_error);
+	// The number of the first block which is not indexed.
+	let next_block = match db.get(columns::INDEX, LAST_BLOCK_KEY) {
+		Some(last_block) => Some(decode(last_block)? + 1),
+		None => db.get(columns::INDEX, FIRST_BLOCK_KEY).map(decode).transpose()?,
+	};
+	if next_block.map_or(true, |next_block| next_block < first_block) {
+		transaction.set_from_vec(columns::INDEX, FIRST_BLOCK_KEY, first_block.encode());
+	}
+	Ok(())
+}
+
+/// Write the index entries of a block to `transaction`.
+///
+/// Entries previously written for the same block are replaced: keys which are not indexed
+/// anymore are removed. A block which is not `complete` is recorded so that queries including
+/// it fail.
+pub(crate) fn write_block<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	db: &dyn Database<DbHash>,
+	number: NumberFor<Block>,
+	hash: Block::Hash,
+	entries: Vec<IndexEntry>,
+	complete: bool,
+) -> ClientResult<()> {
+	let number = number.saturated_into::<u64>();
+	let bucket = number / BUCKET_SIZE;
+	let mut by_key = BTreeMap::<IndexKey, Vec<IndexedItem>>::new();
+	for (key, item) in entries {
+		by_key.entry(key).or_default().push(item);
+	}
+
+	let block_keys = block_keys_key::<Block>(number, &hash);
+	for stale in read::<Vec<IndexKey>>(db, &block_keys)? {
+		if !by_key.contains_key(&stale) {
+			transaction.remove(columns::INDEX, &items_key::<Block>(&stale, number, &hash));
+			update_bucket::<Block>(
+				transaction,
+				db,
+				&blocks_key(&stale, bucket),
+				(number, hash),
+				false,
+			)?;
+		}
+	}
+
+	for (key, items) in &mut by_key {
+		items.sort();
+		items.dedup();
+		transaction.set_from_vec(
+			columns::INDEX,
+			&items_key::<Block>(key, number, &hash),
+			items.encode(),
+		);
+		update_bucket::<Block>(transaction, db, &blocks_key(key, bucket), (number, hash), true)?;
+	}
+	if by_key.is_empty() {
+		transaction.remove(columns::INDEX, &block_keys);
+	} else {
+		let keys = by_key.into_keys().collect::<Vec<_>>();
+		transaction.set_from_vec(columns::INDEX, &block_keys, keys.encode());
+	}
+
+	if number >= read::<u64>(db, LAST_BLOCK_KEY)? {
+		transaction.set_from_vec(columns::INDEX, LAST_BLOCK_KEY, number.encode());
+	}
+
+	update_bucket::<Block>(transaction, db, &incomplete_key(bucket), (number, hash), !complete)
+}
+
+/// Returns the items indexed under `key` in the canonical blocks `from..=to`, or `None` if the
+/// index is not enabled.
+///
+/// At most `max_blocks` blocks with items under `key` are looked up. `canonical_hash` returns
+/// the hash of the canonical block with the given number.
+pub(crate) fn read_items<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	canonical_hash: impl Fn(NumberFor<Block>) -> ClientResult<Option<Block::Hash>>,
+	key: &IndexKey,
+	from: NumberFor<Block>,
+	to: NumberFor<Block>,
+	max_blocks: usize,
+) -> ClientResult<Option<IndexedItems<Block>>> {
+	let Some(first_block) = db.get(columns::INDEX, FIRST_BLOCK_KEY) else { return Ok(None) };
+	let first_block = u64::decode(&mut &first_block[..]).map_err(decode_error)?;
+
+	let mut page = IndexedItems { locations: Vec::new(), next: None };
+	let (from, to) = (from.saturated_into::<u64>(), to.saturated_into::<u64>());
+	if from > to {
+		return Ok(Some(page))
+	}
+	if from < first_block {
+		return Err(ClientError::Backend(format!(
+			"Blocks below #{first_block} were imported before the index was enabled"
+		)))
+	}
+
+	// Look up the blocks with items, stopping between two block numbers once `max_blocks` were
+	// looked up, so that the query can be resumed from the next block number.
+	let max_blocks = max_blocks.max(1);
+	let mut looked_up = 0;
+	let mut last = to;
+	let mut previous = None;
+	'buckets: for bucket in from / BUCKET_SIZE..=to / BUCKET_SIZE {
+		for (number, hash) in read::<Vec<(u64, Block::Hash)>>(db, &blocks_key(key, bucket))? {
+			if number < from {
+				continue
+			}
+			if number > to {
+				break 'buckets
+			}
+			if looked_up >= max_blocks && previous != Some(number) {
+				page.next = Some(number.saturated_into());
+				last = number - 1;
+				break 'buckets
+			}
+			looked_up += 1;
+			previous = Some(number);
+
+			if canonical_hash(number.saturated_into())? != Some(hash) {
+				continue
+			}
+			let items = read::<Vec<IndexedItem>>(db, &items_key::<Block>(key, number, &hash))?;
+			page.locations.extend(items.into_iter().map(|item| IndexedLocation {
+				number: number.saturated_into(),
+				hash,
+				item,
+			}));
+		}
+	}
+
+	// Fail if a canonical block of the page is not completely indexed.
+	for bucket in from / BUCKET_SIZE..=last / BUCKET_SIZE {
+		for (number, hash) in read::<Vec<(u64, Block::Hash)>>(db, &incomplete_key(bucket))? {
+			if number < from || number > last {
+				continue
+			}
+			if canonical_hash(number.saturated_into())? == Some(hash) {
+				return Err(ClientError::Backend(format!(
+					"Block #{number} ({hash:?}) is not completely indexed, it was imported without \
+					 being executed or with items which could not be decoded"
+				)))
+			}
+		}
+	}
+
+	Ok(Some(page))
+}
@@ -32,10 +32,15 @@ pub mod offchain;
 
 pub mod bench;
 
+mod check;
 mod children;
+mod convert;
+mod index;
 mod parity_db;
 mod pinned_blocks_cache;
+mod prune;
 mod record_stats_state;
+mod snapshot;
 mod stats;
 #[cfg(any(feature = "rocksdb", test))]
 mod upgrade;
@@ -49,7 +54,10 @@ use std::{
 	collections::{HashMap, HashSet},
 	io,
 	path::{Path, PathBuf},
-	sync::Arc,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
 };
 
 use crate::{
@@ -65,7 +73,8 @@ use sc_client_api::{
 	blockchain::{BlockGap, BlockGapType},
 	leaves::{FinalizationOutcome, LeafSet},
 	utils::is_descendent_of,
-	IoInfo, MemoryInfo, MemorySize, TrieCacheContext, UsageInfo,
+	IndexEntry, IndexKey, IndexedItems, IoInfo, MemoryInfo, MemorySize, TrieCacheContext,
+	UsageInfo,
 };
 use sc_state_db::{IsPruned, LastCanonicalized, StateDb};
 use sp_arithmetic::traits::Saturating;
@@ -100,6 +109,10 @@ pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use check::{check_database, Inconsistency, IntegrityReport};
+pub use convert::{convert_database, ConversionSummary};
+pub use prune::{prune_database, PruningSummary};
+pub use snapshot::{read_snapshot, Snapshot, SnapshotSummary};
 
 const CACHE_HEADERS: usize = 8;
 
@@ -442,6 +455,8 @@ pub(crate) mod columns {
 	/// Transactions
 	pub const TRANSACTION: u32 = 11;
 	pub const BODY_INDEX: u32 = 12;
+	/// Extrinsic and event index, see [`crate::index`].
+	pub const INDEX: u32 = 13;
 }
 
 struct PendingBlock<Block: BlockT> {
@@ -841,6 +856,7 @@ pub struct BlockImportOperation<Block: BlockT> {
 	create_gap: bool,
 	reset_storage: bool,
 	index_ops: Vec<IndexOperation>,
+	index_entries: Option<(Vec<IndexEntry>, bool)>,
 }
 
 impl<Block: BlockT> BlockImportOperation<Block> {
@@ -999,6 +1015,11 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
 	fn set_create_gap(&mut self, create_gap: bool) {
 		self.create_gap = create_gap;
 	}
+
+	fn set_index_entries(&mut self, entries: Vec<IndexEntry>, complete: bool) -> ClientResult<()> {
+		self.index_entries = Some((entries, complete));
+		Ok(())
+	}
 }
 
 struct StorageDb<Block: BlockT> {
@@ -1121,6 +1142,7 @@ pub struct Backend<Block: BlockT> {
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
 	shared_trie_cache: Option<sp_trie::cache::SharedTrieCache<HashingFor<Block>>>,
+	index_enabled: AtomicBool,
 }
 
 impl<Block: BlockT> Backend<Block> {
@@ -1183,6 +1205,22 @@ impl<Block: BlockT> Backend<Block> {
 		Self::new(db_setting, canonicalization_delay).expect("failed to create test-db")
 	}
 
+	/// Enable the extrinsic and event index, kept in the [`columns::INDEX`] column.
+	///
+	/// Blocks imported before the index was enabled are not indexed. If blocks were imported while
+	/// the index was disabled, it only covers the blocks imported from now on.
+	pub fn enable_index(&self) -> ClientResult<()> {
+		let best_number = self.blockchain.info().best_number;
+		// The genesis block has neither extrinsics nor events.
+		let first_block =
+			if best_number.is_zero() { 0 } else { best_number.saturated_into::<u64>() + 1 };
+		let mut transaction = Transaction::new();
+		index::enable(&mut transaction, &*self.storage.db, first_block)?;
+		self.storage.db.commit(transaction)?;
+		self.index_enabled.store(true, Ordering::Relaxed);
+		Ok(())
+	}
+
 	/// Expose the Database that is used by this backend.
 	/// The second argument is the Column that stores the State.
 	///
@@ -1216,6 +1254,18 @@ impl<Block: BlockT> Backend<Block> {
 		config: &DatabaseSettings,
 		should_init: bool,
 	) -> ClientResult<Self> {
+		if db.get(columns::META, meta_keys::PRUNING_PROGRESS).is_some() {
+			return Err(sp_blockchain::Error::Backend(
+				"Offline pruning of the database was interrupted, run it again to complete it"
+					.into(),
+			))
+		}
+		if db.get(columns::META, meta_keys::CONVERSION_PROGRESS).is_some() {
+			return Err(sp_blockchain::Error::Backend(
+				"Conversion of the database was interrupted, run it again to complete it".into(),
+			))
+		}
+
 		let mut db_init_transaction = Transaction::new();
 
 		let requested_state_pruning = config.state_pruning.clone();
@@ -1269,6 +1319,7 @@ impl<Block: BlockT> Backend<Block> {
 			blocks_pruning: config.blocks_pruning,
 			genesis_state: RwLock::new(None),
 			shared_trie_cache,
+			index_enabled: AtomicBool::new(false),
 		};
 
 		// Older DB versions have no last state key. Check if the state is available and set it.
@@ -1550,6 +1601,18 @@ impl<Block: BlockT> Backend<Block> {
 					justifications.encode(),
 				);
 			}
+			if let Some((entries, complete)) = operation.index_entries {
+				if self.index_enabled.load(Ordering::Relaxed) {
+					index::write_block::<Block>(
+						&mut transaction,
+						&*self.storage.db,
+						number,
+						hash,
+						entries,
+						complete,
+					)?;
+				}
+			}
 
 			if number.is_zero() {
 				transaction.set(columns::META, meta_keys::GENESIS_HASH, hash.as_ref());
@@ -1978,44 +2041,7 @@ impl<Block: BlockT> Backend<Block> {
 		id: BlockId<Block>,
 	) -> ClientResult<()> {
 		debug!(target: "db", "Removing block #{id}");
-		utils::remove_from_db(
-			transaction,
-			&*self.storage.db,
-			columns::KEY_LOOKUP,
-			columns::BODY,
-			id,
-		)?;
-		utils::remove_from_db(
-			transaction,
-			&*self.storage.db,
-			columns::KEY_LOOKUP,
-			columns::JUSTIFICATIONS,
-			id,
-		)?;
-		if let Some(index) =
-			read_db(&*self.storage.db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?
-		{
-			utils::remove_from_db(
-				transaction,
-				&*self.storage.db,
-				columns::KEY_LOOKUP,
-				columns::BODY_INDEX,
-				id,
-			)?;
-			match Vec::<DbExtrinsic<Block>>::decode(&mut &index[..]) {
-				Ok(index) =>
-					for ex in index {
-						if let DbExtrinsic::Indexed { hash, .. } = ex {
-							transaction.release(columns::TRANSACTION, hash);
-						}
-					},
-				Err(err) =>
-					return Err(sp_blockchain::Error::Backend(format!(
-						"Error decoding body list: {err}",
-					))),
-			}
-		}
-		Ok(())
+		remove_block_body(transaction, &*self.storage.db, id)
 	}
 
 	fn empty_state(&self) -> RecordStatsState<RefTrackingState<Block>, Block> {
@@ -2028,6 +2054,32 @@ impl<Block: BlockT> Backend<Block> {
 	}
 }
 
+/// Remove the body, justifications and indexed transactions of a block.
+fn remove_block_body<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	db: &dyn Database<DbHash>,
+	id: BlockId<Block>,
+) -> ClientResult<()> {
+	utils::remove_from_db(transaction, db, columns::KEY_LOOKUP, columns::BODY, id)?;
+	utils::remove_from_db(transaction, db, columns::KEY_LOOKUP, columns::JUSTIFICATIONS, id)?;
+	if let Some(index) = read_db(db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)? {
+		utils::remove_from_db(transaction, db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?;
+		match Vec::<DbExtrinsic<Block>>::decode(&mut &index[..]) {
+			Ok(index) =>
+				for ex in index {
+					if let DbExtrinsic::Indexed { hash, .. } = ex {
+						transaction.release(columns::TRANSACTION, hash);
+					}
+				},
+			Err(err) =>
+				return Err(sp_blockchain::Error::Backend(format!(
+					"Error decoding body list: {err}",
+				))),
+		}
+	}
+	Ok(())
+}
+
 fn apply_state_commit(
 	transaction: &mut Transaction<DbHash>,
 	commit: sc_state_db::CommitSet<Vec<u8>>,
@@ -2164,6 +2216,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			create_gap: true,
 			reset_storage: false,
 			index_ops: Default::default(),
+			index_entries: Default::default(),
 		})
 	}
 
@@ -2634,6 +2687,26 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 		)
 	}
 
+	fn indexed_items(
+		&self,
+		key: &IndexKey,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+		max_blocks: usize,
+	) -> ClientResult<Option<IndexedItems<Block>>> {
+		if !self.index_enabled.load(Ordering::Relaxed) {
+			return Ok(None)
+		}
+		index::read_items::<Block>(
+			&*self.storage.db,
+			|number| self.blockchain.hash(number),
+			key,
+			from,
+			to,
+			max_blocks,
+		)
+	}
+
 	fn pin_block(&self, hash: <Block as BlockT>::Hash) -> sp_blockchain::Result<()> {
 		let hint = || {
 			let header_metadata = self.blockchain.header_metadata(hash);
@@ -5015,4 +5088,170 @@ pub(crate) mod tests {
 		backend.unpin_block(fork_hash_3);
 		assert!(bc.body(fork_hash_3).unwrap().is_none());
 	}
+
+	#[test]
+	fn indexed_items_are_queried_on_canonical_chain() {
+		use sc_client_api::{IndexedItem, IndexedLocation};
+
+		let backend = Backend::<Block>::new_test(1000, 100);
+		let alice = IndexKey::Account(vec![1; 32]);
+		let transfer = IndexKey::Event { pallet: "Balances".into(), name: "Transfer".into() };
+		assert!(backend.indexed_items(&alice, 0, 1, 10).unwrap().is_none());
+		backend.enable_index().unwrap();
+
+		let insert = |number: u64, parent_hash: H256, extrinsics_root: H256, entries, complete| {
+			let header = Header {
+				number,
+				parent_hash,
+				state_root: Default::default(),
+				digest: Default::default(),
+				extrinsics_root,
+			};
+			let state =
+				if extrinsics_root.is_zero() { NewBlockState::Best } else { NewBlockState::Normal };
+			let mut op = backend.begin_operation().unwrap();
+			op.set_block_data(header.clone(), Some(vec![]), None, None, state).unwrap();
+			op.set_index_entries(entries, complete).unwrap();
+			backend.commit_operation(op).unwrap();
+			header.hash()
+		};
+
+		let mut hashes = vec![];
+		let mut parent_hash = Default::default();
+		for number in 0..300 {
+			let entries = match number {
+				1 => vec![
+					(alice.clone(), IndexedItem::Extrinsic(1)),
+					(alice.clone(), IndexedItem::Event(2)),
+					(transfer.clone(), IndexedItem::Event(2)),
+				],
+				2 => vec![(alice.clone(), IndexedItem::Extrinsic(0))],
+				257 => vec![(alice.clone(), IndexedItem::Event(0))],
+				_ => vec![],
+			};
+			// Block 280 is imported without being executed.
+			parent_hash = insert(number, parent_hash, Default::default(), entries, number != 280);
+			hashes.push(parent_hash);
+		}
+		// Fork blocks with entries of their own, which must not be returned.
+		insert(
+			1,
+			hashes[0],
+			H256::repeat_byte(1),
+			vec![(alice.clone(), IndexedItem::Extrinsic(0))],
+			true,
+		);
+		insert(270, hashes[269], H256::repeat_byte(1), vec![], false);
+
+		let location = |number: usize, item| IndexedLocation::<Block> {
+			number: number as u64,
+			hash: hashes[number],
+			item,
+		};
+		let page = backend.indexed_items(&alice, 0, 279, 10).unwrap().unwrap();
+		assert_eq!(
+			page.locations,
+			vec![
+				location(1, IndexedItem::Extrinsic(1)),
+				location(1, IndexedItem::Event(2)),
+				location(2, IndexedItem::Extrinsic(0)),
+				location(257, IndexedItem::Event(0)),
+			],
+		);
+		assert_eq!(page.next, None);
+
+		// Both blocks #1 are looked up in the first page.
+		let page = backend.indexed_items(&alice, 0, 279, 1).unwrap().unwrap();
+		assert_eq!(
+			page.locations,
+			vec![location(1, IndexedItem::Extrinsic(1)), location(1, IndexedItem::Event(2))],
+		);
+		assert_eq!(page.next, Some(2));
+		let page = backend.indexed_items(&alice, 2, 279, 1).unwrap().unwrap();
+		assert_eq!(page.locations, vec![location(2, IndexedItem::Extrinsic(0))]);
+		assert_eq!(page.next, Some(257));
+		let page = backend.indexed_items(&alice, 257, 279, 1).unwrap().unwrap();
+		assert_eq!(page.locations, vec![location(257, IndexedItem::Event(0))]);
+		assert_eq!(page.next, None);
+
+		assert_eq!(
+			backend.indexed_items(&transfer, 0, 279, 10).unwrap().unwrap().locations,
+			vec![location(1, IndexedItem::Event(2))],
+		);
+		assert!(backend
+			.indexed_items(&transfer, 2, 1, 10)
+			.unwrap()
+			.unwrap()
+			.locations
+			.is_empty());
+
+		// The canonical block #280 was not executed.
+		assert!(backend.indexed_items(&transfer, 0, 299, 10).is_err());
+		assert!(backend.indexed_items(&transfer, 281, 299, 10).is_ok());
+	}
+
+	/// Insert a block header, recording that the block was indexed without entries.
+	fn insert_indexed_header(backend: &Backend<Block>, number: u64, parent_hash: H256) -> H256 {
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: Default::default(),
+			digest: Default::default(),
+			extrinsics_root: Default::default(),
+		};
+		let mut op = backend.begin_operation().unwrap();
+		op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Best)
+			.unwrap();
+		op.set_index_entries(vec![], true).unwrap();
+		backend.commit_operation(op).unwrap();
+		header.hash()
+	}
+
+	#[test]
+	fn blocks_imported_before_the_index_are_not_queried() {
+		let backend = Backend::<Block>::new_test(1000, 100);
+		let mut parent_hash = Default::default();
+		for number in 0..3 {
+			parent_hash = insert_header(&backend, number, parent_hash, None, Default::default());
+		}
+		backend.enable_index().unwrap();
+
+		// The first indexed block is kept when the index is enabled again.
+		for number in 3..5 {
+			parent_hash = insert_indexed_header(&backend, number, parent_hash);
+		}
+		backend.enable_index().unwrap();
+		let key = IndexKey::Account(vec![1; 32]);
+		assert!(backend.indexed_items(&key, 2, 10, 10).is_err());
+		assert!(backend.indexed_items(&key, 3, 10, 10).unwrap().unwrap().locations.is_empty());
+	}
+
+	#[test]
+	fn blocks_imported_while_the_index_is_disabled_are_not_queried() {
+		let backend = Backend::<Block>::new_test(1000, 100);
+		backend.enable_index().unwrap();
+		let mut parent_hash = Default::default();
+		for number in 0..3 {
+			parent_hash = insert_indexed_header(&backend, number, parent_hash);
+		}
+
+		// Blocks imported while the index is disabled are not indexed, even with entries.
+		backend.index_enabled.store(false, Ordering::Relaxed);
+		for number in 3..5 {
+			parent_hash = insert_indexed_header(&backend, number, parent_hash);
+		}
+		backend.enable_index().unwrap();
+		parent_hash = insert_indexed_header(&backend, 5, parent_hash);
+
+		let key = IndexKey::Account(vec![1; 32]);
+		assert!(backend.indexed_items(&key, 0, 10, 10).is_err());
+		assert!(backend.indexed_items(&key, 4, 10, 10).is_err());
+		assert!(backend.indexed_items(&key, 5, 10, 10).unwrap().unwrap().locations.is_empty());
+
+		// Without blocks imported in between, enabling the index again keeps its first block.
+		backend.index_enabled.store(false, Ordering::Relaxed);
+		backend.enable_index().unwrap();
+		insert_indexed_header(&backend, 6, parent_hash);
+		assert!(backend.indexed_items(&key, 5, 10, 10).unwrap().unwrap().locations.is_empty());
+	}
 }
@@ -51,6 +51,7 @@ pub fn open<H: Clone + AsRef<[u8]>>(
 				columns::BODY_INDEX,
 				columns::TRANSACTION,
 				columns::JUSTIFICATIONS,
+				columns::INDEX,
 			];
 
 			for i in compressed {
@@ -0,0 +1,613 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline pruning of an archive database.
+//!
+//! The states of the last canonical blocks are walked to find the trie nodes they use, along with
+//! the states of the blocks above them when the database is in [`PruningMode::ArchiveAll`] mode.
+//! The state-db metadata is then switched to [`PruningMode::Constrained`] with these blocks in the
+//! pruning window, and every other trie node is removed from the database. When blocks pruning is
+//! enabled, the bodies and justifications of old finalized blocks are removed as well.
+//!
+//! The database can't be opened until the removal completes. An interrupted pruning is resumed by
+//! running it again.
+//!
+//! Only RocksDB databases can be pruned: ParityDB doesn't keep the keys of the trie nodes, so the
+//! unused nodes can't be found. The keys of the nodes in use are written to a temporary RocksDB
+//! database next to the pruned one, so that they don't have to fit in memory.
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+};
+
+use codec::{Decode, Encode};
+use kvdb::KeyValueDB;
+use log::info;
+use sc_client_api::blockchain::Backend as _;
+use sc_state_db::{Constraints, LastCanonicalized, StateDb};
+use sp_blockchain::{Error as ClientError, HeaderBackend, HeaderMetadata, Result as ClientResult};
+use sp_database::{Database, Transaction};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor},
+	SaturatedConversion,
+};
+
+use crate::{
+	apply_state_commit, columns, remove_block_body,
+	utils::{self, meta_keys, DatabaseType, SharedKvdb, COLUMN_META},
+	BlockchainDb, BlocksPruning, DatabaseSettings, DbHash, PruningMode, StateMetaDb,
+};
+
+/// Number of entries removed from the database at once.
+const BATCH_SIZE: u64 = 100_000;
+
+/// Number of trie nodes marked in use kept in memory before being written to the mark set.
+const MARK_BATCH_SIZE: usize = 1_000_000;
+
+/// Column of the mark set holding the trie nodes in use.
+const MARK_COLUMN: u32 = 0;
+
+/// First and last canonical blocks whose state is kept, and roots of all the states kept.
+type Progress<Hash> = (u64, u64, Vec<Hash>);
+
+/// Keys of the trie nodes in use, with the number of the last canonical block using each of them.
+///
+/// The keys are written to a database of their own, the mark set, by batches of
+/// [`MARK_BATCH_SIZE`] keys.
+struct NodesInUse<'a> {
+	marks: &'a dyn KeyValueDB,
+	/// Nodes marked since the last write to the mark set.
+	pending: HashMap<Vec<u8>, u64>,
+}
+
+impl<'a> NodesInUse<'a> {
+	fn new(marks: &'a dyn KeyValueDB) -> Self {
+		Self { marks, pending: HashMap::new() }
+	}
+
+	/// Number of the last block using the node with the given key, if it is in use.
+	fn last_used(&self, key: &[u8]) -> ClientResult<Option<u64>> {
+		if let Some(last_used) = self.pending.get(key) {
+			return Ok(Some(*last_used))
+		}
+		let value = self
+			.marks
+			.get(MARK_COLUMN, key)
+			.map_err(|e| ClientError::Backend(format!("Error reading mark set: {e}")))?;
+		value
+			.map(|value| u64::decode(&mut &value[..]))
+			.transpose()
+			.map_err(|e| ClientError::Backend(format!("Error decoding mark: {e}")))
+	}
+
+	/// Mark the node with the given key as used until block `number`.
+	fn mark(&mut self, key: &[u8], number: u64) -> ClientResult<()> {
+		if self.last_used(key)?.is_some_and(|last_used| last_used >= number) {
+			return Ok(())
+		}
+		self.pending.insert(key.to_vec(), number);
+		if self.pending.len() >= MARK_BATCH_SIZE {
+			self.flush()?;
+		}
+		Ok(())
+	}
+
+	/// Write the pending marks to the mark set.
+	fn flush(&mut self) -> ClientResult<()> {
+		let mut transaction = self.marks.transaction();
+		for (key, number) in self.pending.drain() {
+			transaction.put_vec(MARK_COLUMN, &key, number.encode());
+		}
+		self.marks
+			.write(transaction)
+			.map_err(|e| ClientError::Backend(format!("Error writing mark set: {e}")))
+	}
+
+	/// Keys of the nodes in use written to the mark set, with the last block using them.
+	fn iter(&self) -> impl Iterator<Item = ClientResult<(Vec<u8>, u64)>> + '_ {
+		self.marks.iter(MARK_COLUMN).map(|entry| {
+			let (key, value) =
+				entry.map_err(|e| ClientError::Backend(format!("Error reading mark set: {e}")))?;
+			let last_used = u64::decode(&mut &value[..])
+				.map_err(|e| ClientError::Backend(format!("Error decoding mark: {e}")))?;
+			Ok((key.to_vec(), last_used))
+		})
+	}
+}
+
+/// Summary of an offline pruning.
+#[derive(Debug)]
+pub struct PruningSummary<Block: BlockT> {
+	/// First and last canonical blocks whose state is kept.
+	pub window: (NumberFor<Block>, NumberFor<Block>),
+	/// Number of trie nodes kept.
+	pub kept_nodes: u64,
+	/// Number of trie nodes removed.
+	pub removed_nodes: u64,
+	/// Number of blocks whose body was removed.
+	pub removed_bodies: u64,
+}
+
+/// Prune the archive database of `settings` in place.
+///
+/// The state of the number of canonical blocks given by `settings.state_pruning` is kept, and the
+/// bodies of finalized blocks are removed according to `settings.blocks_pruning`. If a previous
+/// pruning of the database was interrupted, it is resumed.
+pub fn prune_database<Block: BlockT>(
+	settings: &DatabaseSettings,
+) -> ClientResult<PruningSummary<Block>> {
+	let state_blocks = match settings.state_pruning.clone().unwrap_or_default() {
+		PruningMode::Constrained(Constraints { max_blocks: Some(blocks) }) if blocks > 0 => blocks,
+		_ =>
+			return Err(ClientError::Backend(
+				"State pruning must keep the state of a number of blocks".into(),
+			)),
+	};
+	let path = match &settings.source {
+		#[cfg(feature = "rocksdb")]
+		crate::DatabaseSource::RocksDb { path, .. } => Some(path),
+		_ => None,
+	}
+	.ok_or_else(|| ClientError::Backend("Only RocksDB databases can be pruned".into()))?;
+	let kvdb = utils::open_raw_rocksdb::<Block>(path)?;
+	let db = sp_database::as_database::<_, DbHash>(SharedKvdb(kvdb.clone()));
+	utils::check_database_type(&*db, DatabaseType::Full)?;
+
+	// The mark set is created next to the database, and removed once the pruning completes.
+	let marks_path = path.with_extension("prune-marks");
+	let marks = utils::create_raw_rocksdb(&marks_path, 1)?;
+	let summary = prune::<Block>(&*kvdb, db, &*marks, state_blocks, settings.blocks_pruning)?;
+	drop(marks);
+	std::fs::remove_dir_all(&marks_path).map_err(|e| {
+		ClientError::Backend(format!("Error removing {}: {e}", marks_path.display()))
+	})?;
+	Ok(summary)
+}
+
+/// Prune the state and the block bodies of the database.
+///
+/// `marks` is an empty database with one column, used to hold the trie nodes in use.
+fn prune<Block: BlockT>(
+	kvdb: &dyn KeyValueDB,
+	db: Arc<dyn Database<DbHash>>,
+	marks: &dyn KeyValueDB,
+	state_blocks: u32,
+	blocks_pruning: BlocksPruning,
+) -> ClientResult<PruningSummary<Block>> {
+	let mut nodes = NodesInUse::new(marks);
+	let progress = match db.get(COLUMN_META, meta_keys::PRUNING_PROGRESS) {
+		Some(progress) => {
+			let progress = Progress::<Block::Hash>::decode(&mut &progress[..]).map_err(|e| {
+				ClientError::Backend(format!("Error decoding pruning progress: {e}"))
+			})?;
+			info!(target: "db", "Resuming pruning of the database");
+			for root in &progress.2 {
+				mark_nodes::<Block>(&*db, *root, u64::MAX, &mut nodes)?;
+			}
+			nodes.flush()?;
+			progress
+		},
+		None => constrain::<Block>(db.clone(), state_blocks, &mut nodes)?,
+	};
+
+	let (kept_nodes, removed_nodes) = remove_unused_nodes(kvdb, &*db, &nodes)?;
+	let removed_bodies = remove_bodies::<Block>(&*db, blocks_pruning)?;
+
+	let mut transaction = Transaction::new();
+	transaction.remove(COLUMN_META, meta_keys::PRUNING_PROGRESS);
+	db.commit(transaction)?;
+
+	let summary = PruningSummary {
+		window: (progress.0.saturated_into(), progress.1.saturated_into()),
+		kept_nodes,
+		removed_nodes,
+		removed_bodies,
+	};
+	info!(
+		target: "db",
+		"Database pruned, state kept from block #{} to #{}",
+		summary.window.0,
+		summary.window.1,
+	);
+	Ok(summary)
+}
+
+/// Switch the state-db of an archive database to [`PruningMode::Constrained`], keeping the state
+/// of the last `state_blocks` canonical blocks.
+///
+/// Returns the pruning progress, once the trie nodes in use are written to `nodes`.
+fn constrain<Block: BlockT>(
+	db: Arc<dyn Database<DbHash>>,
+	state_blocks: u32,
+	nodes: &mut NodesInUse,
+) -> ClientResult<Progress<Block::Hash>> {
+	let (_, state_db) = StateDb::<Block::Hash, Vec<u8>, _>::open(
+		StateMetaDb(db.clone()),
+		None,
+		!db.supports_ref_counting(),
+		false,
+	)
+	.map_err(ClientError::from_state_db)?;
+	let mode = state_db.pruning_mode();
+	if !mode.is_archive() {
+		return Err(ClientError::Backend("Database state is already pruned".into()))
+	}
+
+	let blockchain = BlockchainDb::<Block>::new(db.clone())?;
+	let last = match state_db.last_canonicalized() {
+		LastCanonicalized::Block(number) => number,
+		LastCanonicalized::NotCanonicalizing => match blockchain.info().finalized_state {
+			Some((_, number)) => number.saturated_into(),
+			None => return Err(ClientError::Backend("Database has no finalized state".into())),
+		},
+		LastCanonicalized::None =>
+			return Err(ClientError::Backend("Database has no canonical state".into())),
+	};
+
+	// Canonical blocks kept in the pruning window, up to the first one without state.
+	let mut window = Vec::new();
+	for number in (last.saturating_sub(state_blocks as u64 - 1)..=last).rev() {
+		let hash = blockchain.hash(number.saturated_into())?.ok_or_else(|| {
+			ClientError::UnknownBlock(format!("Missing canonical block #{number}"))
+		})?;
+		let root = *blockchain.expect_header(hash)?.state_root();
+		if !utils::has_state_root::<HashingFor<Block>>(&*db, root) {
+			break
+		}
+		window.push((hash, number, root));
+	}
+	window.reverse();
+	let (last_hash, first) = match (window.last(), window.first()) {
+		(Some(last), Some(first)) => (last.0, first.1),
+		_ => return Err(ClientError::Backend(format!("State of block #{last} is missing"))),
+	};
+
+	// Only `ArchiveAll` databases have the state of non-canonical blocks outside of the state-db
+	// journal.
+	let non_canonical = match mode {
+		PruningMode::ArchiveAll => non_canonical_blocks(&*db, &blockchain, (last_hash, last))?,
+		_ => Vec::new(),
+	};
+
+	for (_, number, root) in &window {
+		info!(target: "db", "Walking state of block #{number}");
+		mark_nodes::<Block>(&*db, *root, *number, nodes)?;
+	}
+	for (_, number, _, root) in &non_canonical {
+		info!(target: "db", "Walking state of non-canonical block #{number}");
+		mark_nodes::<Block>(&*db, *root, u64::MAX, nodes)?;
+	}
+	nodes.flush()?;
+
+	// Nodes are deleted when the block following the last one using them is pruned.
+	let mut deleted = vec![Vec::new(); window.len()];
+	for node in nodes.iter() {
+		let (key, last_used) = node?;
+		if last_used < last {
+			deleted[(last_used + 1 - first) as usize].push(key);
+		}
+	}
+
+	let commit = sc_state_db::constrain_archive(
+		&StateMetaDb(db.clone()),
+		window
+			.iter()
+			.zip(deleted)
+			.map(|((hash, number, _), keys)| (*hash, *number, keys))
+			.collect(),
+		&non_canonical
+			.iter()
+			.map(|(hash, number, parent_hash, _)| (*hash, *number, *parent_hash))
+			.collect::<Vec<_>>(),
+	)
+	.map_err(ClientError::from_state_db)?;
+
+	let roots = window
+		.iter()
+		.map(|block| block.2)
+		.chain(non_canonical.iter().map(|block| block.3));
+	let progress: Progress<Block::Hash> = (first, last, roots.collect());
+	let mut transaction = Transaction::new();
+	apply_state_commit(&mut transaction, commit);
+	transaction.set_from_vec(COLUMN_META, meta_keys::PRUNING_PROGRESS, progress.encode());
+	db.commit(transaction)?;
+	info!(target: "db", "State pruning enabled, keeping the state from block #{first} to #{last}");
+
+	Ok(progress)
+}
+
+/// Hash, number, parent hash and state root of the blocks descending from the `last` canonical
+/// block whose state is in the database, parents first.
+fn non_canonical_blocks<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	blockchain: &BlockchainDb<Block>,
+	last: (Block::Hash, u64),
+) -> ClientResult<Vec<(Block::Hash, u64, Block::Hash, Block::Hash)>> {
+	let mut blocks = BTreeMap::new();
+	for leaf in blockchain.leaves()? {
+		let mut branch = Vec::new();
+		let mut hash = leaf;
+		loop {
+			let header = blockchain.header_metadata(hash)?;
+			let number: u64 = header.number.saturated_into();
+			if number <= last.1 {
+				if hash != last.0 {
+					branch.clear();
+				}
+				break
+			}
+			branch.push((hash, number, header.parent, header.state_root));
+			hash = header.parent;
+		}
+
+		for block in branch
+			.into_iter()
+			.rev()
+			.take_while(|block| utils::has_state_root::<HashingFor<Block>>(db, block.3))
+		{
+			blocks.insert((block.1, block.0), block);
+		}
+	}
+	Ok(blocks.into_values().collect())
+}
+
+/// Add the trie nodes of the state with the given root to `nodes`, used until block `number`.
+fn mark_nodes<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	root: Block::Hash,
+	number: u64,
+	nodes: &mut NodesInUse,
+) -> ClientResult<()> {
+	let mut result = Ok(());
+	utils::for_each_state_node::<HashingFor<Block>>(db, root, &mut |key, _| {
+		if result.is_ok() {
+			result = nodes.mark(key, number);
+		}
+	})?;
+	result
+}
+
+/// Remove the trie nodes that are not in use, returning the number of nodes kept and removed.
+fn remove_unused_nodes(
+	kvdb: &dyn KeyValueDB,
+	db: &dyn Database<DbHash>,
+	nodes: &NodesInUse,
+) -> ClientResult<(u64, u64)> {
+	let mut transaction = Transaction::new();
+	let (mut kept, mut removed) = (0, 0);
+	for entry in kvdb.iter(columns::STATE) {
+		let (key, _) =
+			entry.map_err(|e| ClientError::Backend(format!("Error reading state column: {e}")))?;
+		if nodes.last_used(&key)?.is_some() {
+			kept += 1;
+			continue
+		}
+
+		transaction.remove(columns::STATE, &key);
+		removed += 1;
+		if removed.is_multiple_of(BATCH_SIZE) {
+			db.commit(std::mem::take(&mut transaction))?;
+			info!(target: "db", "Pruning state: {removed} trie nodes removed");
+		}
+	}
+	db.commit(transaction)?;
+	info!(target: "db", "Pruned state: {kept} trie nodes kept, {removed} removed");
+	Ok((kept, removed))
+}
+
+/// Remove the bodies of the finalized blocks that are not kept by `blocks_pruning`, returning the
+/// number of bodies removed.
+fn remove_bodies<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	blocks_pruning: BlocksPruning,
+) -> ClientResult<u64> {
+	// Always keep the last finalized block
+	let keep = match blocks_pruning {
+		BlocksPruning::Some(blocks) => std::cmp::max(blocks, 1) as u64,
+		BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => return Ok(0),
+	};
+	let finalized: u64 = utils::read_meta::<Block>(db, columns::HEADER)?
+		.finalized_number
+		.saturated_into();
+
+	let mut transaction = Transaction::new();
+	let mut removed = 0;
+	for number in 0..(finalized + 1).saturating_sub(keep) {
+		let id = BlockId::<Block>::Number(number.saturated_into());
+		if utils::read_db(db, columns::KEY_LOOKUP, columns::BODY, id)?.is_none() &&
+			utils::read_db(db, columns::KEY_LOOKUP, columns::BODY_INDEX, id)?.is_none()
+		{
+			continue
+		}
+
+		remove_block_body(&mut transaction, db, id)?;
+		removed += 1;
+		if removed.is_multiple_of(BATCH_SIZE) {
+			db.commit(std::mem::take(&mut transaction))?;
+			info!(target: "db", "Pruning blocks: {removed} bodies removed");
+		}
+	}
+	db.commit(transaction)?;
+	info!(target: "db", "Pruned blocks: {removed} bodies removed");
+	Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_block, Block},
+		Backend, DatabaseSource,
+	};
+	use sc_client_api::{
+		backend::{Backend as _, BlockImportOperation as _},
+		TrieCacheContext,
+	};
+	use sp_core::H256;
+	use sp_runtime::testing::{MockCallU64, TestXt};
+	use sp_state_machine::Backend as _;
+
+	type UncheckedXt = TestXt<MockCallU64, ()>;
+
+	fn open(
+		db: &SharedKvdb,
+		state_pruning: PruningMode,
+		create: bool,
+	) -> ClientResult<Backend<Block>> {
+		let settings = DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(state_pruning),
+			source: DatabaseSource::Custom {
+				db: sp_database::as_database(db.clone()),
+				require_create_flag: create,
+			},
+			blocks_pruning: BlocksPruning::KeepAll,
+			metrics_registry: None,
+		};
+		Backend::<Block>::new(settings, 8)
+	}
+
+	/// Create an archive database with four finalized blocks on top of genesis and an unfinalized
+	/// block, returning the hashes of the blocks.
+	fn archive_database(mode: PruningMode) -> (SharedKvdb, Vec<H256>) {
+		let db = SharedKvdb(Arc::new(kvdb_memorydb::create(utils::NUM_COLUMNS)));
+		let backend = open(&db, mode, true).unwrap();
+
+		let mut blocks = Vec::new();
+		let mut parent_hash = Default::default();
+		for number in 0..6 {
+			parent_hash = insert_block(
+				&backend,
+				number,
+				parent_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(number.into(), ())],
+				None,
+			)
+			.unwrap();
+			blocks.push(parent_hash);
+		}
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, blocks[4]).unwrap();
+		for hash in &blocks[1..5] {
+			op.mark_finalized(*hash, None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		(db, blocks)
+	}
+
+	fn mark_set() -> kvdb_memorydb::InMemory {
+		kvdb_memorydb::create(1)
+	}
+
+	#[test]
+	fn marks_are_kept_across_batches() {
+		let marks = mark_set();
+		let mut nodes = NodesInUse::new(&marks);
+		nodes.mark(b"a", 3).unwrap();
+		nodes.flush().unwrap();
+		assert!(nodes.pending.is_empty());
+
+		// A mark is only ever raised.
+		nodes.mark(b"a", 2).unwrap();
+		assert!(nodes.pending.is_empty());
+		nodes.mark(b"a", 4).unwrap();
+		nodes.mark(b"b", 1).unwrap();
+		assert_eq!(nodes.last_used(b"a").unwrap(), Some(4));
+		nodes.flush().unwrap();
+
+		let marked = nodes.iter().collect::<ClientResult<Vec<_>>>().unwrap();
+		assert_eq!(marked, vec![(b"a".to_vec(), 4), (b"b".to_vec(), 1)]);
+		assert_eq!(nodes.last_used(b"c").unwrap(), None);
+	}
+
+	/// Check that the state of the block at `number` can be read from `backend`.
+	fn has_state(backend: &Backend<Block>, blocks: &[H256], number: usize) -> bool {
+		match backend.state_at(blocks[number], TrieCacheContext::Untrusted) {
+			// Every state has an entry keyed by the hash of the parent block.
+			Ok(state) => {
+				let parent_hash = blocks[number - 1].as_ref().to_vec();
+				assert_eq!(state.storage(&parent_hash).unwrap(), Some(parent_hash));
+				true
+			},
+			Err(_) => false,
+		}
+	}
+
+	fn check_pruned(mode: PruningMode) {
+		let (db, blocks) = archive_database(mode);
+		let database = sp_database::as_database(db.clone());
+		let summary =
+			prune::<Block>(&db, database, &mark_set(), 2, BlocksPruning::Some(2)).unwrap();
+		assert_eq!(summary.window, (3, 4));
+		assert!(summary.removed_nodes > 0);
+		assert_eq!(summary.removed_bodies, 3);
+
+		let backend = open(&db, PruningMode::blocks_pruning(2), false).unwrap();
+		assert!(!has_state(&backend, &blocks, 2));
+		for number in 3..6 {
+			assert!(has_state(&backend, &blocks, number));
+		}
+		for (number, hash) in blocks.iter().enumerate() {
+			assert_eq!(backend.blockchain().body(*hash).unwrap().is_some(), number > 2);
+		}
+
+		// The pruned database keeps pruning as blocks get finalized.
+		backend.finalize_block(blocks[5], None).unwrap();
+		assert!(!has_state(&backend, &blocks, 3));
+		assert!(has_state(&backend, &blocks, 4));
+		assert!(has_state(&backend, &blocks, 5));
+	}
+
+	#[test]
+	fn archive_database_is_pruned() {
+		check_pruned(PruningMode::ArchiveAll);
+	}
+
+	#[test]
+	fn archive_canonical_database_is_pruned() {
+		check_pruned(PruningMode::ArchiveCanonical);
+	}
+
+	#[test]
+	fn interrupted_pruning_is_resumed() {
+		let (db, blocks) = archive_database(PruningMode::ArchiveCanonical);
+		let database = sp_database::as_database(db.clone());
+		let marks = mark_set();
+		constrain::<Block>(database.clone(), 2, &mut NodesInUse::new(&marks)).unwrap();
+		assert!(open(&db, PruningMode::blocks_pruning(2), false).is_err());
+
+		// The nodes in use are marked again in an empty mark set.
+		let summary =
+			prune::<Block>(&db, database.clone(), &mark_set(), 2, BlocksPruning::KeepFinalized)
+				.unwrap();
+		assert_eq!(summary.window, (3, 4));
+		assert_eq!(summary.removed_bodies, 0);
+
+		let backend = open(&db, PruningMode::blocks_pruning(2), false).unwrap();
+		assert!(!has_state(&backend, &blocks, 2));
+		assert!(has_state(&backend, &blocks, 4));
+		assert!(
+			prune::<Block>(&db, database, &mark_set(), 2, BlocksPruning::KeepFinalized).is_err()
+		);
+	}
+}
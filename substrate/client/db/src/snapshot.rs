@@ -0,0 +1,411 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Binary snapshots of the state of a finalized block.
+//!
+//! A snapshot holds a finalized block along with every trie node of its state, and allows starting
+//! a node at that block without syncing the chain up to it. After the magic bytes and the version
+//! of the format, a snapshot is made of chunks, each being the length of its payload as a
+//! little-endian `u32`, the SCALE-encoded payload, and the BLAKE2-256 hash of the index of the
+//! chunk followed by the payload:
+//!
+//! - the first chunk holds the genesis hash and the snapshot block;
+//! - the following ones hold the trie nodes, keyed as in a `PrefixedMemoryDB` so that they don't
+//!   depend on the type of database;
+//! - the last one holds the number of trie nodes, marking the end of the snapshot.
+//!
+//! The key values of the state are read back from the trie nodes of a snapshot, so that the block
+//! is imported like the target block of warp sync.
+
+use std::{
+	fs,
+	io::{Read, Write},
+	path::Path,
+};
+
+use codec::{Decode, Encode};
+use kvdb::{DBTransaction, KeyValueDB};
+use log::info;
+use sc_client_api::{backend::Backend as _, blockchain::Backend as _};
+use sp_blockchain::{Error as ClientError, HeaderBackend, Result as ClientResult};
+use sp_core::{hashing::blake2_256, Hasher};
+use sp_runtime::{
+	traits::{Block as BlockT, HashingFor, Header as HeaderT, NumberFor, Zero},
+	Justifications,
+};
+use sp_state_machine::KeyValueStates;
+
+use crate::{utils, Backend};
+
+/// Magic bytes starting a snapshot.
+const MAGIC: &[u8; 8] = b"substate";
+
+/// Version of the snapshot format.
+const VERSION: u32 = 1;
+
+/// Size of the trie nodes above which a chunk is written.
+const CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Payload of a snapshot chunk.
+#[derive(Encode, Decode)]
+enum Chunk<Block: BlockT> {
+	/// Genesis hash and snapshot block.
+	Blocks {
+		genesis_hash: Block::Hash,
+		header: Block::Header,
+		body: Option<Vec<Block::Extrinsic>>,
+		justifications: Option<Justifications>,
+	},
+	/// Trie nodes, keyed by their prefixed key.
+	Nodes(Vec<(Vec<u8>, Vec<u8>)>),
+	/// End of the snapshot, with the number of trie nodes.
+	End(u64),
+}
+
+/// Summary of a snapshot export.
+#[derive(Debug)]
+pub struct SnapshotSummary<Block: BlockT> {
+	/// Hash and number of the snapshot block.
+	pub block: (Block::Hash, NumberFor<Block>),
+	/// Number of trie nodes.
+	pub nodes: u64,
+}
+
+/// Block and state read from a snapshot.
+pub struct Snapshot<Block: BlockT> {
+	/// Hash of the genesis block of the chain.
+	pub genesis_hash: Block::Hash,
+	/// Header of the snapshot block.
+	pub header: Block::Header,
+	/// Body of the snapshot block.
+	pub body: Option<Vec<Block::Extrinsic>>,
+	/// Justifications of the snapshot block.
+	pub justifications: Option<Justifications>,
+	/// Number of trie nodes of the state.
+	pub nodes: u64,
+	/// Key values of the state of the snapshot block.
+	pub state: KeyValueStates,
+}
+
+fn snapshot_error(err: impl std::fmt::Display) -> ClientError {
+	ClientError::Backend(format!("Invalid snapshot: {err}"))
+}
+
+fn checksum(index: u64, payload: &[u8]) -> [u8; 32] {
+	let mut data = Vec::with_capacity(8 + payload.len());
+	data.extend(index.to_le_bytes());
+	data.extend(payload);
+	blake2_256(&data)
+}
+
+/// Writes the chunks of a snapshot.
+struct ChunkWriter<'a> {
+	writer: &'a mut dyn Write,
+	index: u64,
+}
+
+impl<'a> ChunkWriter<'a> {
+	fn new(writer: &'a mut dyn Write) -> ClientResult<Self> {
+		let mut chunks = Self { writer, index: 0 };
+		chunks.write_all(MAGIC)?;
+		chunks.write_all(&VERSION.to_le_bytes())?;
+		Ok(chunks)
+	}
+
+	fn write<Block: BlockT>(&mut self, chunk: &Chunk<Block>) -> ClientResult<()> {
+		let payload = chunk.encode();
+		let len = u32::try_from(payload.len())
+			.map_err(|_| ClientError::Backend("Snapshot chunk is too large".into()))?;
+		self.write_all(&len.to_le_bytes())?;
+		self.write_all(&payload)?;
+		self.write_all(&checksum(self.index, &payload))?;
+		self.index += 1;
+		Ok(())
+	}
+
+	fn write_all(&mut self, data: &[u8]) -> ClientResult<()> {
+		self.writer
+			.write_all(data)
+			.map_err(|e| ClientError::Backend(format!("Error writing snapshot: {e}")))
+	}
+}
+
+/// Reads the chunks of a snapshot, checking their checksum.
+struct ChunkReader<'a> {
+	reader: &'a mut dyn Read,
+	index: u64,
+}
+
+impl<'a> ChunkReader<'a> {
+	fn new(reader: &'a mut dyn Read) -> ClientResult<Self> {
+		let mut chunks = Self { reader, index: 0 };
+		let mut magic = [0; 8];
+		chunks.read_exact(&mut magic)?;
+		if &magic != MAGIC {
+			return Err(snapshot_error("not a snapshot"))
+		}
+		let mut version = [0; 4];
+		chunks.read_exact(&mut version)?;
+		match u32::from_le_bytes(version) {
+			VERSION => Ok(chunks),
+			version => Err(snapshot_error(format!("unsupported version {version}"))),
+		}
+	}
+
+	fn read<Block: BlockT>(&mut self) -> ClientResult<Chunk<Block>> {
+		let mut len = [0; 4];
+		self.read_exact(&mut len)?;
+		let len = u32::from_le_bytes(len) as u64;
+		// The payload is not allocated upfront, as a corrupted length would be trusted.
+		let mut payload = Vec::new();
+		match (&mut self.reader).take(len).read_to_end(&mut payload) {
+			Ok(read) if read as u64 == len => (),
+			Ok(_) => return Err(snapshot_error("unexpected end of file")),
+			Err(e) => return Err(ClientError::Backend(format!("Error reading snapshot: {e}"))),
+		}
+		let mut expected = [0; 32];
+		self.read_exact(&mut expected)?;
+		if checksum(self.index, &payload) != expected {
+			return Err(snapshot_error(format!("checksum mismatch in chunk {}", self.index)))
+		}
+		self.index += 1;
+		Chunk::decode(&mut &payload[..]).map_err(snapshot_error)
+	}
+
+	fn read_exact(&mut self, data: &mut [u8]) -> ClientResult<()> {
+		self.reader.read_exact(data).map_err(|e| match e.kind() {
+			std::io::ErrorKind::UnexpectedEof => snapshot_error("unexpected end of file"),
+			_ => ClientError::Backend(format!("Error reading snapshot: {e}")),
+		})
+	}
+}
+
+impl<Block: BlockT> Backend<Block> {
+	/// Write a snapshot of the finalized block `hash` to `writer`.
+	pub fn export_snapshot(
+		&self,
+		hash: Block::Hash,
+		writer: &mut dyn Write,
+	) -> ClientResult<SnapshotSummary<Block>> {
+		let info = self.blockchain.info();
+		let header = self.blockchain.expect_header(hash)?;
+		let number = *header.number();
+		if number > info.finalized_number || self.blockchain.hash(number)? != Some(hash) {
+			return Err(ClientError::Backend(format!("Block #{number} ({hash}) is not finalized")))
+		}
+		if !self.have_state_at(hash, number) {
+			return Err(ClientError::Backend(format!(
+				"State of block #{number} ({hash}) is not available"
+			)))
+		}
+
+		let mut chunks = ChunkWriter::new(writer)?;
+		chunks.write(&Chunk::<Block>::Blocks {
+			genesis_hash: info.genesis_hash,
+			header: header.clone(),
+			body: self.blockchain.body(hash)?,
+			justifications: self.blockchain.justifications(hash)?,
+		})?;
+
+		let (mut nodes, mut size, mut count) = (Vec::new(), 0, 0);
+		let mut result = Ok(());
+		utils::for_each_prefixed_state_node::<HashingFor<Block>>(
+			&*self.storage.db,
+			*header.state_root(),
+			&mut |key, data| {
+				if result.is_err() {
+					return
+				}
+				nodes.push((key.to_vec(), data.to_vec()));
+				size += key.len() + data.len();
+				count += 1;
+				if size >= CHUNK_SIZE {
+					result = chunks.write(&Chunk::<Block>::Nodes(std::mem::take(&mut nodes)));
+					size = 0;
+					info!(target: "db", "Exported {count} trie nodes");
+				}
+			},
+		)?;
+		result?;
+		if !nodes.is_empty() {
+			chunks.write(&Chunk::<Block>::Nodes(nodes))?;
+		}
+		chunks.write(&Chunk::<Block>::End(count))?;
+
+		info!(target: "db", "Exported snapshot of block #{number} ({hash}), {count} trie nodes");
+		Ok(SnapshotSummary { block: (hash, number), nodes: count })
+	}
+}
+
+/// Read the snapshot from `reader`, checking that it holds the complete state of its block.
+///
+/// The trie nodes are written chunk by chunk to a temporary database created at `nodes_path`, and
+/// removed once the key values of the state are read from them.
+pub fn read_snapshot<Block: BlockT>(
+	reader: &mut dyn Read,
+	nodes_path: &Path,
+) -> ClientResult<Snapshot<Block>> {
+	let nodes = utils::create_raw_rocksdb(nodes_path, 1)?;
+	let snapshot = read_chunks::<Block>(reader, &*nodes);
+	drop(nodes);
+	fs::remove_dir_all(nodes_path).map_err(|e| {
+		ClientError::Backend(format!("Error removing {}: {e}", nodes_path.display()))
+	})?;
+	snapshot
+}
+
+/// Read the chunks of a snapshot.
+///
+/// `nodes` is an empty database with one column, used to hold the trie nodes.
+fn read_chunks<Block: BlockT>(
+	reader: &mut dyn Read,
+	nodes: &dyn KeyValueDB,
+) -> ClientResult<Snapshot<Block>> {
+	let mut chunks = ChunkReader::new(reader)?;
+	let (genesis_hash, header, body, justifications) = match chunks.read::<Block>()? {
+		Chunk::Blocks { genesis_hash, header, body, justifications } =>
+			(genesis_hash, header, body, justifications),
+		_ => return Err(snapshot_error("missing blocks")),
+	};
+	let (hash, number) = (header.hash(), *header.number());
+	if number.is_zero() {
+		return Err(snapshot_error("invalid block number"))
+	}
+
+	let mut count = 0;
+	loop {
+		let chunk = match chunks.read::<Block>()? {
+			Chunk::Nodes(chunk) => chunk,
+			Chunk::End(total) if total == count => break,
+			Chunk::End(_) => return Err(snapshot_error("missing trie nodes")),
+			Chunk::Blocks { .. } => return Err(snapshot_error("unexpected blocks")),
+		};
+		let mut transaction = DBTransaction::new();
+		for (key, data) in chunk {
+			let node_hash = <HashingFor<Block> as Hasher>::hash(&data);
+			if !key.ends_with(node_hash.as_ref()) {
+				return Err(snapshot_error("trie node doesn't match its key"))
+			}
+			transaction.put_vec(0, &key, data);
+			count += 1;
+		}
+		nodes
+			.write(transaction)
+			.map_err(|e| ClientError::Backend(format!("Error writing trie nodes: {e}")))?;
+		info!(target: "db", "Read {count} trie nodes");
+	}
+	let state = utils::state_key_values::<HashingFor<Block>>(nodes, *header.state_root())
+		.map_err(snapshot_error)?;
+
+	info!(target: "db", "Read snapshot of block #{number} ({hash}), {count} trie nodes");
+	Ok(Snapshot { genesis_hash, header, body, justifications, nodes: count, state })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_block, Block},
+		BlocksPruning,
+	};
+	use sc_client_api::{backend::BlockImportOperation as _, TrieCacheContext};
+	use sp_core::H256;
+	use sp_runtime::testing::{MockCallU64, TestXt};
+	use sp_state_machine::Backend as _;
+
+	type UncheckedXt = TestXt<MockCallU64, ()>;
+
+	/// Create a backend with four finalized blocks on top of genesis and an unfinalized block,
+	/// returning the hashes of the blocks.
+	fn source() -> (Backend<Block>, Vec<H256>) {
+		let backend = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::KeepAll, 0);
+		let mut blocks = Vec::new();
+		let mut parent_hash = Default::default();
+		for number in 0..6 {
+			parent_hash = insert_block(
+				&backend,
+				number,
+				parent_hash,
+				None,
+				Default::default(),
+				vec![UncheckedXt::new_transaction(number.into(), ())],
+				None,
+			)
+			.unwrap();
+			blocks.push(parent_hash);
+		}
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, blocks[4]).unwrap();
+		for hash in &blocks[1..5] {
+			op.mark_finalized(*hash, None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+		(backend, blocks)
+	}
+
+	fn export(backend: &Backend<Block>, hash: H256) -> ClientResult<Vec<u8>> {
+		let mut snapshot = Vec::new();
+		backend.export_snapshot(hash, &mut snapshot)?;
+		Ok(snapshot)
+	}
+
+	fn read(snapshot: &[u8]) -> ClientResult<Snapshot<Block>> {
+		read_chunks::<Block>(&mut &snapshot[..], &kvdb_memorydb::create(1))
+	}
+
+	#[test]
+	fn snapshot_is_read() {
+		let (source, blocks) = source();
+		let snapshot = export(&source, blocks[4]).unwrap();
+
+		let snapshot = read(&snapshot).unwrap();
+		assert_eq!(snapshot.genesis_hash, blocks[0]);
+		assert_eq!(snapshot.header.hash(), blocks[4]);
+		assert_eq!(snapshot.body, source.blockchain().body(blocks[4]).unwrap());
+		assert!(snapshot.nodes > 0);
+
+		// Every state has an entry keyed by the hash of the parent block.
+		let state = source.state_at(blocks[4], TrieCacheContext::Untrusted).unwrap();
+		let top = &snapshot.state.0[0];
+		assert_eq!(snapshot.state.0.len(), 1);
+		assert_eq!(top.key_values.len(), state.pairs(Default::default()).unwrap().count());
+		let parent_hash = blocks[3].as_ref().to_vec();
+		assert!(top.key_values.contains(&(parent_hash.clone(), parent_hash)));
+	}
+
+	#[test]
+	fn unfinalized_block_is_not_exported() {
+		let (source, blocks) = source();
+		assert!(export(&source, blocks[5]).is_err());
+	}
+
+	#[test]
+	fn corrupted_snapshot_is_rejected() {
+		let (source, blocks) = source();
+		let snapshot = export(&source, blocks[4]).unwrap();
+
+		let mut corrupted = snapshot.clone();
+		let last = corrupted.len() - 40;
+		corrupted[last] ^= 1;
+		let err = read(&corrupted).err().unwrap();
+		assert!(err.to_string().contains("checksum mismatch"));
+
+		let err = read(&snapshot[..snapshot.len() - 1]).err().unwrap();
+		assert!(err.to_string().contains("unexpected end of file"));
+	}
+}
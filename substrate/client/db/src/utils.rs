@@ -19,13 +19,19 @@
 //! Db-based backend utility structures and functions, used by both
 //! full and light storages.
 
-use std::{fmt, fs, io, path::Path, sync::Arc};
+use std::{cell::RefCell, fmt, fs, io, marker::PhantomData, path::Path, sync::Arc};
 
+use hash_db::{HashDBRef, Prefix, EMPTY_PREFIX};
+use kvdb::KeyValueDB;
 use log::{debug, info};
 
-use crate::{Database, DatabaseSource, DbHash};
+use crate::{columns, Database, DatabaseSource, DbHash};
 use codec::Decode;
 use sc_client_api::blockchain::{BlockGap, BlockGapType};
+use sp_core::{
+	storage::{well_known_keys, ChildInfo, ChildType, PrefixedStorageKey},
+	Hasher,
+};
 use sp_database::Transaction;
 use sp_runtime::{
 	generic::BlockId,
@@ -34,11 +40,14 @@ use sp_runtime::{
 		Zero,
 	},
 };
-use sp_trie::DBValue;
+use sp_state_machine::{KeyValueStates, KeyValueStorageLevel};
+use sp_trie::{
+	empty_trie_root, prefixed_key, trie_types::TrieDBBuilder, DBValue, KeySpacedDB, LayoutV1, Trie,
+};
 
 /// Number of columns in the db. Must be the same for both full && light dbs.
 /// Otherwise RocksDb will fail to open database && check its type.
-pub const NUM_COLUMNS: u32 = 13;
+pub const NUM_COLUMNS: u32 = 14;
 /// Meta column. The set of keys in the column is shared by full && light storages.
 pub const COLUMN_META: u32 = 0;
 
@@ -65,6 +74,10 @@ pub mod meta_keys {
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// Progress of a database conversion, stored in the database being converted to.
+	pub const CONVERSION_PROGRESS: &[u8; 7] = b"convert";
+	/// Progress of an offline pruning of the database.
+	pub const PRUNING_PROGRESS: &[u8; 7] = b"pruning";
 }
 
 /// Database metadata.
@@ -362,6 +375,88 @@ fn open_kvdb_rocksdb<Block: BlockT>(
 	Err(OpenDbError::NotEnabled("with-kvdb-rocksdb"))
 }
 
+/// Open an existing RocksDB database as a key-value database, upgrading it first.
+#[cfg(feature = "rocksdb")]
+pub(crate) fn open_raw_rocksdb<Block: BlockT>(
+	path: &Path,
+) -> sp_blockchain::Result<Arc<dyn KeyValueDB>> {
+	crate::upgrade::upgrade_db::<Block>(path, DatabaseType::Full)
+		.map_err(|e| sp_blockchain::Error::Backend(format!("Error upgrading RocksDB: {e}")))?;
+
+	let mut config = kvdb_rocksdb::DatabaseConfig::with_columns(NUM_COLUMNS);
+	config.create_if_missing = false;
+	let db = kvdb_rocksdb::Database::open(&config, path)
+		.map_err(|e| sp_blockchain::Error::Backend(format!("Error opening RocksDB: {e}")))?;
+	Ok(Arc::new(db))
+}
+
+#[cfg(not(feature = "rocksdb"))]
+pub(crate) fn open_raw_rocksdb<Block: BlockT>(
+	_path: &Path,
+) -> sp_blockchain::Result<Arc<dyn KeyValueDB>> {
+	Err(sp_blockchain::Error::Backend(
+		"`rocksdb` feature not enabled, database can not be opened".into(),
+	))
+}
+
+/// Create an empty RocksDB database with `columns` columns, replacing any database at `path`.
+#[cfg(feature = "rocksdb")]
+pub(crate) fn create_raw_rocksdb(
+	path: &Path,
+	columns: u32,
+) -> sp_blockchain::Result<Arc<dyn KeyValueDB>> {
+	if path.exists() {
+		fs::remove_dir_all(path).map_err(|e| {
+			sp_blockchain::Error::Backend(format!("Error removing {}: {e}", path.display()))
+		})?;
+	}
+
+	let config = kvdb_rocksdb::DatabaseConfig::with_columns(columns);
+	let db = kvdb_rocksdb::Database::open(&config, path)
+		.map_err(|e| sp_blockchain::Error::Backend(format!("Error creating RocksDB: {e}")))?;
+	Ok(Arc::new(db))
+}
+
+#[cfg(not(feature = "rocksdb"))]
+pub(crate) fn create_raw_rocksdb(
+	_path: &Path,
+	_columns: u32,
+) -> sp_blockchain::Result<Arc<dyn KeyValueDB>> {
+	Err(sp_blockchain::Error::Backend(
+		"`rocksdb` feature not enabled, database can not be created".into(),
+	))
+}
+
+/// Key-value database shared between a [`Database`] adapter and readers of its columns.
+#[derive(Clone)]
+pub(crate) struct SharedKvdb(pub Arc<dyn KeyValueDB>);
+
+impl KeyValueDB for SharedKvdb {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<kvdb::DBValue>> {
+		self.0.get(col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<kvdb::DBValue>> {
+		self.0.get_by_prefix(col, prefix)
+	}
+
+	fn write(&self, transaction: kvdb::DBTransaction) -> io::Result<()> {
+		self.0.write(transaction)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = io::Result<kvdb::DBKeyValue>> + 'a> {
+		self.0.iter(col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = io::Result<kvdb::DBKeyValue>> + 'a> {
+		self.0.iter_with_prefix(col, prefix)
+	}
+}
+
 /// Check database type.
 pub fn check_database_type(
 	db: &dyn Database<DbHash>,
@@ -585,6 +680,232 @@ impl DatabaseType {
 	}
 }
 
+/// Trie nodes read straight from the state column.
+///
+/// `visit` is called with the key and the data of every node read. The key is the database key,
+/// or the prefixed key of the node when `visit_prefixed` is set.
+struct StateNodes<'a, H> {
+	db: &'a dyn Database<DbHash>,
+	prefix_keys: bool,
+	visit_prefixed: bool,
+	visit: RefCell<&'a mut dyn FnMut(&[u8], &[u8])>,
+	_hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> StateNodes<'_, H> {
+	fn db_key(&self, key: &H::Out, prefix: Prefix) -> Vec<u8> {
+		if self.prefix_keys {
+			prefixed_key::<H>(key, prefix)
+		} else {
+			key.as_ref().to_vec()
+		}
+	}
+}
+
+impl<H: Hasher> HashDBRef<H, DBValue> for StateNodes<'_, H> {
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Option<DBValue> {
+		let db_key = self.db_key(key, prefix);
+		let data = self.db.get(columns::STATE, &db_key)?;
+		if self.visit_prefixed && !self.prefix_keys {
+			(self.visit.borrow_mut())(&prefixed_key::<H>(key, prefix), &data);
+		} else {
+			(self.visit.borrow_mut())(&db_key, &data);
+		}
+		Some(data)
+	}
+
+	fn contains(&self, key: &H::Out, prefix: Prefix) -> bool {
+		self.db.contains(columns::STATE, &self.db_key(key, prefix))
+	}
+}
+
+/// Trie nodes held in the first column of a key-value database, keyed as in a `PrefixedMemoryDB`.
+///
+/// The first error met reading the database is kept in `error`, as `HashDBRef` can't return it.
+struct PrefixedNodes<'a, H> {
+	db: &'a dyn KeyValueDB,
+	error: RefCell<Option<io::Error>>,
+	_hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> HashDBRef<H, DBValue> for PrefixedNodes<'_, H> {
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Option<DBValue> {
+		self.db.get(0, &prefixed_key::<H>(key, prefix)).unwrap_or_else(|e| {
+			self.error.borrow_mut().get_or_insert(e);
+			None
+		})
+	}
+
+	fn contains(&self, key: &H::Out, prefix: Prefix) -> bool {
+		HashDBRef::get(self, key, prefix).is_some()
+	}
+}
+
+/// Iterate over the key values of the trie with the given root.
+fn for_each_trie_entry<H: Hasher>(
+	db: &dyn HashDBRef<H, DBValue>,
+	root: &H::Out,
+	f: &mut dyn FnMut(&[u8], &[u8]),
+) -> sp_blockchain::Result<()> {
+	if *root == empty_trie_root::<LayoutV1<H>>() {
+		return Ok(())
+	}
+
+	let trie = TrieDBBuilder::<H>::new(db, root).build();
+	let iter = trie
+		.iter()
+		.map_err(|e| sp_blockchain::Error::Backend(format!("Error reading state: {e}")))?;
+	for entry in iter {
+		let (key, value) = entry
+			.map_err(|e| sp_blockchain::Error::Backend(format!("Error reading state: {e}")))?;
+		f(&key, &value);
+	}
+	Ok(())
+}
+
+/// Whether the root node of the state with the given root is in the state column.
+///
+/// The rest of the state may still be missing.
+pub(crate) fn has_state_root<H: Hasher>(db: &dyn Database<DbHash>, root: H::Out) -> bool {
+	if root == empty_trie_root::<LayoutV1<H>>() {
+		return true
+	}
+	if db.supports_ref_counting() {
+		db.contains(columns::STATE, root.as_ref())
+	} else {
+		db.contains(columns::STATE, &prefixed_key::<H>(&root, EMPTY_PREFIX))
+	}
+}
+
+/// Visit every node of the state with the given root, including the nodes of its child tries.
+///
+/// Only canonical states can be visited, as the nodes are read from the state column without going
+/// through the state-db overlay. `visit` is called with the key and the data of each node in the
+/// state column, possibly more than once for nodes used in several places. Fails if a node is
+/// missing.
+pub(crate) fn for_each_state_node<H: Hasher>(
+	db: &dyn Database<DbHash>,
+	root: H::Out,
+	visit: &mut dyn FnMut(&[u8], &[u8]),
+) -> sp_blockchain::Result<()> {
+	walk_state_nodes::<H>(db, root, false, visit)
+}
+
+/// Visit every node of the state with the given root like [`for_each_state_node`], with the key
+/// the node has in a `PrefixedMemoryDB` rather than its key in the state column.
+pub(crate) fn for_each_prefixed_state_node<H: Hasher>(
+	db: &dyn Database<DbHash>,
+	root: H::Out,
+	visit: &mut dyn FnMut(&[u8], &[u8]),
+) -> sp_blockchain::Result<()> {
+	walk_state_nodes::<H>(db, root, true, visit)
+}
+
+fn walk_state_nodes<H: Hasher>(
+	db: &dyn Database<DbHash>,
+	root: H::Out,
+	visit_prefixed: bool,
+	visit: &mut dyn FnMut(&[u8], &[u8]),
+) -> sp_blockchain::Result<()> {
+	let nodes = StateNodes::<H> {
+		db,
+		prefix_keys: !db.supports_ref_counting(),
+		visit_prefixed,
+		visit: RefCell::new(visit),
+		_hasher: PhantomData,
+	};
+
+	let mut child_tries = Vec::new();
+	for_each_trie_entry::<H>(&nodes, &root, &mut |key, value| {
+		if key.starts_with(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			child_tries.push((key.to_vec(), value.to_vec()));
+		}
+	})?;
+
+	for (key, value) in child_tries {
+		let Some((child_info, child_root)) = child_trie::<H>(&key, &value)? else { continue };
+		let child_nodes = KeySpacedDB::new(&nodes, child_info.keyspace());
+		for_each_trie_entry::<H>(&child_nodes, &child_root, &mut |_, _| ())?;
+	}
+
+	Ok(())
+}
+
+/// Child trie and root of the top trie entry with the given key and value, `None` if the key is
+/// not the key of a default child trie.
+fn child_trie<H: Hasher>(
+	key: &[u8],
+	value: &[u8],
+) -> sp_blockchain::Result<Option<(ChildInfo, H::Out)>> {
+	let prefixed_key = PrefixedStorageKey::new(key.to_vec());
+	let child_info = match ChildType::from_prefixed_key(&prefixed_key) {
+		Some((ChildType::ParentKeyId, storage_key)) => ChildInfo::new_default(storage_key),
+		None => return Ok(None),
+	};
+	let mut child_root = H::Out::default();
+	if value.len() != child_root.as_ref().len() {
+		return Err(sp_blockchain::Error::Backend(format!(
+			"Invalid root of child trie {:?}",
+			child_info.storage_key(),
+		)))
+	}
+	child_root.as_mut().copy_from_slice(value);
+	Ok(Some((child_info, child_root)))
+}
+
+/// Read the key values of the state with the given root from its trie nodes, held in the first
+/// column of `nodes` and keyed as in a `PrefixedMemoryDB`.
+///
+/// The key values are grouped by trie like the ones downloaded by state sync, the top trie first
+/// and without the roots of the child tries. Fails if a node is missing.
+pub(crate) fn state_key_values<H: Hasher>(
+	nodes: &dyn KeyValueDB,
+	root: H::Out,
+) -> sp_blockchain::Result<KeyValueStates> {
+	let nodes = PrefixedNodes::<H> { db: nodes, error: RefCell::new(None), _hasher: PhantomData };
+	let result = read_key_values::<H>(&nodes, root);
+	match nodes.error.take() {
+		Some(e) => Err(sp_blockchain::Error::Backend(format!("Error reading trie nodes: {e}"))),
+		None => result,
+	}
+}
+
+fn read_key_values<H: Hasher>(
+	nodes: &PrefixedNodes<H>,
+	root: H::Out,
+) -> sp_blockchain::Result<KeyValueStates> {
+	let (mut top, mut child_tries) = (Vec::new(), Vec::new());
+	for_each_trie_entry::<H>(nodes, &root, &mut |key, value| {
+		if well_known_keys::is_child_storage_key(key) {
+			child_tries.push((key.to_vec(), value.to_vec()));
+		} else {
+			top.push((key.to_vec(), value.to_vec()));
+		}
+	})?;
+
+	let mut levels = vec![KeyValueStorageLevel {
+		state_root: Vec::new(),
+		parent_storage_keys: Vec::new(),
+		key_values: top,
+	}];
+	for (key, value) in child_tries {
+		let Some((child_info, child_root)) = child_trie::<H>(&key, &value)? else {
+			return Err(sp_blockchain::Error::Backend(format!("Unsupported child trie {key:?}")))
+		};
+		let mut key_values = Vec::new();
+		let child_nodes = KeySpacedDB::new(nodes, child_info.keyspace());
+		for_each_trie_entry::<H>(&child_nodes, &child_root, &mut |key, value| {
+			key_values.push((key.to_vec(), value.to_vec()));
+		})?;
+		levels.push(KeyValueStorageLevel {
+			state_root: value,
+			parent_storage_keys: vec![key],
+			key_values,
+		});
+	}
+	Ok(KeyValueStates(levels))
+}
+
 pub(crate) struct JoinInput<'a, 'b>(&'a [u8], &'b [u8]);
 
 pub(crate) fn join_input<'a, 'b>(i1: &'a [u8], i2: &'b [u8]) -> JoinInput<'a, 'b> {
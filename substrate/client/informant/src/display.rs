@@ -119,9 +119,11 @@ impl<B: BlockT> InformantDisplay<B> {
 					"⚙️ ",
 					"State sync".into(),
 					format!(
-						", {}, {}%, {:.2} Mib",
+						", {}, {}%, {} ranges left ({} active), {:.2} Mib",
 						state.phase,
 						state.percentage,
+						state.pending_ranges,
+						state.active_ranges,
 						(state.size as f32) / (1024f32 * 1024f32)
 					),
 				),
@@ -36,7 +36,7 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		disconnected_peers::DisconnectedPeers,
-		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		state_sync::{ImportResult, RangeId, StateSync, StateSyncProvider},
 		warp::{WarpSyncPhase, WarpSyncProgress},
 		StrategyKey, SyncingAction, SyncingStrategy,
 	},
@@ -277,8 +277,8 @@ pub(crate) enum PeerSyncState<B: BlockT> {
 	DownloadingStale(B::Hash),
 	/// Downloading justification for given block hash.
 	DownloadingJustification(B::Hash),
-	/// Downloading state.
-	DownloadingState,
+	/// Downloading the given range of the state.
+	DownloadingState(RangeId),
 	/// Actively downloading block history after warp sync.
 	DownloadingGap(NumberFor<B>),
 }
@@ -374,6 +374,11 @@ where
 		}
 
 		if let Some(state) = self.peers.remove(peer_id) {
+			if let (PeerSyncState::DownloadingState(range), Some(sync)) =
+				(state.state, &mut self.state_sync)
+			{
+				sync.on_request_failed(range);
+			}
 			if !state.state.is_available() {
 				if let Some(bad_peer) =
 					self.disconnected_peers.on_disconnect_during_request(*peer_id)
@@ -886,7 +891,7 @@ where
 			.collect::<Vec<_>>();
 		self.actions.extend(justification_requests);
 
-		let state_request = self.state_request().into_iter().map(|(peer_id, request)| {
+		let state_requests = self.state_requests().into_iter().map(|(peer_id, request)| {
 			trace!(
 				target: LOG_TARGET,
 				"Created `StrategyRequest` to {peer_id}.",
@@ -914,7 +919,7 @@ where
 				remove_obsolete: false,
 			}
 		});
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		Ok(std::mem::take(&mut self.actions))
 	}
@@ -1364,7 +1369,7 @@ where
 					},
 					PeerSyncState::Available |
 					PeerSyncState::DownloadingJustification(..) |
-					PeerSyncState::DownloadingState => Vec::new(),
+					PeerSyncState::DownloadingState(_) => Vec::new(),
 				}
 			} else {
 				// When request.is_none() this is a block announcement. Just accept blocks.
@@ -1655,7 +1660,12 @@ where
 				PeerSyncState::DownloadingNew(_) |
 				PeerSyncState::DownloadingStale(_) |
 				PeerSyncState::DownloadingGap(_) |
-				PeerSyncState::DownloadingState => {
+				PeerSyncState::DownloadingState(_) => {
+					if let (PeerSyncState::DownloadingState(range), Some(sync)) =
+						(peer_sync.state, &mut self.state_sync)
+					{
+						sync.on_request_failed(range);
+					}
 					// Cancel a request first, as `add_peer` may generate a new request.
 					self.actions
 						.push(SyncingAction::CancelRequest { peer_id, key: Self::STRATEGY_KEY });
@@ -1940,20 +1950,17 @@ where
 		requests
 	}
 
-	/// Get a state request scheduled by sync to be sent out (if any).
-	fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+	/// Get state requests scheduled by sync to be sent out.
+	///
+	/// A different range of the state is requested from each available peer.
+	fn state_requests(&mut self) -> Vec<(PeerId, StateRequest)> {
+		let mut requests = Vec::new();
 		if self.allowed_requests.is_empty() {
-			return None;
+			return requests;
 		}
-		if self.state_sync.is_some() &&
-			self.peers.iter().any(|(_, peer)| peer.state == PeerSyncState::DownloadingState)
-		{
-			// Only one pending state request is allowed.
-			return None;
-		}
-		if let Some(sync) = &self.state_sync {
+		if let Some(sync) = &mut self.state_sync {
 			if sync.is_complete() {
-				return None;
+				return requests;
 			}
 
 			for (id, peer) in self.peers.iter_mut() {
@@ -1961,15 +1968,17 @@ where
 					peer.common_number >= sync.target_number() &&
 					self.disconnected_peers.is_peer_available(&id)
 				{
-					peer.state = PeerSyncState::DownloadingState;
-					let request = sync.next_request();
+					let Some((range, request)) = sync.next_request() else { break };
+					peer.state = PeerSyncState::DownloadingState(range);
 					trace!(target: LOG_TARGET, "New StateRequest for {}: {:?}", id, request);
-					self.allowed_requests.clear();
-					return Some((*id, request));
+					requests.push((*id, request));
 				}
 			}
+			if !requests.is_empty() {
+				self.allowed_requests.clear();
+			}
 		}
-		None
+		requests
 	}
 
 	#[must_use]
@@ -1986,21 +1995,24 @@ where
 			},
 		};
 
+		let mut range = None;
 		if let Some(peer) = self.peers.get_mut(peer_id) {
-			if let PeerSyncState::DownloadingState = peer.state {
+			if let PeerSyncState::DownloadingState(downloading) = peer.state {
 				peer.state = PeerSyncState::Available;
 				self.allowed_requests.set_all();
+				range = Some(downloading);
 			}
 		}
-		let import_result = if let Some(sync) = &mut self.state_sync {
+		let import_result = if let (Some(sync), Some(range)) = (&mut self.state_sync, range) {
 			debug!(
 				target: LOG_TARGET,
-				"Importing state data from {} with {} keys, {} proof nodes.",
+				"Importing state data of range {:?} from {} with {} keys, {} proof nodes.",
+				range,
 				peer_id,
 				response.entries.len(),
 				response.proof.len(),
 			);
-			sync.import(response)
+			sync.import(range, response)
 		} else {
 			debug!(target: LOG_TARGET, "Ignored obsolete state response from {peer_id}");
 			return Err(BadPeer(*peer_id, rep::NOT_REQUESTED));
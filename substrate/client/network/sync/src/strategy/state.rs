@@ -23,7 +23,7 @@ use crate::{
 	service::network::NetworkServiceHandle,
 	strategy::{
 		disconnected_peers::DisconnectedPeers,
-		state_sync::{ImportResult, StateSync, StateSyncProvider},
+		state_sync::{ImportResult, RangeId, StateSync, StateSyncProvider},
 		StrategyKey, SyncingAction,
 	},
 	types::{BadPeer, SyncState, SyncStatus},
@@ -56,7 +56,8 @@ mod rep {
 
 enum PeerState {
 	Available,
-	DownloadingState,
+	/// Downloading the given range of the state.
+	DownloadingState(RangeId),
 }
 
 impl PeerState {
@@ -149,7 +150,9 @@ impl<B: BlockT> StateStrategy<B> {
 	/// Notify that a peer has disconnected.
 	pub fn remove_peer(&mut self, peer_id: &PeerId) {
 		if let Some(state) = self.peers.remove(peer_id) {
-			if !state.state.is_available() {
+			if let PeerState::DownloadingState(range) = state.state {
+				// The range is requested again from another peer.
+				self.state_sync.on_request_failed(range);
 				if let Some(bad_peer) =
 					self.disconnected_peers.on_disconnect_during_request(*peer_id)
 				{
@@ -192,9 +195,19 @@ impl<B: BlockT> StateStrategy<B> {
 		peer_id: &PeerId,
 		response: &[u8],
 	) -> Result<(), BadPeer> {
-		if let Some(peer) = self.peers.get_mut(&peer_id) {
-			peer.state = PeerState::Available;
-		}
+		let range = match self.peers.get_mut(&peer_id) {
+			Some(peer) => match std::mem::replace(&mut peer.state, PeerState::Available) {
+				PeerState::DownloadingState(range) => range,
+				PeerState::Available => {
+					debug!(target: LOG_TARGET, "Ignored unexpected state response from {peer_id}");
+					return Ok(())
+				},
+			},
+			None => {
+				debug!(target: LOG_TARGET, "Ignored state response from unknown peer {peer_id}");
+				return Ok(())
+			},
+		};
 
 		let response = match StateResponse::decode(response) {
 			Ok(response) => response,
@@ -204,19 +217,21 @@ impl<B: BlockT> StateStrategy<B> {
 					"Failed to decode state response from peer {peer_id:?}: {error:?}.",
 				);
 
+				self.state_sync.on_request_failed(range);
 				return Err(BadPeer(*peer_id, rep::BAD_RESPONSE));
 			},
 		};
 
 		debug!(
 			target: LOG_TARGET,
-			"Importing state data from {} with {} keys, {} proof nodes.",
+			"Importing state data of range {:?} from {} with {} keys, {} proof nodes.",
+			range,
 			peer_id,
 			response.entries.len(),
 			response.proof.len(),
 		);
 
-		match self.state_sync.import(response) {
+		match self.state_sync.import(range, response) {
 			ImportResult::Import(hash, header, state, body, justifications) => {
 				let origin = BlockOrigin::NetworkInitialSync;
 				let block = IncomingBlock {
@@ -282,36 +297,31 @@ impl<B: BlockT> StateStrategy<B> {
 		}
 	}
 
-	/// Produce state request.
-	fn state_request(&mut self) -> Option<(PeerId, StateRequest)> {
+	/// Produce state requests.
+	///
+	/// A different range of the state is requested from each available peer.
+	fn state_requests(&mut self) -> Vec<(PeerId, StateRequest)> {
+		let mut requests = Vec::new();
 		if self.state_sync.is_complete() {
-			return None
+			return requests
 		}
 
-		if self
-			.peers
-			.values()
-			.any(|peer| matches!(peer.state, PeerState::DownloadingState))
-		{
-			// Only one state request at a time is possible.
-			return None
+		let target_number = self.state_sync.target_number();
+		while let Some(peer_id) = self.next_peer(target_number) {
+			let Some((range, request)) = self.state_sync.next_request() else { break };
+			if let Some(peer) = self.peers.get_mut(&peer_id) {
+				peer.state = PeerState::DownloadingState(range);
+			}
+			trace!(
+				target: LOG_TARGET,
+				"New state request for range {range:?} to {peer_id}: {request:?}.",
+			);
+			requests.push((peer_id, request));
 		}
-
-		let peer_id =
-			self.schedule_next_peer(PeerState::DownloadingState, self.state_sync.target_number())?;
-		let request = self.state_sync.next_request();
-		trace!(
-			target: LOG_TARGET,
-			"New state request to {peer_id}: {request:?}.",
-		);
-		Some((peer_id, request))
+		requests
 	}
 
-	fn schedule_next_peer(
-		&mut self,
-		new_state: PeerState,
-		min_best_number: NumberFor<B>,
-	) -> Option<PeerId> {
+	fn next_peer(&self, min_best_number: NumberFor<B>) -> Option<PeerId> {
 		let mut targets: Vec<_> = self.peers.values().map(|p| p.best_number).collect();
 		if targets.is_empty() {
 			return None
@@ -321,12 +331,11 @@ impl<B: BlockT> StateStrategy<B> {
 		let threshold = std::cmp::max(median, min_best_number);
 		// Find a random peer that is synced as much as peer majority and is above
 		// `min_best_number`.
-		for (peer_id, peer) in self.peers.iter_mut() {
+		for (peer_id, peer) in self.peers.iter() {
 			if peer.state.is_available() &&
 				peer.best_number >= threshold &&
 				self.disconnected_peers.is_peer_available(peer_id)
 			{
-				peer.state = new_state;
 				return Some(*peer_id)
 			}
 		}
@@ -355,7 +364,7 @@ impl<B: BlockT> StateStrategy<B> {
 		&mut self,
 		network_service: &NetworkServiceHandle,
 	) -> impl Iterator<Item = SyncingAction<B>> {
-		let state_request = self.state_request().into_iter().map(|(peer_id, request)| {
+		let state_requests = self.state_requests().into_iter().map(|(peer_id, request)| {
 			let (tx, rx) = oneshot::channel();
 
 			network_service.start_request(
@@ -378,7 +387,7 @@ impl<B: BlockT> StateStrategy<B> {
 				remove_obsolete: false,
 			}
 		});
-		self.actions.extend(state_request);
+		self.actions.extend(state_requests);
 
 		std::mem::take(&mut self.actions).into_iter()
 	}
@@ -396,7 +405,7 @@ mod test {
 	use crate::{
 		schema::v1::{StateRequest, StateResponse},
 		service::network::NetworkServiceProvider,
-		strategy::state_sync::{ImportResult, StateSyncProgress, StateSyncProvider},
+		strategy::state_sync::{ImportResult, RangeId, StateSyncProgress, StateSyncProvider},
 	};
 	use codec::Decode;
 	use sc_block_builder::BlockBuilderBuilder;
@@ -404,6 +413,7 @@ mod test {
 	use sc_consensus::{ImportedAux, ImportedState};
 	use sp_core::H256;
 	use sp_runtime::traits::Zero;
+	use std::collections::HashSet;
 	use substrate_test_runtime_client::{
 		runtime::{Block, Hash},
 		BlockBuilderExt, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
@@ -413,8 +423,9 @@ mod test {
 		pub StateSync<B: BlockT> {}
 
 		impl<B: BlockT> StateSyncProvider<B> for StateSync<B> {
-			fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-			fn next_request(&self) -> StateRequest;
+			fn import(&mut self, range: RangeId, response: StateResponse) -> ImportResult<B>;
+			fn next_request(&mut self) -> Option<(RangeId, StateRequest)>;
+			fn on_request_failed(&mut self, range: RangeId);
 			fn is_complete(&self) -> bool;
 			fn target_number(&self) -> NumberFor<B>;
 			fn target_hash(&self) -> B::Hash;
@@ -445,9 +456,7 @@ mod test {
 			ProtocolName::Static(""),
 		);
 
-		assert!(state_strategy
-			.schedule_next_peer(PeerState::DownloadingState, Zero::zero())
-			.is_none());
+		assert!(state_strategy.next_peer(Zero::zero()).is_none());
 	}
 
 	#[test]
@@ -478,8 +487,7 @@ mod test {
 				ProtocolName::Static(""),
 			);
 
-			let peer_id =
-				state_strategy.schedule_next_peer(PeerState::DownloadingState, Zero::zero());
+			let peer_id = state_strategy.next_peer(Zero::zero());
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() >= 6);
 		}
 	}
@@ -512,7 +520,7 @@ mod test {
 				ProtocolName::Static(""),
 			);
 
-			let peer_id = state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+			let peer_id = state_strategy.next_peer(10);
 			assert!(*peers.get(&peer_id.unwrap()).unwrap() == 10);
 		}
 	}
@@ -552,9 +560,10 @@ mod test {
 
 		// Disconnect the peer with an inflight request.
 		state_strategy.add_peer(tenth_peer, H256::random(), 10);
-		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+		let peer_id: Option<PeerId> = state_strategy.next_peer(10);
 		assert_eq!(tenth_peer, peer_id.unwrap());
+		state_strategy.peers.get_mut(&tenth_peer).unwrap().state =
+			PeerState::DownloadingState(RangeId(0));
 		state_strategy.remove_peer(&tenth_peer);
 
 		// Peer is backed off.
@@ -562,13 +571,11 @@ mod test {
 
 		// No peer available for 10'th best block because of the backoff.
 		state_strategy.add_peer(tenth_peer, H256::random(), 10);
-		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 10);
+		let peer_id: Option<PeerId> = state_strategy.next_peer(10);
 		assert!(peer_id.is_none());
 
 		// Other requests can still happen.
-		let peer_id: Option<PeerId> =
-			state_strategy.schedule_next_peer(PeerState::DownloadingState, 9);
+		let peer_id: Option<PeerId> = state_strategy.next_peer(9);
 		assert_eq!(ninth_peer, peer_id.unwrap());
 	}
 
@@ -596,14 +603,14 @@ mod test {
 			ProtocolName::Static(""),
 		);
 
-		let (_peer_id, request) = state_strategy.state_request().unwrap();
+		let (_peer_id, request) = state_strategy.state_requests().pop().unwrap();
 		let hash = Hash::decode(&mut &*request.block).unwrap();
 
 		assert_eq!(hash, target_block.header().hash());
 	}
 
 	#[test]
-	fn no_parallel_state_requests() {
+	fn parallel_state_requests_are_sent_to_different_peers() {
 		let client = Arc::new(TestClientBuilder::new().set_no_genesis().build());
 		let target_block = BlockBuilderBuilder::new(&*client)
 			.on_parent_block(client.chain_info().best_hash)
@@ -626,17 +633,25 @@ mod test {
 			ProtocolName::Static(""),
 		);
 
-		// First request is sent.
-		assert!(state_strategy.state_request().is_some());
-
-		// No parallel request is sent.
-		assert!(state_strategy.state_request().is_none());
+		// A request is sent to each peer at least as synced as the median.
+		let requests = state_strategy.state_requests();
+		assert_eq!(requests.len(), 5);
+		let peers = requests.iter().map(|(peer_id, _)| *peer_id).collect::<HashSet<_>>();
+		assert_eq!(peers.len(), 5);
+		let starts = requests
+			.iter()
+			.map(|(_, request)| request.start.clone())
+			.collect::<HashSet<_>>();
+		assert_eq!(starts.len(), 5);
+
+		// No more requests are sent until a peer is available.
+		assert!(state_strategy.state_requests().is_empty());
 	}
 
 	#[test]
 	fn received_state_response_makes_peer_available_again() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -645,7 +660,8 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state =
+			PeerState::DownloadingState(RangeId(0));
 
 		let dummy_response = StateResponse::default().encode_to_vec();
 		state_strategy.on_state_response(&peer_id, dummy_response);
@@ -653,11 +669,54 @@ mod test {
 		assert!(state_strategy.peers.get(&peer_id).unwrap().state.is_available());
 	}
 
+	#[test]
+	fn range_of_disconnected_peer_is_requested_again() {
+		let mut state_sync_provider = MockStateSync::<Block>::new();
+		state_sync_provider
+			.expect_on_request_failed()
+			.with(mockall::predicate::eq(RangeId(3)))
+			.times(1)
+			.return_const(());
+		let peer_id = PeerId::random();
+		let initial_peers = std::iter::once((peer_id, 10));
+		let mut state_strategy = StateStrategy::new_with_provider(
+			Box::new(state_sync_provider),
+			initial_peers,
+			ProtocolName::Static(""),
+		);
+		// Manually set the peer's state.
+		state_strategy.peers.get_mut(&peer_id).unwrap().state =
+			PeerState::DownloadingState(RangeId(3));
+
+		state_strategy.remove_peer(&peer_id);
+	}
+
+	#[test]
+	fn unexpected_state_response_is_ignored() {
+		// The provider doesn't expect any import.
+		let state_sync_provider = MockStateSync::<Block>::new();
+		let peer_id = PeerId::random();
+		let initial_peers = std::iter::once((peer_id, 10));
+		let mut state_strategy = StateStrategy::new_with_provider(
+			Box::new(state_sync_provider),
+			initial_peers,
+			ProtocolName::Static(""),
+		);
+
+		let dummy_response = StateResponse::default().encode_to_vec();
+		assert!(state_strategy.on_state_response_inner(&peer_id, &dummy_response).is_ok());
+		assert!(state_strategy
+			.on_state_response_inner(&PeerId::random(), &dummy_response)
+			.is_ok());
+	}
+
 	#[test]
 	fn bad_state_response_drops_peer() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Provider says that state response is bad.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::BadResponse);
+		state_sync_provider
+			.expect_import()
+			.return_once(|_, _| ImportResult::BadResponse);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -666,7 +725,8 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state.
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state =
+			PeerState::DownloadingState(RangeId(0));
 		let dummy_response = StateResponse::default().encode_to_vec();
 		// Receiving response drops the peer.
 		assert!(matches!(
@@ -679,7 +739,7 @@ mod test {
 	fn partial_state_response_doesnt_generate_actions() {
 		let mut state_sync_provider = MockStateSync::<Block>::new();
 		// Sync provider says that the response is partial.
-		state_sync_provider.expect_import().return_once(|_| ImportResult::Continue);
+		state_sync_provider.expect_import().return_once(|_, _| ImportResult::Continue);
 		let peer_id = PeerId::random();
 		let initial_peers = std::iter::once((peer_id, 10));
 		let mut state_strategy = StateStrategy::new_with_provider(
@@ -688,7 +748,8 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state =
+			PeerState::DownloadingState(RangeId(0));
 
 		let dummy_response = StateResponse::default().encode_to_vec();
 		state_strategy.on_state_response(&peer_id, dummy_response);
@@ -723,7 +784,7 @@ mod test {
 			body.clone(),
 			justifications.clone(),
 		);
-		state_sync_provider.expect_import().return_once(move |_| import);
+		state_sync_provider.expect_import().return_once(move |_, _| import);
 
 		// Reference values to check against.
 		let expected_origin = BlockOrigin::NetworkInitialSync;
@@ -750,7 +811,8 @@ mod test {
 			ProtocolName::Static(""),
 		);
 		// Manually set the peer's state .
-		state_strategy.peers.get_mut(&peer_id).unwrap().state = PeerState::DownloadingState;
+		state_strategy.peers.get_mut(&peer_id).unwrap().state =
+			PeerState::DownloadingState(RangeId(0));
 
 		// Receive response.
 		let dummy_response = StateResponse::default().encode_to_vec();
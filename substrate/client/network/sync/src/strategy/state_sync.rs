@@ -17,6 +17,11 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! State sync support.
+//!
+//! The state is split into ranges of keys of the top trie and of child tries, which are
+//! downloaded and verified independently of each other. This allows querying several peers
+//! concurrently: ranges being downloaded are split further when there are more peers than ranges
+//! left to download.
 
 use crate::{
 	schema::v1::{KeyValueStateEntry, StateEntry, StateRequest, StateResponse},
@@ -24,22 +29,39 @@ use crate::{
 };
 use codec::{Decode, Encode};
 use log::debug;
-use sc_client_api::{CompactProof, KeyValueStates, ProofProvider};
+use sc_client_api::{CompactProof, ProofProvider};
 use sc_consensus::ImportedState;
-use smallvec::SmallVec;
-use sp_core::storage::well_known_keys;
+use sp_core::{hexdisplay::HexDisplay, storage::well_known_keys};
 use sp_runtime::{
 	traits::{Block as BlockT, Header, NumberFor},
 	Justifications,
 };
-use std::{collections::HashMap, fmt, sync::Arc};
+use std::{
+	collections::{BTreeMap, HashMap, HashSet},
+	fmt,
+	sync::Arc,
+};
+
+/// Maximum number of ranges the state is split into.
+const MAX_RANGES: usize = 256;
+
+/// Size of the key space used to estimate the progress, see [`key_position`].
+const KEY_SPACE: u128 = 1 << 64;
+
+/// Identifier of a range of the state, as returned by [`StateSyncProvider::next_request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RangeId(pub u64);
 
 /// Generic state sync provider. Used for mocking in tests.
 pub trait StateSyncProvider<B: BlockT>: Send + Sync {
-	/// Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B>;
-	/// Produce next state request.
-	fn next_request(&self) -> StateRequest;
+	/// Validate and import a state response to the request for the given range.
+	fn import(&mut self, range: RangeId, response: StateResponse) -> ImportResult<B>;
+	/// Produce next state request, for a range which is not being downloaded.
+	///
+	/// Returns `None` if the remaining ranges are all being downloaded and can't be split.
+	fn next_request(&mut self) -> Option<(RangeId, StateRequest)>;
+	/// Notify that the request for the given range failed, so that the range is requested again.
+	fn on_request_failed(&mut self, range: RangeId);
 	/// Check if the state is complete.
 	fn is_complete(&self) -> bool;
 	/// Returns target block number.
@@ -75,6 +97,10 @@ pub struct StateSyncProgress {
 	pub percentage: u32,
 	/// Total state size in bytes downloaded so far.
 	pub size: u64,
+	/// Number of ranges of the state being downloaded.
+	pub active_ranges: u32,
+	/// Number of ranges of the state left to download, including the ones being downloaded.
+	pub pending_ranges: u32,
 	/// Current state sync phase.
 	pub phase: StateSyncPhase,
 }
@@ -89,8 +115,141 @@ pub enum ImportResult<B: BlockT> {
 	BadResponse,
 }
 
+/// Position of a key in the key space, from its first 8 bytes.
+fn key_position(key: &[u8]) -> u128 {
+	let mut position = [0u8; 8];
+	let len = key.len().min(position.len());
+	position[..len].copy_from_slice(&key[..len]);
+	u64::from_be_bytes(position).into()
+}
+
+/// Returns the smallest key greater than all the keys starting with `prefix`, if any.
+fn prefix_end(prefix: &[u8]) -> Option<Vec<u8>> {
+	let mut end = prefix.to_vec();
+	while let Some(last) = end.pop() {
+		if last < u8::MAX {
+			end.push(last + 1);
+			return Some(end)
+		}
+	}
+	None
+}
+
+/// Returns a key between `start` and `end`, both excluded, if any.
+///
+/// Keys are seen as fractions of the key space, and the returned key is close to the middle of
+/// the two. `end` is the end of the key space if `None`.
+fn middle_key(start: &[u8], end: Option<&[u8]>) -> Option<Vec<u8>> {
+	let width = start.len().max(end.map_or(0, <[u8]>::len)) + 1;
+	let digit = |key: &[u8], index: usize| u16::from(key.get(index).copied().unwrap_or_default());
+
+	// Sum the two keys, the end of the key space counting as an integer part of 1.
+	let mut sum = vec![0u16; width];
+	let mut carry = 0;
+	for index in (0..width).rev() {
+		let digits = digit(start, index) + end.map_or(0, |end| digit(end, index)) + carry;
+		sum[index] = digits & 0xff;
+		carry = digits >> 8;
+	}
+	let mut remainder = carry + u16::from(end.is_none());
+
+	// Halve the sum.
+	let middle = sum
+		.into_iter()
+		.map(|digit| {
+			let value = (remainder << 8) | digit;
+			remainder = value & 1;
+			(value >> 1) as u8
+		})
+		.collect::<Vec<_>>();
+
+	(middle.as_slice() > start && end.is_none_or(|end| middle.as_slice() < end)).then_some(middle)
+}
+
+/// Range of keys of the top trie or of a child trie.
+#[derive(Debug)]
+struct StateRange {
+	/// Prefixed storage key of the child trie, `None` for the top trie.
+	child: Option<Vec<u8>>,
+	/// Last key downloaded, `None` if the range is downloaded from the start of the trie.
+	last_key: Option<Vec<u8>>,
+	/// Last key of the range, `None` if the range extends to the end of the trie.
+	end: Option<Vec<u8>>,
+	/// Whether a request for the range is in flight.
+	in_flight: bool,
+	/// Number of responses imported for the range.
+	responses: u32,
+}
+
+impl StateRange {
+	fn new(child: Option<Vec<u8>>, last_key: Option<Vec<u8>>, end: Option<Vec<u8>>) -> Self {
+		Self { child, last_key, end, in_flight: false, responses: 0 }
+	}
+
+	/// Start keys of the next request for the range.
+	fn start(&self) -> Vec<Vec<u8>> {
+		match &self.child {
+			Some(child) => vec![child.clone(), self.last_key.clone().unwrap_or_default()],
+			None => self.last_key.iter().cloned().collect(),
+		}
+	}
+
+	/// Whether `key` is within the part of the range left to download.
+	///
+	/// Returns `None` if `key` is past the end of the range.
+	fn contains(&self, key: &[u8]) -> Option<bool> {
+		if self.end.as_ref().is_some_and(|end| key > end.as_slice()) {
+			return None
+		}
+		Some(self.last_key.as_ref().is_none_or(|last_key| key > last_key.as_slice()))
+	}
+
+	/// Whether the part of the range left to download is empty.
+	fn is_exhausted(&self) -> bool {
+		matches!((&self.last_key, &self.end), (Some(last_key), Some(end)) if last_key >= end)
+	}
+
+	/// Estimated size of the part of the range left to download.
+	fn remaining(&self) -> u128 {
+		let start = self.last_key.as_deref().map_or(0, key_position);
+		let end = self.end.as_deref().map_or(KEY_SPACE, key_position);
+		end.saturating_sub(start)
+	}
+
+	/// Returns a key splitting the part of the range left to download, if any.
+	fn split_key(&self) -> Option<Vec<u8>> {
+		let start = self.last_key.as_deref().unwrap_or_default();
+		let end = self.end.as_deref();
+
+		// Keys are mostly made of the hashes of a pallet and storage item names, followed by the
+		// hashed keys of a map. The range is preferably split within the map being downloaded,
+		// where keys are likely to be dense.
+		for prefix_len in [32, 16] {
+			if start.len() <= prefix_len {
+				continue
+			}
+			let Some(prefix_end) = prefix_end(&start[..prefix_len]) else { continue };
+			if end.is_some_and(|end| prefix_end.as_slice() > end) {
+				continue
+			}
+			if let Some(middle) = middle_key(start, Some(&prefix_end)) {
+				return Some(middle)
+			}
+		}
+
+		middle_key(start, end)
+	}
+}
+
+/// Key values read from a state response.
+struct RangeResponse {
+	/// Root and key values of each trie read, in order, starting with the top trie.
+	levels: Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>)>,
+	/// Depth of the trie the response stopped in, 0 if the state was read to its end.
+	stopped_at: usize,
+}
+
 struct StateSyncMetadata<B: BlockT> {
-	last_key: SmallVec<[Vec<u8>; 2]>,
 	target_header: B::Header,
 	target_body: Option<Vec<B::Extrinsic>>,
 	target_justifications: Option<Justifications>,
@@ -112,28 +271,6 @@ impl<B: BlockT> StateSyncMetadata<B> {
 	fn target_root(&self) -> B::Hash {
 		*self.target_header.state_root()
 	}
-
-	fn next_request(&self) -> StateRequest {
-		StateRequest {
-			block: self.target_hash().encode(),
-			start: self.last_key.clone().into_vec(),
-			no_proof: self.skip_proof,
-		}
-	}
-
-	fn progress(&self) -> StateSyncProgress {
-		let cursor = *self.last_key.get(0).and_then(|last| last.get(0)).unwrap_or(&0u8);
-		let percent_done = cursor as u32 * 100 / 256;
-		StateSyncProgress {
-			percentage: percent_done,
-			size: self.imported_bytes,
-			phase: if self.complete {
-				StateSyncPhase::ImportingState
-			} else {
-				StateSyncPhase::DownloadingState
-			},
-		}
-	}
 }
 
 /// State sync state machine.
@@ -141,6 +278,13 @@ impl<B: BlockT> StateSyncMetadata<B> {
 /// Accumulates partial state data until it is ready to be imported.
 pub struct StateSync<B: BlockT, Client> {
 	metadata: StateSyncMetadata<B>,
+	/// Ranges left to download.
+	ranges: BTreeMap<RangeId, StateRange>,
+	next_range_id: u64,
+	/// Storage key under which each child trie is downloaded, by child trie root.
+	///
+	/// Child tries with the same root are only downloaded once.
+	child_roots: HashMap<Vec<u8>, Vec<u8>>,
 	state: HashMap<Vec<u8>, (Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>,
 	client: Arc<Client>,
 }
@@ -161,7 +305,6 @@ where
 		Self {
 			client,
 			metadata: StateSyncMetadata {
-				last_key: SmallVec::default(),
 				target_header,
 				target_body,
 				target_justifications,
@@ -169,10 +312,47 @@ where
 				imported_bytes: 0,
 				skip_proof,
 			},
+			ranges: BTreeMap::from([(RangeId(0), StateRange::new(None, None, None))]),
+			next_range_id: 1,
+			child_roots: HashMap::default(),
 			state: HashMap::default(),
 		}
 	}
 
+	fn add_range(&mut self, range: StateRange) -> RangeId {
+		let id = RangeId(self.next_range_id);
+		self.next_range_id += 1;
+		self.ranges.insert(id, range);
+		id
+	}
+
+	/// Split a range being downloaded, and return the identifier of its second half.
+	fn split_range(&mut self) -> Option<RangeId> {
+		if self.ranges.len() >= MAX_RANGES {
+			return None
+		}
+
+		// The ranges for which the most responses were received are the most likely to be large.
+		let (id, _, split_key) = self
+			.ranges
+			.iter()
+			.filter(|(_, range)| range.in_flight)
+			.filter_map(|(id, range)| {
+				Some((*id, (range.responses, range.remaining()), range.split_key()?))
+			})
+			.max_by_key(|(_, priority, _)| *priority)?;
+
+		let range = self.ranges.get_mut(&id).expect("Range was found above; qed");
+		let end = range.end.replace(split_key.clone());
+		let child = range.child.clone();
+		debug!(
+			target: LOG_TARGET,
+			"Splitting state range {id:?} at {}",
+			HexDisplay::from(&split_key),
+		);
+		Some(self.add_range(StateRange::new(child, Some(split_key), end)))
+	}
+
 	fn process_state_key_values(
 		&mut self,
 		state_root: Vec<u8>,
@@ -182,12 +362,6 @@ where
 
 		let entry = self.state.entry(state_root).or_default();
 
-		if entry.0.len() > 0 && entry.1.len() > 1 {
-			// Already imported child_trie with same root.
-			// Warning this will not work with parallel download.
-			return;
-		}
-
 		let mut child_storage_roots = Vec::new();
 
 		for (key, value) in key_values {
@@ -205,103 +379,227 @@ where
 		}
 	}
 
-	fn process_state_verified(&mut self, values: KeyValueStates) {
-		for values in values.0 {
-			self.process_state_key_values(values.state_root, values.key_values);
-		}
-	}
-
-	fn process_state_unverified(&mut self, response: StateResponse) -> bool {
-		let mut complete = true;
-		// if the trie is a child trie and one of its parent trie is empty,
-		// the parent cursor stays valid.
-		// Empty parent trie content only happens when all the response content
-		// is part of a single child trie.
-		if self.metadata.last_key.len() == 2 && response.entries[0].entries.is_empty() {
-			// Do not remove the parent trie position.
-			self.metadata.last_key.pop();
-		} else {
-			self.metadata.last_key.clear();
-		}
-		for state in response.entries {
-			debug!(
-				target: LOG_TARGET,
-				"Importing state from {:?} to {:?}",
-				state.entries.last().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-				state.entries.first().map(|e| sp_core::hexdisplay::HexDisplay::from(&e.key)),
-			);
-
-			if !state.complete {
-				if let Some(e) = state.entries.last() {
-					self.metadata.last_key.push(e.key.clone());
-				}
-				complete = false;
-			}
-
-			let KeyValueStateEntry { state_root, entries, complete: _ } = state;
-			self.process_state_key_values(
-				state_root,
-				entries.into_iter().map(|StateEntry { key, value }| (key, value)),
-			);
-		}
-		complete
-	}
-}
-
-impl<B, Client> StateSyncProvider<B> for StateSync<B, Client>
-where
-	B: BlockT,
-	Client: ProofProvider<B> + Send + Sync + 'static,
-{
-	///  Validate and import a state response.
-	fn import(&mut self, response: StateResponse) -> ImportResult<B> {
+	/// Read the key values of a response to a request starting at `start`.
+	fn read_response(
+		&mut self,
+		start: &[Vec<u8>],
+		response: StateResponse,
+	) -> Option<RangeResponse> {
 		if response.entries.is_empty() && response.proof.is_empty() {
 			debug!(target: LOG_TARGET, "Bad state response");
-			return ImportResult::BadResponse
+			return None
 		}
 		if !self.metadata.skip_proof && response.proof.is_empty() {
 			debug!(target: LOG_TARGET, "Missing proof");
-			return ImportResult::BadResponse
+			return None
 		}
-		let complete = if !self.metadata.skip_proof {
+
+		if !self.metadata.skip_proof {
 			debug!(target: LOG_TARGET, "Importing state from {} trie nodes", response.proof.len());
 			let proof_size = response.proof.len() as u64;
 			let proof = match CompactProof::decode(&mut response.proof.as_ref()) {
 				Ok(proof) => proof,
 				Err(e) => {
 					debug!(target: LOG_TARGET, "Error decoding proof: {:?}", e);
-					return ImportResult::BadResponse
+					return None
 				},
 			};
-			let (values, completed) = match self.client.verify_range_proof(
-				self.metadata.target_root(),
-				proof,
-				self.metadata.last_key.as_slice(),
-			) {
-				Err(e) => {
+			let (values, completed) =
+				match self.client.verify_range_proof(self.metadata.target_root(), proof, start) {
+					Err(e) => {
+						debug!(
+							target: LOG_TARGET,
+							"StateResponse failed proof verification: {}",
+							e,
+						);
+						return None
+					},
+					Ok(values) => values,
+				};
+			debug!(target: LOG_TARGET, "Imported with {} keys", values.len());
+
+			self.metadata.imported_bytes += proof_size;
+			let levels =
+				values.0.into_iter().map(|level| (level.state_root, level.key_values)).collect();
+			Some(RangeResponse { levels, stopped_at: completed })
+		} else {
+			let stopped_at = match response.entries.as_slice() {
+				[top, ..] if top.complete => 0,
+				[_, .., child] if !child.complete => 2,
+				_ => 1,
+			};
+			let levels = response
+				.entries
+				.into_iter()
+				.map(|state| {
 					debug!(
 						target: LOG_TARGET,
-						"StateResponse failed proof verification: {}",
-						e,
+						"Importing state from {:?} to {:?}",
+						state.entries.first().map(|e| HexDisplay::from(&e.key)),
+						state.entries.last().map(|e| HexDisplay::from(&e.key)),
 					);
-					return ImportResult::BadResponse
+
+					let KeyValueStateEntry { state_root, entries, complete: _ } = state;
+					let key_values =
+						entries.into_iter().map(|StateEntry { key, value }| (key, value)).collect();
+					(state_root, key_values)
+				})
+				.collect();
+			Some(RangeResponse { levels, stopped_at })
+		}
+	}
+
+	/// Import the response to a request for a range of the top trie.
+	///
+	/// Returns the last key of the range imported and whether the range is complete, or `None`
+	/// if the response is invalid.
+	fn import_top_range(
+		&mut self,
+		range: &StateRange,
+		response: RangeResponse,
+	) -> Option<(Option<Vec<u8>>, bool)> {
+		let RangeResponse { levels, stopped_at } = response;
+		let mut levels = levels.into_iter();
+		let (_, top) = levels.next()?;
+
+		let mut complete = stopped_at == 0;
+		let mut entries = Vec::new();
+		for (key, value) in top {
+			match range.contains(&key) {
+				Some(true) => entries.push((key, value)),
+				Some(false) => {},
+				None => {
+					complete = true;
+					break
 				},
-				Ok(values) => values,
-			};
-			debug!(target: LOG_TARGET, "Imported with {} keys", values.len());
+			}
+		}
 
-			let complete = completed == 0;
-			if !complete && !values.update_last_key(completed, &mut self.metadata.last_key) {
-				debug!(target: LOG_TARGET, "Error updating key cursor, depth: {}", completed);
+		// Child tries are read right after their storage key, and only once per response.
+		let last_child = levels.len().checked_sub(1);
+		let mut read_roots = HashSet::new();
+		for (index, (root, key_values)) in levels.enumerate() {
+			let Some(storage_key) = entries
+				.iter()
+				.find(|(key, value)| {
+					*value == root && well_known_keys::is_child_storage_key(key.as_slice())
+				})
+				.map(|(key, _)| key.clone())
+			else {
+				// The child trie is not in the range.
+				continue
 			};
+			if !read_roots.insert(root.clone()) {
+				continue
+			}
+			let owner = self.child_roots.entry(root.clone()).or_insert_with(|| storage_key.clone());
+			if *owner != storage_key {
+				// The child trie is downloaded under another storage key.
+				continue
+			}
 
-			self.process_state_verified(values);
-			self.metadata.imported_bytes += proof_size;
-			complete
-		} else {
-			self.process_state_unverified(response)
+			if stopped_at == 2 && Some(index) == last_child {
+				let Some((last_key, _)) = key_values.last() else {
+					// Nothing was read from the child trie, its storage key is read again with the
+					// next request.
+					entries.retain(|(key, _)| *key != storage_key);
+					self.child_roots.remove(&root);
+					continue
+				};
+				let child_range =
+					StateRange::new(Some(storage_key.clone()), Some(last_key.clone()), None);
+				let id = self.add_range(child_range);
+				debug!(
+					target: LOG_TARGET,
+					"Downloading child trie {} in state range {id:?}",
+					HexDisplay::from(&storage_key),
+				);
+			}
+			self.process_state_key_values(root, key_values);
+		}
+
+		let last_key = entries.last().map(|(key, _)| key.clone());
+		self.process_state_key_values(Vec::new(), entries);
+		Some((last_key, complete))
+	}
+
+	/// Import the response to a request for a range of a child trie.
+	///
+	/// Returns the last key of the range imported and whether the range is complete, or `None`
+	/// if the response is invalid.
+	fn import_child_range(
+		&mut self,
+		range: &StateRange,
+		response: RangeResponse,
+	) -> Option<(Option<Vec<u8>>, bool)> {
+		let RangeResponse { levels, stopped_at } = response;
+		// The response continues with the top trie after the child trie, which is ignored.
+		let mut complete = !(stopped_at == 2 && levels.len() == 2);
+		let (root, key_values) = levels.into_iter().nth(1)?;
+		if self.child_roots.get(&root) != range.child.as_ref() {
+			debug!(target: LOG_TARGET, "Unexpected child trie root in state response");
+			return None
+		}
+
+		let mut entries = Vec::new();
+		for (key, value) in key_values {
+			match range.contains(&key) {
+				Some(true) => entries.push((key, value)),
+				Some(false) => {},
+				None => {
+					complete = true;
+					break
+				},
+			}
+		}
+
+		let last_key = entries.last().map(|(key, _)| key.clone());
+		self.process_state_key_values(root, entries);
+		Some((last_key, complete))
+	}
+}
+
+impl<B, Client> StateSyncProvider<B> for StateSync<B, Client>
+where
+	B: BlockT,
+	Client: ProofProvider<B> + Send + Sync + 'static,
+{
+	///  Validate and import a state response.
+	fn import(&mut self, id: RangeId, response: StateResponse) -> ImportResult<B> {
+		let Some(range) = self.ranges.remove(&id) else {
+			debug!(target: LOG_TARGET, "State response for unknown range {id:?}");
+			return ImportResult::Continue
 		};
-		if complete {
+		let mut range = StateRange { in_flight: false, ..range };
+
+		let imported = self.read_response(&range.start(), response).and_then(|response| {
+			if range.child.is_some() {
+				self.import_child_range(&range, response)
+			} else {
+				self.import_top_range(&range, response)
+			}
+		});
+		let Some((last_key, complete)) = imported else {
+			debug!(target: LOG_TARGET, "Failed to import state range {id:?}");
+			self.ranges.insert(id, range);
+			return ImportResult::BadResponse
+		};
+
+		if last_key.is_none() && !complete {
+			debug!(target: LOG_TARGET, "State response for range {id:?} makes no progress");
+			self.ranges.insert(id, range);
+			return ImportResult::BadResponse
+		}
+
+		range.responses += 1;
+		range.last_key = last_key.or(range.last_key);
+		if complete || range.is_exhausted() {
+			debug!(target: LOG_TARGET, "State range {id:?} is complete");
+		} else {
+			self.ranges.insert(id, range);
+		}
+
+		if self.ranges.is_empty() {
 			self.metadata.complete = true;
 			let target_hash = self.metadata.target_hash();
 			ImportResult::Import(
@@ -317,8 +615,32 @@ where
 	}
 
 	/// Produce next state request.
-	fn next_request(&self) -> StateRequest {
-		self.metadata.next_request()
+	fn next_request(&mut self) -> Option<(RangeId, StateRequest)> {
+		if self.metadata.complete {
+			return None
+		}
+
+		let id = match self.ranges.iter().find(|(_, range)| !range.in_flight) {
+			Some((id, _)) => *id,
+			None => self.split_range()?,
+		};
+		let range = self.ranges.get_mut(&id)?;
+		range.in_flight = true;
+
+		let request = StateRequest {
+			block: self.metadata.target_hash().encode(),
+			start: range.start(),
+			no_proof: self.metadata.skip_proof,
+		};
+		Some((id, request))
+	}
+
+	/// Notify that the request for the given range failed.
+	fn on_request_failed(&mut self, id: RangeId) {
+		if let Some(range) = self.ranges.get_mut(&id) {
+			range.in_flight = false;
+			debug!(target: LOG_TARGET, "Request for state range {id:?} failed");
+		}
 	}
 
 	/// Check if the state is complete.
@@ -338,6 +660,234 @@ where
 
 	/// Returns state sync estimated progress.
 	fn progress(&self) -> StateSyncProgress {
-		self.metadata.progress()
+		let (percentage, phase) = if self.metadata.complete {
+			(100, StateSyncPhase::ImportingState)
+		} else {
+			// Child tries are not accounted for, so the download is never reported as done.
+			let remaining = self
+				.ranges
+				.values()
+				.filter(|range| range.child.is_none())
+				.map(StateRange::remaining)
+				.sum::<u128>()
+				.min(KEY_SPACE);
+			let percentage = ((KEY_SPACE - remaining) * 100 / KEY_SPACE) as u32;
+			(percentage.min(99), StateSyncPhase::DownloadingState)
+		};
+
+		StateSyncProgress {
+			percentage,
+			size: self.metadata.imported_bytes,
+			active_ranges: self.ranges.values().filter(|range| range.in_flight).count() as u32,
+			pending_ranges: self.ranges.len() as u32,
+			phase,
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use sc_client_api::KeyValueStates;
+	use sp_blockchain::HeaderBackend;
+	use sp_core::{blake2_128, storage::ChildInfo, twox_128};
+	use substrate_test_runtime_client::{
+		runtime::Block, DefaultTestClientBuilderExt, TestClient, TestClientBuilder,
+		TestClientBuilderExt,
+	};
+
+	/// Size limit of the test responses, so that the state is downloaded with many requests.
+	const RESPONSE_SIZE_LIMIT: usize = 1024;
+
+	type StateLevels = Vec<(Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>, Vec<Vec<u8>>)>;
+
+	fn test_client() -> TestClient {
+		let map_prefix = [twox_128(b"Test"), twox_128(b"Map")].concat();
+		let first = ChildInfo::new_default(b"first");
+		let second = ChildInfo::new_default(b"second");
+		let third = ChildInfo::new_default(b"third");
+
+		let mut builder = TestClientBuilder::new();
+		for i in 0..200u32 {
+			builder = builder.add_extra_storage(
+				[&map_prefix[..], &blake2_128(&i.encode())].concat(),
+				vec![0; 32],
+			)
+		}
+		// The first two child tries have the same root.
+		for i in 0..50u32 {
+			builder = builder
+				.add_extra_child_storage(&first, i.encode(), vec![1; 32])
+				.add_extra_child_storage(&second, i.encode(), vec![1; 32])
+				.add_extra_child_storage(&third, blake2_128(&i.encode()), vec![2; 32]);
+		}
+		builder.build()
+	}
+
+	fn respond(client: &TestClient, request: &StateRequest) -> StateResponse {
+		let hash = Decode::decode(&mut &request.block[..]).unwrap();
+		if request.no_proof {
+			let entries = client
+				.storage_collection(hash, &request.start, RESPONSE_SIZE_LIMIT)
+				.unwrap()
+				.into_iter()
+				.map(|(state, complete)| KeyValueStateEntry {
+					state_root: state.state_root,
+					entries: state
+						.key_values
+						.into_iter()
+						.map(|(key, value)| StateEntry { key, value })
+						.collect(),
+					complete,
+				})
+				.collect();
+			StateResponse { entries, proof: Vec::new() }
+		} else {
+			let (proof, _) =
+				client.read_proof_collection(hash, &request.start, RESPONSE_SIZE_LIMIT).unwrap();
+			StateResponse { entries: Vec::new(), proof: proof.encode() }
+		}
+	}
+
+	fn sorted(state: KeyValueStates) -> StateLevels {
+		let mut levels = state
+			.0
+			.into_iter()
+			.map(|mut level| {
+				level.key_values.sort();
+				level.parent_storage_keys.sort();
+				(level.state_root, level.key_values, level.parent_storage_keys)
+			})
+			.collect::<Vec<_>>();
+		levels.sort();
+		levels
+	}
+
+	/// The state of the test client, as imported by state sync.
+	fn expected_state(client: &TestClient) -> StateLevels {
+		let hash = client.chain_info().genesis_hash;
+		let collection = client.storage_collection(hash, &[], usize::MAX).unwrap();
+		let mut levels = collection.into_iter().map(|(level, _)| level).collect::<Vec<_>>();
+
+		let (child_keys, top) = std::mem::take(&mut levels[0].key_values)
+			.into_iter()
+			.partition::<Vec<_>, _>(|(key, _)| well_known_keys::is_child_storage_key(key));
+		levels[0].key_values = top;
+		for level in &mut levels[1..] {
+			level.parent_storage_keys = child_keys
+				.iter()
+				.filter(|(_, root)| *root == level.state_root)
+				.map(|(key, _)| key.clone())
+				.collect();
+		}
+		sorted(KeyValueStates(levels))
+	}
+
+	/// Download the state of the test client, with at most `max_requests` concurrent requests.
+	///
+	/// Returns the maximum number of ranges downloaded concurrently.
+	fn download_state(skip_proof: bool, max_requests: usize) -> u32 {
+		let client = Arc::new(test_client());
+		let header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+		let mut state_sync =
+			StateSync::<Block, _>::new(client.clone(), header, None, None, skip_proof);
+
+		let mut max_active_ranges = 0;
+		let mut percentage = 0;
+		loop {
+			let requests = std::iter::from_fn(|| state_sync.next_request())
+				.take(max_requests)
+				.collect::<Vec<_>>();
+			assert!(!requests.is_empty());
+
+			let progress = state_sync.progress();
+			assert!(progress.percentage >= percentage);
+			percentage = progress.percentage;
+			max_active_ranges = max_active_ranges.max(progress.active_ranges);
+
+			for (range, request) in requests {
+				match state_sync.import(range, respond(&client, &request)) {
+					ImportResult::Import(_, _, state, _, _) => {
+						assert!(state_sync.is_complete());
+						assert_eq!(state_sync.next_request(), None);
+						assert_eq!(sorted(state.state), expected_state(&client));
+						return max_active_ranges
+					},
+					ImportResult::Continue => {},
+					ImportResult::BadResponse => panic!("Unexpected bad response"),
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn state_is_downloaded_sequentially() {
+		for skip_proof in [false, true] {
+			assert_eq!(download_state(skip_proof, 1), 1);
+		}
+	}
+
+	#[test]
+	fn state_is_downloaded_in_parallel() {
+		for skip_proof in [false, true] {
+			assert_eq!(download_state(skip_proof, 8), 8);
+		}
+	}
+
+	#[test]
+	fn failed_range_is_requested_again() {
+		let client = Arc::new(test_client());
+		let header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+		let mut state_sync = StateSync::<Block, _>::new(client.clone(), header, None, None, false);
+
+		let (range, request) = state_sync.next_request().unwrap();
+		let (second_range, second_request) = state_sync.next_request().unwrap();
+		assert_ne!(range, second_range);
+		assert_ne!(request.start, second_request.start);
+
+		state_sync.on_request_failed(range);
+		assert_eq!(state_sync.next_request(), Some((range, request.clone())));
+
+		assert!(matches!(
+			state_sync.import(range, StateResponse::default()),
+			ImportResult::BadResponse
+		));
+		assert_eq!(state_sync.next_request(), Some((range, request.clone())));
+
+		assert!(matches!(
+			state_sync.import(range, respond(&client, &request)),
+			ImportResult::Continue
+		));
+		let (_, next_request) = state_sync.next_request().unwrap();
+		assert!(next_request.start > request.start);
+	}
+
+	#[test]
+	fn middle_key_works() {
+		assert_eq!(middle_key(&[], None), Some(vec![0x80]));
+		assert_eq!(middle_key(&[0x80], None), Some(vec![0xc0, 0x00]));
+		assert_eq!(middle_key(&[0x10], Some(&[0x11])), Some(vec![0x10, 0x80]));
+		assert_eq!(middle_key(&[0x10, 0xff], Some(&[0x11])), Some(vec![0x10, 0xff, 0x80]));
+		assert_eq!(middle_key(&[0x10], Some(&[0x10, 0x00])), None);
+		assert_eq!(middle_key(&[0xff, 0xff], None), Some(vec![0xff, 0xff, 0x80]));
+
+		assert_eq!(prefix_end(&[0x01, 0xff]), Some(vec![0x02]));
+		assert_eq!(prefix_end(&[0xff, 0xff]), None);
+	}
+
+	#[test]
+	fn range_is_split_within_map() {
+		let map_prefix = [twox_128(b"Test"), twox_128(b"Map")].concat();
+		let last_key = [&map_prefix[..], &[0x10; 16]].concat();
+		let range = StateRange::new(None, Some(last_key.clone()), None);
+		let split_key = range.split_key().unwrap();
+		assert!(split_key > last_key);
+		assert!(split_key.starts_with(&map_prefix));
+
+		// A range ending within the map is split before its end.
+		let end = [&map_prefix[..], &[0x20; 16]].concat();
+		let range = StateRange::new(None, Some(last_key.clone()), Some(end.clone()));
+		let split_key = range.split_key().unwrap();
+		assert!(split_key > last_key && split_key < end);
 	}
 }
@@ -83,6 +83,10 @@ const POOL_NO_TAGS: i32 = POOL_INVALID_TX + 9;
 const POOL_INVALID_BLOCK_ID: i32 = POOL_INVALID_TX + 10;
 /// The pool is not accepting future transactions.
 const POOL_FUTURE_TX: i32 = POOL_INVALID_TX + 11;
+/// The pool does not support transaction bundles.
+const POOL_BUNDLES_NOT_SUPPORTED: i32 = POOL_INVALID_TX + 12;
+/// The transaction bundle is empty, too large or contains duplicated transactions.
+const POOL_INVALID_BUNDLE: i32 = POOL_INVALID_TX + 13;
 /// Other error.
 const OTHER_ERR: i32 = BASE_ERROR + 40;
 
@@ -175,6 +179,19 @@ impl From<Error> for ErrorObjectOwned {
 					None::<()>,
 				)
 			},
+			Error::Pool(PoolError::BundlesNotSupported) => ErrorObject::owned(
+				POOL_BUNDLES_NOT_SUPPORTED,
+				"Bundles not supported",
+				Some("The transaction pool does not support transaction bundles"),
+			),
+			Error::Pool(PoolError::InvalidBundle) => ErrorObject::owned(
+				POOL_INVALID_BUNDLE,
+				"Invalid bundle",
+				Some(
+					"The bundle is empty, too large, contains duplicated transactions or \
+					 transactions requiring tags it does not provide",
+				),
+			),
 			Error::UnsafeRpcCalled(e) => e.into(),
 			other => ErrorObject::owned(
 				OTHER_ERR,
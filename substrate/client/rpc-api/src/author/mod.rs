@@ -33,6 +33,13 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_submitExtrinsic")]
 	async fn submit_extrinsic(&self, extrinsic: Bytes) -> Result<Hash, Error>;
 
+	/// Submit hex-encoded extrinsics as a bundle.
+	///
+	/// The extrinsics are included in a block in the given order and all together, or not at all.
+	/// Returns the hash of the bundle.
+	#[method(name = "author_submitBundle", with_extensions)]
+	async fn submit_bundle(&self, extrinsics: Vec<Bytes>) -> Result<Hash, Error>;
+
 	/// Insert a key into the keystore.
 	#[method(name = "author_insertKey", with_extensions)]
 	fn insert_key(&self, key_type: String, suri: String, public: Bytes) -> Result<(), Error>;
@@ -0,0 +1,109 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate mempool introspection API.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+/// A transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransaction<Hash, AccountId, Nonce> {
+	/// The hash of the transaction.
+	pub hash: Hash,
+	/// The SCALE-encoded transaction.
+	pub extrinsic: Bytes,
+	/// The priority of the transaction.
+	pub priority: u64,
+	/// The number of blocks the transaction remains valid for.
+	pub longevity: u64,
+	/// The tags the transaction requires.
+	pub requires: Vec<Bytes>,
+	/// The tags the transaction provides.
+	pub provides: Vec<Bytes>,
+	/// Whether the transaction is propagated to other peers.
+	pub propagate: bool,
+	/// The signer of the transaction, if it could be decoded.
+	pub sender: Option<AccountId>,
+	/// The nonce of the transaction, if it could be decoded.
+	pub nonce: Option<Nonce>,
+}
+
+/// The transactions of a single view of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolView<BlockHash, Hash, AccountId, Nonce> {
+	/// The block the view is associated with, if any.
+	pub at: Option<BlockHash>,
+	/// The ready transactions, ordered by priority.
+	pub ready: Vec<MempoolTransaction<Hash, AccountId, Nonce>>,
+	/// The future transactions, waiting for their requirements to be provided.
+	pub future: Vec<MempoolTransaction<Hash, AccountId, Nonce>>,
+}
+
+/// The number of transactions of a single view of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolViewStatus<BlockHash> {
+	/// The block the view is associated with, if any.
+	pub at: Option<BlockHash>,
+	/// The number of ready transactions.
+	pub ready: usize,
+	/// The number of future transactions.
+	pub future: usize,
+}
+
+/// The status of the pool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolStatus<BlockHash> {
+	/// The number of ready transactions at the most recent best block.
+	pub ready: usize,
+	/// The size of the ready transactions at the most recent best block, in bytes.
+	pub ready_bytes: usize,
+	/// The number of future transactions at the most recent best block.
+	pub future: usize,
+	/// The size of the future transactions at the most recent best block, in bytes.
+	pub future_bytes: usize,
+	/// The number of transactions of every view of the pool.
+	pub views: Vec<MempoolViewStatus<BlockHash>>,
+}
+
+/// Substrate mempool introspection API.
+///
+/// The signer and the nonce of a transaction are decoded from the tags it provides, which is
+/// only possible if the runtime tags the transactions like `frame_system::CheckNonce` does.
+#[rpc(client, server)]
+pub trait MempoolApi<Hash, BlockHash, AccountId, Nonce> {
+	/// Returns the ready and future transactions of every view of the pool.
+	#[method(name = "mempool_content", with_extensions)]
+	fn content(&self) -> RpcResult<Vec<MempoolView<BlockHash, Hash, AccountId, Nonce>>>;
+
+	/// Returns the number of ready and future transactions of every view of the pool.
+	#[method(name = "mempool_status")]
+	fn status(&self) -> RpcResult<MempoolStatus<BlockHash>>;
+
+	/// Returns the ready and future transactions signed by `sender` of every view of the pool.
+	#[method(name = "mempool_inspect", with_extensions)]
+	fn inspect(
+		&self,
+		sender: AccountId,
+	) -> RpcResult<Vec<MempoolView<BlockHash, Hash, AccountId, Nonce>>>;
+}
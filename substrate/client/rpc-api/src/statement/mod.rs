@@ -20,7 +20,7 @@
 
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use sp_core::Bytes;
-use sp_statement_store::SubmitResult;
+use sp_statement_store::{SubmitResult, TopicFilter};
 
 pub mod error;
 
@@ -93,4 +93,16 @@ pub trait StatementApi {
 	/// Remove a statement from the store.
 	#[method(name = "statement_remove")]
 	fn remove(&self, statement_hash: [u8; 32]) -> RpcResult<()>;
+
+	/// Subscribe to new statements which match `topic_filter`.
+	///
+	/// If `dest` is not given, only statements with no `DecryptionKey` field are delivered,
+	/// otherwise only statements whose decryption key is identified as `dest`. Each notification
+	/// is a SCALE-encoded statement.
+	#[subscription(
+		name = "statement_subscribe" => "statement_statement",
+		unsubscribe = "statement_unsubscribe",
+		item = Bytes,
+	)]
+	fn subscribe_statements(&self, topic_filter: TopicFilter, dest: Option<[u8; 32]>);
 }
@@ -34,7 +34,8 @@ use jsonrpsee::{
 };
 use tower::Service;
 use utils::{
-	build_rpc_api, deny_unsafe, format_listen_addrs, get_proxy_ip, ListenAddrError, RpcSettings,
+	build_rpc_api, deny_unsafe, format_listen_addrs, get_api_key, get_proxy_ip, ListenAddrError,
+	RpcSettings,
 };
 
 pub use ip_network::IpNetwork;
@@ -42,7 +43,9 @@ pub use jsonrpsee::{
 	core::id_providers::{RandomIntegerIdProvider, RandomStringIdProvider},
 	server::{middleware::rpc::RpcServiceBuilder, BatchRequestConfig},
 };
-pub use middleware::{Metrics, MiddlewareLayer, NodeHealthProxyLayer, RpcMetrics};
+pub use middleware::{
+	Metrics, MiddlewareLayer, NodeHealthProxyLayer, ProcessQuotas, QuotaConfig, RpcMetrics,
+};
 pub use utils::{RpcEndpoint, RpcMethods};
 
 const MEGABYTE: u32 = 1024 * 1024;
@@ -103,6 +106,8 @@ pub struct Config<M: Send + Sync + 'static> {
 	pub tokio_handle: tokio::runtime::Handle,
 	/// RPC logger capacity (default: 1024).
 	pub request_logger_limit: u32,
+	/// Per-method costs and per-API-key quotas, shared by all endpoints.
+	pub quotas: Option<QuotaConfig>,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +115,7 @@ struct PerConnection {
 	methods: Methods,
 	stop_handle: StopHandle,
 	metrics: Option<RpcMetrics>,
+	quotas: Option<ProcessQuotas>,
 	tokio_handle: tokio::runtime::Handle,
 }
 
@@ -118,13 +124,21 @@ pub async fn start_server<M>(config: Config<M>) -> Result<Server, Box<dyn StdErr
 where
 	M: Send + Sync,
 {
-	let Config { endpoints, metrics, tokio_handle, rpc_api, id_provider, request_logger_limit } =
-		config;
+	let Config {
+		endpoints,
+		metrics,
+		tokio_handle,
+		rpc_api,
+		id_provider,
+		request_logger_limit,
+		quotas,
+	} = config;
 
 	let (stop_handle, server_handle) = stop_channel();
 	let cfg = PerConnection {
 		methods: build_rpc_api(rpc_api).into(),
 		metrics,
+		quotas: quotas.map(ProcessQuotas::new),
 		tokio_handle: tokio_handle.clone(),
 		stop_handle,
 	};
@@ -218,42 +232,48 @@ where
 					tower::service_fn(move |mut req: http::Request<hyper::body::Incoming>| {
 						req.extensions_mut().insert(deny_unsafe);
 
-						let PerConnection { methods, metrics, tokio_handle, stop_handle } =
+						let PerConnection { methods, metrics, quotas, tokio_handle, stop_handle } =
 							cfg2.clone();
 						let service_builder = service_builder2.clone();
 
 						let proxy_ip =
 							if rate_limit_trust_proxy_headers { get_proxy_ip(&req) } else { None };
 
-						let rate_limit_cfg = if rate_limit_whitelisted_ips2
+						// Trusted callers bypass the rate-limit and the anonymous quota, but their API
+						// keys are still authenticated.
+						let (rate_limit_cfg, quota_ip) = if rate_limit_whitelisted_ips2
 							.iter()
 							.any(|ips| ips.contains(proxy_ip.unwrap_or(ip)))
 						{
 							log::debug!(target: "rpc", "ip={ip}, proxy_ip={:?} is trusted, disabling rate-limit", proxy_ip);
-							None
+							(None, None)
 						} else {
 							if !rate_limit_whitelisted_ips2.is_empty() {
 								log::debug!(target: "rpc", "ip={ip}, proxy_ip={:?} is not trusted, rate-limit enabled", proxy_ip);
 							}
-							rate_limit
+							(rate_limit, Some(proxy_ip.unwrap_or(ip)))
 						};
 
 						let is_websocket = ws::is_upgrade_request(&req);
 						let transport_label = if is_websocket { "ws" } else { "http" };
 
-						let middleware_layer = match (metrics, rate_limit_cfg) {
-							(None, None) => None,
-							(Some(metrics), None) => Some(
-								MiddlewareLayer::new()
-									.with_metrics(Metrics::new(metrics, transport_label)),
-							),
-							(None, Some(rate_limit)) =>
-								Some(MiddlewareLayer::new().with_rate_limit_per_minute(rate_limit)),
-							(Some(metrics), Some(rate_limit)) => Some(
-								MiddlewareLayer::new()
-									.with_metrics(Metrics::new(metrics, transport_label))
-									.with_rate_limit_per_minute(rate_limit),
-							),
+						let middleware_layer = if metrics.is_none() &&
+							rate_limit_cfg.is_none() &&
+							quotas.is_none()
+						{
+							None
+						} else {
+							let mut layer = MiddlewareLayer::new();
+							if let Some(metrics) = metrics {
+								layer = layer.with_metrics(Metrics::new(metrics, transport_label));
+							}
+							if let Some(rate_limit) = rate_limit_cfg {
+								layer = layer.with_rate_limit_per_minute(rate_limit);
+							}
+							if let Some(quotas) = quotas {
+								layer = layer.with_quotas(quotas, get_api_key(&req), quota_ip);
+							}
+							Some(layer)
 						};
 
 						let rpc_middleware = RpcServiceBuilder::new()
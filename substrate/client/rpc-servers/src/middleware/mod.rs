@@ -19,6 +19,7 @@
 //! JSON-RPC specific middleware.
 
 use std::{
+	net::IpAddr,
 	num::NonZeroU32,
 	time::{Duration, Instant},
 };
@@ -33,10 +34,12 @@ use jsonrpsee::{
 
 mod metrics;
 mod node_health;
+mod quota;
 mod rate_limit;
 
 pub use metrics::*;
 pub use node_health::*;
+pub use quota::*;
 pub use rate_limit::*;
 
 const MAX_JITTER: Duration = Duration::from_millis(50);
@@ -46,9 +49,18 @@ const MAX_RETRIES: usize = 10;
 #[derive(Debug, Clone, Default)]
 pub struct MiddlewareLayer {
 	rate_limit: Option<RateLimit>,
+	quota: Option<QuotaCaller>,
 	metrics: Option<Metrics>,
 }
 
+/// Caller on whose behalf calls are charged against the quotas.
+#[derive(Debug, Clone)]
+struct QuotaCaller {
+	quotas: ProcessQuotas,
+	api_key: Option<String>,
+	ip: Option<IpAddr>,
+}
+
 impl MiddlewareLayer {
 	/// Create an empty MiddlewareLayer.
 	pub fn new() -> Self {
@@ -57,12 +69,24 @@ impl MiddlewareLayer {
 
 	/// Enable new rate limit middleware enforced per minute.
 	pub fn with_rate_limit_per_minute(self, n: NonZeroU32) -> Self {
-		Self { rate_limit: Some(RateLimit::per_minute(n)), metrics: self.metrics }
+		Self { rate_limit: Some(RateLimit::per_minute(n)), ..self }
+	}
+
+	/// Enable quota middleware charging calls of the caller identified by `api_key` or `ip`.
+	///
+	/// Anonymous calls of trusted callers, passed without `ip`, are not charged.
+	pub fn with_quotas(
+		self,
+		quotas: ProcessQuotas,
+		api_key: Option<String>,
+		ip: Option<IpAddr>,
+	) -> Self {
+		Self { quota: Some(QuotaCaller { quotas, api_key, ip }), ..self }
 	}
 
 	/// Enable metrics middleware.
 	pub fn with_metrics(self, metrics: Metrics) -> Self {
-		Self { rate_limit: self.rate_limit, metrics: Some(metrics) }
+		Self { metrics: Some(metrics), ..self }
 	}
 
 	/// Register a new websocket connection.
@@ -80,12 +104,17 @@ impl<S> tower::Layer<S> for MiddlewareLayer {
 	type Service = Middleware<S>;
 
 	fn layer(&self, service: S) -> Self::Service {
-		Middleware { service, rate_limit: self.rate_limit.clone(), metrics: self.metrics.clone() }
+		Middleware {
+			service,
+			rate_limit: self.rate_limit.clone(),
+			quota: self.quota.clone(),
+			metrics: self.metrics.clone(),
+		}
 	}
 }
 
-/// JSON-RPC middleware that handles metrics
-/// and rate-limiting.
+/// JSON-RPC middleware that handles metrics,
+/// quotas and rate-limiting.
 ///
 /// These are part of the same middleware
 /// because the metrics needs to know whether
@@ -94,6 +123,7 @@ impl<S> tower::Layer<S> for MiddlewareLayer {
 pub struct Middleware<S> {
 	service: S,
 	rate_limit: Option<RateLimit>,
+	quota: Option<QuotaCaller>,
 	metrics: Option<Metrics>,
 }
 
@@ -110,9 +140,25 @@ where
 
 		let service = self.service.clone();
 		let rate_limit = self.rate_limit.clone();
+		let quota = self.quota.clone();
 		let metrics = self.metrics.clone();
 
 		async move {
+			if let Some(caller) = quota.as_ref() {
+				if let Err(rejection) =
+					caller.quotas.check(caller.api_key.as_deref(), caller.ip, req.method_name())
+				{
+					log::debug!(
+						target: "rpc",
+						"Rejected call to {} from ip={:?}: {:?}",
+						req.method_name(),
+						caller.ip,
+						rejection,
+					);
+					return rejection.into_response(req.id);
+				}
+			}
+
 			let mut is_rate_limited = false;
 
 			if let Some(limit) = rate_limit.as_ref() {
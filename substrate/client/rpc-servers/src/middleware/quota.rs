@@ -0,0 +1,367 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Per-process RPC quotas with per-method costs and API keys.
+//!
+//! The quotas are kept in the memory of the node process. They are shared by all RPC interfaces
+//! of the node, but neither by several nodes nor across restarts: every node behind a load
+//! balancer grants the whole quota, and restarting a node resets the quotas.
+//!
+//! The quotas are not persisted on purpose. They are rates refilled continuously over a minute, so
+//! a restart grants at most one extra minute of calls, which doesn't justify writing to disk on
+//! every call. The quotas of nodes behind a load balancer are divided by the number of nodes.
+//!
+//! Every call is charged the cost of its method against the quota of the caller. Callers which
+//! present an API key are charged against the quota of that key, which is shared by all
+//! connections using the key. Other callers are charged against the anonymous quota of their IP
+//! address, or rejected if no anonymous quota is configured. Trusted callers are not charged for
+//! anonymous calls, but their API keys are authenticated and charged like any other.
+//!
+//! The quotas are loaded from a JSON file such as:
+//!
+//! ```json
+//! {
+//!   "defaultCost": 1,
+//!   "methodCosts": { "state_getKeysPaged": 50, "archive_v1_storage": 20 },
+//!   "anonymousQuotaPerMinute": 600,
+//!   "apiKeys": { "0a1b2c3d": { "quotaPerMinute": 100000 } }
+//! }
+//! ```
+
+use governor::{
+	clock::{Clock, DefaultClock, QuantaClock},
+	middleware::NoOpMiddleware,
+	state::{keyed::DefaultKeyedStateStore, InMemoryState, NotKeyed},
+	Quota,
+};
+use jsonrpsee::{
+	types::{
+		error::{INVALID_REQUEST_CODE, SERVER_IS_BUSY_CODE},
+		ErrorObject, Id,
+	},
+	MethodResponse,
+};
+use serde::Deserialize;
+use std::{
+	collections::HashMap,
+	error::Error as StdError,
+	net::IpAddr,
+	num::NonZeroU32,
+	path::Path,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+/// Number of anonymous calls after which stale per-IP states are dropped.
+const RETAIN_RECENT_INTERVAL: usize = 10_000;
+
+type DirectLimiter = governor::RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>;
+type KeyedLimiter =
+	governor::RateLimiter<IpAddr, DefaultKeyedStateStore<IpAddr>, DefaultClock, NoOpMiddleware>;
+
+/// Quota of a single API key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ApiKeyConfig {
+	/// Cost units the key may spend per minute.
+	pub quota_per_minute: NonZeroU32,
+}
+
+/// RPC quota configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct QuotaConfig {
+	/// Cost of methods not listed in `method_costs`.
+	#[serde(default = "default_cost")]
+	pub default_cost: NonZeroU32,
+	/// Cost of individual methods.
+	#[serde(default)]
+	pub method_costs: HashMap<String, NonZeroU32>,
+	/// Cost units a caller without an API key may spend per minute, per IP address.
+	///
+	/// Callers without an API key are rejected if not set.
+	#[serde(default)]
+	pub anonymous_quota_per_minute: Option<NonZeroU32>,
+	/// Accepted API keys and their quotas.
+	#[serde(default)]
+	pub api_keys: HashMap<String, ApiKeyConfig>,
+}
+
+fn default_cost() -> NonZeroU32 {
+	NonZeroU32::MIN
+}
+
+impl QuotaConfig {
+	/// Load the quota configuration from a JSON file.
+	pub fn from_file(path: &Path) -> Result<Self, Box<dyn StdError + Send + Sync>> {
+		let file = std::fs::File::open(path)
+			.map_err(|e| format!("Failed to open RPC quota file {}: {e}", path.display()))?;
+		serde_json::from_reader(std::io::BufReader::new(file))
+			.map_err(|e| format!("Invalid RPC quota file {}: {e}", path.display()).into())
+	}
+}
+
+/// Why a call was rejected by [`ProcessQuotas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaRejection {
+	/// No API key was presented and anonymous calls are not allowed.
+	MissingApiKey,
+	/// The presented API key is not known.
+	UnknownApiKey,
+	/// The quota is exhausted, the call may be retried after the given duration.
+	Exceeded(Duration),
+	/// The cost of the method is higher than the whole quota of the caller.
+	CostTooHigh,
+}
+
+impl QuotaRejection {
+	/// Convert the rejection into a JSON-RPC error response.
+	///
+	/// Calls which may be retried later are rejected with the "server is busy" error code, the
+	/// others with the "invalid request" error code.
+	pub fn into_response(self, id: Id) -> MethodResponse {
+		let error = match self {
+			Self::MissingApiKey =>
+				ErrorObject::owned(INVALID_REQUEST_CODE, "RPC API key required", None::<()>),
+			Self::UnknownApiKey =>
+				ErrorObject::owned(INVALID_REQUEST_CODE, "RPC API key not recognized", None::<()>),
+			Self::Exceeded(wait) => ErrorObject::owned(
+				SERVER_IS_BUSY_CODE,
+				"RPC quota exceeded",
+				Some(serde_json::json!({ "retryAfterMs": wait.as_millis() as u64 })),
+			),
+			Self::CostTooHigh => ErrorObject::owned(
+				INVALID_REQUEST_CODE,
+				"RPC method cost exceeds the quota",
+				None::<()>,
+			),
+		};
+		MethodResponse::error(id, error)
+	}
+}
+
+struct QuotasInner {
+	default_cost: NonZeroU32,
+	method_costs: HashMap<String, NonZeroU32>,
+	api_keys: HashMap<String, DirectLimiter>,
+	anonymous: Option<KeyedLimiter>,
+	anonymous_calls: AtomicUsize,
+	clock: QuantaClock,
+}
+
+/// In-memory RPC quotas shared by all connections of the server, see the [module
+/// documentation](self).
+#[derive(Clone)]
+pub struct ProcessQuotas {
+	inner: Arc<QuotasInner>,
+}
+
+impl std::fmt::Debug for ProcessQuotas {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.debug_struct("ProcessQuotas")
+			.field("default_cost", &self.inner.default_cost)
+			.field("method_costs", &self.inner.method_costs)
+			.field("api_keys", &self.inner.api_keys.len())
+			.field("anonymous", &self.inner.anonymous.is_some())
+			.finish()
+	}
+}
+
+impl ProcessQuotas {
+	/// Create new quotas from the configuration.
+	pub fn new(config: QuotaConfig) -> Self {
+		let clock = QuantaClock::default();
+		let api_keys = config
+			.api_keys
+			.into_iter()
+			.map(|(key, cfg)| {
+				let quota = Quota::per_minute(cfg.quota_per_minute);
+				(key, DirectLimiter::direct_with_clock(quota, &clock))
+			})
+			.collect();
+		let anonymous = config
+			.anonymous_quota_per_minute
+			.map(|n| KeyedLimiter::new(Quota::per_minute(n), Default::default(), &clock));
+
+		Self {
+			inner: Arc::new(QuotasInner {
+				default_cost: config.default_cost,
+				method_costs: config.method_costs,
+				api_keys,
+				anonymous,
+				anonymous_calls: AtomicUsize::new(0),
+				clock,
+			}),
+		}
+	}
+
+	/// Cost of a call to `method`.
+	pub fn cost(&self, method: &str) -> NonZeroU32 {
+		self.inner.method_costs.get(method).copied().unwrap_or(self.inner.default_cost)
+	}
+
+	/// Charge a call to `method` against the quota of the caller.
+	///
+	/// `ip` is the address of the caller, or `None` if the caller is trusted and thus not charged
+	/// for anonymous calls. The API key is authenticated and charged in either case.
+	pub fn check(
+		&self,
+		api_key: Option<&str>,
+		ip: Option<IpAddr>,
+		method: &str,
+	) -> Result<(), QuotaRejection> {
+		let cost = self.cost(method);
+		let outcome = match api_key {
+			Some(key) =>
+				self.inner.api_keys.get(key).ok_or(QuotaRejection::UnknownApiKey)?.check_n(cost),
+			None => {
+				let limiter = self.inner.anonymous.as_ref().ok_or(QuotaRejection::MissingApiKey)?;
+				let Some(ip) = ip else { return Ok(()) };
+				let calls = self.inner.anonymous_calls.fetch_add(1, Ordering::Relaxed);
+				if calls % RETAIN_RECENT_INTERVAL == RETAIN_RECENT_INTERVAL - 1 {
+					limiter.retain_recent();
+				}
+				limiter.check_key_n(&ip, cost)
+			},
+		};
+
+		match outcome {
+			Ok(Ok(())) => Ok(()),
+			Ok(Err(not_until)) =>
+				Err(QuotaRejection::Exceeded(not_until.wait_time_from(self.inner.clock.now()))),
+			Err(_) => Err(QuotaRejection::CostTooHigh),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn quotas() -> ProcessQuotas {
+		let config: QuotaConfig = serde_json::from_str(
+			r#"{
+				"methodCosts": { "state_getKeysPaged": 5 },
+				"anonymousQuotaPerMinute": 2,
+				"apiKeys": { "key": { "quotaPerMinute": 10 } }
+			}"#,
+		)
+		.unwrap();
+		ProcessQuotas::new(config)
+	}
+
+	fn ip(n: u8) -> Option<IpAddr> {
+		Some(IpAddr::from([10, 0, 0, n]))
+	}
+
+	#[test]
+	fn config_defaults_work() {
+		let config: QuotaConfig = serde_json::from_str("{}").unwrap();
+		assert_eq!(config.default_cost, NonZeroU32::MIN);
+		assert!(config.method_costs.is_empty());
+		assert!(config.anonymous_quota_per_minute.is_none());
+		assert!(config.api_keys.is_empty());
+		assert!(serde_json::from_str::<QuotaConfig>(r#"{ "defaultCost": 0 }"#).is_err());
+		assert!(serde_json::from_str::<QuotaConfig>(r#"{ "unknown": 1 }"#).is_err());
+	}
+
+	#[test]
+	fn api_key_quota_is_charged_by_method_cost() {
+		let quotas = quotas();
+		assert_eq!(quotas.cost("state_getKeysPaged").get(), 5);
+		assert_eq!(quotas.cost("system_health").get(), 1);
+
+		assert_eq!(quotas.check(Some("key"), ip(1), "state_getKeysPaged"), Ok(()));
+		// The quota of a key is shared by all its callers.
+		assert_eq!(quotas.check(Some("key"), ip(2), "state_getKeysPaged"), Ok(()));
+		assert!(matches!(
+			quotas.check(Some("key"), ip(1), "system_health"),
+			Err(QuotaRejection::Exceeded(_))
+		));
+	}
+
+	#[test]
+	fn anonymous_quota_is_per_ip() {
+		let quotas = quotas();
+		assert_eq!(quotas.check(None, ip(1), "system_health"), Ok(()));
+		assert_eq!(quotas.check(None, ip(1), "system_health"), Ok(()));
+		assert!(matches!(
+			quotas.check(None, ip(1), "system_health"),
+			Err(QuotaRejection::Exceeded(_))
+		));
+		assert_eq!(quotas.check(None, ip(2), "system_health"), Ok(()));
+		assert_eq!(
+			quotas.check(None, ip(2), "state_getKeysPaged"),
+			Err(QuotaRejection::CostTooHigh)
+		);
+	}
+
+	#[test]
+	fn api_keys_are_authenticated() {
+		let quotas = quotas();
+		assert_eq!(
+			quotas.check(Some("other"), ip(1), "system_health"),
+			Err(QuotaRejection::UnknownApiKey)
+		);
+
+		let quotas = ProcessQuotas::new(serde_json::from_str("{}").unwrap());
+		assert_eq!(quotas.check(None, ip(1), "system_health"), Err(QuotaRejection::MissingApiKey));
+	}
+
+	#[test]
+	fn trusted_callers_are_authenticated() {
+		let quotas = quotas();
+		// Anonymous calls of trusted callers are not charged.
+		for _ in 0..3 {
+			assert_eq!(quotas.check(None, None, "state_getKeysPaged"), Ok(()));
+		}
+		// API keys of trusted callers are still authenticated and charged.
+		assert_eq!(
+			quotas.check(Some("other"), None, "system_health"),
+			Err(QuotaRejection::UnknownApiKey)
+		);
+		assert_eq!(quotas.check(Some("key"), None, "state_getKeysPaged"), Ok(()));
+		assert_eq!(quotas.check(Some("key"), None, "state_getKeysPaged"), Ok(()));
+		assert!(matches!(
+			quotas.check(Some("key"), None, "system_health"),
+			Err(QuotaRejection::Exceeded(_))
+		));
+
+		let quotas = ProcessQuotas::new(serde_json::from_str("{}").unwrap());
+		assert_eq!(quotas.check(None, None, "system_health"), Err(QuotaRejection::MissingApiKey));
+	}
+
+	#[test]
+	fn rejections_use_standard_error_codes() {
+		let code = |rejection: QuotaRejection| {
+			let response = rejection.into_response(Id::Number(1));
+			let json: serde_json::Value = serde_json::from_str(response.as_result()).unwrap();
+			json["error"]["code"].as_i64().unwrap()
+		};
+		assert_eq!(code(QuotaRejection::MissingApiKey), INVALID_REQUEST_CODE as i64);
+		assert_eq!(code(QuotaRejection::UnknownApiKey), INVALID_REQUEST_CODE as i64);
+		assert_eq!(code(QuotaRejection::CostTooHigh), INVALID_REQUEST_CODE as i64);
+		assert_eq!(
+			code(QuotaRejection::Exceeded(Duration::from_secs(1))),
+			SERVER_IS_BUSY_CODE as i64
+		);
+	}
+}
@@ -36,6 +36,7 @@ use tower_http::cors::{AllowOrigin, CorsLayer};
 const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
 const X_REAL_IP: HeaderName = HeaderName::from_static("x-real-ip");
 const FORWARDED: HeaderName = HeaderName::from_static("forwarded");
+const X_API_KEY: HeaderName = HeaderName::from_static("x-api-key");
 
 #[derive(Debug)]
 pub(crate) struct ListenAddrError;
@@ -280,6 +281,25 @@ pub(crate) fn get_proxy_ip<B>(req: &http::Request<B>) -> Option<IpAddr> {
 	None
 }
 
+/// Extracts the RPC API key from the HTTP request.
+///
+/// It is extracted in the following order:
+/// 1. `X-Api-Key` header.
+/// 2. `Authorization: Bearer <key>` header.
+///
+/// Keys are not read from the URL, which is commonly logged by proxies.
+pub(crate) fn get_api_key<B>(req: &http::Request<B>) -> Option<String> {
+	if let Some(key) = req.headers().get(&X_API_KEY).and_then(|v| v.to_str().ok()) {
+		return Some(key.trim().to_string());
+	}
+
+	req.headers()
+		.get(http::header::AUTHORIZATION)
+		.and_then(|v| v.to_str().ok())
+		.and_then(|v| v.strip_prefix("Bearer "))
+		.map(|key| key.trim().to_string())
+}
+
 /// Get the `deny_unsafe` setting based on the address and the RPC methods exposed by the interface.
 pub fn deny_unsafe(addr: &SocketAddr, methods: &RpcMethods) -> DenyUnsafe {
 	match (addr.ip().is_loopback(), methods) {
@@ -357,6 +377,29 @@ mod tests {
 		assert_eq!(Some(IpAddr::from_str("127.0.0.1").unwrap()), ip);
 	}
 
+	#[test]
+	fn api_key_from_headers() {
+		assert!(get_api_key(&request()).is_none());
+
+		let mut req = request();
+		req.headers_mut().insert(&X_API_KEY, HeaderValue::from_static("key1"));
+		req.headers_mut()
+			.insert(http::header::AUTHORIZATION, HeaderValue::from_static("Bearer key2"));
+		assert_eq!(get_api_key(&req).as_deref(), Some("key1"));
+
+		let mut req = request();
+		req.headers_mut()
+			.insert(http::header::AUTHORIZATION, HeaderValue::from_static("Bearer key2"));
+		assert_eq!(get_api_key(&req).as_deref(), Some("key2"));
+
+		// the query is not used, so the key doesn't end up in logged URLs
+		let req = HttpRequest::builder()
+			.uri("/?foo=bar&api_key=key3")
+			.body(HttpBody::empty())
+			.unwrap();
+		assert!(get_api_key(&req).is_none());
+	}
+
 	#[test]
 	fn ip_from_x_forwarded_works() {
 		let mut req = request();
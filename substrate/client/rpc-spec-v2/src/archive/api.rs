@@ -21,7 +21,7 @@
 use crate::{
 	archive::{
 		error::{Error, Infallible},
-		types::MethodResult,
+		types::{IndexQueryResult, MethodResult},
 	},
 	common::events::{
 		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
@@ -97,8 +97,50 @@ pub trait ArchiveApi<Hash> {
 		call_parameters: String,
 	) -> Result<MethodResult, Error>;
 
+	/// Get the extrinsics and events touching an account in a range of blocks.
+	///
+	/// Returns the locations of the items indexed under the hexadecimal-encoded SCALE-encoded
+	/// account id in the canonical blocks from `from_height` to `to_height` inclusive, ordered by
+	/// height. Requires the node to maintain an extrinsic and event index.
+	///
+	/// Large results are split: `nextHeight` is then the height to query the remaining items from.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "archive_v1_itemsByAccount")]
+	fn archive_v1_items_by_account(
+		&self,
+		account: String,
+		from_height: u64,
+		to_height: u64,
+	) -> Result<IndexQueryResult, Error>;
+
+	/// Get the events with the given pallet and event name in a range of blocks.
+	///
+	/// Returns the locations of the matching events in the canonical blocks from `from_height` to
+	/// `to_height` inclusive, ordered by height. Requires the node to maintain an extrinsic and
+	/// event index.
+	///
+	/// Large results are split: `nextHeight` is then the height to query the remaining items from.
+	///
+	/// # Unstable
+	///
+	/// This method is unstable and subject to change in the future.
+	#[method(name = "archive_v1_eventsByName")]
+	fn archive_v1_events_by_name(
+		&self,
+		pallet: String,
+		event: String,
+		from_height: u64,
+		to_height: u64,
+	) -> Result<IndexQueryResult, Error>;
+
 	/// Returns storage entries at a specific block's state.
 	///
+	/// When `with_proof` is set, a `storageProof` event containing a read proof of all the
+	/// queried keys is generated before the `storageDone` event.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -112,6 +154,7 @@ pub trait ArchiveApi<Hash> {
 		hash: Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		with_proof: Option<bool>,
 	);
 
 	/// Returns the storage difference between two blocks.
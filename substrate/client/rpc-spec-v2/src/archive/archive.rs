@@ -22,14 +22,14 @@ use crate::{
 	archive::{
 		archive_storage::ArchiveStorageDiff,
 		error::{Error as ArchiveError, Infallible},
-		types::MethodResult,
+		types::{IndexQueryResult, IndexedItemIndex, IndexedItemLocation, MethodResult},
 		ArchiveApiServer,
 	},
 	common::{
 		events::{
 			ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageEvent, StorageQuery,
 		},
-		storage::{QueryResult, StorageSubscriptionClient},
+		storage::{QueryProof, QueryResult, StorageSubscriptionClient},
 	},
 	hex_string, SubscriptionTaskExecutor,
 };
@@ -38,8 +38,8 @@ use codec::Encode;
 use futures::FutureExt;
 use jsonrpsee::{core::async_trait, PendingSubscriptionSink};
 use sc_client_api::{
-	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
-	StorageProvider,
+	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, IndexKey,
+	IndexedItem, ProofProvider, StorageKey, StorageProvider,
 };
 use sc_rpc::utils::Subscription;
 use sp_api::{CallApiAt, CallContext};
@@ -63,6 +63,12 @@ pub(crate) const LOG_TARGET: &str = "rpc-spec-v2::archive";
 /// its down buffer capacity per connection as well.
 const STORAGE_QUERY_BUF: usize = 16;
 
+/// The maximum number of blocks queried by a single index query.
+const MAX_INDEX_QUERY_RANGE: u64 = 100_000;
+
+/// The maximum number of blocks with items returned by a single index query.
+const MAX_INDEX_QUERY_BLOCKS: usize = 1_000;
+
 /// An API for archive RPC calls.
 pub struct Archive<BE: Backend<Block>, Block: BlockT, Client> {
 	/// Substrate client.
@@ -88,6 +94,50 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 		let genesis_hash = hex_string(&genesis_hash.as_ref());
 		Self { client, backend, executor, genesis_hash, _phantom: PhantomData }
 	}
+
+	/// Query the items indexed under `key` in the blocks `from_height..=to_height`.
+	fn query_index(
+		&self,
+		key: IndexKey,
+		from_height: u64,
+		to_height: u64,
+	) -> Result<IndexQueryResult, ArchiveError> {
+		if from_height > to_height || to_height - from_height >= MAX_INDEX_QUERY_RANGE {
+			return Err(ArchiveError::InvalidParam(format!(
+				"Invalid block range {from_height}..={to_height}, at most {MAX_INDEX_QUERY_RANGE} \
+				 blocks can be queried"
+			)))
+		}
+		let height = |height: u64| {
+			NumberFor::<Block>::try_from(U256::from(height))
+				.map_err(|_| ArchiveError::InvalidParam(format!("Invalid block height: {height}")))
+		};
+
+		let page = self
+			.backend
+			.indexed_items(
+				&key,
+				height(from_height)?,
+				height(to_height)?,
+				MAX_INDEX_QUERY_BLOCKS,
+			)
+			.map_err(|error| ArchiveError::IndexQuery(error.to_string()))?
+			.ok_or(ArchiveError::IndexUnavailable)?;
+
+		let items = page
+			.locations
+			.into_iter()
+			.map(|location| IndexedItemLocation {
+				block_hash: hex_string(&location.hash.as_ref()),
+				block_height: location.number.saturated_into(),
+				item: match location.item {
+					IndexedItem::Extrinsic(index) => IndexedItemIndex::Extrinsic(index),
+					IndexedItem::Event(index) => IndexedItemIndex::Event(index),
+				},
+			})
+			.collect();
+		Ok(IndexQueryResult { items, next_height: page.next.map(|next| next.saturated_into()) })
+	}
 }
 
 /// Parse hex-encoded string parameter as raw bytes.
@@ -115,6 +165,7 @@ where
 		+ BlockchainEvents<Block>
 		+ CallApiAt<Block>
 		+ StorageProvider<Block, BE>
+		+ ProofProvider<Block>
 		+ 'static,
 {
 	fn archive_v1_body(&self, hash: Block::Hash) -> Result<Option<Vec<String>>, Infallible> {
@@ -214,12 +265,33 @@ where
 		})
 	}
 
+	fn archive_v1_items_by_account(
+		&self,
+		account: String,
+		from_height: u64,
+		to_height: u64,
+	) -> Result<IndexQueryResult, ArchiveError> {
+		let account = parse_hex_param(account)?;
+		self.query_index(IndexKey::Account(account), from_height, to_height)
+	}
+
+	fn archive_v1_events_by_name(
+		&self,
+		pallet: String,
+		event: String,
+		from_height: u64,
+		to_height: u64,
+	) -> Result<IndexQueryResult, ArchiveError> {
+		self.query_index(IndexKey::Event { pallet, name: event }, from_height, to_height)
+	}
+
 	fn archive_v1_storage(
 		&self,
 		pending: PendingSubscriptionSink,
 		hash: Block::Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		with_proof: Option<bool>,
 	) {
 		let mut storage_client =
 			StorageSubscriptionClient::<Client, Block, BE>::new(self.client.clone());
@@ -268,14 +340,19 @@ where
 			};
 
 			let (tx, mut rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
-			let storage_fut = storage_client.generate_events(hash, items, child_trie, tx);
+			let (proof_tx, proof_rx) =
+				with_proof.unwrap_or_default().then(tokio::sync::oneshot::channel).unzip();
+			let storage_fut = storage_client.generate_events(hash, items, child_trie, tx, proof_tx);
 
 			// We don't care about the return value of this join:
 			// - process_events might encounter an error (if the client disconnected)
 			// - storage_fut might encounter an error while processing a trie queries and
 			// the error is propagated via the sink.
-			let _ = futures::future::join(storage_fut, process_storage_events(&mut rx, &mut sink))
-				.await;
+			let _ = futures::future::join(
+				storage_fut,
+				process_storage_events(&mut rx, proof_rx, &mut sink),
+			)
+			.await;
 		};
 
 		self.executor.spawn("substrate-rpc-subscription", Some("rpc"), fut.boxed());
@@ -355,10 +432,16 @@ async fn process_storage_diff_events(
 }
 
 /// Sends all the events of the storage method to the sink.
-async fn process_storage_events(rx: &mut mpsc::Receiver<QueryResult>, sink: &mut Subscription) {
+async fn process_storage_events(
+	rx: &mut mpsc::Receiver<QueryResult>,
+	mut proof: Option<tokio::sync::oneshot::Receiver<QueryProof>>,
+	sink: &mut Subscription,
+) {
 	loop {
 		tokio::select! {
 			_ = sink.closed() => {
+				// Nobody is waiting for the proof anymore.
+				proof = None;
 				break
 			}
 
@@ -384,5 +467,19 @@ async fn process_storage_events(rx: &mut mpsc::Receiver<QueryResult>, sink: &mut
 		}
 	}
 
+	if let Some(proof) = proof {
+		let proof = proof.await.unwrap_or_else(|_| Err("Storage proof was not generated".into()));
+		match proof {
+			Ok(proof) =>
+				if sink.send(&ArchiveStorageEvent::proof(proof)).await.is_err() {
+					return
+				},
+			Err(error) => {
+				let _ = sink.send(&ArchiveStorageEvent::err(error)).await;
+				return
+			},
+		}
+	}
+
 	let _ = sink.send(&ArchiveStorageEvent::StorageDone).await;
 }
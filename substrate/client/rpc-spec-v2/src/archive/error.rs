@@ -32,6 +32,12 @@ pub enum Error {
 	/// Failed to fetch leaves.
 	#[error("Failed to fetch leaves of the chain: {0}")]
 	FetchLeaves(String),
+	/// The node doesn't maintain an index of extrinsics and events.
+	#[error("Extrinsic and event index is not enabled")]
+	IndexUnavailable,
+	/// Failed to query the index.
+	#[error("Failed to query the index: {0}")]
+	IndexQuery(String),
 }
 
 // Base code for all `archive` errors.
@@ -42,6 +48,10 @@ const INVALID_PARAM_ERROR: i32 = BASE_ERROR + 1;
 const RUNTIME_CALL_ERROR: i32 = BASE_ERROR + 2;
 /// Failed to fetch leaves.
 const FETCH_LEAVES_ERROR: i32 = BASE_ERROR + 3;
+/// Index not enabled.
+const INDEX_UNAVAILABLE_ERROR: i32 = BASE_ERROR + 4;
+/// Failed to query the index.
+const INDEX_QUERY_ERROR: i32 = BASE_ERROR + 5;
 
 impl From<Error> for ErrorObject<'static> {
 	fn from(e: Error) -> Self {
@@ -51,6 +61,8 @@ impl From<Error> for ErrorObject<'static> {
 			Error::InvalidParam(_) => ErrorObject::owned(INVALID_PARAM_ERROR, msg, None::<()>),
 			Error::RuntimeCall(_) => ErrorObject::owned(RUNTIME_CALL_ERROR, msg, None::<()>),
 			Error::FetchLeaves(_) => ErrorObject::owned(FETCH_LEAVES_ERROR, msg, None::<()>),
+			Error::IndexUnavailable => ErrorObject::owned(INDEX_UNAVAILABLE_ERROR, msg, None::<()>),
+			Error::IndexQuery(_) => ErrorObject::owned(INDEX_QUERY_ERROR, msg, None::<()>),
 		}
 		.into()
 	}
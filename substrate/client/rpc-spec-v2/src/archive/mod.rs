@@ -34,4 +34,7 @@ pub mod error;
 
 pub use api::ArchiveApiServer;
 pub use archive::Archive;
-pub use types::{MethodResult, MethodResultErr, MethodResultOk};
+pub use types::{
+	IndexQueryResult, IndexedItemIndex, IndexedItemLocation, MethodResult, MethodResultErr,
+	MethodResultOk,
+};
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	archive::MethodResult,
+	archive::{IndexQueryResult, IndexedItemIndex, IndexedItemLocation, MethodResult},
 	common::events::{
 		ArchiveStorageDiffEvent, ArchiveStorageDiffItem, ArchiveStorageDiffOperationType,
 		ArchiveStorageDiffResult, ArchiveStorageDiffType, ArchiveStorageEvent, StorageQuery,
@@ -36,7 +36,7 @@ use jsonrpsee::{
 };
 
 use sc_block_builder::BlockBuilderBuilder;
-use sc_client_api::ChildInfo;
+use sc_client_api::{ChildInfo, IndexKey, IndexedItem};
 use sc_rpc::testing::TokioTestExecutor;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::BlockOrigin;
@@ -178,6 +178,100 @@ async fn archive_finalized_height() {
 	assert_eq!(client_height, height);
 }
 
+/// Indexes every extrinsic under the account of Alice and every block under a `System::Block`
+/// event.
+struct TestIndexer;
+
+impl sc_client_api::BlockIndexer<Block> for TestIndexer {
+	fn index_block(
+		&self,
+		_header: &Header,
+		body: &[runtime::Extrinsic],
+		storage_changes: Option<&sc_client_api::StorageCollection>,
+	) -> (Vec<sc_client_api::IndexEntry>, bool) {
+		assert!(storage_changes.is_some());
+		let alice = IndexKey::Account(Sr25519Keyring::Alice.to_account_id().encode());
+		let block = IndexKey::Event { pallet: "System".into(), name: "Block".into() };
+		let entries = (0..body.len() as u32)
+			.map(|index| (alice.clone(), IndexedItem::Extrinsic(index)))
+			.chain(std::iter::once((block, IndexedItem::Event(0))))
+			.collect();
+		(entries, true)
+	}
+}
+
+#[tokio::test]
+async fn archive_index_queries() {
+	let builder = TestClientBuilder::new();
+	let backend = builder.backend();
+	backend.enable_index().unwrap();
+	let client = Arc::new(builder.build());
+	client.set_block_indexer(Arc::new(TestIndexer));
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		Arc::new(TokioTestExecutor::default()),
+	)
+	.into_rpc();
+
+	let mut hashes = vec![];
+	// Block 2 has no extrinsics.
+	for nonce in [Some(0), None, Some(1)] {
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap();
+		if let Some(nonce) = nonce {
+			builder
+				.push_transfer(runtime::Transfer {
+					from: Sr25519Keyring::Alice.into(),
+					to: Sr25519Keyring::Ferdie.into(),
+					amount: 42,
+					nonce,
+				})
+				.unwrap();
+		}
+		let block = builder.build().unwrap().block;
+		hashes.push(format!("{:?}", block.header.hash()));
+		client.import(BlockOrigin::Own, block).await.unwrap();
+	}
+
+	let location = |height: u64, item| IndexedItemLocation {
+		block_hash: hashes[height as usize - 1].clone(),
+		block_height: height,
+		item,
+	};
+	let alice = hex_string(&Sr25519Keyring::Alice.to_account_id().encode());
+
+	let result: IndexQueryResult =
+		api.call("archive_v1_itemsByAccount", rpc_params![&alice, 0, 3]).await.unwrap();
+	assert_eq!(
+		result.items,
+		vec![
+			location(1, IndexedItemIndex::Extrinsic(0)),
+			location(3, IndexedItemIndex::Extrinsic(0))
+		]
+	);
+	assert_eq!(result.next_height, None);
+
+	let result: IndexQueryResult = api
+		.call("archive_v1_eventsByName", rpc_params!["System", "Block", 2, 3])
+		.await
+		.unwrap();
+	assert_eq!(
+		result.items,
+		vec![location(2, IndexedItemIndex::Event(0)), location(3, IndexedItemIndex::Event(0))]
+	);
+
+	let err = api
+		.call::<_, IndexQueryResult>("archive_v1_itemsByAccount", rpc_params![&alice, 3, 1])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3001);
+}
+
 #[tokio::test]
 async fn archive_hash_by_height() {
 	let (client, api) = setup_api();
@@ -506,6 +600,141 @@ async fn archive_storage_hashes_values_child_trie() {
 	);
 }
 
+#[tokio::test]
+async fn archive_storage_with_proof() {
+	let (client, api) = setup_api();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(VALUE.to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let block_hash = format!("{:?}", block.header.hash());
+	let key = hex_string(&KEY);
+	let missing: &[u8] = b":missing";
+
+	let items: Vec<StorageQuery<String>> = vec![
+		StorageQuery {
+			key: key.clone(),
+			query_type: StorageQueryType::Value,
+			pagination_start_key: None,
+		},
+		StorageQuery {
+			key: hex_string(&missing),
+			query_type: StorageQueryType::Hash,
+			pagination_start_key: None,
+		},
+	];
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_v1_storage",
+			rpc_params![&block_hash, items, Option::<String>::None, true],
+		)
+		.await
+		.unwrap();
+
+	assert_eq!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::Storage(StorageResult {
+			key,
+			result: StorageResultType::Value(hex_string(&VALUE)),
+			child_trie_key: None,
+		}),
+	);
+
+	// The proof covers both the existing and the missing key.
+	let proof = match get_next_event::<ArchiveStorageEvent>(&mut sub).await {
+		ArchiveStorageEvent::StorageProof(proof) => proof.proof,
+		event => panic!("Expected storage proof, got {event:?}"),
+	};
+	let proof = sp_state_machine::StorageProof::new(
+		proof.into_iter().map(|node| array_bytes::hex2bytes(&node).unwrap()),
+	);
+	let values = sp_state_machine::read_proof_check::<Blake2Hasher, _>(
+		block.header.state_root,
+		proof,
+		[KEY, missing],
+	)
+	.unwrap();
+	assert_eq!(values.get(KEY), Some(&Some(VALUE.to_vec())));
+	assert_eq!(values.get(missing), Some(&None));
+
+	assert_eq!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::StorageDone,
+	);
+}
+
+#[tokio::test]
+async fn archive_storage_descendants_with_proof() {
+	let (client, api) = setup_api();
+
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(client.chain_info().genesis_hash)
+		.with_parent_block_number(0)
+		.build()
+		.unwrap();
+	let (prefix, key_a, key_b): (&[u8], &[u8], &[u8]) = (b":AAA", b":AAAA", b":AAAB");
+	builder.push_storage_change(key_a.to_vec(), Some(b"a".to_vec())).unwrap();
+	builder.push_storage_change(key_b.to_vec(), Some(b"b".to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	let block_hash = format!("{:?}", block.header.hash());
+	let items: Vec<StorageQuery<String>> = vec![StorageQuery {
+		key: hex_string(&prefix),
+		query_type: StorageQueryType::DescendantsValues,
+		pagination_start_key: None,
+	}];
+
+	let mut sub = api
+		.subscribe_unbounded(
+			"archive_v1_storage",
+			rpc_params![&block_hash, items, Option::<String>::None, true],
+		)
+		.await
+		.unwrap();
+
+	for (key, value) in [(key_a, &b"a"[..]), (key_b, &b"b"[..])] {
+		assert_eq!(
+			get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+			ArchiveStorageEvent::Storage(StorageResult {
+				key: hex_string(&key),
+				result: StorageResultType::Value(hex_string(&value)),
+				child_trie_key: None,
+			}),
+		);
+	}
+
+	// The proof covers the descendants and the queried key itself.
+	let proof = match get_next_event::<ArchiveStorageEvent>(&mut sub).await {
+		ArchiveStorageEvent::StorageProof(proof) => proof.proof,
+		event => panic!("Expected storage proof, got {event:?}"),
+	};
+	let proof = sp_state_machine::StorageProof::new(
+		proof.into_iter().map(|node| array_bytes::hex2bytes(&node).unwrap()),
+	);
+	let values = sp_state_machine::read_proof_check::<Blake2Hasher, _>(
+		block.header.state_root,
+		proof,
+		[prefix, key_a, key_b],
+	)
+	.unwrap();
+	assert_eq!(values.get(prefix), Some(&None));
+	assert_eq!(values.get(key_a), Some(&Some(b"a".to_vec())));
+	assert_eq!(values.get(key_b), Some(&Some(b"b".to_vec())));
+
+	assert_eq!(
+		get_next_event::<ArchiveStorageEvent>(&mut sub).await,
+		ArchiveStorageEvent::StorageDone,
+	);
+}
+
 #[tokio::test]
 async fn archive_storage_closest_merkle_value() {
 	let (client, api) = setup_api();
@@ -60,6 +60,40 @@ pub struct MethodResultErr {
 	pub error: String,
 }
 
+/// The location of an indexed item returned by the index query methods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedItemLocation {
+	/// The hexadecimal encoded hash of the block.
+	pub block_hash: String,
+	/// The height of the block.
+	pub block_height: u64,
+	/// The item within the block.
+	#[serde(flatten)]
+	pub item: IndexedItemIndex,
+}
+
+/// The result of the index query methods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexQueryResult {
+	/// The locations of the items, ordered by height.
+	pub items: Vec<IndexedItemLocation>,
+	/// The height to query the remaining items from, if the result is incomplete.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub next_height: Option<u64>,
+}
+
+/// The index of an indexed item within its block.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type", content = "index")]
+pub enum IndexedItemIndex {
+	/// Index of an extrinsic in the block body.
+	Extrinsic(u32),
+	/// Index of an event in the events of the block.
+	Event(u32),
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -87,4 +121,28 @@ mod tests {
 		let ok_dec: MethodResult = serde_json::from_str(exp).unwrap();
 		assert_eq!(ok_dec, ok);
 	}
+
+	#[test]
+	fn indexed_item_location() {
+		let location = IndexedItemLocation {
+			block_hash: "0x01".into(),
+			block_height: 2,
+			item: IndexedItemIndex::Event(3),
+		};
+
+		let ser = serde_json::to_string(&location).unwrap();
+		let exp = r#"{"blockHash":"0x01","blockHeight":2,"type":"event","index":3}"#;
+		assert_eq!(ser, exp);
+
+		let location_dec: IndexedItemLocation = serde_json::from_str(exp).unwrap();
+		assert_eq!(location_dec, location);
+
+		let result = IndexQueryResult { items: vec![location], next_height: Some(4) };
+		let ser = serde_json::to_string(&result).unwrap();
+		let exp = r#"{"items":[{"blockHash":"0x01","blockHeight":2,"type":"event","index":3}],"nextHeight":4}"#;
+		assert_eq!(ser, exp);
+
+		let result_dec: IndexQueryResult = serde_json::from_str(exp).unwrap();
+		assert_eq!(result_dec, result);
+	}
 }
@@ -82,6 +82,9 @@ pub trait ChainHeadApi<Hash> {
 
 	/// Returns storage entries at a specific block's state.
 	///
+	/// When `with_proof` is set, an `operationStorageProof` event containing a read proof of
+	/// all the queried keys is generated before the `operationStorageDone` event.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -92,6 +95,7 @@ pub trait ChainHeadApi<Hash> {
 		hash: Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		with_proof: Option<bool>,
 	) -> ResponsePayload<'static, MethodResponse>;
 
 	/// Call into the Runtime API at a specified block's state.
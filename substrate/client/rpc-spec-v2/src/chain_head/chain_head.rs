@@ -27,11 +27,17 @@ use crate::{
 		api::ChainHeadApiServer,
 		chain_head_follow::ChainHeadFollower,
 		error::Error as ChainHeadRpcError,
-		event::{FollowEvent, MethodResponse, OperationError, OperationId, OperationStorageItems},
+		event::{
+			FollowEvent, MethodResponse, OperationError, OperationId, OperationStorageItems,
+			OperationStorageProof,
+		},
 		subscription::{StopHandle, SubscriptionManagement, SubscriptionManagementError},
 		FollowEventSendError, FollowEventSender,
 	},
-	common::{events::StorageQuery, storage::QueryResult},
+	common::{
+		events::StorageQuery,
+		storage::{QueryProof, QueryResult},
+	},
 	hex_string, SubscriptionTaskExecutor,
 };
 use codec::Encode;
@@ -42,8 +48,8 @@ use jsonrpsee::{
 };
 use log::debug;
 use sc_client_api::{
-	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider, StorageKey,
-	StorageProvider,
+	Backend, BlockBackend, BlockchainEvents, CallExecutor, ChildInfo, ExecutorProvider,
+	ProofProvider, StorageKey, StorageProvider,
 };
 use sc_rpc::utils::Subscription;
 use sp_api::CallApiAt;
@@ -195,6 +201,7 @@ where
 		+ BlockchainEvents<Block>
 		+ CallApiAt<Block>
 		+ StorageProvider<Block, BE>
+		+ ProofProvider<Block>
 		+ 'static,
 {
 	fn chain_head_unstable_follow(&self, pending: PendingSubscriptionSink, with_runtime: bool) {
@@ -383,6 +390,7 @@ where
 		hash: Block::Hash,
 		items: Vec<StorageQuery<String>>,
 		child_trie: Option<String>,
+		with_proof: Option<bool>,
 	) -> ResponsePayload<'static, MethodResponse> {
 		let conn_id = ext
 			.get::<ConnectionId>()
@@ -443,6 +451,8 @@ where
 			}
 
 			let (tx, rx) = tokio::sync::mpsc::channel(STORAGE_QUERY_BUF);
+			let (proof_tx, proof_rx) =
+				with_proof.unwrap_or_default().then(tokio::sync::oneshot::channel).unzip();
 			let operation_id = block_guard.operation().operation_id();
 			let stop_handle = block_guard.operation().stop_handle().clone();
 			let response_sender = block_guard.response_sender();
@@ -450,8 +460,8 @@ where
 			// May fail if the channel is closed or the connection is closed.
 			// which is okay to ignore.
 			let _ = futures::future::join(
-				storage_client.generate_events(hash, items, child_trie, tx),
-				process_storage_items(rx, response_sender, operation_id, &stop_handle),
+				storage_client.generate_events(hash, items, child_trie, tx, proof_tx),
+				process_storage_items(rx, proof_rx, response_sender, operation_id, &stop_handle),
 			)
 			.await;
 		};
@@ -662,6 +672,7 @@ where
 
 async fn process_storage_items<Hash>(
 	mut storage_query_stream: mpsc::Receiver<QueryResult>,
+	mut proof: Option<tokio::sync::oneshot::Receiver<QueryProof>>,
 	mut sender: FollowEventSender<Hash>,
 	operation_id: String,
 	stop_handle: &StopHandle,
@@ -669,6 +680,8 @@ async fn process_storage_items<Hash>(
 	loop {
 		tokio::select! {
 			_ = stop_handle.stopped() => {
+				// The proof of a stopped operation is never generated.
+				proof = None;
 				break;
 			},
 
@@ -696,6 +709,23 @@ async fn process_storage_items<Hash>(
 		}
 	}
 
+	if let Some(proof) = proof {
+		let proof = proof.await.unwrap_or_else(|_| Err("Storage proof was not generated".into()));
+		match proof {
+			Ok(proof) =>
+				sender
+					.send(FollowEvent::OperationStorageProof(OperationStorageProof {
+						operation_id: operation_id.clone(),
+						proof,
+					}))
+					.await?,
+			Err(error) =>
+				return sender
+					.send(FollowEvent::OperationError(OperationError { operation_id, error }))
+					.await,
+		}
+	}
+
 	sender
 		.send(FollowEvent::OperationStorageDone(OperationId { operation_id }))
 		.await?;
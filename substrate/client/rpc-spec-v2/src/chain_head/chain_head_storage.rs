@@ -20,13 +20,13 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use sc_client_api::{Backend, ChildInfo, ProofProvider, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use crate::common::{
 	events::{StorageQuery, StorageQueryType},
-	storage::{IterQueryType, QueryIter, QueryResult, Storage},
+	storage::{IterQueryType, QueryIter, QueryProof, QueryResult, Storage},
 };
 
 /// Generates the events of the `chainHead_storage` method.
@@ -53,39 +53,48 @@ impl<Client, Block, BE> ChainHeadStorage<Client, Block, BE>
 where
 	Block: BlockT + 'static,
 	BE: Backend<Block> + 'static,
-	Client: StorageProvider<Block, BE> + Send + Sync + 'static,
+	Client: StorageProvider<Block, BE> + ProofProvider<Block> + Send + Sync + 'static,
 {
 	/// Generate the block events for the `chainHead_storage` method.
+	///
+	/// If `proof_tx` is provided, a read proof of all the queried keys is sent to it once all
+	/// the results have been produced.
 	pub async fn generate_events(
 		&mut self,
 		hash: Block::Hash,
 		items: Vec<StorageQuery<StorageKey>>,
 		child_key: Option<ChildInfo>,
 		tx: mpsc::Sender<QueryResult>,
+		proof_tx: Option<oneshot::Sender<QueryProof>>,
 	) -> Result<(), tokio::task::JoinError> {
 		let this = self.clone();
 
 		tokio::task::spawn_blocking(move || {
+			let mut proof_keys = Vec::new();
+
 			for item in items {
 				match item.query_type {
 					StorageQueryType::Value => {
 						let rp = this.client.query_value(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::Hash => {
 						let rp = this.client.query_hash(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::ClosestDescendantMerkleValue => {
 						let rp =
 							this.client.query_merkle_value(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::DescendantsValues => {
 						let query = QueryIter {
@@ -98,6 +107,7 @@ where
 							hash,
 							child_key.as_ref(),
 							&tx,
+							proof_tx.as_ref().map(|_| &mut proof_keys),
 						)
 					},
 					StorageQueryType::DescendantsHashes => {
@@ -111,10 +121,16 @@ where
 							hash,
 							child_key.as_ref(),
 							&tx,
+							proof_tx.as_ref().map(|_| &mut proof_keys),
 						)
 					},
 				}
 			}
+
+			if let Some(proof_tx) = proof_tx {
+				let proof = this.client.query_proof(hash, proof_keys, child_key.as_ref());
+				let _ = proof_tx.send(proof);
+			}
 		})
 		.await?;
 
@@ -245,6 +245,16 @@ pub struct OperationStorageItems {
 	pub items: Vec<StorageResult>,
 }
 
+/// The read proof produced by the `chainHead_storage` method.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationStorageProof {
+	/// The operation id of the event.
+	pub operation_id: String,
+	/// The hex-encoded trie nodes proving the storage items of the operation.
+	pub proof: Vec<String>,
+}
+
 /// Indicate a problem during the operation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -270,6 +280,8 @@ pub struct OperationError {
 /// - OperationStorageItems: Items produced by the `chianHead_storage`
 /// - OperationWaitingForContinue: Generated after OperationStorageItems and requires the user to
 ///   call `chainHead_continue`
+/// - OperationStorageProof: Read proof of the items produced by the `chainHead_storage`, only
+///   generated when requested
 /// - OperationStorageDone: The `chianHead_storage` method has produced all the results
 /// - OperationInaccessible: The server was unable to provide the result, retries might succeed in
 ///   the future
@@ -300,6 +312,8 @@ pub enum FollowEvent<Hash> {
 	/// Ask the user to call `chainHead_continue` to produce more events
 	/// regarding the operation id.
 	OperationWaitingForContinue(OperationId),
+	/// The read proof of the storage items, generated after all the items.
+	OperationStorageProof(OperationStorageProof),
 	/// The responses of the `chainHead_storage` method have been produced.
 	OperationStorageDone(OperationId),
 	/// The RPC server was unable to provide the response of the following operation id.
@@ -561,6 +575,22 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn follow_op_storage_proof_event() {
+		let event: FollowEvent<String> =
+			FollowEvent::OperationStorageProof(OperationStorageProof {
+				operation_id: "123".into(),
+				proof: vec!["0x1234".into()],
+			});
+
+		let ser = serde_json::to_string(&event).unwrap();
+		let exp = r#"{"event":"operationStorageProof","operationId":"123","proof":["0x1234"]}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: FollowEvent<String> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
 	#[test]
 	fn follow_op_storage_done_event() {
 		let event: FollowEvent<String> =
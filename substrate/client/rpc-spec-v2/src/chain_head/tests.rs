@@ -1163,6 +1163,84 @@ async fn get_storage_value() {
 	);
 }
 
+#[tokio::test]
+async fn get_storage_value_with_proof() {
+	let (client, api, mut block_sub, sub_id, block) = setup_api().await;
+	let key = hex_string(&KEY);
+
+	// Import a new block with storage changes.
+	let mut builder = BlockBuilderBuilder::new(&*client)
+		.on_parent_block(block.hash())
+		.with_parent_block_number(1)
+		.build()
+		.unwrap();
+	builder.push_storage_change(KEY.to_vec(), Some(VALUE.to_vec())).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = format!("{:?}", block.hash());
+	client.import(BlockOrigin::Own, block.clone()).await.unwrap();
+
+	// Ensure the imported block is propagated and pinned for this subscription.
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::NewBlock(_)
+	);
+	assert_matches!(
+		get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+		FollowEvent::BestBlockChanged(_)
+	);
+
+	let response: MethodResponse = api
+		.call(
+			"chainHead_v1_storage",
+			rpc_params![
+				&sub_id,
+				&block_hash,
+				vec![StorageQuery {
+					key: key.clone(),
+					query_type: StorageQueryType::Value,
+					pagination_start_key: None
+				}],
+				Option::<String>::None,
+				true
+			],
+		)
+		.await
+		.unwrap();
+	let operation_id = match response {
+		MethodResponse::Started(started) => started.operation_id,
+		MethodResponse::LimitReached => panic!("Expected started response"),
+	};
+
+	let expected_value = hex_string(&VALUE);
+	assert_matches!(
+			get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+			FollowEvent::OperationStorageItems(res) if res.operation_id == operation_id &&
+				res.items.len() == 1 &&
+				res.items[0].key == key && res.items[0].result == StorageResultType::Value(expected_value)
+	);
+
+	// The proof is generated after all the items.
+	let proof = match get_next_event::<FollowEvent<String>>(&mut block_sub).await {
+		FollowEvent::OperationStorageProof(res) if res.operation_id == operation_id => res.proof,
+		event => panic!("Expected storage proof, got {event:?}"),
+	};
+	let proof = sp_state_machine::StorageProof::new(
+		proof.into_iter().map(|node| array_bytes::hex2bytes(&node).unwrap()),
+	);
+	let values = sp_state_machine::read_proof_check::<Blake2Hasher, _>(
+		block.header.state_root,
+		proof,
+		[KEY],
+	)
+	.unwrap();
+	assert_eq!(values.get(KEY), Some(&Some(VALUE.to_vec())));
+
+	assert_matches!(
+			get_next_event::<FollowEvent<String>>(&mut block_sub).await,
+			FollowEvent::OperationStorageDone(done) if done.operation_id == operation_id
+	);
+}
+
 #[tokio::test]
 async fn get_storage_non_queryable_key() {
 	let (mut _client, api, mut block_sub, sub_id, block) = setup_api().await;
@@ -95,6 +95,8 @@ pub enum ArchiveStorageEvent {
 	Storage(StorageResult),
 	/// Query encountered an error.
 	StorageError(ArchiveStorageMethodErr),
+	/// Read proof of the query results, generated after all the results.
+	StorageProof(ArchiveStorageProof),
 	/// Operation storage is done.
 	StorageDone,
 }
@@ -110,6 +112,11 @@ impl ArchiveStorageEvent {
 		Self::Storage(result)
 	}
 
+	/// Create a new `ArchiveStorageEvent::StorageProof` event.
+	pub fn proof(proof: Vec<String>) -> Self {
+		Self::StorageProof(ArchiveStorageProof { proof })
+	}
+
 	/// Checks if the event is a `StorageDone` event.
 	pub fn is_done(&self) -> bool {
 		matches!(self, Self::StorageDone)
@@ -134,6 +141,14 @@ pub struct ArchiveStorageMethodErr {
 	pub error: String,
 }
 
+/// The read proof of a storage call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveStorageProof {
+	/// The hex-encoded trie nodes proving the results.
+	pub proof: Vec<String>,
+}
+
 /// The type of the archive storage difference query.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -20,9 +20,9 @@
 
 use std::{marker::PhantomData, sync::Arc};
 
-use sc_client_api::{Backend, ChildInfo, StorageKey, StorageProvider};
+use sc_client_api::{Backend, ChildInfo, ProofProvider, StorageKey, StorageProvider};
 use sp_runtime::traits::Block as BlockT;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use super::events::{StorageQuery, StorageQueryType, StorageResult, StorageResultType};
 use crate::hex_string;
@@ -70,6 +70,12 @@ pub enum IterQueryType {
 /// The result of making a query call.
 pub type QueryResult = Result<Option<StorageResult>, String>;
 
+/// The read proof of the keys queried by an operation, as hex-encoded trie nodes.
+pub type QueryProof = Result<Vec<String>, String>;
+
+/// The maximum number of keys proven by a single operation.
+pub const MAX_PROOF_KEYS: usize = 16_384;
+
 impl<Client, Block, BE> Storage<Client, Block, BE>
 where
 	Block: BlockT + 'static,
@@ -159,12 +165,17 @@ where
 	///
 	/// Because this relies on a bounded channel, it will pause the storage iteration
 	// if the channel is becomes full which in turn provides backpressure.
+	///
+	/// The query key and the iterated keys are appended to `proof_keys`, if provided. Proving the
+	/// query key as well proves that no other descendant exists. At most [`MAX_PROOF_KEYS`] + 1
+	/// keys are collected, so that an operation with too many keys to prove can be detected.
 	pub fn query_iter_pagination_with_producer(
 		&self,
 		query: QueryIter,
 		hash: Block::Hash,
 		child_key: Option<&ChildInfo>,
 		tx: &mpsc::Sender<QueryResult>,
+		mut proof_keys: Option<&mut Vec<StorageKey>>,
 	) {
 		let QueryIter { ty, query_key, pagination_start_key } = query;
 
@@ -187,7 +198,17 @@ where
 			},
 		};
 
+		if let Some(proof_keys) = proof_keys.as_mut() {
+			proof_keys.push(query_key.clone());
+		}
+
 		for key in keys_iter {
+			if let Some(proof_keys) = proof_keys.as_mut() {
+				if proof_keys.len() <= MAX_PROOF_KEYS {
+					proof_keys.push(key.clone());
+				}
+			}
+
 			let result = match ty {
 				IterQueryType::Value => self.query_value(hash, &key, child_key),
 				IterQueryType::Hash => self.query_hash(hash, &key, child_key),
@@ -215,6 +236,41 @@ where
 	}
 }
 
+impl<Client, Block, BE> Storage<Client, Block, BE>
+where
+	Block: BlockT + 'static,
+	Client: ProofProvider<Block> + 'static,
+{
+	/// Generate a single read proof of the provided keys.
+	///
+	/// The proof contains the trie nodes needed to check the values of the keys against the
+	/// state root of the block, the same way as the proof returned by `state_getReadProof`.
+	/// Fails if more than [`MAX_PROOF_KEYS`] keys are provided.
+	pub fn query_proof(
+		&self,
+		hash: Block::Hash,
+		mut keys: Vec<StorageKey>,
+		child_key: Option<&ChildInfo>,
+	) -> QueryProof {
+		if keys.len() > MAX_PROOF_KEYS {
+			return Err(format!("Too many keys to prove, at most {MAX_PROOF_KEYS} are supported"));
+		}
+		keys.sort();
+		keys.dedup();
+		let mut keys_iter = keys.iter().map(|key| key.0.as_ref());
+
+		let result = if let Some(child_key) = child_key {
+			self.client.read_child_proof(hash, child_key, &mut keys_iter)
+		} else {
+			self.client.read_proof(hash, &mut keys_iter)
+		};
+
+		result
+			.map(|proof| proof.into_iter_nodes().map(|node| hex_string(&node)).collect())
+			.map_err(|error| error.to_string())
+	}
+}
+
 /// Generates storage events for `chainHead_storage` and `archive_storage` subscriptions.
 pub struct StorageSubscriptionClient<Client, Block, BE> {
 	/// Storage client.
@@ -239,39 +295,48 @@ impl<Client, Block, BE> StorageSubscriptionClient<Client, Block, BE>
 where
 	Block: BlockT + 'static,
 	BE: Backend<Block> + 'static,
-	Client: StorageProvider<Block, BE> + Send + Sync + 'static,
+	Client: StorageProvider<Block, BE> + ProofProvider<Block> + Send + Sync + 'static,
 {
 	/// Generate storage events to the provided sender.
+	///
+	/// If `proof_tx` is provided, a read proof of all the queried keys is sent to it once all
+	/// the results have been produced.
 	pub async fn generate_events(
 		&mut self,
 		hash: Block::Hash,
 		items: Vec<StorageQuery<StorageKey>>,
 		child_key: Option<ChildInfo>,
 		tx: mpsc::Sender<QueryResult>,
+		proof_tx: Option<oneshot::Sender<QueryProof>>,
 	) -> Result<(), tokio::task::JoinError> {
 		let this = self.clone();
 
 		tokio::task::spawn_blocking(move || {
+			let mut proof_keys = Vec::new();
+
 			for item in items {
 				match item.query_type {
 					StorageQueryType::Value => {
 						let rp = this.client.query_value(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::Hash => {
 						let rp = this.client.query_hash(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::ClosestDescendantMerkleValue => {
 						let rp =
 							this.client.query_merkle_value(hash, &item.key, child_key.as_ref());
 						if tx.blocking_send(rp).is_err() {
-							break;
+							return;
 						}
+						proof_keys.push(item.key);
 					},
 					StorageQueryType::DescendantsValues => {
 						let query = QueryIter {
@@ -284,6 +349,7 @@ where
 							hash,
 							child_key.as_ref(),
 							&tx,
+							proof_tx.as_ref().map(|_| &mut proof_keys),
 						)
 					},
 					StorageQueryType::DescendantsHashes => {
@@ -297,10 +363,16 @@ where
 							hash,
 							child_key.as_ref(),
 							&tx,
+							proof_tx.as_ref().map(|_| &mut proof_keys),
 						)
 					},
 				}
 			}
+
+			if let Some(proof_tx) = proof_tx {
+				let proof = this.client.query_proof(hash, proof_keys, child_key.as_ref());
+				let _ = proof_tx.send(proof);
+			}
 		})
 		.await?;
 
@@ -96,6 +96,8 @@ impl<Hash> From<Error> for TransactionEvent<Hash> {
 				TransactionEvent::Invalid(TransactionError {
 					error: "The pool is not accepting future transactions".into(),
 				}),
+			Error::Pool(error @ (PoolError::BundlesNotSupported | PoolError::InvalidBundle)) =>
+				TransactionEvent::Invalid(TransactionError { error: error.to_string() }),
 		}
 	}
 }
@@ -98,6 +98,22 @@ where
 		})
 	}
 
+	async fn submit_bundle(&self, ext: &Extensions, exts: Vec<Bytes>) -> Result<TxHash<P>> {
+		check_if_safe(ext)?;
+		let xts = exts
+			.iter()
+			.map(|ext| Decode::decode(&mut &ext[..]))
+			.collect::<std::result::Result<Vec<_>, _>>()
+			.map_err(|err| Error::Client(Box::new(err)))?;
+		let best_block_hash = self.client.info().best_hash;
+		self.pool.submit_bundle(best_block_hash, TX_SOURCE, xts).await.map_err(|e| {
+			e.into_pool_error()
+				.map(|e| Error::Pool(e))
+				.unwrap_or_else(|e| Error::Verification(Box::new(e)))
+				.into()
+		})
+	}
+
 	fn insert_key(
 		&self,
 		ext: &Extensions,
@@ -111,6 +111,68 @@ async fn author_submit_transaction_should_not_cause_error() {
 	);
 }
 
+#[tokio::test]
+async fn author_submit_bundle_should_work() {
+	let setup = TestSetup::default();
+	let api = setup.to_rpc();
+
+	let xts: Vec<Bytes> = vec![
+		uxt(Sr25519Keyring::Alice, 0).encode().into(),
+		uxt(Sr25519Keyring::Alice, 1).encode().into(),
+	];
+	let tx_hashes: Vec<H256> = xts.iter().map(|xt| blake2_256(xt).into()).collect();
+	let bundle_hash: H256 = blake2_256(&tx_hashes.encode()).into();
+	let response: H256 = api.call("author_submitBundle", [xts.clone()]).await.unwrap();
+
+	assert_eq!(response, bundle_hash);
+	assert_eq!(setup.pool.ready_bundles().len(), 1);
+	assert_eq!(setup.pool.status().ready, 0);
+
+	assert_matches!(
+		api.call::<_, H256>("author_submitBundle", [xts]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("Already Imported") && err.code() == 1013
+	);
+	assert_matches!(
+		api.call::<_, H256>("author_submitBundle", [Vec::<Bytes>::new()]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("Invalid bundle") && err.code() == 1023
+	);
+
+	let too_many: Vec<Bytes> =
+		(0..17).map(|nonce| uxt(Sr25519Keyring::Bob, nonce).encode().into()).collect();
+	assert_matches!(
+		api.call::<_, H256>("author_submitBundle", [too_many]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("Invalid bundle") && err.code() == 1023
+	);
+
+	// bundles with transactions requiring a nonce of Bob which is neither in the state nor
+	// provided by a preceding transaction of the bundle are rejected
+	for nonces in [vec![1], vec![0, 2], vec![1, 0]] {
+		let xts: Vec<Bytes> = nonces
+			.into_iter()
+			.map(|nonce| uxt(Sr25519Keyring::Bob, nonce).encode().into())
+			.collect();
+		assert_matches!(
+			api.call::<_, H256>("author_submitBundle", [xts]).await,
+			Err(RpcError::JsonRpc(err)) if err.message().contains("Invalid bundle") && err.code() == 1023
+		);
+	}
+	assert_eq!(setup.pool.ready_bundles().len(), 1);
+}
+
+#[tokio::test]
+async fn author_submit_bundle_is_unsafe() {
+	let setup = TestSetup::default();
+	let mut api = setup.to_rpc();
+	api.extensions_mut().insert(DenyUnsafe::Yes);
+
+	let xts: Vec<Bytes> = vec![uxt(Sr25519Keyring::Alice, 0).encode().into()];
+	assert_matches!(
+		api.call::<_, H256>("author_submitBundle", [xts]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("RPC call is unsafe to be called externally")
+	);
+	assert!(setup.pool.ready_bundles().is_empty());
+}
+
 #[tokio::test]
 async fn author_should_watch_extrinsic() {
 	let api = TestSetup::into_rpc();
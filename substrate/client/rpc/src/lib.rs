@@ -31,6 +31,7 @@ pub use sc_rpc_api::DenyUnsafe;
 pub mod author;
 pub mod chain;
 pub mod dev;
+pub mod mempool;
 pub mod mixnet;
 pub mod offchain;
 pub mod state;
@@ -0,0 +1,156 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Substrate mempool introspection API.
+
+#[cfg(test)]
+mod tests;
+
+use codec::{Decode, DecodeAll, Encode};
+use jsonrpsee::{core::RpcResult, Extensions};
+use sc_rpc_api::check_if_safe;
+use sc_transaction_pool_api::{
+	BlockHash, InPoolTransaction, PoolViewContent, TransactionPool, TransactionTag, TxHash,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Re-export the API for backward compatibility.
+pub use sc_rpc_api::mempool::*;
+
+/// Mempool introspection API.
+///
+/// The signer and the nonce of the transactions are decoded as `AccountId` and `Nonce` from
+/// the tags they provide.
+pub struct Mempool<P, AccountId, Nonce> {
+	/// Transactions pool
+	pool: Arc<P>,
+	_marker: PhantomData<(AccountId, Nonce)>,
+}
+
+impl<P, AccountId, Nonce> Mempool<P, AccountId, Nonce> {
+	/// Create new instance of Mempool API.
+	pub fn new(pool: Arc<P>) -> Self {
+		Mempool { pool, _marker: Default::default() }
+	}
+}
+
+impl<P, AccountId, Nonce> Mempool<P, AccountId, Nonce>
+where
+	P: TransactionPool,
+	AccountId: Decode + PartialEq,
+	Nonce: Decode,
+{
+	/// Returns the content of every view of the pool, keeping the transactions matching `filter`.
+	fn views(
+		&self,
+		filter: impl Fn(&MempoolTransaction<TxHash<P>, AccountId, Nonce>) -> bool,
+	) -> Vec<MempoolView<BlockHash<P>, TxHash<P>, AccountId, Nonce>> {
+		self.pool
+			.views_content()
+			.into_iter()
+			.map(|PoolViewContent { at, ready, future }| MempoolView {
+				at,
+				ready: ready.iter().map(|tx| to_rpc(&**tx)).filter(&filter).collect(),
+				future: future.iter().map(to_rpc).filter(&filter).collect(),
+			})
+			.collect()
+	}
+}
+
+impl<P, AccountId, Nonce> MempoolApiServer<TxHash<P>, BlockHash<P>, AccountId, Nonce>
+	for Mempool<P, AccountId, Nonce>
+where
+	P: TransactionPool + Sync + Send + 'static,
+	AccountId: Decode + PartialEq + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Nonce: Decode + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn content(
+		&self,
+		ext: &Extensions,
+	) -> RpcResult<Vec<MempoolView<BlockHash<P>, TxHash<P>, AccountId, Nonce>>> {
+		check_if_safe(ext)?;
+		Ok(self.views(|_| true))
+	}
+
+	fn status(&self) -> RpcResult<MempoolStatus<BlockHash<P>>> {
+		let status = self.pool.status();
+		let views = self
+			.pool
+			.views_status()
+			.into_iter()
+			.map(|view| MempoolViewStatus {
+				at: view.at,
+				ready: view.status.ready,
+				future: view.status.future,
+			})
+			.collect();
+
+		Ok(MempoolStatus {
+			ready: status.ready,
+			ready_bytes: status.ready_bytes,
+			future: status.future,
+			future_bytes: status.future_bytes,
+			views,
+		})
+	}
+
+	fn inspect(
+		&self,
+		ext: &Extensions,
+		sender: AccountId,
+	) -> RpcResult<Vec<MempoolView<BlockHash<P>, TxHash<P>, AccountId, Nonce>>> {
+		check_if_safe(ext)?;
+		Ok(self.views(|tx| tx.sender.as_ref() == Some(&sender)))
+	}
+}
+
+/// Converts a transaction of the pool to its RPC representation.
+fn to_rpc<T, AccountId, Nonce>(tx: &T) -> MempoolTransaction<T::Hash, AccountId, Nonce>
+where
+	T: InPoolTransaction,
+	T::Transaction: Encode,
+	T::Hash: Clone,
+	AccountId: Decode,
+	Nonce: Decode,
+{
+	let (sender, nonce) = sender_and_nonce(tx.provides()).unzip();
+	MempoolTransaction {
+		hash: tx.hash().clone(),
+		extrinsic: tx.data().encode().into(),
+		priority: *tx.priority(),
+		longevity: *tx.longevity(),
+		requires: tx.requires().iter().cloned().map(Into::into).collect(),
+		provides: tx.provides().iter().cloned().map(Into::into).collect(),
+		propagate: tx.is_propagable(),
+		sender,
+		nonce,
+	}
+}
+
+/// Decodes the signer and the nonce of a transaction from the tags it provides.
+///
+/// Signed transactions checked by `frame_system::CheckNonce` provide the encoded
+/// `(AccountId, Nonce)` pair.
+fn sender_and_nonce<AccountId: Decode, Nonce: Decode>(
+	provides: &[TransactionTag],
+) -> Option<(AccountId, Nonce)> {
+	provides
+		.iter()
+		.find_map(|tag| <(AccountId, Nonce)>::decode_all(&mut &tag[..]).ok())
+}
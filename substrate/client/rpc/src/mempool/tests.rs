@@ -0,0 +1,180 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+
+use jsonrpsee::{core::EmptyServerParams as EmptyParams, RpcModule};
+use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool::{BasicPool, FullChainApi};
+use sc_transaction_pool_api::TransactionSource;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_crypto_hashing::blake2_256;
+use std::sync::Arc;
+use substrate_test_runtime_client::{
+	runtime::{AccountId, Block, Extrinsic, ExtrinsicBuilder, Nonce, Transfer},
+	Backend, Client, Sr25519Keyring,
+};
+
+type FullTransactionPool = BasicPool<FullChainApi<Client<Backend>, Block>, Block>;
+
+type Views = Vec<MempoolView<H256, H256, AccountId, Nonce>>;
+
+fn uxt(sender: Sr25519Keyring, nonce: Nonce) -> Extrinsic {
+	let tx = Transfer {
+		amount: Default::default(),
+		nonce,
+		from: sender.into(),
+		to: Sr25519Keyring::Bob.into(),
+	};
+	ExtrinsicBuilder::new_transfer(tx).build()
+}
+
+struct TestSetup {
+	pub client: Arc<Client<Backend>>,
+	pub pool: Arc<FullTransactionPool>,
+}
+
+impl Default for TestSetup {
+	fn default() -> Self {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner,
+			client.clone(),
+		));
+		TestSetup { client, pool }
+	}
+}
+
+impl TestSetup {
+	fn to_rpc(&self) -> RpcModule<Mempool<FullTransactionPool, AccountId, Nonce>> {
+		let mut module = Mempool::new(self.pool.clone()).into_rpc();
+		module.extensions_mut().insert(DenyUnsafe::No);
+		module
+	}
+
+	async fn submit(&self, xt: Extrinsic) -> H256 {
+		let best_hash = self.client.info().best_hash;
+		self.pool.submit_one(best_hash, TransactionSource::External, xt).await.unwrap()
+	}
+}
+
+#[tokio::test]
+async fn mempool_content_reports_ready_and_future_transactions() {
+	let setup = TestSetup::default();
+	let api = setup.to_rpc();
+
+	let ready = uxt(Sr25519Keyring::Alice, 0);
+	let future = uxt(Sr25519Keyring::Alice, 2);
+	let ready_hash = setup.submit(ready.clone()).await;
+	setup.submit(future.clone()).await;
+
+	let views: Views = api.call("mempool_content", EmptyParams::new()).await.unwrap();
+	assert_eq!(views.len(), 1);
+	let view = &views[0];
+	assert_eq!(view.at, Some(setup.client.info().genesis_hash));
+
+	assert_eq!(view.ready.len(), 1);
+	let tx = &view.ready[0];
+	assert_eq!(tx.hash, ready_hash);
+	assert_eq!(tx.hash, H256::from(blake2_256(&ready.encode())));
+	assert_eq!(tx.extrinsic, Bytes::from(ready.encode()));
+	assert_eq!(tx.sender, Some(Sr25519Keyring::Alice.into()));
+	assert_eq!(tx.nonce, Some(0));
+	assert!(tx.requires.is_empty());
+
+	assert_eq!(view.future.len(), 1);
+	let tx = &view.future[0];
+	assert_eq!(tx.extrinsic, Bytes::from(future.encode()));
+	assert_eq!(tx.sender, Some(Sr25519Keyring::Alice.into()));
+	assert_eq!(tx.nonce, Some(2));
+	let missing_tag = (AccountId::from(Sr25519Keyring::Alice), 1 as Nonce).encode();
+	assert_eq!(tx.requires, vec![Bytes::from(missing_tag)]);
+}
+
+#[tokio::test]
+async fn mempool_status_reports_views() {
+	let setup = TestSetup::default();
+	let api = setup.to_rpc();
+
+	setup.submit(uxt(Sr25519Keyring::Alice, 0)).await;
+	setup.submit(uxt(Sr25519Keyring::Alice, 1)).await;
+	setup.submit(uxt(Sr25519Keyring::Bob, 1)).await;
+
+	let status: MempoolStatus<H256> = api.call("mempool_status", EmptyParams::new()).await.unwrap();
+	assert_eq!((status.ready, status.future), (2, 1));
+	assert_eq!(
+		status.views,
+		vec![MempoolViewStatus { at: Some(setup.client.info().genesis_hash), ready: 2, future: 1 }]
+	);
+}
+
+#[tokio::test]
+async fn mempool_inspect_filters_by_sender() {
+	let setup = TestSetup::default();
+	let api = setup.to_rpc();
+
+	let alice_hash = setup.submit(uxt(Sr25519Keyring::Alice, 0)).await;
+	setup.submit(uxt(Sr25519Keyring::Bob, 0)).await;
+
+	let views: Views = api
+		.call("mempool_inspect", [AccountId::from(Sr25519Keyring::Alice)])
+		.await
+		.unwrap();
+	assert_eq!(views.len(), 1);
+	assert_eq!(views[0].ready.iter().map(|tx| tx.hash).collect::<Vec<_>>(), vec![alice_hash]);
+	assert!(views[0].future.is_empty());
+
+	let views: Views = api
+		.call("mempool_inspect", [AccountId::from(Sr25519Keyring::Charlie)])
+		.await
+		.unwrap();
+	assert!(views[0].ready.is_empty());
+}
+
+#[tokio::test]
+async fn mempool_content_is_unsafe() {
+	let mut api = TestSetup::default().to_rpc();
+	api.extensions_mut().insert(DenyUnsafe::Yes);
+
+	let request = r#"{"jsonrpc":"2.0","method":"mempool_content","params":[],"id":1}"#;
+	let (resp, _) = api.raw_json_request(request, 1).await.expect("Raw calls should succeed");
+
+	assert_eq!(
+		resp,
+		r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#
+	);
+}
+
+#[tokio::test]
+async fn mempool_inspect_is_unsafe() {
+	let mut api = TestSetup::default().to_rpc();
+	api.extensions_mut().insert(DenyUnsafe::Yes);
+
+	let request = r#"{"jsonrpc":"2.0","method":"mempool_inspect","params":["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"],"id":1}"#;
+	let (resp, _) = api.raw_json_request(request, 1).await.expect("Raw calls should succeed");
+
+	assert_eq!(
+		resp,
+		r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#
+	);
+}
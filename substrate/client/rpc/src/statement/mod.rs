@@ -18,26 +18,38 @@
 
 //! Substrate statement store API.
 
+#[cfg(test)]
+mod tests;
+
+use crate::{
+	utils::{spawn_subscription_task, BoundedVecDeque, PendingSubscription},
+	SubscriptionTaskExecutor,
+};
 use codec::{Decode, Encode};
+use futures::StreamExt;
 use jsonrpsee::{
 	core::{async_trait, RpcResult},
-	Extensions,
+	Extensions, PendingSubscriptionSink,
 };
 /// Re-export the API for backward compatibility.
 pub use sc_rpc_api::statement::{error::Error, StatementApiServer};
 use sp_core::Bytes;
-use sp_statement_store::{StatementSource, SubmitResult};
+use sp_statement_store::{StatementSource, SubmitResult, TopicFilter};
 use std::sync::Arc;
 
 /// Statement store API
 pub struct StatementStore {
 	store: Arc<dyn sp_statement_store::StatementStore>,
+	executor: SubscriptionTaskExecutor,
 }
 
 impl StatementStore {
 	/// Create new instance of Offchain API.
-	pub fn new(store: Arc<dyn sp_statement_store::StatementStore>) -> Self {
-		StatementStore { store }
+	pub fn new(
+		store: Arc<dyn sp_statement_store::StatementStore>,
+		executor: SubscriptionTaskExecutor,
+	) -> Self {
+		StatementStore { store, executor }
 	}
 }
 
@@ -137,4 +149,24 @@ impl StatementApiServer for StatementStore {
 	fn remove(&self, hash: [u8; 32]) -> RpcResult<()> {
 		Ok(self.store.remove(&hash).map_err(|e| Error::StatementStore(e.to_string()))?)
 	}
+
+	fn subscribe_statements(
+		&self,
+		pending: PendingSubscriptionSink,
+		topic_filter: TopicFilter,
+		dest: Option<[u8; 32]>,
+	) {
+		let stream = self
+			.store
+			.subscribe_statements(topic_filter, dest)
+			.map(|statement| Bytes::from(statement.encode()));
+
+		let fut = async move {
+			PendingSubscription::from(pending)
+				.pipe_from_stream(stream, BoundedVecDeque::default())
+				.await;
+		};
+
+		spawn_subscription_task(&self.executor, fut);
+	}
 }
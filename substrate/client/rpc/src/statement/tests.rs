@@ -0,0 +1,137 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::testing::{test_executor, timeout_secs};
+use assert_matches::assert_matches;
+use futures::channel::mpsc;
+use parking_lot::Mutex;
+use sp_statement_store::{DecryptionKey, Hash, Result, Statement, StatementStream, Topic};
+
+type Subscription = (TopicFilter, Option<DecryptionKey>, mpsc::UnboundedSender<Statement>);
+
+/// Statement store which only supports subscriptions.
+#[derive(Default)]
+struct TestStore {
+	subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl sp_statement_store::StatementStore for TestStore {
+	fn statements(&self) -> Result<Vec<(Hash, Statement)>> {
+		unimplemented!()
+	}
+
+	fn take_recent_statements(&self) -> Result<Vec<(Hash, Statement)>> {
+		unimplemented!()
+	}
+
+	fn statement(&self, _hash: &Hash) -> Result<Option<Statement>> {
+		unimplemented!()
+	}
+
+	fn has_statement(&self, _hash: &Hash) -> bool {
+		unimplemented!()
+	}
+
+	fn broadcasts(&self, _match_all_topics: &[Topic]) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted(&self, _match_all_topics: &[Topic], _dest: [u8; 32]) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted_clear(&self, _match_all_topics: &[Topic], _dest: [u8; 32]) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn broadcasts_stmt(&self, _match_all_topics: &[Topic]) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted_stmt(&self, _match_all_topics: &[Topic], _dest: [u8; 32]) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn posted_clear_stmt(
+		&self,
+		_match_all_topics: &[Topic],
+		_dest: [u8; 32],
+	) -> Result<Vec<Vec<u8>>> {
+		unimplemented!()
+	}
+
+	fn submit(&self, _statement: Statement, _source: StatementSource) -> SubmitResult {
+		unimplemented!()
+	}
+
+	fn remove(&self, _hash: &Hash) -> Result<()> {
+		unimplemented!()
+	}
+
+	fn remove_by(&self, _who: [u8; 32]) -> Result<()> {
+		unimplemented!()
+	}
+
+	fn subscribe_statements(
+		&self,
+		filter: TopicFilter,
+		dest: Option<DecryptionKey>,
+	) -> StatementStream {
+		let (sender, receiver) = mpsc::unbounded();
+		self.subscriptions.lock().push((filter, dest, sender));
+		receiver.boxed()
+	}
+}
+
+#[tokio::test]
+async fn statement_subscribe_streams_statements() {
+	let store = Arc::new(TestStore::default());
+	let api = StatementStore::new(store.clone(), test_executor()).into_rpc();
+
+	let filter = TopicFilter::MatchAll(vec![[1u8; 32]]);
+	let mut sub = api
+		.subscribe_unbounded("statement_subscribe", (filter.clone(), Some([2u8; 32])))
+		.await
+		.unwrap();
+
+	let sender = {
+		let mut subscriptions = store.subscriptions.lock();
+		assert_eq!(subscriptions.len(), 1);
+		let (subscribed_filter, dest, sender) = subscriptions.remove(0);
+		assert_eq!(subscribed_filter, filter);
+		assert_eq!(dest, Some([2u8; 32]));
+		sender
+	};
+
+	let mut statement = Statement::new();
+	statement.set_topic(0, [1u8; 32]);
+	statement.set_decryption_key([2u8; 32]);
+	statement.set_plain_data(vec![42]);
+	sender.unbounded_send(statement.clone()).unwrap();
+
+	let (bytes, _) = assert_matches!(
+		timeout_secs(10, sub.next::<Bytes>()).await,
+		Ok(Some(Ok(item))) => item
+	);
+	assert_eq!(Statement::decode(&mut &bytes[..]).unwrap(), statement);
+
+	// The subscription ends with the stream of the store.
+	drop(sender);
+	assert_matches!(timeout_secs(10, sub.next::<Bytes>()).await, Ok(None));
+}
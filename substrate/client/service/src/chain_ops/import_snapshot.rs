@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use futures::{future, prelude::*};
+use log::{info, warn};
+use sc_client_api::HeaderBackend;
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sp_consensus::BlockOrigin;
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor, Zero};
+use std::{
+	io::Read,
+	path::Path,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	task::Poll,
+};
+
+/// Imports the block and state of a snapshot, like the target block of warp sync.
+///
+/// The chain must not have any block besides the genesis block of the snapshot. The trie nodes of
+/// the snapshot are held in a temporary database at `nodes_path` while its state is read.
+pub fn import_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	mut input: impl Read,
+	nodes_path: &Path,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink {
+		imported: AtomicBool,
+		has_error: AtomicBool,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			for result in results {
+				if let (Err(err), hash) = result {
+					warn!("There was an error importing the snapshot block {:?}: {}", hash, err);
+					self.has_error.store(true, Ordering::Release);
+				}
+			}
+			self.imported.store(true, Ordering::Release);
+		}
+	}
+
+	let snapshot = match sc_client_db::read_snapshot::<B>(&mut input, nodes_path) {
+		Ok(snapshot) => snapshot,
+		Err(e) => return future::ready(Err(e.into())).boxed(),
+	};
+	let info = client.info();
+	if snapshot.genesis_hash != info.genesis_hash {
+		return future::ready(Err(Error::Other(format!(
+			"Snapshot of chain with genesis {}, expected {}",
+			snapshot.genesis_hash, info.genesis_hash
+		))))
+		.boxed()
+	}
+	if !info.best_number.is_zero() {
+		return future::ready(Err(Error::Other(format!(
+			"Cannot import a snapshot on top of block #{}",
+			info.best_number
+		))))
+		.boxed()
+	}
+
+	let hash = snapshot.header.hash();
+	let number = *snapshot.header.number();
+	// The state is imported and checked against the state root like the one of the target block
+	// of warp sync, which the consensus block imports know how to handle.
+	import_queue.service_ref().import_blocks(
+		BlockOrigin::NetworkInitialSync,
+		vec![IncomingBlock::<B> {
+			hash,
+			header: Some(snapshot.header),
+			body: snapshot.body,
+			indexed_body: None,
+			justifications: snapshot.justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			state: Some(ImportedState { block: hash, state: snapshot.state }),
+			skip_execution: true,
+		}],
+	);
+
+	let link = WaitLink { imported: AtomicBool::new(false), has_error: AtomicBool::new(false) };
+	future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &link);
+		if !link.imported.load(Ordering::Acquire) {
+			return Poll::Pending
+		}
+		if link.has_error.load(Ordering::Acquire) {
+			return Poll::Ready(Err(Error::Other(format!(
+				"Failed to import snapshot block #{number} ({hash})"
+			))))
+		}
+		info!("🎉 Imported snapshot of block #{number} ({hash})");
+		Poll::Ready(Ok(()))
+	})
+	.boxed()
+}
@@ -22,10 +22,12 @@ mod check_block;
 mod export_blocks;
 mod export_raw_state;
 mod import_blocks;
+mod import_snapshot;
 mod revert_chain;
 
 pub use check_block::*;
 pub use export_blocks::*;
 pub use export_raw_state::*;
 pub use import_blocks::*;
+pub use import_snapshot::*;
 pub use revert_chain::*;
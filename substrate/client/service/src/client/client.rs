@@ -40,8 +40,8 @@ use sc_client_api::{
 	},
 	execution_extensions::ExecutionExtensions,
 	notifications::{StorageEventStream, StorageNotifications},
-	CallExecutor, ExecutorProvider, KeysIter, OnFinalityAction, OnImportAction, PairsIter,
-	ProofProvider, StaleBlock, TrieCacheContext, UnpinWorkerMessage, UsageProvider,
+	BlockIndexer, CallExecutor, ExecutorProvider, KeysIter, OnFinalityAction, OnImportAction,
+	PairsIter, ProofProvider, StaleBlock, TrieCacheContext, UnpinWorkerMessage, UsageProvider,
 };
 use sc_consensus::{
 	BlockCheckParams, BlockImportParams, ForkChoiceStrategy, ImportResult, StateAction,
@@ -110,6 +110,8 @@ where
 	// Holds the block hash currently being imported. TODO: replace this with block queue.
 	importing_block: RwLock<Option<Block::Hash>>,
 	block_rules: BlockRules<Block>,
+	// Extracts the index entries of imported blocks, if the index is enabled.
+	block_indexer: RwLock<Option<Arc<dyn BlockIndexer<Block>>>>,
 	config: ClientConfig<Block>,
 	telemetry: Option<TelemetryHandle>,
 	unpin_worker_sender: TracingUnboundedSender<UnpinWorkerMessage<Block>>,
@@ -417,6 +419,7 @@ where
 			finality_actions: Default::default(),
 			importing_block: Default::default(),
 			block_rules: BlockRules::new(fork_blocks, bad_blocks),
+			block_indexer: Default::default(),
 			config,
 			telemetry,
 			unpin_worker_sender,
@@ -437,6 +440,13 @@ where
 		&self.finality_notification_sinks
 	}
 
+	/// Set the indexer extracting the index entries of imported blocks.
+	///
+	/// Blocks imported before the indexer is set are not indexed.
+	pub fn set_block_indexer(&self, indexer: Arc<dyn BlockIndexer<Block>>) {
+		*self.block_indexer.write() = Some(indexer);
+	}
+
 	/// Get a reference to the state at a given block.
 	pub fn state_at(&self, hash: Block::Hash) -> sp_blockchain::Result<B::State> {
 		self.backend.state_at(hash, TrieCacheContext::Untrusted)
@@ -709,6 +719,15 @@ where
 			origin,
 		);
 
+		if let Some(indexer) = self.block_indexer.read().as_ref() {
+			let main_sc = storage_changes.as_ref().map(|(main_sc, _)| main_sc);
+			let (entries, complete) = match &body {
+				Some(body) => indexer.index_block(import_headers.post(), body, main_sc),
+				None => (Vec::new(), false),
+			};
+			operation.op.set_index_entries(entries, complete && main_sc.is_some())?;
+		}
+
 		operation.op.set_block_data(
 			import_headers.post().clone(),
 			body,
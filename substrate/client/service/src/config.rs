@@ -34,7 +34,8 @@ pub use sc_network::{
 	Multiaddr,
 };
 pub use sc_rpc_server::{
-	IpNetwork, RpcEndpoint, RpcMethods, SubscriptionIdProvider as RpcSubscriptionIdProvider,
+	IpNetwork, QuotaConfig as RpcQuotaConfig, RpcEndpoint, RpcMethods,
+	SubscriptionIdProvider as RpcSubscriptionIdProvider,
 };
 pub use sc_telemetry::TelemetryEndpoints;
 pub use sc_transaction_pool::TransactionPoolOptions;
@@ -98,6 +99,8 @@ pub struct Configuration {
 	pub force_authoring: bool,
 	/// Disable GRANDPA when running in validator mode
 	pub disable_grandpa: bool,
+	/// Index the extrinsics and events of imported blocks.
+	pub archive_index: bool,
 	/// Development key seed.
 	///
 	/// When running in development mode, the seed will be used to generate authority keys by the
@@ -340,6 +343,8 @@ pub struct RpcConfiguration {
 	pub rate_limit_whitelisted_ips: Vec<IpNetwork>,
 	/// RPC rate limit trust proxy headers.
 	pub rate_limit_trust_proxy_headers: bool,
+	/// RPC per-method costs and per-API-key quotas.
+	pub quotas: Option<RpcQuotaConfig>,
 	/// RPC logger capacity (default: 1024).
 	pub request_logger_limit: u32,
 }
@@ -445,6 +445,7 @@ where
 		id_provider: rpc_id_provider,
 		tokio_handle: tokio_handle.clone(),
 		request_logger_limit: rpc_configuration.request_logger_limit,
+		quotas: rpc_configuration.quotas.clone(),
 	};
 
 	// TODO: https://github.com/paritytech/substrate/issues/13773
@@ -256,6 +256,7 @@ fn node_config<E: ChainSpecExtension + Clone + 'static + Send + Sync>(
 			rate_limit: None,
 			rate_limit_whitelisted_ips: Default::default(),
 			rate_limit_trust_proxy_headers: Default::default(),
+			quotas: None,
 			request_logger_limit: 1024,
 		},
 		prometheus_config: None,
@@ -263,6 +264,7 @@ fn node_config<E: ChainSpecExtension + Clone + 'static + Send + Sync>(
 		offchain_worker: Default::default(),
 		force_authoring: false,
 		disable_grandpa: false,
+		archive_index: false,
 		dev_key_seed: key_seed,
 		tracing_targets: None,
 		tracing_receiver: Default::default(),
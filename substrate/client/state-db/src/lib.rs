@@ -46,13 +46,13 @@ mod pruning;
 #[cfg(test)]
 mod test;
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
 use log::trace;
-use noncanonical::NonCanonicalOverlay;
+use noncanonical::{NonCanonicalOverlay, LAST_CANONICAL};
 use parking_lot::RwLock;
-use pruning::{HaveBlock, RefWindow};
+use pruning::{HaveBlock, RefWindow, LAST_PRUNED};
 use std::{
-	collections::{hash_map::Entry, HashMap},
+	collections::{hash_map::Entry, HashMap, HashSet},
 	fmt,
 };
 
@@ -288,6 +288,128 @@ fn to_meta_key<S: Codec>(suffix: &[u8], data: &S) -> Vec<u8> {
 	buffer
 }
 
+/// Apply `f` to the trie node keys of the journal record stored under the meta `key`.
+///
+/// Journal records refer to trie nodes by their key in the backing database, which differs between
+/// databases with and without reference counting. This allows rewriting them when the meta entries
+/// are moved to another database. Returns `None` if `key` is not the key of a journal record.
+///
+/// Deletions of pruning journal records missing from the [`pending_deletions`] of the block are
+/// dropped, as the reference counting database would otherwise apply them.
+pub fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	pending_deletions: &HashMap<u64, HashSet<Vec<u8>>>,
+	mut f: impl FnMut(&mut Vec<u8>),
+) -> Result<Option<DBValue>, codec::Error> {
+	match pruning::map_journal_keys::<BlockHash>(key, value, pending_deletions, &mut f)? {
+		Some(value) => Ok(Some(value)),
+		None => noncanonical::map_journal_keys::<BlockHash>(key, value, &mut f),
+	}
+}
+
+/// Read the keys each canonical block awaiting pruning deletes, by block number.
+///
+/// Without reference counting, the deletion of a key inserted again by a later block is only
+/// cancelled in memory and is left out of the returned sets.
+pub fn pending_deletions<BlockHash: Hash, D: MetaDb>(
+	db: &D,
+) -> Result<HashMap<u64, HashSet<Vec<u8>>>, Error<D::Error>> {
+	pruning::pending_deletions::<BlockHash, D>(db)
+}
+
+/// Switch a database in an archive [`PruningMode`] to [`PruningMode::Constrained`].
+///
+/// `window` lists the hash and number of the canonical blocks kept in the pruning window in
+/// ascending order, ending with the last canonicalized block, along with the keys to delete when
+/// the block is pruned. `non_canonical` lists the hash, number and parent hash of the blocks above
+/// the window, parents first. It is only used for [`PruningMode::ArchiveAll`] databases, which have
+/// no non-canonical journal; their state must already be in the database.
+///
+/// Trie nodes that are not part of the kept states are left in the database and should be removed
+/// separately.
+pub fn constrain_archive<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	window: Vec<(BlockHash, u64, Vec<Key>)>,
+	non_canonical: &[(BlockHash, u64, BlockHash)],
+) -> Result<CommitSet<Key>, Error<D::Error>> {
+	let (first, last) = match (window.first(), window.last()) {
+		(Some(first), Some(last)) => (first.1, (last.0.clone(), last.1)),
+		_ => return Err(StateDbError::BlockMissing.into()),
+	};
+	if last.1 - first + 1 != window.len() as u64 {
+		return Err(StateDbError::InvalidBlockNumber.into())
+	}
+
+	let mut commit = CommitSet::default();
+	match fetch_stored_pruning_mode(db)? {
+		Some(PruningMode::ArchiveAll) => {
+			commit.meta.inserted.push((to_meta_key(LAST_CANONICAL, &()), last.encode()));
+			noncanonical::journal_blocks(non_canonical, &mut commit)?;
+		},
+		Some(PruningMode::ArchiveCanonical) => {
+			let last_canonical = db
+				.get_meta(&to_meta_key(LAST_CANONICAL, &()))
+				.map_err(Error::Db)?
+				.map(|buffer| <(BlockHash, u64)>::decode(&mut buffer.as_slice()))
+				.transpose()?;
+			if last_canonical != Some(last) {
+				return Err(StateDbError::InvalidBlock.into())
+			}
+		},
+		stored =>
+			return Err(StateDbError::Metadata(format!(
+				"Expected an archive database, found pruning mode {:?}",
+				stored
+			))
+			.into()),
+	}
+
+	if first > 0 {
+		commit.meta.inserted.push((to_meta_key(LAST_PRUNED, &()), (first - 1).encode()));
+	}
+	for (hash, number, deleted) in window {
+		pruning::journal_block(hash, number, deleted, &mut commit);
+	}
+	commit
+		.meta
+		.inserted
+		.push((to_meta_key(PRUNING_MODE, &()), PRUNING_MODE_CONSTRAINED.to_vec()));
+	Ok(commit)
+}
+
+/// Blocks recorded in the state-db journals of a database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JournaledBlocks<BlockHash> {
+	/// Hash and number of the last canonicalized block.
+	pub last_canonical: Option<(BlockHash, u64)>,
+	/// Hash and number of the canonical blocks waiting to be pruned, in ascending order.
+	pub window: Vec<(BlockHash, u64)>,
+	/// Hash, number and parent hash of the non-canonical blocks, parents first.
+	pub non_canonical: Vec<(BlockHash, u64, BlockHash)>,
+}
+
+/// Read the blocks recorded in the state-db journals of `db`, without loading the journaled
+/// changes.
+pub fn journaled_blocks<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+) -> Result<JournaledBlocks<BlockHash>, Error<D::Error>> {
+	let last_canonical = db
+		.get_meta(&to_meta_key(LAST_CANONICAL, &()))
+		.map_err(Error::Db)?
+		.map(|buffer| <(BlockHash, u64)>::decode(&mut buffer.as_slice()))
+		.transpose()?;
+	let window = match fetch_stored_pruning_mode(db)? {
+		Some(PruningMode::Constrained(_)) => pruning::journaled_blocks::<_, Key, _>(db)?,
+		_ => Vec::new(),
+	};
+	let non_canonical = match &last_canonical {
+		Some((_, number)) => noncanonical::journaled_blocks::<_, Key, _>(db, number + 1)?,
+		None => Vec::new(),
+	};
+	Ok(JournaledBlocks { last_canonical, window, non_canonical })
+}
+
 /// Status information about the last canonicalized block.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LastCanonicalized {
@@ -705,10 +827,14 @@ fn choose_pruning_mode(
 #[cfg(test)]
 mod tests {
 	use crate::{
+		constrain_archive, journaled_blocks, map_journal_keys, pending_deletions,
 		test::{make_changeset, make_db, TestDb},
-		Constraints, Error, IsPruned, PruningMode, StateDb, StateDbError,
+		to_meta_key, CommitSet, Constraints, Error, IsPruned, JournaledBlocks, LastCanonicalized,
+		PruningMode, StateDb, StateDbError,
 	};
+	use codec::Encode;
 	use sp_core::H256;
+	use std::collections::{HashMap, HashSet};
 
 	fn make_test_db(settings: PruningMode) -> (TestDb, StateDb<H256, H256, TestDb>) {
 		let mut db = make_db(&[91, 921, 922, 93, 94]);
@@ -786,6 +912,66 @@ mod tests {
 		assert!(db.data_eq(&make_db(&[1, 21, 3, 91, 921, 922, 93, 94])));
 	}
 
+	#[test]
+	fn archive_is_constrained() {
+		let (mut db, _) = make_test_db(PruningMode::ArchiveAll);
+		let window = vec![
+			(H256::from_low_u64_be(21), 2, Vec::new()),
+			(H256::from_low_u64_be(3), 3, vec![H256::from_low_u64_be(93)]),
+		];
+		let non_canonical = [(H256::from_low_u64_be(4), 4, H256::from_low_u64_be(3))];
+		db.commit(&constrain_archive(&db, window, &non_canonical).unwrap());
+
+		let mode = PruningMode::Constrained(Constraints { max_blocks: Some(1) });
+		let (_, state_db) =
+			StateDb::<H256, H256, TestDb>::open(db.clone(), Some(mode.clone()), false, false)
+				.unwrap();
+		assert_eq!(state_db.pruning_mode(), mode);
+		assert_eq!(state_db.last_canonicalized(), LastCanonicalized::Block(3));
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(1), 1), IsPruned::Pruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(21), 2), IsPruned::NotPruned);
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(4), 4), IsPruned::NotPruned);
+
+		db.commit(&state_db.canonicalize_block(&H256::from_low_u64_be(4)).unwrap());
+		assert_eq!(state_db.is_pruned(&H256::from_low_u64_be(3), 3), IsPruned::Pruned);
+		assert!(db.data_eq(&make_db(&[1, 21, 22, 3, 4, 91, 921, 922, 94])));
+	}
+
+	#[test]
+	fn only_archive_is_constrained() {
+		let window = |n| vec![(H256::from_low_u64_be(n), n, Vec::<H256>::new())];
+
+		let (db, _) = make_test_db(PruningMode::ArchiveCanonical);
+		assert!(constrain_archive(&db, window(3), &[]).is_ok());
+		assert_eq!(
+			constrain_archive(&db, window(2), &[]).err(),
+			Some(Error::StateDb(StateDbError::InvalidBlock)),
+		);
+
+		let (db, _) = make_test_db(PruningMode::blocks_pruning(1));
+		assert!(matches!(
+			constrain_archive(&db, window(3), &[]),
+			Err(Error::StateDb(StateDbError::Metadata(_))),
+		));
+	}
+
+	#[test]
+	fn journaled_blocks_are_read() {
+		let hash = H256::from_low_u64_be;
+		let (db, _) = make_test_db(PruningMode::Constrained(Constraints { max_blocks: Some(2) }));
+		assert_eq!(
+			journaled_blocks::<H256, H256, _>(&db).unwrap(),
+			JournaledBlocks {
+				last_canonical: Some((hash(3), 3)),
+				window: vec![(hash(21), 2), (hash(3), 3)],
+				non_canonical: vec![(hash(4), 4, hash(3))],
+			},
+		);
+
+		let (db, _) = make_test_db(PruningMode::ArchiveCanonical);
+		assert_eq!(journaled_blocks::<H256, H256, _>(&db).unwrap().window, Vec::new());
+	}
+
 	#[test]
 	fn block_record_unavailable() {
 		let (mut db, state_db) =
@@ -946,4 +1132,63 @@ mod tests {
 			check_stored_and_requested_mode_compatibility(created, reopened, expected);
 		}
 	}
+
+	#[test]
+	fn journal_keys_are_mapped() {
+		let prefixed = |key: &[u8]| [&[0xff][..], key].concat();
+		let strip = |key: &mut Vec<u8>| {
+			key.remove(0);
+		};
+		let hash = H256::from_low_u64_be(1);
+		let pending = HashMap::from([(5u64, HashSet::from([prefixed(b"b")]))]);
+
+		let key = to_meta_key(b"pruning_journal", &5u64);
+		let record = (hash, vec![prefixed(b"a")], vec![prefixed(b"b"), prefixed(b"c")]);
+		assert_eq!(
+			map_journal_keys::<H256>(&key, &record.encode(), &pending, strip).unwrap(),
+			Some((hash, vec![b"a".to_vec()], vec![b"b".to_vec()]).encode()),
+		);
+
+		let key = to_meta_key(b"noncanonical_journal", &(5u64, 1u64));
+		let record = (hash, hash, vec![(prefixed(b"a"), b"value".to_vec())], vec![prefixed(b"b")]);
+		assert_eq!(
+			map_journal_keys::<H256>(&key, &record.encode(), &pending, strip).unwrap(),
+			Some(
+				(hash, hash, vec![(b"a".to_vec(), b"value".to_vec())], vec![b"b".to_vec()])
+					.encode()
+			),
+		);
+
+		let key = to_meta_key(b"last_pruned", &());
+		assert_eq!(map_journal_keys::<H256>(&key, &5u64.encode(), &pending, strip).unwrap(), None);
+		let key = to_meta_key(b"pruning_journal", &5u64);
+		assert!(map_journal_keys::<H256>(&key, &[1, 2, 3], &pending, strip).is_err());
+	}
+
+	#[test]
+	fn pending_deletions_leave_out_reinserted_keys() {
+		let hash = H256::from_low_u64_be(1);
+		let mut db = TestDb::default();
+		let mut commit = CommitSet::default();
+		commit.meta.inserted = vec![
+			(to_meta_key(b"last_pruned", &()), 4u64.encode()),
+			(
+				to_meta_key(b"pruning_journal", &5u64),
+				(hash, Vec::<Vec<u8>>::new(), vec![b"a".to_vec(), b"b".to_vec()]).encode(),
+			),
+			(
+				to_meta_key(b"pruning_journal", &6u64),
+				(hash, vec![b"a".to_vec()], vec![b"c".to_vec()]).encode(),
+			),
+		];
+		db.commit(&commit);
+
+		assert_eq!(
+			pending_deletions::<H256, _>(&db).unwrap(),
+			HashMap::from([
+				(5, HashSet::from([b"b".to_vec()])),
+				(6, HashSet::from([b"c".to_vec()])),
+			]),
+		);
+	}
 }
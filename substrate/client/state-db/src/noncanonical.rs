@@ -81,6 +81,69 @@ fn to_journal_key(block: u64, index: u64) -> Vec<u8> {
 	to_meta_key(NON_CANONICAL_JOURNAL, &(block, index))
 }
 
+/// Apply `f` to the keys of a journal record, if `key` is the key of one.
+pub(crate) fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	f: &mut impl FnMut(&mut Vec<u8>),
+) -> Result<Option<DBValue>, codec::Error> {
+	if key.len() != 16 + NON_CANONICAL_JOURNAL.len() || !key.ends_with(NON_CANONICAL_JOURNAL) {
+		return Ok(None)
+	}
+	let mut record: JournalRecord<BlockHash, Vec<u8>> = Decode::decode(&mut &value[..])?;
+	record.inserted.iter_mut().for_each(|(key, _)| f(key));
+	record.deleted.iter_mut().for_each(f);
+	Ok(Some(record.encode()))
+}
+
+/// Add the journal records of blocks whose nodes are already in the database.
+///
+/// `blocks` holds the hash, number and parent hash of each block, parents first.
+pub(crate) fn journal_blocks<BlockHash: Hash, Key: Hash>(
+	blocks: &[(BlockHash, u64, BlockHash)],
+	commit: &mut CommitSet<Key>,
+) -> Result<(), StateDbError> {
+	let mut indexes = HashMap::<u64, u64>::new();
+	for (hash, number, parent_hash) in blocks {
+		let index = indexes.entry(*number).or_default();
+		if *index >= MAX_BLOCKS_PER_LEVEL {
+			return Err(StateDbError::TooManySiblingBlocks { number: *number })
+		}
+		let record: JournalRecord<BlockHash, Key> = JournalRecord {
+			hash: hash.clone(),
+			parent_hash: parent_hash.clone(),
+			inserted: Vec::new(),
+			deleted: Vec::new(),
+		};
+		commit.meta.inserted.push((to_journal_key(*number, *index), record.encode()));
+		*index += 1;
+	}
+	Ok(())
+}
+
+/// Hash, number and parent hash of the blocks in the journal from block `number` up, parents
+/// first.
+pub(crate) fn journaled_blocks<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+	mut number: u64,
+) -> Result<Vec<(BlockHash, u64, BlockHash)>, Error<D::Error>> {
+	let mut blocks = Vec::new();
+	loop {
+		let count = blocks.len();
+		for index in 0..MAX_BLOCKS_PER_LEVEL {
+			if let Some(record) = db.get_meta(&to_journal_key(number, index)).map_err(Error::Db)? {
+				let record: JournalRecord<BlockHash, Key> = Decode::decode(&mut record.as_slice())?;
+				blocks.push((record.hash, number, record.parent_hash));
+			}
+		}
+		if blocks.len() == count {
+			break
+		}
+		number += 1;
+	}
+	Ok(blocks)
+}
+
 #[cfg_attr(test, derive(PartialEq, Debug))]
 struct BlockOverlay<BlockHash: Hash, Key: Hash> {
 	hash: BlockHash,
@@ -25,8 +25,8 @@
 //! The changes are journaled in the DB.
 
 use crate::{
-	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, Error, Hash, MetaDb, StateDbError,
-	DEFAULT_MAX_BLOCK_CONSTRAINT, LOG_TARGET,
+	noncanonical::LAST_CANONICAL, to_meta_key, CommitSet, DBValue, Error, Hash, MetaDb,
+	StateDbError, DEFAULT_MAX_BLOCK_CONSTRAINT, LOG_TARGET,
 };
 use codec::{Decode, Encode};
 use log::trace;
@@ -279,6 +279,74 @@ fn to_journal_key(block: u64) -> Vec<u8> {
 	to_meta_key(PRUNING_JOURNAL, &block)
 }
 
+/// Apply `f` to the keys of a journal record, if `key` is the key of one.
+///
+/// Deletions missing from the `pending_deletions` of the block are left out of the record.
+pub(crate) fn map_journal_keys<BlockHash: Hash>(
+	key: &[u8],
+	value: &[u8],
+	pending_deletions: &HashMap<u64, HashSet<Vec<u8>>>,
+	f: &mut impl FnMut(&mut Vec<u8>),
+) -> Result<Option<DBValue>, codec::Error> {
+	if key.len() != 8 + PRUNING_JOURNAL.len() || !key.ends_with(PRUNING_JOURNAL) {
+		return Ok(None)
+	}
+	let block = u64::decode(&mut &key[..8])?;
+	let mut record: JournalRecord<BlockHash, Vec<u8>> = Decode::decode(&mut &value[..])?;
+	if let Some(pending) = pending_deletions.get(&block) {
+		record.deleted.retain(|key| pending.contains(key));
+	}
+	record.inserted.iter_mut().chain(record.deleted.iter_mut()).for_each(f);
+	Ok(Some(record.encode()))
+}
+
+/// Read the deletions of the blocks awaiting pruning in a database without reference counting.
+///
+/// Such databases don't journal that a block inserting a key again cancels its pending deletion,
+/// the cancelled deletions are only left out of the returned sets.
+pub(crate) fn pending_deletions<BlockHash: Hash, D: MetaDb>(
+	db: &D,
+) -> Result<HashMap<u64, HashSet<Vec<u8>>>, Error<D::Error>> {
+	let base = match db.get_meta(&to_meta_key(LAST_PRUNED, &())).map_err(Error::Db)? {
+		Some(buffer) => u64::decode(&mut buffer.as_slice())? + 1,
+		None => 0,
+	};
+	match DeathRowQueue::<BlockHash, Vec<u8>, D>::new_mem(db, base)? {
+		DeathRowQueue::Mem { death_rows, .. } =>
+			Ok((base..).zip(death_rows.into_iter().map(|row| row.deleted)).collect()),
+		DeathRowQueue::DbBacked { .. } => unreachable!("`new_mem` creates a memory queue; qed"),
+	}
+}
+
+/// Add the journal record of a canonical block whose nodes are already in the database.
+pub(crate) fn journal_block<BlockHash: Hash, Key: Hash>(
+	hash: BlockHash,
+	number: u64,
+	deleted: Vec<Key>,
+	commit: &mut CommitSet<Key>,
+) {
+	let record: JournalRecord<BlockHash, Key> =
+		JournalRecord { hash, inserted: Vec::new(), deleted };
+	commit.meta.inserted.push((to_journal_key(number), record.encode()));
+}
+
+/// Hash and number of the blocks in the journal, in ascending order.
+pub(crate) fn journaled_blocks<BlockHash: Hash, Key: Hash, D: MetaDb>(
+	db: &D,
+) -> Result<Vec<(BlockHash, u64)>, Error<D::Error>> {
+	let mut block = match db.get_meta(&to_meta_key(LAST_PRUNED, &())).map_err(Error::Db)? {
+		Some(buffer) => u64::decode(&mut buffer.as_slice())? + 1,
+		None => 0,
+	};
+	let mut blocks = Vec::new();
+	while let Some(record) = db.get_meta(&to_journal_key(block)).map_err(Error::Db)? {
+		let record: JournalRecord<BlockHash, Key> = Decode::decode(&mut record.as_slice())?;
+		blocks.push((record.hash, block));
+		block += 1;
+	}
+	Ok(blocks)
+}
+
 /// The result return by `RefWindow::have_block`
 #[derive(Debug, PartialEq, Eq)]
 pub enum HaveBlock {
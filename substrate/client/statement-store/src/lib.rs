@@ -51,8 +51,9 @@ mod metrics;
 
 pub use sp_statement_store::{Error, StatementStore, MAX_TOPICS};
 
+use futures::{channel::mpsc, StreamExt};
 use metrics::MetricsLink as PrometheusMetrics;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_keystore::LocalKeystore;
 use sp_api::ProvideRuntimeApi;
@@ -64,7 +65,7 @@ use sp_statement_store::{
 		InvalidStatement, StatementSource, StatementStoreExt, ValidStatement, ValidateStatement,
 	},
 	AccountId, BlockHash, Channel, DecryptionKey, Hash, InvalidReason, Proof, RejectionReason,
-	Result, Statement, SubmitResult, Topic,
+	Result, Statement, StatementStream, SubmitResult, Topic, TopicFilter,
 };
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
@@ -90,6 +91,9 @@ pub const MAX_STATEMENT_SIZE: usize =
 
 const MAINTENANCE_PERIOD: std::time::Duration = std::time::Duration::from_secs(30);
 
+/// Number of statements queued for a subscriber, after which the subscriber is dropped.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
 mod col {
 	pub const META: u8 = 0;
 	pub const STATEMENTS: u8 = 1;
@@ -170,6 +174,18 @@ struct Index {
 	total_size: usize,
 }
 
+struct Subscriber {
+	filter: TopicFilter,
+	dest: Option<DecryptionKey>,
+	sender: mpsc::Sender<Statement>,
+}
+
+impl Subscriber {
+	fn matches(&self, statement: &Statement) -> bool {
+		statement.decryption_key() == self.dest && self.filter.matches(statement)
+	}
+}
+
 struct ClientWrapper<Block, Client> {
 	client: Arc<Client>,
 	_block: std::marker::PhantomData<Block>,
@@ -212,6 +228,7 @@ pub struct Store {
 			+ Sync,
 	>,
 	keystore: Arc<LocalKeystore>,
+	subscribers: Mutex<Vec<Subscriber>>,
 	// Used for testing
 	time_override: Option<u64>,
 	metrics: PrometheusMetrics,
@@ -586,6 +603,7 @@ impl Store {
 			index: RwLock::new(Index::new(options)),
 			validate_fn,
 			keystore,
+			subscribers: Mutex::new(Vec::new()),
 			time_override: None,
 			metrics: PrometheusMetrics::new(prometheus),
 		};
@@ -722,6 +740,30 @@ impl Store {
 		self.time_override = Some(time);
 	}
 
+	/// Send a newly added statement to all matching subscribers.
+	///
+	/// Closed subscriptions are dropped, as well as the subscribers which don't keep up with the
+	/// statements: their stream ends once the queued statements are consumed.
+	fn notify_subscribers(&self, statement: &Statement) {
+		self.subscribers.lock().retain_mut(|subscriber| {
+			if subscriber.sender.is_closed() {
+				return false
+			}
+			if !subscriber.matches(statement) {
+				return true
+			}
+			match subscriber.sender.try_send(statement.clone()) {
+				Ok(()) => true,
+				Err(e) => {
+					if e.is_full() {
+						log::debug!(target: LOG_TARGET, "Dropping slow statement subscriber");
+					}
+					false
+				},
+			}
+		});
+	}
+
 	/// Returns `self` as [`StatementStoreExt`].
 	pub fn as_statement_store_ext(self: Arc<Self>) -> StatementStoreExt {
 		StatementStoreExt::new(self)
@@ -992,6 +1034,7 @@ impl StatementStore for Store {
 				return SubmitResult::InternalError(Error::Db(e.to_string()))
 			}
 		} // Release index lock
+		self.notify_subscribers(&statement);
 		self.metrics.report(|metrics| metrics.submitted_statements.inc());
 		log::trace!(target: LOG_TARGET, "Statement submitted: {:?}", HexDisplay::from(&hash));
 		SubmitResult::New
@@ -1047,6 +1090,17 @@ impl StatementStore for Store {
 			Error::Db(e.to_string())
 		})
 	}
+
+	/// Subscribe to new statements matching the topic filter and decryption key.
+	fn subscribe_statements(
+		&self,
+		filter: TopicFilter,
+		dest: Option<DecryptionKey>,
+	) -> StatementStream {
+		let (sender, receiver) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+		self.subscribers.lock().push(Subscriber { filter, dest, sender });
+		receiver.boxed()
+	}
 }
 
 #[cfg(test)]
@@ -1329,6 +1383,70 @@ mod tests {
 		assert_topics(&[0, 1, 2, 3, 42], None, &[]);
 	}
 
+	#[test]
+	fn subscriptions_receive_matching_statements() {
+		use futures::{FutureExt, StreamExt};
+		use sp_statement_store::TopicFilter;
+
+		let (store, _temp) = test_store();
+		let mut all = store.subscribe_statements(TopicFilter::Any, None);
+		let mut match_all =
+			store.subscribe_statements(TopicFilter::MatchAll(vec![topic(0), topic(1)]), None);
+		let mut match_any =
+			store.subscribe_statements(TopicFilter::MatchAny(vec![topic(1), topic(2)]), None);
+		let mut posted = store.subscribe_statements(TopicFilter::Any, Some(dec_key(2)));
+
+		let statement0 = signed_statement(0);
+		let statement1 = signed_statement_with_topics(1, &[topic(0)], None);
+		let statement2 = signed_statement_with_topics(2, &[topic(0), topic(1)], Some(dec_key(2)));
+		let statement3 = signed_statement_with_topics(3, &[topic(0), topic(1), topic(2)], None);
+		let statement4 = signed_statement_with_topics(4, &[topic(2)], None);
+		for s in [&statement0, &statement1, &statement2, &statement3, &statement4] {
+			assert_eq!(store.submit(s.clone(), StatementSource::Network), SubmitResult::New);
+		}
+		// Known statements are not delivered again.
+		assert_eq!(store.submit(statement0.clone(), StatementSource::Network), SubmitResult::Known);
+
+		let drain = |stream: &mut sp_statement_store::StatementStream| {
+			std::iter::from_fn(|| stream.next().now_or_never().flatten()).collect::<Vec<_>>()
+		};
+		assert_eq!(
+			drain(&mut all),
+			vec![statement0, statement1, statement3.clone(), statement4.clone()]
+		);
+		assert_eq!(drain(&mut match_all), vec![statement3.clone()]);
+		assert_eq!(drain(&mut match_any), vec![statement3, statement4]);
+		assert_eq!(drain(&mut posted), vec![statement2]);
+
+		// Closed subscriptions are dropped on the next notification.
+		drop(all);
+		store.submit(signed_statement(5), StatementSource::Network);
+		assert_eq!(store.subscribers.lock().len(), 3);
+	}
+
+	#[test]
+	fn slow_subscribers_are_dropped() {
+		use futures::{FutureExt, StreamExt};
+		use sp_statement_store::TopicFilter;
+
+		let (store, _temp) = test_store();
+		let mut slow = store.subscribe_statements(TopicFilter::Any, None);
+		let statement = signed_statement(0);
+
+		// The capacity of a channel is its buffer size plus one slot for the sender.
+		for _ in 0..=SUBSCRIPTION_BUFFER_SIZE {
+			store.notify_subscribers(&statement);
+		}
+		assert_eq!(store.subscribers.lock().len(), 1);
+		store.notify_subscribers(&statement);
+		assert!(store.subscribers.lock().is_empty());
+
+		// The queued statements are still delivered before the stream ends.
+		let delivered = std::iter::from_fn(|| slow.next().now_or_never().flatten()).count();
+		assert_eq!(delivered, SUBSCRIPTION_BUFFER_SIZE + 1);
+		assert_eq!(slow.next().now_or_never(), Some(None));
+	}
+
 	#[test]
 	fn constraints() {
 		let (store, _temp) = test_store();
@@ -69,6 +69,15 @@ pub enum Error {
 
 	#[error("The pool is not accepting future transactions")]
 	RejectedFutureTransaction,
+
+	#[error("The pool does not support transaction bundles")]
+	BundlesNotSupported,
+
+	#[error(
+		"Transaction bundle is empty, too large, contains duplicated transactions or \
+		 transactions requiring tags it does not provide"
+	)]
+	InvalidBundle,
 }
 
 impl Error {
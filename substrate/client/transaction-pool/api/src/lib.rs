@@ -56,6 +56,46 @@ impl PoolStatus {
 	}
 }
 
+/// The transactions of a single view of the pool.
+///
+/// A view represents the state of the pool at a given block. See
+/// [`TransactionPool::views_content`].
+#[derive(Debug)]
+pub struct PoolViewContent<BlockHash, Tx> {
+	/// The block the view is associated with.
+	///
+	/// `None` for pools which don't keep per-block views.
+	pub at: Option<BlockHash>,
+	/// The ready transactions, ordered by priority.
+	pub ready: Vec<Arc<Tx>>,
+	/// The future transactions, waiting for their requirements to be provided.
+	pub future: Vec<Tx>,
+}
+
+/// The number of transactions of a single view of the pool.
+///
+/// See [`TransactionPool::views_status`].
+#[derive(Debug, Clone)]
+pub struct PoolViewStatus<BlockHash> {
+	/// The block the view is associated with.
+	///
+	/// `None` for pools which don't keep per-block views.
+	pub at: Option<BlockHash>,
+	/// The status of the view.
+	pub status: PoolStatus,
+}
+
+/// A bundle of transactions which are included in a block together and in order, or not at all.
+///
+/// See [`TransactionPool::submit_bundle`].
+#[derive(Debug)]
+pub struct TransactionBundle<Hash, Extrinsic> {
+	/// The hash of the bundle, derived from the hashes of its transactions.
+	pub hash: Hash,
+	/// The transactions of the bundle, along with their hashes.
+	pub transactions: Vec<(Hash, Arc<Extrinsic>)>,
+}
+
 /// Possible transaction status events.
 ///
 /// These events are being emitted by `TransactionPool` watchers,
@@ -338,6 +378,48 @@ pub trait TransactionPool: Send + Sync {
 	/// Return specific ready transaction by hash, if there is one.
 	fn ready_transaction(&self, hash: &TxHash<Self>) -> Option<Arc<Self::InPoolTransaction>>;
 
+	/// Asynchronously imports a bundle of transactions to the pool.
+	///
+	/// The transactions of a bundle are included in a block together and in the given order, or
+	/// not at all. Bundles are kept apart from the individual transactions of the pool and are
+	/// removed once included in a block. Returns the hash of the bundle.
+	///
+	/// The default implementation rejects all bundles.
+	async fn submit_bundle(
+		&self,
+		_at: <Self::Block as BlockT>::Hash,
+		_source: TransactionSource,
+		_xts: Vec<TransactionFor<Self>>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		Err(crate::error::Error::BundlesNotSupported.into())
+	}
+
+	/// Returns the bundles waiting for inclusion, the ones paying the highest fees first.
+	fn ready_bundles(&self) -> Vec<Arc<TransactionBundle<TxHash<Self>, TransactionFor<Self>>>> {
+		Vec::new()
+	}
+
+	/// Removes the given bundles from the pool, e.g. because they failed to be applied.
+	fn remove_bundles(&self, _hashes: &[TxHash<Self>]) {}
+
+	// *** RPC / logging
+	/// Returns the ready and future transactions of every view maintained by the pool.
+	///
+	/// The default implementation returns a single view built from [`Self::ready`] and
+	/// [`Self::futures`], which is not associated with any block.
+	fn views_content(&self) -> Vec<PoolViewContent<BlockHash<Self>, Self::InPoolTransaction>> {
+		vec![PoolViewContent { at: None, ready: self.ready().collect(), future: self.futures() }]
+	}
+
+	/// Returns the status of every view maintained by the pool, in the order of
+	/// [`Self::views_content`].
+	///
+	/// The default implementation returns a single view with the [`Self::status`] of the pool,
+	/// which is not associated with any block.
+	fn views_status(&self) -> Vec<PoolViewStatus<BlockHash<Self>>> {
+		vec![PoolViewStatus { at: None, status: self.status() }]
+	}
+
 	/// Asynchronously returns a set of ready transaction at given block within given timeout.
 	///
 	/// If the timeout is hit during method execution, then the best effort (without executing full
@@ -19,17 +19,21 @@
 //! Utility for building substrate transaction pool trait object.
 
 use crate::{
-	common::api::FullChainApi,
+	common::{
+		api::FullChainApi,
+		journal::{TransactionJournal, TransactionJournalOptions},
+	},
 	fork_aware_txpool::ForkAwareTxPool as ForkAwareFullPool,
 	graph::{base_pool::Transaction, ChainApi, ExtrinsicFor, ExtrinsicHash, IsValidator, Options},
 	single_state_txpool::BasicPool as SingleStateFullPool,
+	transaction_pool_wrapper::Journal,
 	TransactionPoolWrapper, LOG_TARGET,
 };
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{LocalTransactionPool, MaintainedTransactionPool};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
-use std::{marker::PhantomData, sync::Arc, time::Duration};
+use std::{marker::PhantomData, path::Path, sync::Arc, time::Duration};
 
 /// The type of transaction pool.
 #[derive(Debug, Clone)]
@@ -45,11 +49,16 @@ pub enum TransactionPoolType {
 pub struct TransactionPoolOptions {
 	txpool_type: TransactionPoolType,
 	options: Options,
+	journal: Option<TransactionJournalOptions>,
 }
 
 impl Default for TransactionPoolOptions {
 	fn default() -> Self {
-		Self { txpool_type: TransactionPoolType::SingleState, options: Default::default() }
+		Self {
+			txpool_type: TransactionPoolType::SingleState,
+			options: Default::default(),
+			journal: None,
+		}
 	}
 }
 
@@ -81,7 +90,32 @@ impl TransactionPoolOptions {
 			Duration::from_secs(30 * 60)
 		};
 
-		TransactionPoolOptions { options, txpool_type }
+		TransactionPoolOptions { options, txpool_type, journal: None }
+	}
+
+	/// Enables the persistent journal of submitted transactions.
+	///
+	/// Transactions recorded in the journal are resubmitted to the pool after a restart.
+	pub fn with_journal(mut self, journal: TransactionJournalOptions) -> Self {
+		self.journal = Some(journal);
+		self
+	}
+
+	/// Limits the number and the total size of transactions kept in the pool for a single sender.
+	///
+	/// Only enforced by the fork-aware pool. When a sender exceeds the limits, its lowest priority
+	/// transactions are evicted.
+	pub fn with_per_sender_limit(mut self, count: usize, total_bytes: usize) -> Self {
+		self.options.per_sender = Some(crate::graph::base_pool::Limit { count, total_bytes });
+		self
+	}
+
+	/// Resolves a relative path of the journal against `base_path`.
+	pub fn with_journal_base_path(mut self, base_path: &Path) -> Self {
+		if let Some(journal) = self.journal.as_mut().filter(|journal| journal.path.is_relative()) {
+			journal.path = base_path.join(&journal.path);
+		}
+		self
 	}
 
 	/// Creates predefined options for benchmarking
@@ -96,10 +130,12 @@ impl TransactionPoolOptions {
 					count: 100_000,
 					total_bytes: 100 * 1024 * 1024,
 				},
+				per_sender: None,
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
 			},
 			txpool_type: TransactionPoolType::SingleState,
+			journal: None,
 		}
 	}
 }
@@ -229,23 +265,40 @@ where
 			txpool_type = ?self.options.txpool_type,
 			ready = ?self.options.options.ready,
 			future = ?self.options.options.future,
+			per_sender = ?self.options.options.per_sender,
+			journal = ?self.options.journal.as_ref().map(|journal| &journal.path),
 			"Creating transaction pool"
 		);
-		TransactionPoolWrapper::<Block, Client>(match self.options.txpool_type {
-			TransactionPoolType::SingleState => Box::new(SingleStateFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
-			TransactionPoolType::ForkAware => Box::new(ForkAwareFullPool::new_full(
-				self.options.options,
-				self.is_validator,
-				self.prometheus,
-				self.spawner,
-				self.client,
-			)),
-		})
+		let journal = self.options.journal.and_then(|options| {
+			TransactionJournal::open(options)
+				.map(|journal| Journal::spawn(journal, &self.spawner))
+				.inspect_err(|error| {
+					tracing::warn!(
+						target: LOG_TARGET,
+						?error,
+						"Failed to open transaction pool journal, transactions won't be persisted"
+					)
+				})
+				.ok()
+		});
+		TransactionPoolWrapper::<Block, Client>(
+			match self.options.txpool_type {
+				TransactionPoolType::SingleState => Arc::new(SingleStateFullPool::new_full(
+					self.options.options,
+					self.is_validator,
+					self.prometheus,
+					self.spawner,
+					self.client,
+				)),
+				TransactionPoolType::ForkAware => Arc::new(ForkAwareFullPool::new_full(
+					self.options.options,
+					self.is_validator,
+					self.prometheus,
+					self.spawner,
+					self.client,
+				)),
+			},
+			journal,
+		)
 	}
 }
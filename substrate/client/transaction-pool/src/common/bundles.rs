@@ -0,0 +1,201 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Store of the transaction bundles submitted to the pool.
+//!
+//! A bundle is a list of transactions which are included in a block together and in order, or
+//! not at all. Bundles are kept apart from the transactions of the pool: their transactions are
+//! validated on submission, but they are neither part of the ready/future queues nor propagated.
+//! Bundles are ordered by the sum of the priorities of their transactions, which the runtime
+//! derives from the fees they pay. A bundle is removed once any of its transactions is included
+//! in a block, when the block author fails to apply it, or when it's older than
+//! [`BUNDLE_LONGEVITY`] blocks.
+
+use crate::{
+	graph::{self, ExtrinsicHash, NumberFor, RawExtrinsicFor},
+	ValidateTransactionPriority, LOG_TARGET,
+};
+use parking_lot::Mutex;
+use sc_transaction_pool_api::{error::Error, TransactionBundle, TransactionSource};
+use sp_core::hexdisplay::HexDisplay;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash, Header, Saturating},
+	transaction_validity::{TransactionPriority, TransactionValidityError},
+};
+use std::{collections::HashSet, sync::Arc};
+use tracing::{debug, trace};
+
+/// The maximum number of bundles kept in the store.
+const MAX_BUNDLES: usize = 512;
+
+/// The maximum number of transactions in a bundle.
+const MAX_BUNDLE_TRANSACTIONS: usize = 16;
+
+/// The maximum encoded size of the transactions of a bundle, in bytes.
+const MAX_BUNDLE_BYTES: usize = 256 * 1024;
+
+/// The number of blocks after which a bundle which was not included is dropped.
+const BUNDLE_LONGEVITY: u32 = 64;
+
+/// Bundle type of the given [`graph::ChainApi`].
+pub(crate) type BundleFor<ChainApi> =
+	TransactionBundle<ExtrinsicHash<ChainApi>, RawExtrinsicFor<ChainApi>>;
+
+struct StoredBundle<ChainApi: graph::ChainApi> {
+	bundle: Arc<BundleFor<ChainApi>>,
+	/// The sum of the priorities of the transactions of the bundle.
+	priority: TransactionPriority,
+	/// The number of the block the bundle was submitted at.
+	submitted_at: NumberFor<ChainApi>,
+}
+
+/// Store of the transaction bundles submitted to the pool.
+pub(crate) struct BundleStore<ChainApi: graph::ChainApi> {
+	api: Arc<ChainApi>,
+	/// The bundles, ordered by descending priority and then by submission.
+	bundles: Mutex<Vec<StoredBundle<ChainApi>>>,
+}
+
+impl<ChainApi> BundleStore<ChainApi>
+where
+	ChainApi: graph::ChainApi,
+{
+	/// Creates an empty store.
+	pub fn new(api: Arc<ChainApi>) -> Self {
+		Self { api, bundles: Default::default() }
+	}
+
+	/// Validates the transactions of a bundle at the given block and adds it to the store.
+	///
+	/// The bundle is rejected if any of its transactions is invalid, or if it exceeds
+	/// [`MAX_BUNDLE_TRANSACTIONS`] or [`MAX_BUNDLE_BYTES`]. Every tag required by a transaction
+	/// must be provided by the state at `at` or by the preceding transactions of the bundle, so
+	/// that the bundle can be applied on its own. Once the store is full, the bundle replaces the
+	/// bundle with the lowest priority if its own priority is higher.
+	pub async fn submit(
+		&self,
+		at: <ChainApi::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Result<ExtrinsicHash<ChainApi>, ChainApi::Error> {
+		if xts.is_empty() || xts.len() > MAX_BUNDLE_TRANSACTIONS {
+			return Err(Error::InvalidBundle.into())
+		}
+
+		let (tx_hashes, lengths): (Vec<_>, Vec<_>) =
+			xts.iter().map(|xt| self.api.hash_and_length(xt)).unzip();
+		let unique = tx_hashes.iter().collect::<HashSet<_>>();
+		if unique.len() != tx_hashes.len() || lengths.iter().sum::<usize>() > MAX_BUNDLE_BYTES {
+			return Err(Error::InvalidBundle.into())
+		}
+
+		let hash = <<ChainApi::Block as BlockT>::Header as Header>::Hashing::hash_of(&tx_hashes);
+		if self.bundles.lock().iter().any(|stored| stored.bundle.hash == hash) {
+			return Err(Error::AlreadyImported(Box::new(hash)).into())
+		}
+
+		let submitted_at = self.api.resolve_block_number(at)?;
+		let mut transactions = Vec::with_capacity(xts.len());
+		let mut priority: TransactionPriority = 0;
+		let mut provided = HashSet::new();
+		for (tx_hash, xt) in tx_hashes.into_iter().zip(xts) {
+			let xt = Arc::new(xt);
+			let validity = self
+				.api
+				.validate_transaction(
+					at,
+					source,
+					xt.clone(),
+					ValidateTransactionPriority::Submitted,
+				)
+				.await?;
+			match validity {
+				Ok(valid) => {
+					// The runtime only reports the tags which are not provided by the state.
+					if let Some(tag) = valid.requires.iter().find(|tag| !provided.contains(*tag)) {
+						trace!(
+							target: LOG_TARGET,
+							?hash,
+							?tx_hash,
+							tag = %HexDisplay::from(*tag),
+							"Bundled transaction requires a tag which is not provided"
+						);
+						return Err(Error::InvalidBundle.into())
+					}
+					provided.extend(valid.provides);
+					priority = priority.saturating_add(valid.priority);
+					transactions.push((tx_hash, xt));
+				},
+				Err(TransactionValidityError::Invalid(error)) => {
+					trace!(target: LOG_TARGET, ?hash, ?tx_hash, ?error, "Invalid bundled transaction");
+					return Err(Error::InvalidTransaction(error).into())
+				},
+				Err(TransactionValidityError::Unknown(error)) =>
+					return Err(Error::UnknownTransaction(error).into()),
+			}
+		}
+
+		let mut bundles = self.bundles.lock();
+		if bundles.len() >= MAX_BUNDLES {
+			match bundles.last() {
+				Some(worst) if worst.priority < priority => {
+					debug!(target: LOG_TARGET, hash = ?worst.bundle.hash, "Bundle evicted");
+					bundles.pop();
+				},
+				_ => return Err(Error::ImmediatelyDropped.into()),
+			}
+		}
+		debug!(target: LOG_TARGET, ?hash, ?priority, count = transactions.len(), "Bundle submitted");
+		let position = bundles.partition_point(|stored| stored.priority >= priority);
+		bundles.insert(
+			position,
+			StoredBundle {
+				bundle: Arc::new(TransactionBundle { hash, transactions }),
+				priority,
+				submitted_at,
+			},
+		);
+		Ok(hash)
+	}
+
+	/// Returns the bundles, the ones with the highest priority first.
+	pub fn ready(&self) -> Vec<Arc<BundleFor<ChainApi>>> {
+		self.bundles.lock().iter().map(|stored| stored.bundle.clone()).collect()
+	}
+
+	/// Removes the given bundles.
+	pub fn remove(&self, hashes: &[ExtrinsicHash<ChainApi>]) {
+		self.bundles.lock().retain(|stored| !hashes.contains(&stored.bundle.hash));
+	}
+
+	/// Removes the bundles with transactions included in a block and the outdated bundles.
+	///
+	/// `at` is the number of the new best block and `included` the hashes of the transactions
+	/// included in the enacted blocks.
+	pub fn prune(&self, at: NumberFor<ChainApi>, included: &HashSet<ExtrinsicHash<ChainApi>>) {
+		self.bundles.lock().retain(|stored| {
+			let outdated = at.saturating_sub(stored.submitted_at) > BUNDLE_LONGEVITY.into();
+			let included =
+				stored.bundle.transactions.iter().any(|(tx_hash, _)| included.contains(tx_hash));
+			if outdated || included {
+				debug!(target: LOG_TARGET, hash = ?stored.bundle.hash, outdated, "Bundle pruned");
+			}
+			!outdated && !included
+		});
+	}
+}
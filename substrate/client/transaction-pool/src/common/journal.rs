@@ -0,0 +1,473 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Persistent journal of the transactions submitted to the pool.
+//!
+//! The journal is an append-only file of SCALE-encoded [`JournalEntry`]s, prefixed with a single
+//! version byte. Every transaction successfully submitted to the pool is appended to it, so that
+//! it can be resubmitted (and thereby revalidated) against the best block after a restart.
+//!
+//! The journal is bounded by [`TransactionJournalOptions::max_count`] and
+//! [`TransactionJournalOptions::max_bytes`]. Once it grows past half of these limits, it is
+//! compacted by dropping the entries of transactions which are no longer in the pool. Compaction
+//! rewrites the whole file, so it is meant to run on a blocking task and doesn't hold the lock
+//! used by [`TransactionJournal::record`] while doing so.
+
+use crate::LOG_TARGET;
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use sp_runtime::transaction_validity::TransactionSource;
+use std::{
+	collections::HashSet,
+	fs::{self, File, OpenOptions},
+	hash::Hash,
+	io::{self, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, info, warn};
+
+/// The version of the journal file format.
+const JOURNAL_VERSION: u8 = 1;
+
+/// Options of the transaction pool journal.
+#[derive(Debug, Clone)]
+pub struct TransactionJournalOptions {
+	/// The path of the journal file.
+	///
+	/// A relative path is resolved against the data directory of the node, see
+	/// [`crate::TransactionPoolOptions::with_journal_base_path`].
+	pub path: PathBuf,
+	/// The maximum number of transactions kept in the journal.
+	pub max_count: usize,
+	/// The maximum size of the journal file, in bytes.
+	pub max_bytes: usize,
+	/// Transactions submitted longer ago than this are not resubmitted on startup.
+	pub max_age: Duration,
+	/// The minimal interval between two compactions of the journal.
+	pub compaction_interval: Duration,
+}
+
+impl TransactionJournalOptions {
+	/// Creates the options for a journal stored at `path`, using the default limits.
+	pub fn new(path: impl Into<PathBuf>) -> Self {
+		Self {
+			path: path.into(),
+			max_count: 8192,
+			max_bytes: 20 * 1024 * 1024,
+			max_age: Duration::from_secs(60 * 60),
+			compaction_interval: Duration::from_secs(60),
+		}
+	}
+}
+
+/// A transaction recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub(crate) struct JournalEntry<H> {
+	/// The hash of the transaction.
+	pub hash: H,
+	/// The source the transaction was submitted from.
+	pub source: TransactionSource,
+	/// The submission time, in milliseconds since the unix epoch.
+	///
+	/// The wall clock is used as `Instant`s (e.g. the one of
+	/// [`crate::TimedTransactionSource`]) can't be persisted across restarts.
+	pub submitted_at: u64,
+	/// The encoded transaction.
+	pub extrinsic: Vec<u8>,
+}
+
+struct Inner<H> {
+	/// The journal file, opened for appending.
+	file: File,
+	/// The number of entries in the journal file.
+	count: usize,
+	/// The size of the journal file.
+	bytes: usize,
+	/// The entries loaded on startup which were not taken for resubmission yet.
+	pending: Option<Vec<JournalEntry<H>>>,
+	/// Whether the entries loaded on startup are still being resubmitted.
+	///
+	/// The journal is not compacted in the meantime, as that would drop them.
+	replaying: bool,
+	/// Whether a compaction is in progress.
+	compacting: bool,
+	/// The time of the last compaction.
+	last_compaction: Instant,
+}
+
+/// Persistent journal of the transactions submitted to the pool.
+pub(crate) struct TransactionJournal<H> {
+	options: TransactionJournalOptions,
+	inner: Mutex<Inner<H>>,
+}
+
+impl<H> TransactionJournal<H>
+where
+	H: Encode + Decode + Eq + Hash + Clone + std::fmt::Debug,
+{
+	/// Opens the journal, loading the entries which are still recent enough to be resubmitted.
+	///
+	/// The loaded entries are available through [`Self::take_pending`]. The journal is not
+	/// compacted until [`Self::finish_replay`] is called.
+	pub fn open(options: TransactionJournalOptions) -> io::Result<Self> {
+		if let Some(parent) = options.path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+
+		let now = unix_millis();
+		let max_age = options.max_age.as_millis() as u64;
+		let mut entries = read_entries::<H>(&options.path)?;
+		entries.retain(|entry| now.saturating_sub(entry.submitted_at) <= max_age);
+		entries.truncate(options.max_count);
+
+		let bytes = write_entries(&options.path, &entries)?;
+		let file = OpenOptions::new().append(true).open(&options.path)?;
+		info!(
+			target: LOG_TARGET,
+			path = ?options.path,
+			count = entries.len(),
+			"Opened transaction pool journal"
+		);
+
+		Ok(Self {
+			inner: Mutex::new(Inner {
+				file,
+				count: entries.len(),
+				bytes,
+				pending: Some(entries),
+				replaying: true,
+				compacting: false,
+				last_compaction: Instant::now(),
+			}),
+			options,
+		})
+	}
+
+	/// Takes the entries loaded on startup, if they were not taken yet.
+	pub fn take_pending(&self) -> Option<Vec<JournalEntry<H>>> {
+		self.inner.lock().pending.take()
+	}
+
+	/// Marks the entries loaded on startup as resubmitted, which allows to compact the journal.
+	///
+	/// Returns `true` if a compaction should be started, see [`Self::record`].
+	pub fn finish_replay(&self) -> bool {
+		let mut inner = self.inner.lock();
+		inner.replaying = false;
+		self.start_compaction(&mut inner)
+	}
+
+	/// Appends a transaction to the journal.
+	///
+	/// The transaction is not recorded if the journal is full. Returns `true` if the journal
+	/// should be compacted, in which case the caller is expected to call [`Self::compact`],
+	/// preferably from a blocking task.
+	pub fn record(&self, hash: H, source: TransactionSource, extrinsic: Vec<u8>) -> bool {
+		let entry = JournalEntry { hash, source, submitted_at: unix_millis(), extrinsic };
+		let encoded = entry.encode();
+
+		let mut inner = self.inner.lock();
+		if inner.count < self.options.max_count &&
+			inner.bytes + encoded.len() <= self.options.max_bytes
+		{
+			match inner.file.write_all(&encoded) {
+				Ok(()) => {
+					inner.count += 1;
+					inner.bytes += encoded.len();
+				},
+				Err(error) => {
+					warn!(
+						target: LOG_TARGET,
+						?error,
+						tx_hash = ?entry.hash,
+						"Failed to journal transaction"
+					)
+				},
+			}
+		} else {
+			debug!(target: LOG_TARGET, tx_hash = ?entry.hash, "Transaction journal is full");
+		}
+
+		self.start_compaction(&mut inner)
+	}
+
+	/// Marks a compaction as started if the journal grew past half of its limits.
+	fn start_compaction(&self, inner: &mut Inner<H>) -> bool {
+		let due = !inner.replaying &&
+			!inner.compacting &&
+			inner.last_compaction.elapsed() >= self.options.compaction_interval &&
+			(inner.count > self.options.max_count / 2 ||
+				inner.bytes > self.options.max_bytes / 2);
+		inner.compacting |= due;
+		due
+	}
+
+	/// Drops the entries of the transactions which are not part of `in_pool`.
+	///
+	/// The file is rewritten without holding the lock, the entries recorded in the meantime are
+	/// carried over to the compacted journal.
+	pub fn compact(&self, in_pool: &HashSet<H>) {
+		let (before, compacted_bytes) = {
+			let inner = self.inner.lock();
+			(inner.count, inner.bytes)
+		};
+
+		let result = read_entries_prefix::<H>(&self.options.path, compacted_bytes)
+			.and_then(|mut entries| {
+				let mut seen = HashSet::new();
+				entries.retain(|entry| {
+					in_pool.contains(&entry.hash) && seen.insert(entry.hash.clone())
+				});
+				let tmp_path = self.options.path.with_extension("tmp");
+				let bytes = create_entries_file(&tmp_path, &entries)?;
+				Ok((tmp_path, entries.len(), bytes))
+			})
+			.and_then(|(tmp_path, count, bytes)| {
+				// Carry over the entries recorded since the journal was read.
+				let mut inner = self.inner.lock();
+				let tail = read_range(&self.options.path, compacted_bytes, inner.bytes)?;
+				let mut file = OpenOptions::new().append(true).open(&tmp_path)?;
+				file.write_all(&tail)?;
+				fs::rename(&tmp_path, &self.options.path)?;
+				inner.file = file;
+				inner.count = count + inner.count.saturating_sub(before);
+				inner.bytes = bytes + tail.len();
+				Ok(inner.count)
+			});
+
+		let mut inner = self.inner.lock();
+		inner.compacting = false;
+		inner.last_compaction = Instant::now();
+		match result {
+			Ok(after) => {
+				debug!(target: LOG_TARGET, before, after, "Compacted transaction pool journal")
+			},
+			Err(error) => {
+				warn!(target: LOG_TARGET, ?error, "Failed to compact transaction pool journal")
+			},
+		}
+	}
+}
+
+/// Returns the current time in milliseconds since the unix epoch.
+fn unix_millis() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_millis() as u64)
+		.unwrap_or_default()
+}
+
+/// Reads the entries of the journal at `path`.
+///
+/// A missing journal is treated as empty and a journal of an unknown version is discarded. Reading
+/// stops at the first entry which can't be decoded, e.g. if the node was killed while writing it.
+fn read_entries<H: Decode>(path: &Path) -> io::Result<Vec<JournalEntry<H>>> {
+	read_entries_prefix(path, usize::MAX)
+}
+
+/// Reads the entries contained in the first `len` bytes of the journal at `path`.
+fn read_entries_prefix<H: Decode>(path: &Path, len: usize) -> io::Result<Vec<JournalEntry<H>>> {
+	let mut data = Vec::new();
+	match File::open(path) {
+		Ok(file) => file.take(len as u64).read_to_end(&mut data)?,
+		Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(error) => return Err(error),
+	};
+
+	let mut input = match data.split_first() {
+		Some((&JOURNAL_VERSION, input)) => input,
+		Some((version, _)) => {
+			warn!(
+				target: LOG_TARGET,
+				?version,
+				"Discarding transaction pool journal of unknown version"
+			);
+			return Ok(Vec::new())
+		},
+		None => return Ok(Vec::new()),
+	};
+
+	let mut entries = Vec::new();
+	while !input.is_empty() {
+		match JournalEntry::decode(&mut input) {
+			Ok(entry) => entries.push(entry),
+			Err(error) => {
+				warn!(
+					target: LOG_TARGET,
+					?error,
+					"Ignoring corrupted tail of transaction pool journal"
+				);
+				break
+			},
+		}
+	}
+
+	Ok(entries)
+}
+
+/// Reads the bytes of the file at `path` between the offsets `start` and `end`.
+fn read_range(path: &Path, start: usize, end: usize) -> io::Result<Vec<u8>> {
+	let mut data = Vec::with_capacity(end.saturating_sub(start));
+	let mut file = File::open(path)?;
+	file.seek(SeekFrom::Start(start as u64))?;
+	file.take(end.saturating_sub(start) as u64).read_to_end(&mut data)?;
+	Ok(data)
+}
+
+/// Atomically replaces the journal at `path` with `entries`, returning the size of the new file.
+fn write_entries<H: Encode>(path: &Path, entries: &[JournalEntry<H>]) -> io::Result<usize> {
+	let tmp_path = path.with_extension("tmp");
+	let bytes = create_entries_file(&tmp_path, entries)?;
+	fs::rename(&tmp_path, path)?;
+	Ok(bytes)
+}
+
+/// Writes `entries` to a new file at `path` and syncs it, returning the size of the file.
+fn create_entries_file<H: Encode>(path: &Path, entries: &[JournalEntry<H>]) -> io::Result<usize> {
+	let mut data = vec![JOURNAL_VERSION];
+	for entry in entries {
+		entry.encode_to(&mut data);
+	}
+
+	let mut file = File::create(path)?;
+	file.write_all(&data)?;
+	file.sync_all()?;
+
+	Ok(data.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	fn options(dir: &tempfile::TempDir) -> TransactionJournalOptions {
+		TransactionJournalOptions::new(dir.path().join("txpool").join("journal"))
+	}
+
+	fn record(journal: &TransactionJournal<H256>, id: u8) -> bool {
+		journal.record(H256::repeat_byte(id), TransactionSource::External, vec![id; 4])
+	}
+
+	fn in_pool(ids: &[u8]) -> HashSet<H256> {
+		ids.iter().map(|id| H256::repeat_byte(*id)).collect()
+	}
+
+	fn hashes(entries: Vec<JournalEntry<H256>>) -> Vec<H256> {
+		entries.into_iter().map(|entry| entry.hash).collect()
+	}
+
+	#[test]
+	fn entries_are_loaded_on_open() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<H256>::open(options(&dir)).unwrap();
+		assert_eq!(journal.take_pending(), Some(vec![]));
+		record(&journal, 1);
+		record(&journal, 2);
+		drop(journal);
+
+		let journal = TransactionJournal::<H256>::open(options(&dir)).unwrap();
+		let entries = journal.take_pending().unwrap();
+		assert_eq!(entries[0].source, TransactionSource::External);
+		assert_eq!(entries[0].extrinsic, vec![1; 4]);
+		assert_eq!(hashes(entries), vec![H256::repeat_byte(1), H256::repeat_byte(2)]);
+		assert_eq!(journal.take_pending(), None);
+	}
+
+	#[test]
+	fn outdated_and_corrupted_entries_are_dropped() {
+		let dir = tempfile::tempdir().unwrap();
+		let options = options(&dir);
+		fs::create_dir_all(options.path.parent().unwrap()).unwrap();
+		let entry = |id, submitted_at| JournalEntry {
+			hash: H256::repeat_byte(id),
+			source: TransactionSource::Local,
+			submitted_at,
+			extrinsic: vec![id],
+		};
+		write_entries(&options.path, &[entry(1, 0), entry(2, unix_millis())]).unwrap();
+		// a partially written entry
+		OpenOptions::new()
+			.append(true)
+			.open(&options.path)
+			.unwrap()
+			.write_all(&H256::repeat_byte(3).0)
+			.unwrap();
+
+		let journal = TransactionJournal::<H256>::open(options).unwrap();
+		assert_eq!(hashes(journal.take_pending().unwrap()), vec![H256::repeat_byte(2)]);
+	}
+
+	#[test]
+	fn compaction_keeps_transactions_in_pool() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<H256>::open(options(&dir)).unwrap();
+		journal.take_pending();
+		journal.finish_replay();
+		for id in [1, 2, 3, 2] {
+			record(&journal, id);
+		}
+
+		journal.compact(&in_pool(&[2, 3]));
+		record(&journal, 4);
+		drop(journal);
+
+		let journal = TransactionJournal::<H256>::open(options(&dir)).unwrap();
+		assert_eq!(
+			hashes(journal.take_pending().unwrap()),
+			[2, 3, 4].into_iter().map(H256::repeat_byte).collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn compaction_is_due_past_half_of_the_limits() {
+		let dir = tempfile::tempdir().unwrap();
+		let journal = TransactionJournal::<H256>::open(TransactionJournalOptions {
+			max_count: 4,
+			compaction_interval: Duration::ZERO,
+			..options(&dir)
+		})
+		.unwrap();
+		journal.take_pending();
+
+		// not while the loaded entries are resubmitted
+		assert!(!record(&journal, 1));
+		assert!(!record(&journal, 2));
+		assert!(!record(&journal, 3));
+		assert!(journal.finish_replay());
+
+		// nor while a compaction is in progress
+		assert!(!record(&journal, 4));
+		// the journal is full
+		assert!(!record(&journal, 5));
+		journal.compact(&in_pool(&[2, 4, 5]));
+
+		// two entries are left, the third one is past half of the limit again
+		assert!(record(&journal, 6));
+		journal.compact(&in_pool(&[6]));
+		assert!(!record(&journal, 7));
+		drop(journal);
+
+		let journal = TransactionJournal::<H256>::open(options(&dir)).unwrap();
+		assert_eq!(
+			hashes(journal.take_pending().unwrap()),
+			[6, 7].into_iter().map(H256::repeat_byte).collect::<Vec<_>>()
+		);
+	}
+}
@@ -19,8 +19,10 @@
 //! Common components re-used across different txpool implementations.
 
 pub(crate) mod api;
+pub(crate) mod bundles;
 pub(crate) mod enactment_state;
 pub(crate) mod error;
+pub(crate) mod journal;
 pub(crate) mod metrics;
 pub(crate) mod sliding_stat;
 #[cfg(test)]
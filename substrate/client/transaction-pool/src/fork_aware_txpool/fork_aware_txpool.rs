@@ -30,6 +30,7 @@ use super::{
 use crate::{
 	api::FullChainApi,
 	common::{
+		bundles::{BundleFor, BundleStore},
 		sliding_stat::DurationSlidingStats,
 		tracing_log_xt::{log_xt_debug, log_xt_trace},
 		STAT_SLIDING_WINDOW,
@@ -41,7 +42,7 @@ use crate::{
 	},
 	graph::{
 		self,
-		base_pool::{TimedTransactionSource, Transaction},
+		base_pool::{TimedTransactionSource, Transaction, TransactionSender},
 		BlockHash, ExtrinsicFor, ExtrinsicHash, IsValidator, Options, RawExtrinsicFor,
 	},
 	insert_and_log_throttled, ReadyIteratorFor, ValidateTransactionPriority, LOG_TARGET,
@@ -58,8 +59,9 @@ use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
 	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
-	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionSource,
-	TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	MaintainedTransactionPool, PoolStatus, PoolViewContent, PoolViewStatus, TransactionFor,
+	TransactionPool, TransactionPriority, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
@@ -195,6 +197,9 @@ where
 	/// transactions.
 	included_transactions: Mutex<BTreeMap<HashAndNumber<Block>, Vec<ExtrinsicHash<ChainApi>>>>,
 
+	/// Transaction bundles awaiting all-or-nothing inclusion by the block author.
+	bundles: Arc<BundleStore<ChainApi>>,
+
 	/// Stats for submit call durations
 	submit_stats: DurationSlidingStats,
 
@@ -267,6 +272,7 @@ where
 			Default::default(),
 			mempool_max_transactions_count,
 			ready_limits.total_bytes + future_limits.total_bytes,
+			None,
 		);
 		let mempool = Arc::from(mempool);
 
@@ -280,6 +286,8 @@ where
 			import_notification_sink.clone(),
 		));
 
+		let bundles = Arc::new(BundleStore::new(pool_api.clone()));
+
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
@@ -317,6 +325,7 @@ where
 				finality_timeout_threshold: finality_timeout_threshold
 					.unwrap_or(FINALITY_TIMEOUT_THRESHOLD),
 				included_transactions: Default::default(),
+				bundles,
 				submit_stats: DurationSlidingStats::new(Duration::from_secs(STAT_SLIDING_WINDOW)),
 				submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 					STAT_SLIDING_WINDOW,
@@ -420,6 +429,7 @@ where
 			metrics.clone(),
 			options.total_count(),
 			options.ready.total_bytes + options.future.total_bytes,
+			options.per_sender.clone(),
 		);
 		let mempool = Arc::from(mempool);
 
@@ -433,6 +443,8 @@ where
 			import_notification_sink.clone(),
 		));
 
+		let bundles = Arc::new(BundleStore::new(pool_api.clone()));
+
 		let dropped_monitor_task = Self::dropped_monitor_task(
 			dropped_stream,
 			mempool.clone(),
@@ -474,6 +486,7 @@ where
 			is_validator,
 			finality_timeout_threshold: FINALITY_TIMEOUT_THRESHOLD,
 			included_transactions: Default::default(),
+			bundles,
 			submit_stats: DurationSlidingStats::new(Duration::from_secs(STAT_SLIDING_WINDOW)),
 			submit_and_watch_stats: DurationSlidingStats::new(Duration::from_secs(
 				STAT_SLIDING_WINDOW,
@@ -763,9 +776,11 @@ where
 				Err(e.into())
 			},
 			Ok(mut outcome) => {
-				self.mempool
-					.update_transaction_priority(outcome.hash(), outcome.priority())
-					.await;
+				let (hash, priority, sender) =
+					(outcome.hash(), outcome.priority(), outcome.sender());
+				if !self.update_mempool_transaction(hash, priority, sender).await {
+					return Err(TxPoolApiError::ImmediatelyDropped.into())
+				}
 				Ok(outcome.expect_watcher())
 			},
 		}
@@ -862,8 +877,12 @@ where
 			match r {
 				Ok((hash, submission_result)) => match submission_result {
 					Ok(r) => {
-						mempool.update_transaction_priority(r.hash(), r.priority()).await;
-						final_results.push(Ok(r.hash()));
+						let (hash, priority, sender) = (r.hash(), r.priority(), r.sender());
+						if self.update_mempool_transaction(hash, priority, sender).await {
+							final_results.push(Ok(hash));
+						} else {
+							final_results.push(Err(TxPoolApiError::ImmediatelyDropped.into()));
+						}
 					},
 					Err(e) => {
 						mempool.remove_transactions(&[hash]).await;
@@ -1110,6 +1129,36 @@ where
 		result
 	}
 
+	/// Submits a bundle of transactions which shall be included in a block all together or not
+	/// at all.
+	///
+	/// Bundles are kept aside from the views and are not propagated to other peers.
+	async fn submit_bundle(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		self.bundles.submit(at, source, xts).await
+	}
+
+	fn ready_bundles(&self) -> Vec<Arc<BundleFor<ChainApi>>> {
+		self.bundles.ready()
+	}
+
+	fn remove_bundles(&self, hashes: &[TxHash<Self>]) {
+		self.bundles.remove(hashes)
+	}
+
+	/// Returns the ready and future transactions of every view at the tips of the forks.
+	fn views_content(&self) -> Vec<PoolViewContent<BlockHash<ChainApi>, Self::InPoolTransaction>> {
+		self.view_store.views_content()
+	}
+
+	fn views_status(&self) -> Vec<PoolViewStatus<BlockHash<ChainApi>>> {
+		self.view_store.views_status()
+	}
+
 	/// Returns an iterator for ready transactions at a specific block, ordered by priority.
 	async fn ready_at(&self, at: <Self::Block as BlockT>::Hash) -> ReadyIteratorFor<ChainApi> {
 		let (_, result) = self.ready_at_internal(at);
@@ -1201,6 +1250,11 @@ where
 				self.mempool
 					.clone()
 					.update_transaction_priority_sync(outcome.hash(), outcome.priority());
+				let evicted = self
+					.mempool
+					.clone()
+					.update_transaction_sender_sync(outcome.hash(), outcome.sender());
+				self.remove_evicted_transactions(&evicted);
 				outcome.hash()
 			})
 			.or_else(|_| Ok(insertion.hash))
@@ -1570,8 +1624,11 @@ where
 			.map(|(result, tx_hash)| async move {
 				if let Ok(outcome) = result {
 					Ok(self
-						.mempool
-						.update_transaction_priority(outcome.hash(), outcome.priority())
+						.update_mempool_transaction(
+							outcome.hash(),
+							outcome.priority(),
+							outcome.sender(),
+						)
 						.await)
 				} else {
 					Err(tx_hash)
@@ -1716,6 +1773,8 @@ where
 			self.included_transactions.lock().insert(key.clone(), enacted_log);
 		});
 
+		self.bundles.prune(hash_and_number.number, &pruned_log);
+
 		let unknown_count = self.mempool.count_unknown_transactions(pruned_log.iter()).await;
 		self.metrics
 			.report(|metrics| metrics.unknown_from_block_import_txs.inc_by(unknown_count as _));
@@ -1935,6 +1994,41 @@ where
 		self.post_attempt_transaction_replacement(xt_hash, insertion_info)
 	}
 
+	/// Updates the mempool entry of a transaction successfully submitted to the views.
+	///
+	/// Records the priority and the sender of the transaction, and removes from the views the
+	/// transactions evicted because their sender exceeded the per-sender limits. Returns `false` if
+	/// the submitted transaction itself was evicted.
+	async fn update_mempool_transaction(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		priority: Option<TransactionPriority>,
+		sender: Option<TransactionSender>,
+	) -> bool {
+		self.mempool.update_transaction_priority(tx_hash, priority).await;
+		let evicted = self.mempool.update_transaction_sender(tx_hash, sender).await;
+		self.remove_evicted_transactions(&evicted);
+		!evicted.contains(&tx_hash)
+	}
+
+	/// Removes the transactions evicted from the mempool due to the per-sender limits from the
+	/// views, notifying the listeners.
+	fn remove_evicted_transactions(&self, evicted: &[ExtrinsicHash<ChainApi>]) {
+		for evicted_hash in evicted {
+			trace!(target: LOG_TARGET, tx_hash = ?evicted_hash, "removed: per-sender limit");
+			self.view_store
+				.listener
+				.transaction_dropped(DroppedTransaction::new_enforced_by_limts(*evicted_hash));
+
+			self.view_store.remove_transaction_subtree(
+				*evicted_hash,
+				|listener, removed_tx_hash| {
+					listener.limits_enforced(&removed_tx_hash);
+				},
+			);
+		}
+	}
+
 	fn post_attempt_transaction_replacement(
 		&self,
 		tx_hash: ExtrinsicHash<ChainApi>,
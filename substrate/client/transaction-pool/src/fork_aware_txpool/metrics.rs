@@ -75,6 +75,10 @@ pub struct Metrics {
 	pub submitted_from_mempool_txs: Counter<U64>,
 	/// Total number of transactions found as invalid during mempool revalidation.
 	pub mempool_revalidation_invalid_txs: MempoolInvalidTxReasonCounter,
+	/// Total number of transactions evicted from mempool to make room for better transactions.
+	pub mempool_replaced_txs: Counter<U64>,
+	/// Total number of transactions evicted from mempool because their sender exceeded its limits.
+	pub mempool_sender_limit_evicted_txs: Counter<U64>,
 	/// Total number of transactions found as invalid during view revalidation.
 	pub view_revalidation_invalid_txs: Counter<U64>,
 	/// Total number of valid transactions processed during view revalidation.
@@ -414,6 +418,20 @@ impl MetricsRegistrant for Metrics {
 				registry,
 			)?,
 			mempool_revalidation_invalid_txs: MempoolInvalidTxReasonCounter::register(registry)?,
+			mempool_replaced_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_mempool_replaced_txs_total",
+					"Total number of transactions evicted from mempool to make room for better transactions.",
+				)?,
+				registry,
+			)?,
+			mempool_sender_limit_evicted_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_mempool_sender_limit_evicted_txs_total",
+					"Total number of transactions evicted from mempool because their sender exceeded its limits.",
+				)?,
+				registry,
+			)?,
 			view_revalidation_invalid_txs: register(
 				Counter::new(
 					"substrate_sub_txpool_view_revalidation_invalid_txs_total",
@@ -58,7 +58,11 @@ use tracing::{debug, trace};
 
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
-	graph::{self, base_pool::TimedTransactionSource, ExtrinsicFor, ExtrinsicHash},
+	graph::{
+		self,
+		base_pool::{Limit, SenderKey, TimedTransactionSource, TransactionSender},
+		ExtrinsicFor, ExtrinsicHash,
+	},
 	ValidateTransactionPriority, LOG_TARGET,
 };
 
@@ -132,6 +136,9 @@ where
 	/// Priority of transaction at some block. It is assumed it will not be changed often. None if
 	/// not known.
 	priority: RwLock<Option<TransactionPriority>>,
+	/// Sender of transaction, derived from the tags when the transaction is validated. None if not
+	/// known.
+	sender: RwLock<Option<TransactionSender>>,
 }
 
 impl<ChainApi, Block> TxInMemPool<ChainApi, Block>
@@ -205,6 +212,7 @@ where
 			validated_at: AtomicU64::new(validated_at),
 			bytes,
 			priority: priority.into(),
+			sender: Default::default(),
 		}
 	}
 
@@ -224,6 +232,11 @@ where
 	pub(crate) fn priority(&self) -> Option<TransactionPriority> {
 		*self.priority.read()
 	}
+
+	/// Returns the sender of the transaction.
+	pub(crate) fn sender(&self) -> Option<TransactionSender> {
+		self.sender.read().clone()
+	}
 }
 
 impl<ChainApi, Block> std::fmt::Debug for TxInMemPool<ChainApi, Block>
@@ -239,6 +252,7 @@ where
 			.field("source", &self.source)
 			.field("validated_at", &self.validated_at)
 			.field("priority", &self.priority)
+			.field("sender", &self.sender)
 			.finish()
 	}
 }
@@ -254,6 +268,7 @@ where
 			self.bytes == other.bytes &&
 			self.source == other.source &&
 			*self.priority.read() == *other.priority.read() &&
+			*self.sender.read() == *other.sender.read() &&
 			self.validated_at.load(atomic::Ordering::Relaxed) ==
 				other.validated_at.load(atomic::Ordering::Relaxed)
 	}
@@ -294,6 +309,18 @@ where
 	}
 }
 
+impl<ChainApi, Block> tx_mem_pool_map::Sender for Arc<TxInMemPool<ChainApi, Block>>
+where
+	Block: BlockT,
+	ChainApi: graph::ChainApi<Block = Block> + 'static,
+{
+	type Sender = SenderKey;
+
+	fn sender_key(&self) -> Option<Self::Sender> {
+		self.sender.read().as_ref().map(|sender| sender.key().to_vec())
+	}
+}
+
 impl<ChainApi, Block> tx_mem_pool_map::PriorityAndTimestamp for Arc<TxInMemPool<ChainApi, Block>>
 where
 	Block: BlockT,
@@ -361,6 +388,9 @@ where
 
 	/// Maximal size of encodings of all transactions in the memory pool.
 	max_transactions_total_bytes: usize,
+
+	/// Limits of transactions kept in the memory pool for a single sender, if any.
+	per_sender_limit: Option<Limit>,
 }
 
 /// Helper structure to encapsulate a result of [`TxMemPool::try_insert`].
@@ -394,6 +424,7 @@ where
 		metrics: PrometheusMetrics,
 		max_transactions_count: usize,
 		max_transactions_total_bytes: usize,
+		per_sender_limit: Option<Limit>,
 	) -> (Self, TxMemPoolBlockingTask) {
 		let (sync_channel, rx) = sync_bridge_channel();
 		let task = Self::sync_bridge_task(rx);
@@ -406,6 +437,7 @@ where
 				metrics,
 				max_transactions_count,
 				max_transactions_total_bytes,
+				per_sender_limit,
 			},
 			task.boxed(),
 		)
@@ -428,6 +460,7 @@ where
 			sync_channel,
 			max_transactions_count,
 			max_transactions_total_bytes,
+			per_sender_limit: None,
 		}
 	}
 
@@ -544,7 +577,12 @@ where
 		debug_assert!(!self.is_limit_exceeded(transactions.len(), self.transactions.bytes()));
 		match insertion_result {
 			None => Err(sc_transaction_pool_api::error::Error::ImmediatelyDropped),
-			Some(to_be_removed) => Ok(InsertionInfo::new_with_removed(hash, source, to_be_removed)),
+			Some(to_be_removed) => {
+				self.metrics.report(|metrics| {
+					metrics.mempool_replaced_txs.inc_by(to_be_removed.len() as _)
+				});
+				Ok(InsertionInfo::new_with_removed(hash, source, to_be_removed))
+			},
 		}
 	}
 
@@ -802,6 +840,90 @@ where
 		}
 	}
 
+	/// Updates the sender of transaction stored in mempool and enforces the per-sender limits.
+	///
+	/// The first transaction of a sender doesn't require any tag, it is attributed to the sender of
+	/// the transactions following it. If the sender exceeds the limits, its transactions with the
+	/// highest nonces are removed from the memory pool, so the remaining ones can still be
+	/// included. Returns the hashes of removed transactions, which may include the given one.
+	pub(super) async fn update_transaction_sender(
+		&self,
+		hash: ExtrinsicHash<ChainApi>,
+		sender: Option<TransactionSender>,
+	) -> Vec<ExtrinsicHash<ChainApi>> {
+		let Some(mut sender) = sender else { return Vec::new() };
+
+		let mut transactions = self.transactions.write().await;
+		if !sender.is_known() {
+			let key = sender
+				.candidate_keys()
+				.find(|key| transactions.contains_sender(*key))
+				.map(<[u8]>::to_vec);
+			if let Some(key) = key {
+				sender.attribute(&key);
+			}
+		}
+
+		if transactions
+			.update_item(&hash, |t| *t.sender.write() = Some(sender.clone()))
+			.is_none()
+		{
+			return Vec::new();
+		}
+
+		// the previous transaction, if it's the first one of the sender, is not attributed yet and
+		// is indexed by the tag it provides
+		if let Some(required) = sender.requires().filter(|_| sender.is_known()) {
+			let previous = transactions
+				.sender_items(required)
+				.into_iter()
+				.map(|(hash, _)| hash)
+				.collect::<Vec<_>>();
+			for previous in previous {
+				transactions.update_item(&previous, |t| {
+					if let Some(previous) = t.sender.write().as_mut() {
+						previous.attribute(sender.key());
+					}
+				});
+			}
+		}
+
+		let Some(limit) = self.per_sender_limit.as_ref() else { return Vec::new() };
+
+		let mut sent = transactions
+			.sender_items(sender.key())
+			.into_iter()
+			.map(|(hash, tx)| (hash, tx.bytes, tx.sender().and_then(|sender| sender.nonce())))
+			.collect::<Vec<_>>();
+		let mut count = sent.len();
+		let mut bytes = sent.iter().map(|(_, bytes, _)| bytes).sum::<usize>();
+		if !limit.is_exceeded(count, bytes) {
+			return Vec::new();
+		}
+
+		// highest nonces last
+		sent.sort_by(|(_, _, a), (_, _, b)| a.cmp(b));
+
+		let mut evicted = Vec::new();
+		while limit.is_exceeded(count, bytes) {
+			let Some((hash, tx_bytes, _)) = sent.pop() else { break };
+			transactions.remove(&hash);
+			count -= 1;
+			bytes -= tx_bytes;
+			evicted.push(hash);
+		}
+
+		self.metrics
+			.report(|metrics| metrics.mempool_sender_limit_evicted_txs.inc_by(evicted.len() as _));
+		debug!(
+			target: LOG_TARGET,
+			?hash,
+			evicted_count = evicted.len(),
+			"mempool::update_transaction_sender: per-sender limit exceeded"
+		);
+		evicted
+	}
+
 	/// Counts the number of transactions in the provided iterator of hashes
 	/// that are not known to the pool.
 	pub(super) async fn count_unknown_transactions<'a>(
@@ -845,6 +967,12 @@ where
 		Option<TransactionPriority>,
 		SyncBridgeSender<()>,
 	),
+	UpdateTransactionSender(
+		Arc<TxMemPool<ChainApi, Block>>,
+		ExtrinsicHash<ChainApi>,
+		Option<TransactionSender>,
+		SyncBridgeSender<Vec<ExtrinsicHash<ChainApi>>>,
+	),
 	TryInsertWithReplacement(
 		Arc<TxMemPool<ChainApi, Block>>,
 		ExtrinsicFor<ChainApi>,
@@ -888,6 +1016,15 @@ where
 		(rx, Self::UpdateTransactionPriority(mempool, hash, prio, tx))
 	}
 
+	fn update_transaction_sender(
+		mempool: Arc<TxMemPool<ChainApi, Block>>,
+		hash: ExtrinsicHash<ChainApi>,
+		sender: Option<TransactionSender>,
+	) -> (SyncBridgeReceiver<Vec<ExtrinsicHash<ChainApi>>>, Self) {
+		let (tx, rx) = sync_bridge_channel();
+		(rx, Self::UpdateTransactionSender(mempool, hash, sender, tx))
+	}
+
 	fn try_insert_with_replacement(
 		mempool: Arc<TxMemPool<ChainApi, Block>>,
 		new_tx: ExtrinsicFor<ChainApi>,
@@ -944,6 +1081,12 @@ where
 					debug!(target: LOG_TARGET, ?error, "UpdateTransactionPriority2: sending response failed");
 				}
 			},
+			TxMemPoolSyncRequest::UpdateTransactionSender(mempool, hash, sender, tx) => {
+				let result = mempool.update_transaction_sender(hash, sender).await;
+				if let Err(error) = tx.send(result) {
+					debug!(target: LOG_TARGET, ?error, "UpdateTransactionSender: sending response failed");
+				}
+			},
 			TxMemPoolSyncRequest::TryInsertWithReplacement(
 				mempool,
 				new_tx,
@@ -1016,6 +1159,17 @@ where
 		let _ = self.sync_channel.send(request);
 		response.recv().expect(SYNC_BRIDGE_EXPECT)
 	}
+
+	pub(super) fn update_transaction_sender_sync(
+		self: Arc<Self>,
+		hash: ExtrinsicHash<ChainApi>,
+		sender: Option<TransactionSender>,
+	) -> Vec<ExtrinsicHash<ChainApi>> {
+		let (response, request) =
+			TxMemPoolSyncRequest::update_transaction_sender(self.clone(), hash, sender);
+		let _ = self.sync_channel.send(request);
+		response.recv().expect(SYNC_BRIDGE_EXPECT)
+	}
 }
 
 #[cfg(test)]
@@ -1391,4 +1545,45 @@ mod tx_mem_pool_tests {
 			sc_transaction_pool_api::error::Error::ImmediatelyDropped
 		));
 	}
+
+	#[tokio::test]
+	async fn per_sender_limit_evicts_highest_nonces_of_sender() {
+		sp_tracing::try_init_simple();
+		let api = Arc::from(TestApi::default());
+		let mut mempool = TxMemPool::new_test(api.clone(), usize::MAX, usize::MAX);
+		mempool.per_sender_limit = Some(Limit { count: 3, total_bytes: usize::MAX });
+
+		let xts = (0..5).map(|x| Arc::from(uxt(x as _))).collect::<Vec<_>>();
+		let results = mempool.extend_unwatched(TransactionSource::External, 0, &xts).await;
+		let hashes = results.into_iter().map(|r| r.unwrap().hash).collect::<Vec<_>>();
+
+		let tag = |who: [u8; 32], nonce: u64| codec::Encode::encode(&(who, nonce));
+		let sender = |who, nonce| {
+			let requires = if nonce > 0 { vec![tag(who, nonce - 1)] } else { vec![] };
+			TransactionSender::from_tags(&[tag(who, nonce)], &requires)
+		};
+		let (alice, bob) = ([1u8; 32], [2u8; 32]);
+
+		// unknown sender is not limited
+		assert!(mempool.update_transaction_sender(hashes[0], None).await.is_empty());
+
+		// the first transaction is attributed to alice once her next transaction is known
+		for (hash, nonce) in [(0, 0), (3, 3), (1, 1)] {
+			mempool.update_transaction_priority(hashes[hash], Some(10)).await;
+			let evicted =
+				mempool.update_transaction_sender(hashes[hash], sender(alice, nonce)).await;
+			assert!(evicted.is_empty());
+		}
+		let first = mempool.get_by_hash(hashes[0]).await.unwrap().sender().unwrap();
+		assert_eq!(first.key(), &alice[..]);
+		assert!(mempool.update_transaction_sender(hashes[4], sender(bob, 0)).await.is_empty());
+
+		// the highest nonce of alice is evicted, regardless of the priority
+		mempool.update_transaction_priority(hashes[2], Some(1)).await;
+		let evicted = mempool.update_transaction_sender(hashes[2], sender(alice, 2)).await;
+
+		assert_eq!(evicted, vec![hashes[3]]);
+		assert_eq!(mempool.len(), 4);
+		assert!(mempool.get_by_hash(hashes[3]).await.is_none());
+	}
 }
@@ -20,11 +20,13 @@
 //!
 //! The module includes `SizeTrackedStore`, a map designed for concurrent access, and
 //! `IndexedStorage`, which manages transaction entries by key and priority. Transactions are stored
-//! efficiently with operations to insert items based on priority and manage space utilization. This
-//! module provides core functionality for maintaining the `TxMemPool` state.
+//! efficiently with operations to insert items based on priority and manage space utilization.
+//! Items are also indexed by their sender. This module provides core functionality for maintaining
+//! the `TxMemPool` state.
 
 use std::{
-	collections::{BTreeMap, HashMap},
+	borrow::Borrow,
+	collections::{BTreeMap, HashMap, HashSet},
 	sync::{
 		atomic::{AtomicIsize, Ordering as AtomicOrdering},
 		Arc,
@@ -38,6 +40,16 @@ pub(super) trait Size {
 	fn size(&self) -> usize;
 }
 
+/// Something that can report the sender it was submitted by.
+///
+/// The sender is allowed to change, as long as the item is mutated with
+/// [`SizeTrackedStoreWriteAccess::update_item`].
+pub(super) trait Sender {
+	type Sender: Eq + std::hash::Hash + std::fmt::Debug;
+
+	fn sender_key(&self) -> Option<Self::Sender>;
+}
+
 /// Trait for items with a priority and timestamp ordering.
 ///
 /// `PriorityAndTimestamp` defines methods to access the priority and timestamp,
@@ -84,11 +96,14 @@ struct IndexedStorage<K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	/// HashMap storing transactions by unique key for quick access.
 	items_by_hashes: HashMap<K, V>,
 	/// BTreeMap ordering transactions for prioritized access based on sort key.
 	items_by_priority: BTreeMap<SortKey<S, K>, V>,
+	/// HashMap indexing the keys of transactions by their sender, if known.
+	items_by_sender: HashMap<V::Sender, HashSet<K>>,
 }
 
 /// Core structure for storing and managing transactions in TxMemPool.
@@ -103,6 +118,7 @@ pub struct SizeTrackedStore<K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	/// Internal storage maintaining transaction entries.
 	index: Arc<RwLock<IndexedStorage<K, S, V>>>,
@@ -116,9 +132,14 @@ impl<K, S, V> Default for IndexedStorage<K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	fn default() -> Self {
-		Self { items_by_hashes: Default::default(), items_by_priority: Default::default() }
+		Self {
+			items_by_hashes: Default::default(),
+			items_by_priority: Default::default(),
+			items_by_sender: Default::default(),
+		}
 	}
 }
 
@@ -126,6 +147,7 @@ impl<K, S, V> IndexedStorage<K, S, V>
 where
 	K: Ord + std::hash::Hash,
 	S: Ord,
+	V: Sender,
 {
 	/// Retrieves a reference to the value corresponding to the key, if present.
 	pub fn get(&self, key: &K) -> Option<&V> {
@@ -162,12 +184,60 @@ where
 	}
 }
 
+impl<K, S, V> IndexedStorage<K, S, V>
+where
+	K: Ord + std::hash::Hash + Copy,
+	S: Ord,
+	V: Sender,
+{
+	/// Returns `true` if the map contains items of the given sender.
+	pub fn contains_sender<Q>(&self, sender: &Q) -> bool
+	where
+		V::Sender: Borrow<Q>,
+		Q: std::hash::Hash + Eq + ?Sized,
+	{
+		self.items_by_sender.contains_key(sender)
+	}
+
+	/// Returns the keys and values of the items of the given sender.
+	pub fn sender_items<Q>(&self, sender: &Q) -> Vec<(K, &V)>
+	where
+		V::Sender: Borrow<Q>,
+		Q: std::hash::Hash + Eq + ?Sized,
+	{
+		self.items_by_sender
+			.get(sender)
+			.into_iter()
+			.flatten()
+			.filter_map(|key| self.items_by_hashes.get(key).map(|item| (*key, item)))
+			.collect()
+	}
+
+	/// Adds the key of an item to the sender index.
+	fn index_sender(&mut self, sender: Option<V::Sender>, key: K) {
+		if let Some(sender) = sender {
+			self.items_by_sender.entry(sender).or_default().insert(key);
+		}
+	}
+
+	/// Removes the key of an item from the sender index.
+	fn unindex_sender(&mut self, sender: Option<V::Sender>, key: &K) {
+		let Some(sender) = sender else { return };
+		if let Some(keys) = self.items_by_sender.get_mut(&sender) {
+			keys.remove(key);
+			if keys.is_empty() {
+				self.items_by_sender.remove(&sender);
+			}
+		}
+	}
+}
+
 impl<K, A, B, V> IndexedStorage<K, PriorityKey<A, B>, V>
 where
 	K: Ord + std::hash::Hash + Copy,
 	A: Ord,
 	B: Ord,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B>,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Sender,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Inserts a key-value pair into the map, ordering by priority.
@@ -177,7 +247,9 @@ where
 		if let Some(ref removed) = r {
 			let a = self.items_by_priority.remove(&SortKey::new(&key, removed));
 			debug_assert_eq!(r, a);
+			self.unindex_sender(removed.sender_key(), &key);
 		}
+		self.index_sender(val.sender_key(), key);
 		let a = self.items_by_priority.insert(SortKey::new(&key, &val), val);
 		debug_assert!(a.is_none());
 		r
@@ -190,13 +262,14 @@ where
 			let k = SortKey::new(key, r);
 			let a = self.items_by_priority.remove(&k);
 			debug_assert_eq!(r.clone(), a.expect("item should be in both maps. qed."));
+			self.unindex_sender(r.sender_key(), key);
 		});
 		r
 	}
 
 	/// Allows to mutate item for given key with a closure, if key exists.
 	///
-	/// Intended to mutate priority and timestamp. Changing size is not possible.
+	/// Intended to mutate priority, timestamp and sender. Changing size is not possible.
 	pub fn update_item<F>(&mut self, key: &K, f: F) -> Option<()>
 	where
 		F: FnOnce(&mut V),
@@ -204,14 +277,21 @@ where
 		let item = self.items_by_hashes.get_mut(key)?;
 
 		let old_key = SortKey::new(key, item);
+		let old_sender = item.sender_key();
 		f(item);
 		let new_key = SortKey::new(key, item);
+		let new_sender = item.sender_key();
 
-		if old_key != new_key {
+		if old_key != new_key || old_sender != new_sender {
 			self.items_by_priority.remove(&old_key);
 			self.items_by_priority.insert(new_key, item.clone());
 		}
 
+		if old_sender != new_sender {
+			self.unindex_sender(old_sender, key);
+			self.index_sender(new_sender, *key);
+		}
+
 		Some(())
 	}
 }
@@ -221,7 +301,7 @@ where
 	K: Ord + std::hash::Hash + Copy + std::fmt::Debug,
 	A: Ord + std::fmt::Debug,
 	B: Ord + std::fmt::Debug,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size + Sender,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Attempts to insert an item with replacement based on free space and priority.
@@ -283,6 +363,7 @@ impl<K, S, V> Default for SizeTrackedStore<K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	fn default() -> Self {
 		Self {
@@ -297,6 +378,7 @@ impl<K, S, V> SizeTrackedStore<K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	/// Current tracked length of the content.
 	pub fn len(&self) -> usize {
@@ -327,6 +409,7 @@ pub struct SizeTrackedStoreReadAccess<'a, K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	inner_guard: RwLockReadGuard<'a, IndexedStorage<K, S, V>>,
 }
@@ -335,6 +418,7 @@ impl<K, S, V> SizeTrackedStoreReadAccess<'_, K, S, V>
 where
 	K: Ord + std::hash::Hash,
 	S: Ord,
+	V: Sender,
 {
 	/// Returns true if the map contains given key.
 	pub fn contains_key(&self, key: &K) -> bool {
@@ -368,6 +452,7 @@ pub struct SizeTrackedStoreWriteAccess<'a, K, S, V>
 where
 	K: Ord,
 	S: Ord,
+	V: Sender,
 {
 	bytes: &'a AtomicIsize,
 	length: &'a AtomicIsize,
@@ -379,7 +464,7 @@ where
 	K: Ord + std::hash::Hash + Copy + std::fmt::Debug,
 	A: Ord + std::fmt::Debug,
 	B: Ord + std::fmt::Debug,
-	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size,
+	V: Clone + PriorityAndTimestamp<Priority = A, Timestamp = B> + Size + Sender,
 	V: std::cmp::PartialEq + std::fmt::Debug,
 {
 	/// Insert value and return previous (if any).
@@ -427,19 +512,38 @@ where
 
 	/// Allows to mutate item for given key, if exists.
 	///
-	/// Intended to mutate priority and timestamp. Changing size is not possible.
+	/// Intended to mutate priority, timestamp and sender. Changing size is not possible.
 	pub fn update_item<F>(&mut self, hash: &K, f: F) -> Option<()>
 	where
 		F: FnOnce(&mut V),
 	{
 		self.inner_guard.update_item(hash, f)
 	}
+
+	/// Refer to [`IndexedStorage::contains_sender`].
+	pub fn contains_sender<Q>(&self, sender: &Q) -> bool
+	where
+		V::Sender: Borrow<Q>,
+		Q: std::hash::Hash + Eq + ?Sized,
+	{
+		self.inner_guard.contains_sender(sender)
+	}
+
+	/// Refer to [`IndexedStorage::sender_items`].
+	pub fn sender_items<Q>(&self, sender: &Q) -> Vec<(K, &V)>
+	where
+		V::Sender: Borrow<Q>,
+		Q: std::hash::Hash + Eq + ?Sized,
+	{
+		self.inner_guard.sender_items(sender)
+	}
 }
 
 impl<K, S, V> SizeTrackedStoreWriteAccess<'_, K, S, V>
 where
 	K: Ord + std::hash::Hash,
 	S: Ord,
+	V: Sender,
 {
 	/// Returns `true` if the inner map contains a value for the specified key.
 	pub fn contains_key(&self, key: &K) -> bool {
@@ -468,6 +572,15 @@ mod tests {
 		size: usize,
 		prio: u32,
 		ts: u32,
+		sender: Option<u32>,
+	}
+
+	impl Sender for TestItem {
+		type Sender = u32;
+
+		fn sender_key(&self) -> Option<Self::Sender> {
+			self.sender
+		}
 	}
 
 	impl PriorityAndTimestamp for TestItem {
@@ -490,7 +603,11 @@ mod tests {
 
 	impl TestItem {
 		fn new(prio: u32, ts: u32, size: usize) -> Self {
-			Self { prio, ts, size }
+			Self { prio, ts, size, sender: None }
+		}
+
+		fn with_sender(self, sender: u32) -> Self {
+			Self { sender: Some(sender), ..self }
 		}
 	}
 
@@ -761,4 +878,48 @@ mod tests {
 		assert_eq!(map.write().await.pop_first().unwrap(), i0);
 		assert_eq!(map.write().await.pop_first().unwrap(), i2);
 	}
+
+	#[tokio::test]
+	async fn sender_index_is_maintained() {
+		let map = SizeTrackedStore::default();
+
+		let i0 = TestItem::new(1, 0, 10).with_sender(1);
+		let i1 = TestItem::new(2, 0, 10).with_sender(2);
+		let i2 = TestItem::new(3, 0, 10);
+
+		map.write().await.insert(0xa, i0.clone());
+		map.write().await.insert(0xb, i1.clone());
+		map.write().await.insert(0xc, i2.clone());
+
+		async fn keys(
+			map: &SizeTrackedStore<u32, PriorityKey<u32, u32>, TestItem>,
+			sender: u32,
+		) -> Vec<u32> {
+			let mut keys = map
+				.write()
+				.await
+				.sender_items(&sender)
+				.into_iter()
+				.map(|(key, _)| key)
+				.collect::<Vec<_>>();
+			keys.sort();
+			keys
+		}
+		assert_eq!(keys(&map, 1).await, vec![0xa]);
+		assert_eq!(keys(&map, 2).await, vec![0xb]);
+
+		// the sender of an item is updated
+		map.write().await.update_item(&0xc, |item| item.sender = Some(2));
+		map.write().await.update_item(&0xa, |item| item.sender = Some(2));
+		assert!(!map.write().await.contains_sender(&1));
+		assert_eq!(keys(&map, 2).await, vec![0xa, 0xb, 0xc]);
+
+		// removed and replaced items are dropped from the index
+		map.write().await.remove(&0xb);
+		let i3 = TestItem::new(4, 0, 10).with_sender(3);
+		let removed = map.write().await.try_insert_with_replacement(30, 0xd, i3);
+		assert_eq!(removed, Some(vec![0xa]));
+		assert_eq!(keys(&map, 2).await, vec![0xc]);
+		assert_eq!(keys(&map, 3).await, vec![0xd]);
+	}
 }
@@ -36,7 +36,8 @@ use crate::{
 use itertools::Itertools;
 use parking_lot::RwLock;
 use sc_transaction_pool_api::{
-	error::Error as PoolError, PoolStatus, TransactionTag as Tag, TxInvalidityReportMap,
+	error::Error as PoolError, PoolStatus, PoolViewContent, PoolViewStatus, TransactionTag as Tag,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_runtime::{
@@ -188,11 +189,17 @@ where
 pub(super) type ViewStoreSubmitOutcome<ChainApi> =
 	BaseSubmitOutcome<ChainApi, TxStatusStream<ChainApi>>;
 
+/// Type alias to the transactions of a single view.
+pub(super) type ViewContent<ChainApi> = PoolViewContent<
+	BlockHash<ChainApi>,
+	Transaction<ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>>,
+>;
+
 impl<ChainApi: graph::ChainApi> From<ValidatedPoolSubmitOutcome<ChainApi>>
 	for ViewStoreSubmitOutcome<ChainApi>
 {
 	fn from(value: ValidatedPoolSubmitOutcome<ChainApi>) -> Self {
-		Self::new(value.hash(), value.priority())
+		Self::new(value.hash(), value.priority()).with_sender(value.sender())
 	}
 }
 
@@ -443,6 +450,33 @@ where
 			.unwrap_or_default()
 	}
 
+	/// Returns the ready and future transactions of every active view, ordered by block number.
+	pub(super) fn views_content(&self) -> Vec<ViewContent<ChainApi>> {
+		self.active_views
+			.read()
+			.values()
+			.sorted_by_key(|view| view.at.number)
+			.map(|view| {
+				let validated_pool = view.pool.validated_pool();
+				PoolViewContent {
+					at: Some(view.at.hash),
+					ready: validated_pool.ready().collect(),
+					future: validated_pool.pool.read().futures().cloned().collect(),
+				}
+			})
+			.collect()
+	}
+
+	/// Returns the status of every active view, ordered by block number.
+	pub(super) fn views_status(&self) -> Vec<PoolViewStatus<Block::Hash>> {
+		self.active_views
+			.read()
+			.values()
+			.sorted_by_key(|view| view.at.number)
+			.map(|view| PoolViewStatus { at: Some(view.at.hash), status: view.status() })
+			.collect()
+	}
+
 	/// Returns a list of future transactions in the view at given block hash.
 	pub(super) fn futures_at(
 		&self,
@@ -204,6 +204,109 @@ impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
 	}
 }
 
+/// Opaque key identifying the sender of a transaction.
+pub type SenderKey = Vec<u8>;
+
+/// Minimal length of the key identifying the sender of a transaction.
+const MIN_SENDER_KEY_LEN: usize = 8;
+
+/// The sender of a transaction and its nonce, as derived from the tags of the transaction.
+///
+/// The pool knows nothing about accounts: a transaction tagged with `(sender, nonce)` requires the
+/// tag `(sender, nonce - 1)`, and the longest common prefix of both tags is the encoded sender. The
+/// first transaction of a sender doesn't require any tag, so its sender is only known once it is
+/// [attributed](Self::attribute) to the sender of the transaction requiring its tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionSender {
+	/// The tag provided by the transaction: the encoded sender followed by the encoded nonce.
+	provides: Tag,
+	/// The tag required by the transaction, `None` for the first transaction of the sender.
+	requires: Option<Tag>,
+	/// The length of the encoded sender in `provides`, `None` if not known.
+	sender_len: Option<usize>,
+}
+
+impl TransactionSender {
+	/// Derives the sender of a transaction from the tags it provides and requires.
+	///
+	/// A transaction not requiring any tag and providing a single one is assumed to be the first
+	/// transaction of its sender.
+	pub fn from_tags(provides: &[Tag], requires: &[Tag]) -> Option<Self> {
+		if requires.is_empty() {
+			return match provides {
+				[provided] if provided.len() > MIN_SENDER_KEY_LEN =>
+					Some(Self { provides: provided.clone(), requires: None, sender_len: None }),
+				_ => None,
+			}
+		}
+
+		provides
+			.iter()
+			.flat_map(|provided| {
+				requires.iter().map(move |required| {
+					let len = provided.iter().zip(required).take_while(|(a, b)| a == b).count();
+					(len, provided, required)
+				})
+			})
+			.max_by_key(|(len, _, _)| *len)
+			.filter(|(len, provided, _)| *len >= MIN_SENDER_KEY_LEN && *len < provided.len())
+			.map(|(len, provided, required)| Self {
+				provides: provided.clone(),
+				requires: Some(required.clone()),
+				sender_len: Some(len),
+			})
+	}
+
+	/// Returns the key identifying the sender.
+	///
+	/// Until the sender is known, the provided tag stands for the sender.
+	pub fn key(&self) -> &[u8] {
+		&self.provides[..self.sender_len.unwrap_or(self.provides.len())]
+	}
+
+	/// Returns `true` if the sender of the transaction is known.
+	pub fn is_known(&self) -> bool {
+		self.sender_len.is_some()
+	}
+
+	/// Returns the tag required by the transaction, provided by the previous transaction of the
+	/// sender.
+	pub fn requires(&self) -> Option<&[u8]> {
+		self.requires.as_deref()
+	}
+
+	/// Returns the keys the sender of the transaction may be identified by, longest first.
+	pub fn candidate_keys(&self) -> impl Iterator<Item = &[u8]> {
+		(MIN_SENDER_KEY_LEN..self.provides.len()).rev().map(|len| &self.provides[..len])
+	}
+
+	/// Attributes the transaction to the sender identified by `key`, if it's a prefix of the
+	/// provided tag.
+	pub fn attribute(&mut self, key: &[u8]) {
+		if self.provides.len() > key.len() && self.provides.starts_with(key) {
+			self.sender_len = Some(key.len());
+		}
+	}
+
+	/// Returns the nonce of the transaction, comparable with the nonces of the other transactions
+	/// of the sender, if the sender is known.
+	///
+	/// Nonces are encoded as little-endian fixed width integers, the returned bytes are
+	/// big-endian.
+	pub fn nonce(&self) -> Option<Vec<u8>> {
+		self.sender_len.map(|len| self.provides[len..].iter().rev().copied().collect())
+	}
+}
+
+impl<Hash, Extrinsic> Transaction<Hash, Extrinsic> {
+	/// Returns the sender of the transaction, if it can be determined from the tags.
+	///
+	/// See [`TransactionSender::from_tags`].
+	pub fn sender(&self) -> Option<TransactionSender> {
+		TransactionSender::from_tags(&self.provides, &self.requires)
+	}
+}
+
 impl<Hash, Extrinsic> fmt::Debug for Transaction<Hash, Extrinsic>
 where
 	Hash: fmt::Debug,
@@ -633,6 +736,7 @@ impl Limit {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use codec::Encode;
 
 	type Hash = u64;
 
@@ -1274,4 +1378,43 @@ source: TimedTransactionSource { source: External, timestamp: None }, requires:
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn sender_is_derived_from_nonce_tags() {
+		let tag = |who: [u8; 32], nonce: u32| (who, nonce).encode();
+		let alice = [1u8; 32];
+
+		let tx = Transaction {
+			requires: vec![tag(alice, 255)],
+			provides: vec![tag(alice, 256)],
+			..default_tx()
+		};
+		let sender = tx.sender().unwrap();
+		assert!(sender.is_known());
+		assert_eq!(sender.key(), &alice[..]);
+		assert_eq!(sender.requires(), Some(&tag(alice, 255)[..]));
+		assert_eq!(sender.nonce(), Some(256u32.to_be_bytes().to_vec()));
+
+		// first nonce does not require anything, it's attributed to the sender later on
+		let tx = Transaction { provides: vec![tag(alice, 255)], ..default_tx() };
+		let mut first = tx.sender().unwrap();
+		assert!(!first.is_known());
+		assert_eq!(first.key(), &tag(alice, 255)[..]);
+		assert_eq!(first.nonce(), None);
+		assert!(first.candidate_keys().any(|key| key == &alice[..]));
+
+		first.attribute(&[2u8; 32]);
+		assert!(!first.is_known());
+		first.attribute(&alice);
+		assert_eq!(first.key(), &alice[..]);
+		assert!(first.nonce() < sender.nonce());
+
+		// short common prefix is not considered as a sender
+		let tx = Transaction {
+			requires: vec![vec![1, 2, 3, 4]],
+			provides: vec![vec![1, 2, 3, 5]],
+			..default_tx()
+		};
+		assert_eq!(tx.sender(), None);
+	}
 }
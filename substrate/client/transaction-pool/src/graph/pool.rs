@@ -154,6 +154,10 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Limits of transactions kept in the pool for a single sender.
+	///
+	/// Enforced by the fork-aware pool only, `None` means no limit.
+	pub per_sender: Option<base::Limit>,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
@@ -165,6 +169,7 @@ impl Default for Options {
 		Self {
 			ready: base::Limit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
+			per_sender: None,
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
 		}
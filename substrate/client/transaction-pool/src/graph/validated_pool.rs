@@ -39,7 +39,7 @@ use std::{
 use tracing::{debug, trace, warn, Level};
 
 use super::{
-	base_pool::{self as base, PruneStatus},
+	base_pool::{self as base, PruneStatus, TransactionSender},
 	listener::EventHandler,
 	pool::{
 		BlockHash, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash, Options, TransactionFor,
@@ -125,6 +125,9 @@ pub struct BaseSubmitOutcome<B: ChainApi, W> {
 
 	/// The priority of the transaction. Defaults to None if unknown.
 	priority: Option<TransactionPriority>,
+
+	/// The sender of the transaction. Defaults to None if unknown.
+	sender: Option<TransactionSender>,
 }
 
 /// Type alias to outcome of submission to `ValidatedPool`.
@@ -134,7 +137,13 @@ pub type ValidatedPoolSubmitOutcome<B> =
 impl<B: ChainApi, W> BaseSubmitOutcome<B, W> {
 	/// Creates a new instance with given hash and priority.
 	pub fn new(hash: ExtrinsicHash<B>, priority: Option<TransactionPriority>) -> Self {
-		Self { hash, priority, watcher: None }
+		Self { hash, priority, watcher: None, sender: None }
+	}
+
+	/// Sets the sender of the transaction.
+	pub fn with_sender(mut self, sender: Option<TransactionSender>) -> Self {
+		self.sender = sender;
+		self
 	}
 
 	/// Sets the transaction watcher.
@@ -153,6 +162,11 @@ impl<B: ChainApi, W> BaseSubmitOutcome<B, W> {
 		self.hash
 	}
 
+	/// Provides sender of submitted transaction.
+	pub fn sender(&self) -> Option<TransactionSender> {
+		self.sender.clone()
+	}
+
 	/// Provides a watcher. Should only be called on outcomes of `submit_and_watch`. Otherwise will
 	/// panic (that would mean logical error in program).
 	pub fn expect_watcher(&mut self) -> W {
@@ -333,6 +347,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		match tx {
 			ValidatedTransaction::Valid(tx) => {
 				let priority = tx.priority;
+				let sender = tx.sender();
 				trace!(
 					target: LOG_TARGET,
 					tx_hash = ?tx.hash,
@@ -364,7 +379,8 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 
 				let mut event_dispatcher = self.event_dispatcher.write();
 				fire_events(&mut *event_dispatcher, &imported);
-				Ok(ValidatedPoolSubmitOutcome::new(*imported.hash(), Some(priority)))
+				Ok(ValidatedPoolSubmitOutcome::new(*imported.hash(), Some(priority))
+					.with_sender(sender))
 			},
 			ValidatedTransaction::Invalid(tx_hash, error) => {
 				trace!(
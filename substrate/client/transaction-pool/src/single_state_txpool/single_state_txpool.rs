@@ -25,6 +25,7 @@ pub use crate::{
 };
 use crate::{
 	common::{
+		bundles::{BundleFor, BundleStore},
 		enactment_state::{EnactmentAction, EnactmentState},
 		error,
 		tracing_log_xt::log_xt_trace,
@@ -40,9 +41,10 @@ use futures::{channel::oneshot, future, prelude::*, Future, FutureExt};
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolError, ChainEvent, ImportNotificationStream, MaintainedTransactionPool,
-	PoolStatus, TransactionFor, TransactionPool, TransactionSource, TransactionStatusStreamFor,
-	TxHash, TxInvalidityReportMap,
+	error::Error as TxPoolError, BlockHash, ChainEvent, ImportNotificationStream,
+	MaintainedTransactionPool, PoolStatus, PoolViewContent, PoolViewStatus, TransactionFor,
+	TransactionPool,
+	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
@@ -75,6 +77,7 @@ where
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
 	enactment_state: Arc<Mutex<EnactmentState<Block>>>,
+	bundles: Arc<BundleStore<PoolApi>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -164,6 +167,7 @@ where
 		);
 		(
 			Self {
+				bundles: Arc::new(BundleStore::new(pool_api.clone())),
 				api: pool_api,
 				pool,
 				revalidation_queue: Arc::new(revalidation_queue),
@@ -215,6 +219,7 @@ where
 		}
 
 		Self {
+			bundles: Arc::new(BundleStore::new(pool_api.clone())),
 			api: pool_api,
 			pool,
 			revalidation_queue: Arc::new(revalidation_queue),
@@ -366,6 +371,40 @@ where
 		self.pool.validated_pool().ready_by_hash(hash)
 	}
 
+	async fn submit_bundle(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		self.bundles.submit(at, source, xts).await
+	}
+
+	fn ready_bundles(&self) -> Vec<Arc<BundleFor<PoolApi>>> {
+		self.bundles.ready()
+	}
+
+	fn remove_bundles(&self, hashes: &[TxHash<Self>]) {
+		self.bundles.remove(hashes)
+	}
+
+	/// Returns the content of the pool, which reflects the state at the most recent best block.
+	fn views_content(&self) -> Vec<PoolViewContent<BlockHash<Self>, Self::InPoolTransaction>> {
+		vec![PoolViewContent {
+			at: Some(self.enactment_state.lock().recent_best_block()),
+			ready: self.ready().collect(),
+			future: self.futures(),
+		}]
+	}
+
+	/// Returns the status of the pool, which reflects the state at the most recent best block.
+	fn views_status(&self) -> Vec<PoolViewStatus<BlockHash<Self>>> {
+		vec![PoolViewStatus {
+			at: Some(self.enactment_state.lock().recent_best_block()),
+			status: self.status(),
+		}]
+	}
+
 	async fn ready_at(&self, at: <Self::Block as BlockT>::Hash) -> ReadyIteratorFor<PoolApi> {
 		let Ok(at) = self.api.resolve_block_number(at) else {
 			return Box::new(std::iter::empty()) as Box<_>
@@ -696,6 +735,8 @@ where
 		self.metrics
 			.report(|metrics| metrics.block_transactions_pruned.inc_by(pruned_log.len() as u64));
 
+		self.bundles.prune(hash_and_number.number, &pruned_log);
+
 		if next_action.resubmit {
 			let mut resubmit_transactions = Vec::new();
 
@@ -18,26 +18,85 @@
 
 //! Transaction pool wrapper. Provides a type for wrapping object providing actual implementation of
 //! transaction pool.
+//!
+//! The wrapper also maintains the optional journal of submitted transactions, which is shared by
+//! all the implementations. Resubmitting the journaled transactions and compacting the journal are
+//! done by an essential blocking task of the journal, so neither delays the submission or
+//! maintenance of transactions.
 
 use crate::{
 	builder::FullClientTransactionPool,
+	common::journal::{JournalEntry, TransactionJournal},
 	graph::{base_pool::Transaction, ExtrinsicFor, ExtrinsicHash},
-	ChainApi, FullChainApi, ReadyIteratorFor,
+	ChainApi, FullChainApi, ReadyIteratorFor, LOG_TARGET,
 };
 use async_trait::async_trait;
+use codec::{DecodeAll, Encode};
+use futures::future::{Future, FutureExt};
 use sc_transaction_pool_api::{
-	ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
-	MaintainedTransactionPool, PoolStatus, ReadyTransactions, TransactionFor, TransactionPool,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	BlockHash, ChainEvent, ImportNotificationStream, LocalTransactionFor, LocalTransactionPool,
+	MaintainedTransactionPool, PoolStatus, PoolViewContent, PoolViewStatus, ReadyTransactions,
+	TransactionBundle, TransactionFor, TransactionPool, TransactionSource,
+	TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
 };
+use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::traits::Block as BlockT;
-use std::{collections::HashMap, pin::Pin, sync::Arc};
+use std::{
+	collections::{HashMap, HashSet},
+	pin::Pin,
+	sync::Arc,
+};
+use tokio::sync::mpsc;
+use tracing::{info, trace};
+
+/// The maximal number of journaled transactions resubmitted to the pool at once.
+const REPLAY_BATCH_SIZE: usize = 256;
+
+/// A task run by the journal task, e.g. the compaction of the journal.
+type JournalTask = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// The journal of submitted transactions, with the sender of the tasks run on its behalf.
+pub(crate) struct Journal<Hash> {
+	inner: Arc<TransactionJournal<Hash>>,
+	tasks: mpsc::UnboundedSender<JournalTask>,
+}
+
+impl<Hash: Send + Sync + 'static> Journal<Hash> {
+	/// Spawns the essential blocking task running the tasks of the journal.
+	///
+	/// A single long running task is spawned, as essential tasks take the node down when they end.
+	pub(crate) fn spawn(
+		journal: TransactionJournal<Hash>,
+		spawner: &impl SpawnEssentialNamed,
+	) -> Self {
+		let (tasks, mut receiver) = mpsc::unbounded_channel::<JournalTask>();
+		spawner.spawn_essential_blocking(
+			"txpool-journal",
+			Some("transaction-pool"),
+			async move {
+				while let Some(task) = receiver.recv().await {
+					task.await;
+				}
+			}
+			.boxed(),
+		);
+		Journal { inner: Arc::new(journal), tasks }
+	}
+
+	/// Queues the given task on the journal task.
+	fn run(&self, task: impl Future<Output = ()> + Send + 'static) {
+		if self.tasks.send(task.boxed()).is_err() {
+			trace!(target: LOG_TARGET, "The journal task has ended, skipping journal task");
+		}
+	}
+}
 
 /// The wrapper for actual object providing implementation of TransactionPool.
 ///
 /// This wraps actual implementation of the TransactionPool, e.g. fork-aware or single-state.
 pub struct TransactionPoolWrapper<Block, Client>(
-	pub Box<dyn FullClientTransactionPool<Block, Client>>,
+	pub Arc<dyn FullClientTransactionPool<Block, Client>>,
+	pub(crate) Option<Journal<Block::Hash>>,
 )
 where
 	Block: BlockT,
@@ -49,6 +108,109 @@ where
 		+ 'static,
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>;
 
+impl<Block, Client> TransactionPoolWrapper<Block, Client>
+where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	/// Encodes a transaction for the journal, if the journal is enabled.
+	fn encode_for_journal(&self, xt: &Block::Extrinsic) -> Option<Vec<u8>> {
+		self.1.is_some().then(|| xt.encode())
+	}
+
+	/// Records a successfully submitted transaction in the journal, if enabled.
+	fn journal(&self, hash: Block::Hash, source: TransactionSource, encoded: Option<Vec<u8>>) {
+		// Transactions of retracted blocks are resubmitted by the pool itself.
+		if source == TransactionSource::InBlock {
+			return
+		}
+		if let (Some(journal), Some(encoded)) = (&self.1, encoded) {
+			if journal.inner.record(hash, source, encoded) {
+				let (pool, inner) = (self.0.clone(), journal.inner.clone());
+				journal.run(async move { compact_journal(&*pool, &inner) });
+			}
+		}
+	}
+}
+
+/// Compacts the journal, keeping the transactions which are still in the pool.
+fn compact_journal<Block, Client>(
+	pool: &dyn FullClientTransactionPool<Block, Client>,
+	journal: &TransactionJournal<Block::Hash>,
+) where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	let in_pool = pool
+		.ready()
+		.map(|tx| tx.hash)
+		.chain(pool.futures().into_iter().map(|tx| tx.hash))
+		.collect::<HashSet<_>>();
+	journal.compact(&in_pool);
+}
+
+/// Resubmits the transactions loaded from the journal at the given block.
+///
+/// Consecutive transactions from the same source are submitted in batches of at most
+/// [`REPLAY_BATCH_SIZE`] transactions. The journal may be compacted afterwards.
+async fn replay_journal<Block, Client>(
+	pool: Arc<dyn FullClientTransactionPool<Block, Client>>,
+	journal: Arc<TransactionJournal<Block::Hash>>,
+	at: Block::Hash,
+	entries: Vec<JournalEntry<Block::Hash>>,
+) where
+	Block: BlockT,
+	Client: sp_api::ProvideRuntimeApi<Block>
+		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::blockchain::HeaderBackend<Block>
+		+ sp_runtime::traits::BlockIdTo<Block>
+		+ sp_blockchain::HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ 'static,
+	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
+{
+	let total = entries.len();
+	let mut batches: Vec<(TransactionSource, Vec<Block::Extrinsic>)> = Vec::new();
+	for entry in entries {
+		let Ok(xt) = Block::Extrinsic::decode_all(&mut &entry.extrinsic[..]) else {
+			trace!(
+				target: LOG_TARGET,
+				tx_hash = ?entry.hash,
+				"Skipping undecodable journal entry"
+			);
+			continue
+		};
+		match batches.last_mut() {
+			Some((source, xts)) if *source == entry.source && xts.len() < REPLAY_BATCH_SIZE =>
+				xts.push(xt),
+			_ => batches.push((entry.source, vec![xt])),
+		}
+	}
+
+	let mut imported = 0;
+	for (source, xts) in batches {
+		if let Ok(results) = pool.submit_at(at, source, xts).await {
+			imported += results.iter().filter(|result| result.is_ok()).count();
+		}
+	}
+	info!(target: LOG_TARGET, ?at, imported, total, "Resubmitted journaled transactions");
+
+	if journal.finish_replay() {
+		compact_journal(&*pool, &journal);
+	}
+}
+
 #[async_trait]
 impl<Block, Client> TransactionPool for TransactionPoolWrapper<Block, Client>
 where
@@ -75,7 +237,14 @@ where
 		source: TransactionSource,
 		xts: Vec<TransactionFor<Self>>,
 	) -> Result<Vec<Result<TxHash<Self>, Self::Error>>, Self::Error> {
-		self.0.submit_at(at, source, xts).await
+		let encoded = xts.iter().map(|xt| self.encode_for_journal(xt)).collect::<Vec<_>>();
+		let results = self.0.submit_at(at, source, xts).await?;
+		for (encoded, result) in encoded.into_iter().zip(&results) {
+			if let Ok(hash) = result {
+				self.journal(*hash, source, encoded);
+			}
+		}
+		Ok(results)
 	}
 
 	async fn submit_one(
@@ -84,7 +253,10 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<TxHash<Self>, Self::Error> {
-		self.0.submit_one(at, source, xt).await
+		let encoded = self.encode_for_journal(&xt);
+		let hash = self.0.submit_one(at, source, xt).await?;
+		self.journal(hash, source, encoded);
+		Ok(hash)
 	}
 
 	async fn submit_and_watch(
@@ -93,7 +265,11 @@ where
 		source: TransactionSource,
 		xt: TransactionFor<Self>,
 	) -> Result<Pin<Box<TransactionStatusStreamFor<Self>>>, Self::Error> {
-		self.0.submit_and_watch(at, source, xt).await
+		let hash = self.0.hash_of(&xt);
+		let encoded = self.encode_for_journal(&xt);
+		let watcher = self.0.submit_and_watch(at, source, xt).await?;
+		self.journal(hash, source, encoded);
+		Ok(watcher)
 	}
 
 	async fn ready_at(
@@ -139,6 +315,31 @@ where
 		self.0.ready_transaction(hash)
 	}
 
+	async fn submit_bundle(
+		&self,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		xts: Vec<TransactionFor<Self>>,
+	) -> Result<TxHash<Self>, Self::Error> {
+		self.0.submit_bundle(at, source, xts).await
+	}
+
+	fn ready_bundles(&self) -> Vec<Arc<TransactionBundle<TxHash<Self>, TransactionFor<Self>>>> {
+		self.0.ready_bundles()
+	}
+
+	fn remove_bundles(&self, hashes: &[TxHash<Self>]) {
+		self.0.remove_bundles(hashes)
+	}
+
+	fn views_content(&self) -> Vec<PoolViewContent<BlockHash<Self>, Self::InPoolTransaction>> {
+		self.0.views_content()
+	}
+
+	fn views_status(&self) -> Vec<PoolViewStatus<BlockHash<Self>>> {
+		self.0.views_status()
+	}
+
 	async fn ready_at_with_timeout(
 		&self,
 		at: <Self::Block as BlockT>::Hash,
@@ -161,7 +362,13 @@ where
 	Client::Api: sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>,
 {
 	async fn maintain(&self, event: ChainEvent<Self::Block>) {
+		let at = event.hash();
 		self.0.maintain(event).await;
+
+		let Some(journal) = &self.1 else { return };
+		if let Some(entries) = journal.inner.take_pending() {
+			journal.run(replay_journal(self.0.clone(), journal.inner.clone(), at, entries));
+		}
 	}
 }
 
@@ -185,6 +392,9 @@ where
 		at: <Self::Block as BlockT>::Hash,
 		xt: LocalTransactionFor<Self>,
 	) -> Result<Self::Hash, Self::Error> {
-		self.0.submit_local(at, xt)
+		let encoded = self.encode_for_journal(&xt);
+		let hash = self.0.submit_local(at, xt)?;
+		self.journal(hash, TransactionSource::Local, encoded);
+		Ok(hash)
 	}
 }
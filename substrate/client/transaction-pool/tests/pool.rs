@@ -1059,6 +1059,39 @@ fn import_notification_to_pool_maintain_works() {
 	assert_eq!(pool.status().ready, 0);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn journaled_transactions_are_resubmitted_on_restart() {
+	let journal_dir = tempfile::tempdir().unwrap();
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let best_hash = client.info().best_hash;
+	let build_pool = || {
+		Builder::new(sp_core::testing::TaskExecutor::new(), client.clone(), true.into())
+			.with_options(TransactionPoolOptions::default().with_journal(
+				TransactionJournalOptions::new(journal_dir.path().join("journal")),
+			))
+			.build()
+	};
+
+	let pool = build_pool();
+	pool.submit_one(best_hash, SOURCE, uxt(Alice, 0)).await.expect("1. Imported");
+	pool.submit_one(best_hash, SOURCE, uxt(Alice, 1)).await.expect("2. Imported");
+	assert_eq!(pool.status().ready, 2);
+	drop(pool);
+
+	// The journaled transactions are resubmitted in the background after the first maintenance.
+	let pool = build_pool();
+	assert_eq!(pool.status().ready, 0);
+	pool.maintain(ChainEvent::NewBestBlock { hash: best_hash, tree_route: None }).await;
+	tokio::time::timeout(std::time::Duration::from_secs(10), async {
+		while pool.status().ready < 2 {
+			tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("journaled transactions are resubmitted");
+	assert_eq!(pool.status().future, 0);
+}
+
 // When we prune transactions, we need to make sure that we remove
 #[test]
 fn pruning_a_transaction_should_remove_it_from_best_transaction() {
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @dev The on-chain address of the conviction voting precompile.
+address constant CONVICTION_VOTING_PRECOMPILE_ADDRESS = address(0xB0000);
+
+/// @title Conviction Voting Precompile Interface
+/// @notice An interface for voting on referenda through `pallet_conviction_voting`.
+/// It forwards calls directly to the corresponding dispatchable functions, using the
+/// calling contract or account as the voter.
+/// @dev Convictions are given as the index of the `Conviction` enum: `0` means 0.1x votes and
+/// no lock, `1` to `6` mean 1x to 6x votes with an increasing lock period.
+interface IConvictionVoting {
+    /// @notice Vote for or against a referendum with a given conviction.
+    /// @param referendumIndex The index of the referendum to vote on.
+    /// @param aye Whether to vote in favour of the referendum.
+    /// @param conviction The conviction of the vote.
+    /// @param balance The balance to vote with.
+    function voteStandard(uint32 referendumIndex, bool aye, uint8 conviction, uint128 balance) external;
+
+    /// @notice Split a vote between ayes and nays, without conviction.
+    /// @param referendumIndex The index of the referendum to vote on.
+    /// @param aye The balance voting in favour of the referendum.
+    /// @param nay The balance voting against the referendum.
+    function voteSplit(uint32 referendumIndex, uint128 aye, uint128 nay) external;
+
+    /// @notice Split a vote between ayes, nays and abstentions, without conviction.
+    /// @param referendumIndex The index of the referendum to vote on.
+    /// @param aye The balance voting in favour of the referendum.
+    /// @param nay The balance voting against the referendum.
+    /// @param abstain The balance abstaining.
+    function voteSplitAbstain(uint32 referendumIndex, uint128 aye, uint128 nay, uint128 abstain) external;
+
+    /// @notice Delegate the voting power of the caller on a track to another account.
+    /// @param trackId The track to delegate the votes of.
+    /// @param to The account to delegate to.
+    /// @param conviction The conviction of the delegated votes.
+    /// @param balance The balance to delegate.
+    function delegate(uint16 trackId, address to, uint8 conviction, uint128 balance) external;
+
+    /// @notice Undelegate the voting power of the caller on a track.
+    /// @param trackId The track to undelegate the votes of.
+    function undelegate(uint16 trackId) external;
+
+    /// @notice Remove a vote of the caller from a referendum.
+    /// @param trackId The track of the referendum.
+    /// @param referendumIndex The index of the referendum.
+    function removeVote(uint16 trackId, uint32 referendumIndex) external;
+
+    /// @notice Remove the expired locks of an account on a track.
+    /// @param trackId The track to unlock the balance of.
+    /// @param target The account to unlock.
+    function unlock(uint16 trackId, address target) external;
+}
@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, RawOrigin},
+	sp_runtime::traits::StaticLookup,
+	traits::Get,
+};
+use pallet_conviction_voting::{
+	AccountVote, BalanceOf, ClassOf, Config, Conviction, PollIndexOf, Vote, WeightInfo,
+};
+use pallet_revive::{
+	frame_system,
+	precompiles::{alloy, AddressMapper, AddressMatcher, Error, Ext, Precompile, H160},
+	DispatchInfo, ExecOrigin as Origin, Weight,
+};
+
+alloy::sol!("src/interface/IConvictionVoting.sol");
+use IConvictionVoting::IConvictionVotingCalls;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+const ERR_CONVERSION_FAILED: &str = "Conversion failed";
+const ERR_INVALID_CONVICTION: &str = "Invalid conviction";
+
+/// A precompile that lets contracts vote and delegate in `pallet-conviction-voting`.
+///
+/// Calls are dispatched with the calling contract (or account) as the signed origin.
+pub struct ConvictionVotingPrecompile<T, I = ()>(PhantomData<(T, I)>);
+
+impl<Runtime, Instance: 'static> Precompile for ConvictionVotingPrecompile<Runtime, Instance>
+where
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	PollIndexOf<Runtime, Instance>: TryFrom<u32>,
+	ClassOf<Runtime, Instance>: TryFrom<u16>,
+	BalanceOf<Runtime, Instance>: TryFrom<u128>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(11).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = IConvictionVotingCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		// All the functions of the interface change state.
+		if env.is_read_only() {
+			return Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into()));
+		}
+
+		let frame_origin: <Runtime as frame_system::Config>::RuntimeOrigin = match env.caller() {
+			Origin::Root => RawOrigin::Root.into(),
+			Origin::Signed(account_id) => RawOrigin::Signed(account_id).into(),
+		};
+
+		match input {
+			IConvictionVotingCalls::voteStandard(call) => {
+				let vote = AccountVote::Standard {
+					vote: Vote { aye: call.aye, conviction: Self::to_conviction(call.conviction)? },
+					balance: Self::convert(call.balance)?,
+				};
+				Self::vote(env, frame_origin, call.referendumIndex, vote)
+			},
+			IConvictionVotingCalls::voteSplit(call) => {
+				let vote = AccountVote::Split {
+					aye: Self::convert(call.aye)?,
+					nay: Self::convert(call.nay)?,
+				};
+				Self::vote(env, frame_origin, call.referendumIndex, vote)
+			},
+			IConvictionVotingCalls::voteSplitAbstain(call) => {
+				let vote = AccountVote::SplitAbstain {
+					aye: Self::convert(call.aye)?,
+					nay: Self::convert(call.nay)?,
+					abstain: Self::convert(call.abstain)?,
+				};
+				Self::vote(env, frame_origin, call.referendumIndex, vote)
+			},
+			IConvictionVotingCalls::delegate(call) => {
+				let class = Self::convert(call.trackId)?;
+				let to = Self::to_lookup_source(call.to);
+				let conviction = Self::to_conviction(call.conviction)?;
+				let balance = Self::convert(call.balance)?;
+				let max_votes = <Runtime as Config<Instance>>::MaxVotes::get();
+				Self::dispatch_with_post_info(
+					env,
+					<Runtime as Config<Instance>>::WeightInfo::delegate(max_votes),
+					|| {
+						pallet_conviction_voting::Pallet::<Runtime, Instance>::delegate(
+							frame_origin,
+							class,
+							to,
+							conviction,
+							balance,
+						)
+					},
+				)
+			},
+			IConvictionVotingCalls::undelegate(call) => {
+				let class = Self::convert(call.trackId)?;
+				let max_votes = <Runtime as Config<Instance>>::MaxVotes::get();
+				Self::dispatch_with_post_info(
+					env,
+					<Runtime as Config<Instance>>::WeightInfo::undelegate(max_votes),
+					|| {
+						pallet_conviction_voting::Pallet::<Runtime, Instance>::undelegate(
+							frame_origin,
+							class,
+						)
+					},
+				)
+			},
+			IConvictionVotingCalls::removeVote(call) => {
+				env.charge(<Runtime as Config<Instance>>::WeightInfo::remove_vote())?;
+				pallet_conviction_voting::Pallet::<Runtime, Instance>::remove_vote(
+					frame_origin,
+					Some(Self::convert(call.trackId)?),
+					Self::convert(call.referendumIndex)?,
+				)?;
+				Ok(Vec::new())
+			},
+			IConvictionVotingCalls::unlock(call) => {
+				env.charge(<Runtime as Config<Instance>>::WeightInfo::unlock())?;
+				pallet_conviction_voting::Pallet::<Runtime, Instance>::unlock(
+					frame_origin,
+					Self::convert(call.trackId)?,
+					Self::to_lookup_source(call.target),
+				)?;
+				Ok(Vec::new())
+			},
+		}
+	}
+}
+
+impl<Runtime, Instance: 'static> ConvictionVotingPrecompile<Runtime, Instance>
+where
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	PollIndexOf<Runtime, Instance>: TryFrom<u32>,
+	ClassOf<Runtime, Instance>: TryFrom<u16>,
+	BalanceOf<Runtime, Instance>: TryFrom<u128>,
+{
+	/// Convert an ABI value into the corresponding type of the pallet.
+	fn convert<A, B: TryFrom<A>>(value: A) -> Result<B, Error> {
+		value.try_into().map_err(|_| Error::Revert(ERR_CONVERSION_FAILED.into()))
+	}
+
+	/// Convert the index of a conviction into a [`Conviction`].
+	fn to_conviction(conviction: u8) -> Result<Conviction, Error> {
+		conviction.try_into().map_err(|_| Error::Revert(ERR_INVALID_CONVICTION.into()))
+	}
+
+	/// Convert an Ethereum address into the lookup source of the runtime.
+	fn to_lookup_source(
+		address: alloy::primitives::Address,
+	) -> <<Runtime as frame_system::Config>::Lookup as StaticLookup>::Source {
+		let address = H160::from(address.into_array());
+		let account_id = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&address);
+		<Runtime as frame_system::Config>::Lookup::unlookup(account_id)
+	}
+
+	/// Execute the vote call.
+	fn vote(
+		env: &mut impl Ext<T = Runtime>,
+		origin: <Runtime as frame_system::Config>::RuntimeOrigin,
+		referendum_index: u32,
+		vote: AccountVote<BalanceOf<Runtime, Instance>>,
+	) -> Result<Vec<u8>, Error> {
+		let weight = <Runtime as Config<Instance>>::WeightInfo::vote_new()
+			.max(<Runtime as Config<Instance>>::WeightInfo::vote_existing());
+		env.charge(weight)?;
+
+		pallet_conviction_voting::Pallet::<Runtime, Instance>::vote(
+			origin,
+			Self::convert(referendum_index)?,
+			vote,
+		)?;
+		Ok(Vec::new())
+	}
+
+	/// Charge `weight` up front, dispatch `call` and refund the weight it didn't use.
+	fn dispatch_with_post_info(
+		env: &mut impl Ext<T = Runtime>,
+		weight: Weight,
+		call: impl FnOnce() -> DispatchResultWithPostInfo,
+	) -> Result<Vec<u8>, Error> {
+		let charged_amount = env.charge(weight)?;
+		let result = call();
+
+		let pre = DispatchInfo {
+			call_weight: weight,
+			extension_weight: Weight::zero(),
+			..Default::default()
+		};
+		let actual_weight = frame_support::dispatch::extract_actual_weight(&result, &pre);
+		env.adjust_gas(charged_amount, actual_weight);
+
+		result.map(|_| Vec::new()).map_err(|error| Error::Error(error.error.into()))
+	}
+}
@@ -0,0 +1,185 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests mock for `pallet-conviction-voting-precompiles`.
+
+pub use super::*;
+use alloc::collections::BTreeMap;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, PollStatus, Polling, TotalIssuanceOf},
+};
+use pallet_conviction_voting::{Tally, TallyOf};
+use sp_runtime::{BuildStorage, DispatchError};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+	#[runtime::runtime]
+	#[runtime::derive(
+		RuntimeCall,
+		RuntimeEvent,
+		RuntimeError,
+		RuntimeOrigin,
+		RuntimeTask,
+		RuntimeHoldReason,
+		RuntimeFreezeReason
+	)]
+	pub struct Test;
+
+	#[runtime::pallet_index(0)]
+	pub type System = frame_system;
+	#[runtime::pallet_index(10)]
+	pub type Balances = pallet_balances;
+	#[runtime::pallet_index(20)]
+	pub type Voting = pallet_conviction_voting;
+	#[runtime::pallet_index(21)]
+	pub type Revive = pallet_revive;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+/// The state of a poll of [`TestPolls`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, u8),
+	Completed(u64, bool),
+}
+
+parameter_types! {
+	pub static Polls: BTreeMap<u8, TestPollState> = [
+		(1, TestPollState::Ongoing(Tally::from_parts(0, 0, 0), 0)),
+		(2, TestPollState::Completed(1, true)),
+	].into_iter().collect();
+}
+
+/// A [`Polling`] implementation backed by [`Polls`].
+pub struct TestPolls;
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Votes = u64;
+	type Moment = u64;
+	type Class = u8;
+
+	fn classes() -> Vec<u8> {
+		vec![0, 1]
+	}
+
+	fn as_ongoing(index: u8) -> Option<(TallyOf<Test>, Self::Class)> {
+		match Polls::get().remove(&index) {
+			Some(TestPollState::Ongoing(tally, class)) => Some((tally, class)),
+			_ => None,
+		}
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> R,
+	) -> R {
+		let mut polls = Polls::get();
+		let r = match polls.get_mut(&index) {
+			Some(TestPollState::Ongoing(tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(TestPollState::Completed(when, succeeded)) =>
+				f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		};
+		Polls::set(polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = Polls::get();
+		let r = match polls.get_mut(&index) {
+			Some(TestPollState::Ongoing(tally, class)) => f(PollStatus::Ongoing(tally, *class)),
+			Some(TestPollState::Completed(when, succeeded)) =>
+				f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		}?;
+		Polls::set(polls);
+		Ok(r)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		let mut polls = Polls::get();
+		let i = polls.keys().next_back().map_or(0, |x| x + 1);
+		polls.insert(i, TestPollState::Ongoing(Tally::new(0), class));
+		Polls::set(polls);
+		Ok(i)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		let mut polls = Polls::get();
+		match polls.get(&index) {
+			Some(TestPollState::Ongoing(..)) => {},
+			_ => return Err(()),
+		}
+		polls.insert(index, TestPollState::Completed(System::block_number(), approved));
+		Polls::set(polls);
+		Ok(())
+	}
+}
+
+impl pallet_conviction_voting::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type VoteLockingPeriod = ConstU64<3>;
+	type MaxVotes = ConstU32<3>;
+	type WeightInfo = ();
+	type MaxTurnout = TotalIssuanceOf<Balances, Self::AccountId>;
+	type Polls = TestPolls;
+	type BlockNumberProvider = System;
+	type VotingHooks = ();
+}
+
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Test {
+	type AddressMapper = pallet_revive::TestAccountMapper<Self>;
+	type Balance = u64;
+	type Currency = Balances;
+	type Precompiles = (ConvictionVotingPrecompile<Self>,);
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 200)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+	});
+
+	ext
+}
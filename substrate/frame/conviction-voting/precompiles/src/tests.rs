@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+	alloy::sol_types::SolInterface,
+	mock::{new_test_ext, Balances, RuntimeOrigin, Test, TestPolls},
+};
+use frame_support::traits::Polling;
+use pallet_conviction_voting::{Delegating, Tally, TallyOf, Voting, VotingFor};
+use pallet_revive::{precompiles::TransactionLimits, ExecConfig, ExecReturnValue};
+use sp_runtime::DispatchError;
+
+const PRECOMPILE_ADDR: [u8; 20] =
+	<ConvictionVotingPrecompile<Test> as Precompile>::MATCHER.base_address();
+
+fn address(who: u64) -> alloy::primitives::Address {
+	<Test as pallet_revive::Config>::AddressMapper::to_address(&who).0.into()
+}
+
+fn call_result(from: u64, input: IConvictionVotingCalls) -> Result<ExecReturnValue, DispatchError> {
+	pallet_revive::Pallet::<Test>::bare_call(
+		RuntimeOrigin::signed(from),
+		PRECOMPILE_ADDR.into(),
+		0u32.into(),
+		TransactionLimits::WeightAndDeposit { weight_limit: Weight::MAX, deposit_limit: u64::MAX },
+		input.abi_encode(),
+		ExecConfig::new_substrate_tx(),
+	)
+	.result
+}
+
+/// Calls the precompile, asserting that the call succeeded.
+fn call(from: u64, input: IConvictionVotingCalls) {
+	let output = call_result(from, input).unwrap();
+	assert!(!output.did_revert());
+}
+
+fn tally(index: u8) -> TallyOf<Test> {
+	<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("No poll").0
+}
+
+fn vote_standard(aye: bool, conviction: u8, balance: u128) -> IConvictionVotingCalls {
+	IConvictionVotingCalls::voteStandard(IConvictionVoting::voteStandardCall {
+		referendumIndex: 1,
+		aye,
+		conviction,
+		balance,
+	})
+}
+
+#[test]
+fn vote_works() {
+	new_test_ext().execute_with(|| {
+		call(1, vote_standard(true, 2, 5));
+		assert_eq!(tally(1), Tally::from_parts(10, 0, 5));
+
+		// a new vote replaces the previous one
+		call(1, vote_standard(false, 1, 10));
+		assert_eq!(tally(1), Tally::from_parts(0, 10, 0));
+
+		call(
+			1,
+			IConvictionVotingCalls::voteSplit(IConvictionVoting::voteSplitCall {
+				referendumIndex: 1,
+				aye: 10,
+				nay: 20,
+			}),
+		);
+		assert_eq!(tally(1), Tally::from_parts(1, 2, 10));
+
+		call(
+			2,
+			IConvictionVotingCalls::voteSplitAbstain(IConvictionVoting::voteSplitAbstainCall {
+				referendumIndex: 1,
+				aye: 10,
+				nay: 20,
+				abstain: 30,
+			}),
+		);
+		assert_eq!(tally(1), Tally::from_parts(2, 4, 50));
+
+		// the balance is locked until the vote is removed and unlocked
+		assert_eq!(Balances::usable_balance(1), 70);
+	});
+}
+
+#[test]
+fn vote_rejects_invalid_input() {
+	new_test_ext().execute_with(|| {
+		// there are only 7 convictions
+		assert!(call_result(1, vote_standard(true, 7, 5)).unwrap().did_revert());
+
+		// poll indices of the mock don't fit into a `u8`
+		let output = call_result(
+			1,
+			IConvictionVotingCalls::voteSplit(IConvictionVoting::voteSplitCall {
+				referendumIndex: 256,
+				aye: 10,
+				nay: 20,
+			}),
+		);
+		assert!(output.unwrap().did_revert());
+
+		// completed polls can't be voted on
+		let output = call_result(
+			1,
+			IConvictionVotingCalls::voteSplit(IConvictionVoting::voteSplitCall {
+				referendumIndex: 2,
+				aye: 10,
+				nay: 20,
+			}),
+		);
+		assert_eq!(output, Err(pallet_conviction_voting::Error::<Test>::NotOngoing.into()));
+		assert_eq!(tally(1), Tally::from_parts(0, 0, 0));
+	});
+}
+
+#[test]
+fn remove_vote_and_unlock_work() {
+	new_test_ext().execute_with(|| {
+		call(1, vote_standard(true, 0, 50));
+		assert_eq!(tally(1), Tally::from_parts(5, 0, 50));
+
+		call(
+			1,
+			IConvictionVotingCalls::removeVote(IConvictionVoting::removeVoteCall {
+				trackId: 0,
+				referendumIndex: 1,
+			}),
+		);
+		assert_eq!(tally(1), Tally::from_parts(0, 0, 0));
+		assert_eq!(Balances::usable_balance(1), 50);
+
+		// anyone can unlock the balance of another account
+		call(
+			2,
+			IConvictionVotingCalls::unlock(IConvictionVoting::unlockCall {
+				trackId: 0,
+				target: address(1),
+			}),
+		);
+		assert_eq!(Balances::usable_balance(1), 100);
+	});
+}
+
+#[test]
+fn delegate_and_undelegate_work() {
+	new_test_ext().execute_with(|| {
+		call(
+			1,
+			IConvictionVotingCalls::delegate(IConvictionVoting::delegateCall {
+				trackId: 0,
+				to: address(2),
+				conviction: 1,
+				balance: 20,
+			}),
+		);
+		assert!(matches!(
+			VotingFor::<Test>::get(1, 0),
+			Voting::Delegating(Delegating { target: 2, balance: 20, .. })
+		));
+
+		// the delegated votes count towards the vote of the target
+		call(2, vote_standard(true, 0, 10));
+		assert_eq!(tally(1), Tally::from_parts(21, 0, 30));
+
+		call(
+			1,
+			IConvictionVotingCalls::undelegate(IConvictionVoting::undelegateCall { trackId: 0 }),
+		);
+		assert!(matches!(VotingFor::<Test>::get(1, 0), Voting::Casting(_)));
+		assert_eq!(tally(1), Tally::from_parts(1, 0, 10));
+
+		// there is no delegation left to remove
+		let output = call_result(
+			1,
+			IConvictionVotingCalls::undelegate(IConvictionVoting::undelegateCall { trackId: 0 }),
+		);
+		assert_eq!(output, Err(pallet_conviction_voting::Error::<Test>::NotDelegating.into()));
+	});
+}
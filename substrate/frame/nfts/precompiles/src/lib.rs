@@ -0,0 +1,959 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use core::marker::PhantomData;
+use ethereum_standards::{
+	IERC1155Receiver, IERC721Receiver, IERC1155,
+	IERC1155::{IERC1155Calls, IERC1155Events},
+	IERC721,
+	IERC721::{IERC721Calls, IERC721Events},
+};
+use frame_support::{
+	sp_runtime::{
+		traits::{BlockNumberProvider, StaticLookup},
+		DispatchError,
+	},
+	traits::{
+		nonfungibles_v2::{Inspect, InspectEnumerable},
+		Get,
+	},
+};
+use frame_system::RawOrigin;
+use pallet_nfts::{weights::WeightInfo, Config, Item};
+use pallet_revive::precompiles::{
+	alloy::{
+		self,
+		primitives::IntoLogData,
+		sol_types::{Revert, SolCall},
+	},
+	AddressMapper, AddressMatcher, CallResources, Error, Ext, Precompile, ReentrancyProtection,
+	RuntimeCosts, H160, H256, U256,
+};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Mean of extracting the collection id from the precompile address.
+pub trait CollectionIdExtractor {
+	type CollectionId;
+	/// Extracts the collection id from the address.
+	fn collection_id_from_address(address: &[u8; 20]) -> Result<Self::CollectionId, Error>;
+}
+
+/// The configuration of a pallet-nfts precompile.
+pub trait NftsPrecompileConfig {
+	/// The Address matcher used by the precompile.
+	const MATCHER: AddressMatcher;
+
+	/// The [`CollectionIdExtractor`] used by the precompile.
+	type CollectionIdExtractor: CollectionIdExtractor;
+}
+
+/// The collection id extracted by the [`CollectionIdExtractor`] of a precompile configuration.
+pub type CollectionIdOf<C> =
+	<<C as NftsPrecompileConfig>::CollectionIdExtractor as CollectionIdExtractor>::CollectionId;
+
+/// A `CollectionIdExtractor` that stores the collection id directly inside the address.
+pub struct InlineCollectionIdExtractor;
+
+impl CollectionIdExtractor for InlineCollectionIdExtractor {
+	type CollectionId = u32;
+	fn collection_id_from_address(addr: &[u8; 20]) -> Result<Self::CollectionId, Error> {
+		let bytes: [u8; 4] = addr[0..4].try_into().expect("slice is 4 bytes; qed");
+		Ok(u32::from_be_bytes(bytes))
+	}
+}
+
+/// A precompile configuration that uses a prefix [`AddressMatcher`].
+pub struct InlineIdConfig<const PREFIX: u16>;
+
+impl<const P: u16> NftsPrecompileConfig for InlineIdConfig<P> {
+	const MATCHER: AddressMatcher = AddressMatcher::Prefix(core::num::NonZero::new(P).unwrap());
+	type CollectionIdExtractor = InlineCollectionIdExtractor;
+}
+
+/// The interface ids reported by the ERC721 precompile: ERC-165, ERC-721 and ERC-721 metadata.
+const ERC721_INTERFACES: [[u8; 4]; 3] =
+	[[0x01, 0xff, 0xc9, 0xa7], [0x80, 0xac, 0x58, 0xcd], [0x5b, 0x5e, 0x13, 0x9f]];
+
+/// The interface ids reported by the ERC1155 precompile: ERC-165, ERC-1155 and ERC-1155 metadata
+/// URI.
+const ERC1155_INTERFACES: [[u8; 4]; 3] =
+	[[0x01, 0xff, 0xc9, 0xa7], [0xd9, 0xb6, 0x7a, 0x26], [0x0e, 0x89, 0x34, 0x1c]];
+
+/// The collection attribute reported as `name`, the collection metadata being used if it is not
+/// set.
+const NAME_ATTRIBUTE: &[u8] = b"name";
+
+/// The collection attribute reported as `symbol`.
+const SYMBOL_ATTRIBUTE: &[u8] = b"symbol";
+
+const ERR_INVALID_CALLER: &str = "Invalid caller";
+const ERR_INVALID_TOKEN_ID: &str = "Invalid token id";
+const ERR_UNKNOWN_TOKEN: &str = "Unknown token";
+const ERR_INVALID_RECEIVER: &str = "Invalid receiver";
+const ERR_NOT_OWNER: &str = "Not the token owner";
+const ERR_INVALID_AMOUNT: &str = "Invalid amount";
+const ERR_LENGTH_MISMATCH: &str = "Length mismatch";
+
+/// An ERC721 precompile exposing a collection of `pallet-nfts`.
+///
+/// Transfers and item approvals go through the transfer approvals of `pallet-nfts`, so only the
+/// owner of an item can approve it. `setApprovalForAll` approves the operator for every item the
+/// caller owns in the collection at the time of the call, and `isApprovedForAll` is true when the
+/// operator is approved for all of them. Approving an item with `approve` replaces these
+/// approvals.
+///
+/// `name` and `symbol` report the `name` and `symbol` attributes of the collection, `name`
+/// falling back to the collection metadata, and `tokenURI` reports the item metadata.
+pub struct ERC721<Runtime, PrecompileConfig, Instance = ()> {
+	_phantom: PhantomData<(Runtime, PrecompileConfig, Instance)>,
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> Precompile
+	for ERC721<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	CollectionIdOf<PrecompileConfig>: Into<<Runtime as Config<Instance>>::CollectionId>,
+	alloy::primitives::U256: TryInto<<Runtime as Config<Instance>>::ItemId>,
+{
+	type T = Runtime;
+	type Interface = IERC721::IERC721Calls;
+	const MATCHER: AddressMatcher = PrecompileConfig::MATCHER;
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(
+		address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let collection =
+			PrecompileConfig::CollectionIdExtractor::collection_id_from_address(address)?.into();
+
+		match input {
+			IERC721Calls::safeTransferFrom_0(_) |
+			IERC721Calls::safeTransferFrom_1(_) |
+			IERC721Calls::transferFrom(_) |
+			IERC721Calls::approve(_) |
+			IERC721Calls::setApprovalForAll(_)
+				if env.is_read_only() =>
+				Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into())),
+
+			IERC721Calls::supportsInterface(call) => Self::supports_interface(call, env),
+			IERC721Calls::balanceOf(call) => Self::balance_of(collection, call, env),
+			IERC721Calls::ownerOf(call) => Self::owner_of(collection, call, env),
+			IERC721Calls::safeTransferFrom_0(call) => Self::transfer_from(
+				collection,
+				call.from,
+				call.to,
+				call.tokenId,
+				Some(&call.data[..]),
+				env,
+			),
+			IERC721Calls::safeTransferFrom_1(call) => Self::transfer_from(
+				collection,
+				call.from,
+				call.to,
+				call.tokenId,
+				Some(&[][..]),
+				env,
+			),
+			IERC721Calls::transferFrom(call) =>
+				Self::transfer_from(collection, call.from, call.to, call.tokenId, None, env),
+			IERC721Calls::approve(call) => Self::approve(collection, call, env),
+			IERC721Calls::setApprovalForAll(call) =>
+				Self::set_approval_for_all(collection, call, env),
+			IERC721Calls::getApproved(call) => Self::get_approved(collection, call, env),
+			IERC721Calls::isApprovedForAll(call) =>
+				Self::is_approved_for_all(collection, call, env),
+			IERC721Calls::name(_) => Self::name(collection, env),
+			IERC721Calls::symbol(_) => Self::symbol(collection, env),
+			IERC721Calls::tokenURI(call) => Self::token_uri(collection, call, env),
+		}
+	}
+}
+
+/// An ERC1155 precompile exposing a collection of `pallet-nfts`.
+///
+/// Every item is a token type with a supply of one, so balances are either zero or one and
+/// transfers move a value of one. Approvals for all behave as for the [`ERC721`] precompile, and
+/// `uri` reports the item metadata.
+pub struct ERC1155<Runtime, PrecompileConfig, Instance = ()> {
+	_phantom: PhantomData<(Runtime, PrecompileConfig, Instance)>,
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> Precompile
+	for ERC1155<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	CollectionIdOf<PrecompileConfig>: Into<<Runtime as Config<Instance>>::CollectionId>,
+	alloy::primitives::U256: TryInto<<Runtime as Config<Instance>>::ItemId>,
+{
+	type T = Runtime;
+	type Interface = IERC1155::IERC1155Calls;
+	const MATCHER: AddressMatcher = PrecompileConfig::MATCHER;
+	const HAS_CONTRACT_INFO: bool = false;
+
+	fn call(
+		address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		let collection =
+			PrecompileConfig::CollectionIdExtractor::collection_id_from_address(address)?.into();
+
+		match input {
+			IERC1155Calls::safeTransferFrom(_) |
+			IERC1155Calls::safeBatchTransferFrom(_) |
+			IERC1155Calls::setApprovalForAll(_)
+				if env.is_read_only() =>
+				Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into())),
+
+			IERC1155Calls::supportsInterface(call) => Self::supports_interface(call, env),
+			IERC1155Calls::balanceOf(call) => Self::balance_of(collection, call, env),
+			IERC1155Calls::balanceOfBatch(call) => Self::balance_of_batch(collection, call, env),
+			IERC1155Calls::safeTransferFrom(call) =>
+				Self::safe_transfer_from(collection, call, env),
+			IERC1155Calls::safeBatchTransferFrom(call) =>
+				Self::safe_batch_transfer_from(collection, call, env),
+			IERC1155Calls::setApprovalForAll(call) =>
+				Self::set_approval_for_all(collection, call, env),
+			IERC1155Calls::isApprovedForAll(call) =>
+				Self::is_approved_for_all(collection, call, env),
+			IERC1155Calls::uri(call) => Self::uri(collection, call, env),
+		}
+	}
+}
+
+/// Functions shared by the precompiles of a collection.
+struct Helper<Runtime, Instance> {
+	_phantom: PhantomData<(Runtime, Instance)>,
+}
+
+impl<Runtime, Instance: 'static> Helper<Runtime, Instance>
+where
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	alloy::primitives::U256: TryInto<<Runtime as Config<Instance>>::ItemId>,
+{
+	/// Get the caller as an `H160` address.
+	fn caller(env: &mut impl Ext<T = Runtime>) -> Result<H160, Error> {
+		env.caller()
+			.account_id()
+			.map(<Runtime as pallet_revive::Config>::AddressMapper::to_address)
+			.map_err(|_| Error::Revert(Revert { reason: ERR_INVALID_CALLER.into() }))
+	}
+
+	/// Convert an Ethereum address to an account id.
+	fn to_account_id(address: alloy::primitives::Address) -> Runtime::AccountId {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(
+			&address.into_array().into(),
+		)
+	}
+
+	/// Convert an account id to an Ethereum address.
+	fn to_address(account: &Runtime::AccountId) -> alloy::primitives::Address {
+		<Runtime as pallet_revive::Config>::AddressMapper::to_address(account).0.into()
+	}
+
+	/// Convert a token id to the item type of the pallet.
+	fn to_item(
+		token_id: alloy::primitives::U256,
+	) -> Result<<Runtime as Config<Instance>>::ItemId, Error> {
+		token_id
+			.try_into()
+			.map_err(|_| Error::Revert(Revert { reason: ERR_INVALID_TOKEN_ID.into() }))
+	}
+
+	/// Get the details of an item, reverting if it doesn't exist.
+	fn item_details(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		item: &<Runtime as Config<Instance>>::ItemId,
+	) -> Result<pallet_nfts::ItemDetailsFor<Runtime, Instance>, Error> {
+		Item::<Runtime, Instance>::get(collection, item)
+			.ok_or_else(|| Error::Revert(Revert { reason: ERR_UNKNOWN_TOKEN.into() }))
+	}
+
+	/// Whether an approval with the given deadline didn't expire yet.
+	fn is_active(
+		deadline: &Option<pallet_nfts::BlockNumberFor<Runtime, Instance>>,
+		now: pallet_nfts::BlockNumberFor<Runtime, Instance>,
+	) -> bool {
+		deadline.is_none_or(|deadline| now <= deadline)
+	}
+
+	/// Charge the weight of `reads` storage reads.
+	fn charge_reads(env: &mut impl Ext<T = Runtime>, reads: u64) -> Result<(), Error> {
+		env.charge(<Runtime as frame_system::Config>::DbWeight::get().reads(reads))?;
+		Ok(())
+	}
+
+	/// Deposit an event to the runtime.
+	fn deposit_event(
+		env: &mut impl Ext<T = Runtime>,
+		event: impl IntoLogData,
+	) -> Result<(), Error> {
+		let (topics, data) = event.into_log_data().split();
+		let topics = topics.into_iter().map(|v| H256(v.0)).collect::<Vec<_>>();
+		env.frame_meter_mut().charge_weight_token(RuntimeCosts::DepositEvent {
+			num_topic: topics.len() as u32,
+			len: topics.len() as u32,
+		})?;
+		env.deposit_event(topics, data.to_vec());
+		Ok(())
+	}
+
+	/// Transfer an item owned by `from` to `to` on behalf of `spender`.
+	///
+	/// The transfer is dispatched as the `transfer` call of `pallet-nfts` signed by `spender`, so
+	/// `spender` must be the owner of the item or be approved to transfer it.
+	fn transfer(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		item: <Runtime as Config<Instance>>::ItemId,
+		from: &Runtime::AccountId,
+		spender: Runtime::AccountId,
+		to: Runtime::AccountId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<(), Error> {
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::transfer())?;
+		Self::charge_reads(env, 1)?;
+		if Self::item_details(&collection, &item)?.owner != *from {
+			return Err(
+				DispatchError::from(pallet_nfts::Error::<Runtime, Instance>::WrongOwner).into()
+			);
+		}
+
+		pallet_nfts::Pallet::<Runtime, Instance>::transfer(
+			RawOrigin::Signed(spender).into(),
+			collection,
+			item,
+			<Runtime as frame_system::Config>::Lookup::unlookup(to),
+		)?;
+		Ok(())
+	}
+
+	/// Approve or disapprove `operator` for every item of `owner` in the collection.
+	///
+	/// The approvals are set and cancelled through the calls of `pallet-nfts` signed by `owner`.
+	fn set_approval_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		owner: Runtime::AccountId,
+		operator: Runtime::AccountId,
+		approved: bool,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<(), Error> {
+		Self::charge_reads(env, 1)?;
+		let mut items = Vec::new();
+		for item in
+			pallet_nfts::Pallet::<Runtime, Instance>::owned_in_collection(&collection, &owner)
+		{
+			Self::charge_reads(env, 1)?;
+			items.push(item);
+		}
+
+		let delegate = <Runtime as frame_system::Config>::Lookup::unlookup(operator.clone());
+		for item in items {
+			if approved {
+				env.charge(<Runtime as Config<Instance>>::WeightInfo::approve_transfer())?;
+				pallet_nfts::Pallet::<Runtime, Instance>::approve_transfer(
+					RawOrigin::Signed(owner.clone()).into(),
+					collection,
+					item,
+					delegate.clone(),
+					None,
+				)?;
+			} else {
+				Self::charge_reads(env, 1)?;
+				if Self::item_details(&collection, &item)?.approvals.contains_key(&operator) {
+					env.charge(<Runtime as Config<Instance>>::WeightInfo::cancel_approval())?;
+					pallet_nfts::Pallet::<Runtime, Instance>::cancel_approval(
+						RawOrigin::Signed(owner.clone()).into(),
+						collection,
+						item,
+						delegate.clone(),
+					)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Whether `operator` is approved for every item of `owner` in the collection, `owner` owning
+	/// at least one item.
+	fn is_approved_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		owner: &Runtime::AccountId,
+		operator: &Runtime::AccountId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<bool, Error> {
+		Self::charge_reads(env, 2)?;
+		let now = <Runtime as Config<Instance>>::BlockNumberProvider::current_block_number();
+		let mut approved = false;
+		for item in
+			pallet_nfts::Pallet::<Runtime, Instance>::owned_in_collection(&collection, owner)
+		{
+			Self::charge_reads(env, 2)?;
+			match Self::item_details(&collection, &item)?.approvals.get(operator) {
+				Some(deadline) if Self::is_active(deadline, now) => approved = true,
+				_ => return Ok(false),
+			}
+		}
+		Ok(approved)
+	}
+
+	/// Get an attribute of the collection set by its owner, as a string.
+	fn collection_attribute(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		key: &[u8],
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Option<String>, Error> {
+		Self::charge_reads(env, 1)?;
+		Ok(pallet_nfts::Pallet::<Runtime, Instance>::collection_attribute(collection, key)
+			.map(|value| String::from_utf8_lossy(&value).into_owned()))
+	}
+
+	/// Get the metadata of an item as a string, empty if it is not set.
+	fn item_metadata(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		item: &<Runtime as Config<Instance>>::ItemId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<String, Error> {
+		Self::charge_reads(env, 1)?;
+		Ok(pallet_nfts::ItemMetadataOf::<Runtime, Instance>::get(collection, item)
+			.map(|metadata| String::from_utf8_lossy(&metadata.data).into_owned())
+			.unwrap_or_default())
+	}
+
+	/// Call the receiver hook with the given `input` if `to` is a contract, checking that it
+	/// returns the `selector` of the hook.
+	fn check_receiver(
+		to: alloy::primitives::Address,
+		input: Vec<u8>,
+		selector: [u8; 4],
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<(), Error> {
+		let to: H160 = to.into_array().into();
+		if env.code_size(&to) == 0 {
+			return Ok(());
+		}
+
+		let result = env.call(
+			&CallResources::NoLimits,
+			&to,
+			U256::zero(),
+			input,
+			ReentrancyProtection::AllowReentry,
+			false,
+		);
+
+		let output = env.last_frame_output();
+		if result.is_err() || output.did_revert() || output.data.get(..4) != Some(&selector[..]) {
+			return Err(Error::Revert(Revert { reason: ERR_INVALID_RECEIVER.into() }));
+		}
+		Ok(())
+	}
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> ERC721<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	CollectionIdOf<PrecompileConfig>: Into<<Runtime as Config<Instance>>::CollectionId>,
+	alloy::primitives::U256: TryInto<<Runtime as Config<Instance>>::ItemId>,
+{
+	/// Execute the supports_interface call.
+	fn supports_interface(
+		call: &IERC721::supportsInterfaceCall,
+		_env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let supported = ERC721_INTERFACES.contains(&call.interfaceId.0);
+		Ok(IERC721::supportsInterfaceCall::abi_encode_returns(&supported))
+	}
+
+	/// Execute the balance_of call.
+	///
+	/// The items of the owner are counted one by one, each of them being charged as a read.
+	fn balance_of(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::balanceOfCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let owner = Helper::<Runtime, Instance>::to_account_id(call.owner);
+		let mut balance = 0u64;
+		Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+		for _ in pallet_nfts::Pallet::<Runtime, Instance>::owned_in_collection(&collection, &owner)
+		{
+			Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+			balance = balance.saturating_add(1);
+		}
+		Ok(IERC721::balanceOfCall::abi_encode_returns(&alloy::primitives::U256::from(balance)))
+	}
+
+	/// Execute the owner_of call.
+	fn owner_of(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::ownerOfCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+		let item = Helper::<Runtime, Instance>::to_item(call.tokenId)?;
+		let details = Helper::<Runtime, Instance>::item_details(&collection, &item)?;
+		let owner = Helper::<Runtime, Instance>::to_address(&details.owner);
+		Ok(IERC721::ownerOfCall::abi_encode_returns(&owner))
+	}
+
+	/// Execute the transfer_from and safe_transfer_from calls.
+	///
+	/// For safe transfers, `data` is passed to the `onERC721Received` hook of contract recipients.
+	fn transfer_from(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		from: alloy::primitives::Address,
+		to: alloy::primitives::Address,
+		token_id: alloy::primitives::U256,
+		data: Option<&[u8]>,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		if to.is_zero() {
+			return Err(Error::Revert(Revert { reason: ERR_INVALID_RECEIVER.into() }));
+		}
+
+		let operator = Helper::<Runtime, Instance>::caller(env)?;
+		Helper::<Runtime, Instance>::transfer(
+			collection,
+			Helper::<Runtime, Instance>::to_item(token_id)?,
+			&Helper::<Runtime, Instance>::to_account_id(from),
+			<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&operator),
+			Helper::<Runtime, Instance>::to_account_id(to),
+			env,
+		)?;
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC721Events::Transfer(IERC721::Transfer { from, to, tokenId: token_id }),
+		)?;
+
+		if let Some(data) = data {
+			let input = IERC721Receiver::onERC721ReceivedCall {
+				operator: operator.0.into(),
+				from,
+				tokenId: token_id,
+				data: data.to_vec().into(),
+			}
+			.abi_encode();
+			Helper::<Runtime, Instance>::check_receiver(
+				to,
+				input,
+				IERC721Receiver::onERC721ReceivedCall::SELECTOR,
+				env,
+			)?;
+		}
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the approve call.
+	///
+	/// An item has a single approved account, so all previous approvals of the item are cleared.
+	/// Approving the zero address only clears the approvals.
+	fn approve(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::approveCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::clear_all_transfer_approvals())?;
+		env.charge(<Runtime as Config<Instance>>::WeightInfo::approve_transfer())?;
+		Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+
+		let caller = Helper::<Runtime, Instance>::caller(env)?;
+		let caller = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&caller);
+		let item = Helper::<Runtime, Instance>::to_item(call.tokenId)?;
+		let details = Helper::<Runtime, Instance>::item_details(&collection, &item)?;
+		if details.owner != caller {
+			return Err(Error::Revert(Revert { reason: ERR_NOT_OWNER.into() }));
+		}
+
+		pallet_nfts::Pallet::<Runtime, Instance>::clear_all_transfer_approvals(
+			RawOrigin::Signed(caller.clone()).into(),
+			collection,
+			item,
+		)?;
+		if !call.to.is_zero() {
+			pallet_nfts::Pallet::<Runtime, Instance>::approve_transfer(
+				RawOrigin::Signed(caller).into(),
+				collection,
+				item,
+				<Runtime as frame_system::Config>::Lookup::unlookup(
+					Helper::<Runtime, Instance>::to_account_id(call.to),
+				),
+				None,
+			)?;
+		}
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC721Events::Approval(IERC721::Approval {
+				owner: Helper::<Runtime, Instance>::to_address(&details.owner),
+				approved: call.to,
+				tokenId: call.tokenId,
+			}),
+		)?;
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the set_approval_for_all call.
+	fn set_approval_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::setApprovalForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let owner = Helper::<Runtime, Instance>::caller(env)?;
+		Helper::<Runtime, Instance>::set_approval_for_all(
+			collection,
+			<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&owner),
+			Helper::<Runtime, Instance>::to_account_id(call.operator),
+			call.approved,
+			env,
+		)?;
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC721Events::ApprovalForAll(IERC721::ApprovalForAll {
+				owner: owner.0.into(),
+				operator: call.operator,
+				approved: call.approved,
+			}),
+		)?;
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the get_approved call.
+	///
+	/// Returns the first approved account whose approval didn't expire yet.
+	fn get_approved(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::getApprovedCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		Helper::<Runtime, Instance>::charge_reads(env, 2)?;
+		let item = Helper::<Runtime, Instance>::to_item(call.tokenId)?;
+		let details = Helper::<Runtime, Instance>::item_details(&collection, &item)?;
+		let now = <Runtime as Config<Instance>>::BlockNumberProvider::current_block_number();
+		let approved = details
+			.approvals
+			.iter()
+			.find(|(_, deadline)| Helper::<Runtime, Instance>::is_active(deadline, now))
+			.map(|(account, _)| Helper::<Runtime, Instance>::to_address(account))
+			.unwrap_or_default();
+		Ok(IERC721::getApprovedCall::abi_encode_returns(&approved))
+	}
+
+	/// Execute the is_approved_for_all call.
+	fn is_approved_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::isApprovedForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let approved = Helper::<Runtime, Instance>::is_approved_for_all(
+			collection,
+			&Helper::<Runtime, Instance>::to_account_id(call.owner),
+			&Helper::<Runtime, Instance>::to_account_id(call.operator),
+			env,
+		)?;
+		Ok(IERC721::isApprovedForAllCall::abi_encode_returns(&approved))
+	}
+
+	/// Execute the name call.
+	fn name(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let name = match Helper::<Runtime, Instance>::collection_attribute(
+			&collection,
+			NAME_ATTRIBUTE,
+			env,
+		)? {
+			Some(name) => name,
+			// The empty key is the collection metadata.
+			None => Helper::<Runtime, Instance>::collection_attribute(&collection, &[], env)?
+				.unwrap_or_default(),
+		};
+		Ok(IERC721::nameCall::abi_encode_returns(&name))
+	}
+
+	/// Execute the symbol call.
+	fn symbol(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let symbol =
+			Helper::<Runtime, Instance>::collection_attribute(&collection, SYMBOL_ATTRIBUTE, env)?
+				.unwrap_or_default();
+		Ok(IERC721::symbolCall::abi_encode_returns(&symbol))
+	}
+
+	/// Execute the token_uri call.
+	fn token_uri(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC721::tokenURICall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+		let item = Helper::<Runtime, Instance>::to_item(call.tokenId)?;
+		Helper::<Runtime, Instance>::item_details(&collection, &item)?;
+		let uri = Helper::<Runtime, Instance>::item_metadata(&collection, &item, env)?;
+		Ok(IERC721::tokenURICall::abi_encode_returns(&uri))
+	}
+}
+
+impl<Runtime, PrecompileConfig, Instance: 'static> ERC1155<Runtime, PrecompileConfig, Instance>
+where
+	PrecompileConfig: NftsPrecompileConfig,
+	Runtime: crate::Config<Instance> + pallet_revive::Config,
+	CollectionIdOf<PrecompileConfig>: Into<<Runtime as Config<Instance>>::CollectionId>,
+	alloy::primitives::U256: TryInto<<Runtime as Config<Instance>>::ItemId>,
+{
+	/// Execute the supports_interface call.
+	fn supports_interface(
+		call: &IERC1155::supportsInterfaceCall,
+		_env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let supported = ERC1155_INTERFACES.contains(&call.interfaceId.0);
+		Ok(IERC1155::supportsInterfaceCall::abi_encode_returns(&supported))
+	}
+
+	/// Get the balance of `account` for the token type `id`, one if it owns the item and zero
+	/// otherwise.
+	fn balance(
+		collection: &<Runtime as Config<Instance>>::CollectionId,
+		account: alloy::primitives::Address,
+		id: alloy::primitives::U256,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<alloy::primitives::U256, Error> {
+		Helper::<Runtime, Instance>::charge_reads(env, 1)?;
+		let account = Helper::<Runtime, Instance>::to_account_id(account);
+		let owned = Helper::<Runtime, Instance>::to_item(id)
+			.ok()
+			.and_then(|item| Item::<Runtime, Instance>::get(collection, item))
+			.is_some_and(|details| details.owner == account);
+		Ok(alloy::primitives::U256::from(owned as u8))
+	}
+
+	/// Execute the balance_of call.
+	fn balance_of(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::balanceOfCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let balance = Self::balance(&collection, call.account, call.id, env)?;
+		Ok(IERC1155::balanceOfCall::abi_encode_returns(&balance))
+	}
+
+	/// Execute the balance_of_batch call.
+	fn balance_of_batch(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::balanceOfBatchCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		if call.accounts.len() != call.ids.len() {
+			return Err(Error::Revert(Revert { reason: ERR_LENGTH_MISMATCH.into() }));
+		}
+		let balances = call
+			.accounts
+			.iter()
+			.zip(&call.ids)
+			.map(|(account, id)| Self::balance(&collection, *account, *id, env))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(IERC1155::balanceOfBatchCall::abi_encode_returns(&balances))
+	}
+
+	/// Transfer the items `ids` from `from` to `to` on behalf of the caller, each of `values`
+	/// being one, and return the caller.
+	fn transfer_batch(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		from: alloy::primitives::Address,
+		to: alloy::primitives::Address,
+		ids: &[alloy::primitives::U256],
+		values: &[alloy::primitives::U256],
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<alloy::primitives::Address, Error> {
+		if to.is_zero() {
+			return Err(Error::Revert(Revert { reason: ERR_INVALID_RECEIVER.into() }));
+		}
+		if ids.len() != values.len() {
+			return Err(Error::Revert(Revert { reason: ERR_LENGTH_MISMATCH.into() }));
+		}
+		if values.iter().any(|value| *value != alloy::primitives::U256::from(1)) {
+			return Err(Error::Revert(Revert { reason: ERR_INVALID_AMOUNT.into() }));
+		}
+
+		let operator = Helper::<Runtime, Instance>::caller(env)?;
+		let spender = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&operator);
+		let owner = Helper::<Runtime, Instance>::to_account_id(from);
+		let dest = Helper::<Runtime, Instance>::to_account_id(to);
+		for id in ids {
+			Helper::<Runtime, Instance>::transfer(
+				collection,
+				Helper::<Runtime, Instance>::to_item(*id)?,
+				&owner,
+				spender.clone(),
+				dest.clone(),
+				env,
+			)?;
+		}
+		Ok(operator.0.into())
+	}
+
+	/// Execute the safe_transfer_from call.
+	fn safe_transfer_from(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::safeTransferFromCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let operator =
+			Self::transfer_batch(collection, call.from, call.to, &[call.id], &[call.value], env)?;
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC1155Events::TransferSingle(IERC1155::TransferSingle {
+				operator,
+				from: call.from,
+				to: call.to,
+				id: call.id,
+				value: call.value,
+			}),
+		)?;
+
+		let input = IERC1155Receiver::onERC1155ReceivedCall {
+			operator,
+			from: call.from,
+			id: call.id,
+			value: call.value,
+			data: call.data.clone(),
+		}
+		.abi_encode();
+		Helper::<Runtime, Instance>::check_receiver(
+			call.to,
+			input,
+			IERC1155Receiver::onERC1155ReceivedCall::SELECTOR,
+			env,
+		)?;
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the safe_batch_transfer_from call.
+	fn safe_batch_transfer_from(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::safeBatchTransferFromCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let operator =
+			Self::transfer_batch(collection, call.from, call.to, &call.ids, &call.values, env)?;
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC1155Events::TransferBatch(IERC1155::TransferBatch {
+				operator,
+				from: call.from,
+				to: call.to,
+				ids: call.ids.clone(),
+				values: call.values.clone(),
+			}),
+		)?;
+
+		let input = IERC1155Receiver::onERC1155BatchReceivedCall {
+			operator,
+			from: call.from,
+			ids: call.ids.clone(),
+			values: call.values.clone(),
+			data: call.data.clone(),
+		}
+		.abi_encode();
+		Helper::<Runtime, Instance>::check_receiver(
+			call.to,
+			input,
+			IERC1155Receiver::onERC1155BatchReceivedCall::SELECTOR,
+			env,
+		)?;
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the set_approval_for_all call.
+	fn set_approval_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::setApprovalForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let account = Helper::<Runtime, Instance>::caller(env)?;
+		Helper::<Runtime, Instance>::set_approval_for_all(
+			collection,
+			<Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&account),
+			Helper::<Runtime, Instance>::to_account_id(call.operator),
+			call.approved,
+			env,
+		)?;
+
+		Helper::<Runtime, Instance>::deposit_event(
+			env,
+			IERC1155Events::ApprovalForAll(IERC1155::ApprovalForAll {
+				account: account.0.into(),
+				operator: call.operator,
+				approved: call.approved,
+			}),
+		)?;
+
+		Ok(Vec::new())
+	}
+
+	/// Execute the is_approved_for_all call.
+	fn is_approved_for_all(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::isApprovedForAllCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let approved = Helper::<Runtime, Instance>::is_approved_for_all(
+			collection,
+			&Helper::<Runtime, Instance>::to_account_id(call.account),
+			&Helper::<Runtime, Instance>::to_account_id(call.operator),
+			env,
+		)?;
+		Ok(IERC1155::isApprovedForAllCall::abi_encode_returns(&approved))
+	}
+
+	/// Execute the uri call.
+	fn uri(
+		collection: <Runtime as Config<Instance>>::CollectionId,
+		call: &IERC1155::uriCall,
+		env: &mut impl Ext<T = Runtime>,
+	) -> Result<Vec<u8>, Error> {
+		let item = Helper::<Runtime, Instance>::to_item(call.id)?;
+		let uri = Helper::<Runtime, Instance>::item_metadata(&collection, &item, env)?;
+		Ok(IERC1155::uriCall::abi_encode_returns(&uri))
+	}
+}
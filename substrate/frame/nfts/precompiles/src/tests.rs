@@ -0,0 +1,535 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+	alloy::{hex, sol_types::SolInterface},
+	mock::{new_test_ext, Balances, Nfts, RuntimeEvent, RuntimeOrigin, System, Test},
+};
+use alloy::primitives::U256;
+use frame_support::{assert_ok, traits::Currency};
+use pallet_nfts::{
+	AttributeNamespace, CollectionConfig, CollectionSettings, ItemConfig, ItemSettings,
+	MintSettings,
+};
+use pallet_revive::{precompiles::TransactionLimits, ExecConfig, ExecReturnValue};
+use sp_runtime::{AccountId32, DispatchError, Weight};
+
+const COLLECTION_ADDR: [u8; 20] = InlineIdConfig::<0x0121>::MATCHER.base_address();
+const ERC1155_COLLECTION_ADDR: [u8; 20] = InlineIdConfig::<0x0122>::MATCHER.base_address();
+
+fn account(id: u8) -> AccountId32 {
+	<Test as pallet_revive::Config>::AddressMapper::to_fallback_account_id(&H160::repeat_byte(id))
+}
+
+fn address(id: u8) -> alloy::primitives::Address {
+	H160::repeat_byte(id).0.into()
+}
+
+fn assert_event(contract: [u8; 20], event: impl IntoLogData) {
+	let (topics, data) = event.into_log_data().split();
+	let topics = topics.into_iter().map(|v| H256(v.0)).collect::<Vec<_>>();
+	System::assert_has_event(RuntimeEvent::Revive(pallet_revive::Event::ContractEmitted {
+		contract: contract.into(),
+		data: data.to_vec(),
+		topics,
+	}));
+}
+
+fn assert_contract_event(event: IERC721Events) {
+	assert_event(COLLECTION_ADDR, event);
+}
+
+fn bare_call(
+	from: u8,
+	contract: [u8; 20],
+	input: Vec<u8>,
+) -> Result<ExecReturnValue, DispatchError> {
+	pallet_revive::Pallet::<Test>::bare_call(
+		RuntimeOrigin::signed(account(from)),
+		contract.into(),
+		0u32.into(),
+		TransactionLimits::WeightAndDeposit { weight_limit: Weight::MAX, deposit_limit: u64::MAX },
+		input,
+		ExecConfig::new_substrate_tx(),
+	)
+	.result
+}
+
+fn call_result(from: u8, input: IERC721Calls) -> Result<ExecReturnValue, DispatchError> {
+	bare_call(from, COLLECTION_ADDR, input.abi_encode())
+}
+
+/// Calls the precompile and returns its output, asserting that the call succeeded.
+fn call(from: u8, input: IERC721Calls) -> Vec<u8> {
+	let output = call_result(from, input).unwrap();
+	assert!(!output.did_revert());
+	output.data
+}
+
+fn erc1155_call_result(from: u8, input: IERC1155Calls) -> Result<ExecReturnValue, DispatchError> {
+	bare_call(from, ERC1155_COLLECTION_ADDR, input.abi_encode())
+}
+
+/// Calls the ERC1155 precompile and returns its output, asserting that the call succeeded.
+fn erc1155_call(from: u8, input: IERC1155Calls) -> Vec<u8> {
+	let output = erc1155_call_result(from, input).unwrap();
+	assert!(!output.did_revert());
+	output.data
+}
+
+fn owner_of(item: u32) -> alloy::primitives::Address {
+	let output = call(1, IERC721Calls::ownerOf(IERC721::ownerOfCall { tokenId: U256::from(item) }));
+	IERC721::ownerOfCall::abi_decode_returns(&output).unwrap()
+}
+
+fn mint(item: u32, owner: u8) {
+	assert_ok!(Nfts::force_mint(
+		RuntimeOrigin::signed(account(1)),
+		0,
+		item,
+		account(owner),
+		ItemConfig { settings: ItemSettings::all_enabled() },
+	));
+}
+
+/// Creates collection `0` owned by account `1` and mints the items `0` and `1` to account `1`.
+fn create_collection() {
+	for id in 1..=4 {
+		Balances::make_free_balance_be(&account(id), 1000);
+	}
+	assert_ok!(Nfts::force_create(
+		RuntimeOrigin::root(),
+		account(1),
+		CollectionConfig {
+			settings: CollectionSettings::all_enabled(),
+			max_supply: None,
+			mint_settings: MintSettings::default(),
+		}
+	));
+	for item in 0..2 {
+		mint(item, 1);
+	}
+}
+
+#[test]
+fn collection_id_extractor_works() {
+	let address: [u8; 20] =
+		hex::const_decode_to_array(b"0000053900000000000000000000000001210000").unwrap();
+	assert!(InlineIdConfig::<0x0121>::MATCHER.matches(&address));
+	assert_eq!(
+		<InlineIdConfig<0x0121> as NftsPrecompileConfig>::CollectionIdExtractor::collection_id_from_address(
+			&address
+		)
+		.unwrap(),
+		1337u32
+	);
+}
+
+#[test]
+fn balance_of_and_owner_of_work() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let balance_of = |owner| {
+			let output =
+				call(2, IERC721Calls::balanceOf(IERC721::balanceOfCall { owner: address(owner) }));
+			IERC721::balanceOfCall::abi_decode_returns(&output).unwrap()
+		};
+		assert_eq!(balance_of(1), U256::from(2));
+		assert_eq!(balance_of(2), U256::ZERO);
+
+		assert_eq!(owner_of(0), address(1));
+		let output =
+			call_result(1, IERC721Calls::ownerOf(IERC721::ownerOfCall { tokenId: U256::from(7) }));
+		assert!(output.unwrap().did_revert());
+	});
+}
+
+#[test]
+fn transfer_from_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		// only the owner or an approved account may transfer
+		let transfer = || {
+			IERC721Calls::safeTransferFrom_1(IERC721::safeTransferFrom_1Call {
+				from: address(1),
+				to: address(2),
+				tokenId: U256::from(0),
+			})
+		};
+		assert_eq!(
+			call_result(2, transfer()),
+			Err(pallet_nfts::Error::<Test>::NoPermission.into())
+		);
+		assert_eq!(owner_of(0), address(1));
+
+		call(1, transfer());
+		assert_eq!(owner_of(0), address(2));
+		assert_contract_event(IERC721Events::Transfer(IERC721::Transfer {
+			from: address(1),
+			to: address(2),
+			tokenId: U256::from(0),
+		}));
+
+		// `from` must be the owner of the token
+		let output = call_result(
+			2,
+			IERC721Calls::transferFrom(IERC721::transferFromCall {
+				from: address(1),
+				to: address(3),
+				tokenId: U256::from(0),
+			}),
+		);
+		assert_eq!(output, Err(pallet_nfts::Error::<Test>::WrongOwner.into()));
+		assert_eq!(owner_of(0), address(2));
+	});
+}
+
+#[test]
+fn approve_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let get_approved = || {
+			let output = call(
+				1,
+				IERC721Calls::getApproved(IERC721::getApprovedCall { tokenId: U256::from(0) }),
+			);
+			IERC721::getApprovedCall::abi_decode_returns(&output).unwrap()
+		};
+
+		// only the owner can approve
+		let approve = |to| {
+			IERC721Calls::approve(IERC721::approveCall { to: address(to), tokenId: U256::from(0) })
+		};
+		assert!(call_result(2, approve(2)).unwrap().did_revert());
+		assert_eq!(get_approved(), alloy::primitives::Address::ZERO);
+
+		call(1, approve(2));
+		assert_contract_event(IERC721Events::Approval(IERC721::Approval {
+			owner: address(1),
+			approved: address(2),
+			tokenId: U256::from(0),
+		}));
+
+		// a new approval replaces the previous one
+		call(1, approve(3));
+		assert_eq!(get_approved(), address(3));
+		let transfer = |to| {
+			IERC721Calls::transferFrom(IERC721::transferFromCall {
+				from: address(1),
+				to: address(to),
+				tokenId: U256::from(0),
+			})
+		};
+		assert_eq!(
+			call_result(2, transfer(2)),
+			Err(pallet_nfts::Error::<Test>::NoPermission.into())
+		);
+
+		call(3, transfer(4));
+		assert_eq!(owner_of(0), address(4));
+
+		// the approval is cleared by the transfer
+		assert_eq!(get_approved(), alloy::primitives::Address::ZERO);
+	});
+}
+
+#[test]
+fn set_approval_for_all_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let is_approved_for_all = || {
+			let output = call(
+				1,
+				IERC721Calls::isApprovedForAll(IERC721::isApprovedForAllCall {
+					owner: address(1),
+					operator: address(2),
+				}),
+			);
+			IERC721::isApprovedForAllCall::abi_decode_returns(&output).unwrap()
+		};
+		let set_approval_for_all = |approved| {
+			IERC721Calls::setApprovalForAll(IERC721::setApprovalForAllCall {
+				operator: address(2),
+				approved,
+			})
+		};
+
+		assert!(!is_approved_for_all());
+		call(1, set_approval_for_all(true));
+		assert!(is_approved_for_all());
+		assert_contract_event(IERC721Events::ApprovalForAll(IERC721::ApprovalForAll {
+			owner: address(1),
+			operator: address(2),
+			approved: true,
+		}));
+
+		// the approval only covers the items owned when it was given
+		mint(2, 1);
+		assert!(!is_approved_for_all());
+		call(1, set_approval_for_all(true));
+		assert!(is_approved_for_all());
+
+		// the operator can transfer any item of the owner
+		for item in 0..3 {
+			call(
+				2,
+				IERC721Calls::transferFrom(IERC721::transferFromCall {
+					from: address(1),
+					to: address(3),
+					tokenId: U256::from(item),
+				}),
+			);
+			assert_eq!(owner_of(item), address(3));
+		}
+
+		call(1, set_approval_for_all(false));
+		assert!(!is_approved_for_all());
+	});
+}
+
+#[test]
+fn metadata_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		assert_ok!(Nfts::set_collection_metadata(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			b"Collection".to_vec().try_into().unwrap()
+		));
+		assert_ok!(Nfts::set_metadata(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			b"ipfs://item".to_vec().try_into().unwrap()
+		));
+
+		let name = || {
+			let output = call(1, IERC721Calls::name(IERC721::nameCall {}));
+			IERC721::nameCall::abi_decode_returns(&output).unwrap()
+		};
+		let symbol = || {
+			let output = call(1, IERC721Calls::symbol(IERC721::symbolCall {}));
+			IERC721::symbolCall::abi_decode_returns(&output).unwrap()
+		};
+		assert_eq!(name(), "Collection");
+		assert_eq!(symbol(), "");
+
+		// the attributes of the collection take precedence over its metadata
+		for (key, value) in [(&b"name"[..], &b"Kitties"[..]), (b"symbol", b"KIT")] {
+			assert_ok!(Nfts::set_attribute(
+				RuntimeOrigin::signed(account(1)),
+				0,
+				None,
+				AttributeNamespace::CollectionOwner,
+				key.to_vec().try_into().unwrap(),
+				value.to_vec().try_into().unwrap(),
+			));
+		}
+		assert_eq!(name(), "Kitties");
+		assert_eq!(symbol(), "KIT");
+
+		let token_uri = |item| {
+			let output = call(
+				1,
+				IERC721Calls::tokenURI(IERC721::tokenURICall { tokenId: U256::from(item) }),
+			);
+			IERC721::tokenURICall::abi_decode_returns(&output).unwrap()
+		};
+		assert_eq!(token_uri(0), "ipfs://item");
+		assert_eq!(token_uri(1), "");
+
+		let output = call(
+			1,
+			IERC721Calls::supportsInterface(IERC721::supportsInterfaceCall {
+				interfaceId: [0x80, 0xac, 0x58, 0xcd].into(),
+			}),
+		);
+		assert!(IERC721::supportsInterfaceCall::abi_decode_returns(&output).unwrap());
+	});
+}
+
+#[test]
+fn erc1155_balance_of_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let output = erc1155_call(
+			2,
+			IERC1155Calls::balanceOf(IERC1155::balanceOfCall {
+				account: address(1),
+				id: U256::ZERO,
+			}),
+		);
+		assert_eq!(IERC1155::balanceOfCall::abi_decode_returns(&output).unwrap(), U256::from(1));
+
+		// accounts which don't own an item and unknown items have a zero balance
+		let output = erc1155_call(
+			2,
+			IERC1155Calls::balanceOfBatch(IERC1155::balanceOfBatchCall {
+				accounts: vec![address(1), address(2), address(1)],
+				ids: vec![U256::from(1), U256::from(1), U256::from(7)],
+			}),
+		);
+		assert_eq!(
+			IERC1155::balanceOfBatchCall::abi_decode_returns(&output).unwrap(),
+			vec![U256::from(1), U256::ZERO, U256::ZERO]
+		);
+
+		let output = erc1155_call_result(
+			2,
+			IERC1155Calls::balanceOfBatch(IERC1155::balanceOfBatchCall {
+				accounts: vec![address(1)],
+				ids: vec![],
+			}),
+		);
+		assert!(output.unwrap().did_revert());
+	});
+}
+
+#[test]
+fn erc1155_safe_transfer_from_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let transfer = |value| {
+			IERC1155Calls::safeTransferFrom(IERC1155::safeTransferFromCall {
+				from: address(1),
+				to: address(3),
+				id: U256::ZERO,
+				value: U256::from(value),
+				data: Default::default(),
+			})
+		};
+
+		// items have a supply of one
+		assert!(erc1155_call_result(1, transfer(2)).unwrap().did_revert());
+
+		// only the owner or an approved operator may transfer
+		assert_eq!(
+			erc1155_call_result(2, transfer(1)),
+			Err(pallet_nfts::Error::<Test>::NoPermission.into())
+		);
+
+		erc1155_call(
+			1,
+			IERC1155Calls::setApprovalForAll(IERC1155::setApprovalForAllCall {
+				operator: address(2),
+				approved: true,
+			}),
+		);
+		assert_event(
+			ERC1155_COLLECTION_ADDR,
+			IERC1155Events::ApprovalForAll(IERC1155::ApprovalForAll {
+				account: address(1),
+				operator: address(2),
+				approved: true,
+			}),
+		);
+		let output = erc1155_call(
+			1,
+			IERC1155Calls::isApprovedForAll(IERC1155::isApprovedForAllCall {
+				account: address(1),
+				operator: address(2),
+			}),
+		);
+		assert!(IERC1155::isApprovedForAllCall::abi_decode_returns(&output).unwrap());
+
+		erc1155_call(2, transfer(1));
+		assert_eq!(owner_of(0), address(3));
+		assert_event(
+			ERC1155_COLLECTION_ADDR,
+			IERC1155Events::TransferSingle(IERC1155::TransferSingle {
+				operator: address(2),
+				from: address(1),
+				to: address(3),
+				id: U256::ZERO,
+				value: U256::from(1),
+			}),
+		);
+	});
+}
+
+#[test]
+fn erc1155_safe_batch_transfer_from_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		let transfer = |ids: Vec<u32>| {
+			IERC1155Calls::safeBatchTransferFrom(IERC1155::safeBatchTransferFromCall {
+				from: address(1),
+				to: address(2),
+				values: vec![U256::from(1); ids.len()],
+				ids: ids.into_iter().map(U256::from).collect(),
+				data: Default::default(),
+			})
+		};
+
+		// the batch is reverted as a whole when one of the items can't be transferred
+		assert!(erc1155_call_result(1, transfer(vec![0, 7])).unwrap().did_revert());
+		assert_eq!(owner_of(0), address(1));
+
+		erc1155_call(1, transfer(vec![0, 1]));
+		assert_eq!(owner_of(0), address(2));
+		assert_eq!(owner_of(1), address(2));
+		assert_event(
+			ERC1155_COLLECTION_ADDR,
+			IERC1155Events::TransferBatch(IERC1155::TransferBatch {
+				operator: address(1),
+				from: address(1),
+				to: address(2),
+				ids: vec![U256::ZERO, U256::from(1)],
+				values: vec![U256::from(1); 2],
+			}),
+		);
+	});
+}
+
+#[test]
+fn erc1155_uri_works() {
+	new_test_ext().execute_with(|| {
+		create_collection();
+
+		assert_ok!(Nfts::set_metadata(
+			RuntimeOrigin::signed(account(1)),
+			0,
+			0,
+			b"ipfs://item".to_vec().try_into().unwrap()
+		));
+
+		let uri = |id| {
+			let output =
+				erc1155_call(1, IERC1155Calls::uri(IERC1155::uriCall { id: U256::from(id) }));
+			IERC1155::uriCall::abi_decode_returns(&output).unwrap()
+		};
+		assert_eq!(uri(0), "ipfs://item");
+		assert_eq!(uri(1), "");
+
+		let output = erc1155_call(
+			1,
+			IERC1155Calls::supportsInterface(IERC1155::supportsInterfaceCall {
+				interfaceId: [0xd9, 0xb6, 0x7a, 0x26].into(),
+			}),
+		);
+		assert!(IERC1155::supportsInterfaceCall::abi_decode_returns(&output).unwrap());
+	});
+}
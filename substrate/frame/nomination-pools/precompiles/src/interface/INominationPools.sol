@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @dev The on-chain address of the nomination pools precompile.
+address constant NOMINATION_POOLS_PRECOMPILE_ADDRESS = address(0xC0000);
+
+/// @title Nomination Pools Precompile Interface
+/// @notice An interface for staking through `pallet_nomination_pools`.
+/// It forwards calls directly to the corresponding dispatchable functions, using the
+/// calling contract or account as the pool member.
+interface INominationPools {
+    /// @notice Join a pool by bonding funds of the caller.
+    /// @param amount The amount of funds to bond.
+    /// @param poolId The pool to join.
+    function join(uint128 amount, uint32 poolId) external;
+
+    /// @notice Bond additional free funds of the caller into its pool.
+    /// @param amount The amount of funds to bond.
+    function bondExtra(uint128 amount) external;
+
+    /// @notice Bond the pending rewards of the caller into its pool.
+    function bondExtraRewards() external;
+
+    /// @notice Unbond points of a member from its pool.
+    /// @dev Unless the pool is blocked or destroying, only the member itself may unbond.
+    /// @param member The member to unbond.
+    /// @param points The amount of points to unbond.
+    function unbond(address member, uint128 points) external;
+
+    /// @notice Withdraw the unbonded funds of a member once the bonding duration has passed.
+    /// @param member The member to withdraw the funds of.
+    /// @param numSlashingSpans The number of slashing spans of the pool.
+    function withdrawUnbonded(address member, uint32 numSlashingSpans) external;
+
+    /// @notice Pay out the pending rewards of the caller to the caller.
+    function claimPayout() external;
+}
@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{marker::PhantomData, num::NonZero};
+use frame_support::{dispatch::RawOrigin, sp_runtime::traits::StaticLookup};
+use pallet_nomination_pools::{BalanceOf, BondExtra, Config, WeightInfo};
+use pallet_revive::{
+	frame_system,
+	precompiles::{alloy, AddressMapper, AddressMatcher, Error, Ext, Precompile, H160},
+	DispatchInfo, ExecOrigin as Origin, Weight,
+};
+
+alloy::sol!("src/interface/INominationPools.sol");
+use INominationPools::INominationPoolsCalls;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+const ERR_CONVERSION_FAILED: &str = "Conversion failed";
+
+/// A precompile that lets contracts join and stake through `pallet-nomination-pools`.
+///
+/// Calls are dispatched with the calling contract (or account) as the signed origin.
+pub struct NominationPoolsPrecompile<T>(PhantomData<T>);
+
+impl<Runtime> Precompile for NominationPoolsPrecompile<Runtime>
+where
+	Runtime: crate::Config + pallet_revive::Config,
+	BalanceOf<Runtime>: TryFrom<u128>,
+{
+	type T = Runtime;
+	const MATCHER: AddressMatcher = AddressMatcher::Fixed(NonZero::new(12).unwrap());
+	const HAS_CONTRACT_INFO: bool = false;
+	type Interface = INominationPoolsCalls;
+
+	fn call(
+		_address: &[u8; 20],
+		input: &Self::Interface,
+		env: &mut impl Ext<T = Self::T>,
+	) -> Result<Vec<u8>, Error> {
+		// All the functions of the interface change state.
+		if env.is_read_only() {
+			return Err(Error::Error(pallet_revive::Error::<Self::T>::StateChangeDenied.into()));
+		}
+
+		let frame_origin: <Runtime as frame_system::Config>::RuntimeOrigin = match env.caller() {
+			Origin::Root => RawOrigin::Root.into(),
+			Origin::Signed(account_id) => RawOrigin::Signed(account_id).into(),
+		};
+
+		match input {
+			INominationPoolsCalls::join(call) => {
+				env.charge(<Runtime as Config>::WeightInfo::join())?;
+				pallet_nomination_pools::Pallet::<Runtime>::join(
+					frame_origin,
+					Self::convert(call.amount)?,
+					call.poolId,
+				)?;
+			},
+			INominationPoolsCalls::bondExtra(call) => {
+				env.charge(Self::bond_extra_weight())?;
+				pallet_nomination_pools::Pallet::<Runtime>::bond_extra(
+					frame_origin,
+					BondExtra::FreeBalance(Self::convert(call.amount)?),
+				)?;
+			},
+			INominationPoolsCalls::bondExtraRewards(_) => {
+				env.charge(Self::bond_extra_weight())?;
+				pallet_nomination_pools::Pallet::<Runtime>::bond_extra(
+					frame_origin,
+					BondExtra::Rewards,
+				)?;
+			},
+			INominationPoolsCalls::unbond(call) => {
+				env.charge(<Runtime as Config>::WeightInfo::unbond())?;
+				pallet_nomination_pools::Pallet::<Runtime>::unbond(
+					frame_origin,
+					Self::to_lookup_source(call.member),
+					Self::convert(call.points)?,
+				)?;
+			},
+			INominationPoolsCalls::withdrawUnbonded(call) => {
+				let weight =
+					<Runtime as Config>::WeightInfo::withdraw_unbonded_kill(call.numSlashingSpans);
+				let charged_amount = env.charge(weight)?;
+				let result = pallet_nomination_pools::Pallet::<Runtime>::withdraw_unbonded(
+					frame_origin,
+					Self::to_lookup_source(call.member),
+					call.numSlashingSpans,
+				);
+
+				let pre = DispatchInfo {
+					call_weight: weight,
+					extension_weight: Weight::zero(),
+					..Default::default()
+				};
+				let actual_weight = frame_support::dispatch::extract_actual_weight(&result, &pre);
+				env.adjust_gas(charged_amount, actual_weight);
+				result.map_err(|error| Error::Error(error.error.into()))?;
+			},
+			INominationPoolsCalls::claimPayout(_) => {
+				env.charge(<Runtime as Config>::WeightInfo::claim_payout())?;
+				pallet_nomination_pools::Pallet::<Runtime>::claim_payout(frame_origin)?;
+			},
+		}
+
+		Ok(Vec::new())
+	}
+}
+
+impl<Runtime> NominationPoolsPrecompile<Runtime>
+where
+	Runtime: crate::Config + pallet_revive::Config,
+	BalanceOf<Runtime>: TryFrom<u128>,
+{
+	/// Convert an ABI value into the balance type of the pallet.
+	fn convert(value: u128) -> Result<BalanceOf<Runtime>, Error> {
+		value.try_into().map_err(|_| Error::Revert(ERR_CONVERSION_FAILED.into()))
+	}
+
+	/// Convert an Ethereum address into the lookup source of the runtime.
+	fn to_lookup_source(
+		address: alloy::primitives::Address,
+	) -> <<Runtime as frame_system::Config>::Lookup as StaticLookup>::Source {
+		let address = H160::from(address.into_array());
+		let account_id = <Runtime as pallet_revive::Config>::AddressMapper::to_account_id(&address);
+		<Runtime as frame_system::Config>::Lookup::unlookup(account_id)
+	}
+
+	/// The weight of `bond_extra`, which doesn't depend on the source of the funds.
+	fn bond_extra_weight() -> Weight {
+		<Runtime as Config>::WeightInfo::bond_extra_transfer()
+			.max(<Runtime as Config>::WeightInfo::bond_extra_other())
+	}
+}
@@ -0,0 +1,315 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests mock for `pallet-nomination-pools-precompiles`.
+
+pub use super::*;
+use alloc::collections::BTreeMap;
+use frame_support::{
+	assert_ok, derive_impl, parameter_types,
+	traits::{fungible::Mutate, ConstU32, ConstU8, Nothing, VariantCountOf},
+	PalletId,
+};
+use pallet_revive::U256;
+use sp_runtime::{
+	traits::{Convert, IdentityLookup, Zero},
+	AccountId32, BuildStorage, DispatchError, DispatchResult, FixedU128, Perbill,
+};
+use sp_staking::{EraIndex, OnStakingUpdate, Stake, StakerStatus};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+#[frame_support::runtime]
+mod runtime {
+	#[runtime::runtime]
+	#[runtime::derive(
+		RuntimeCall,
+		RuntimeEvent,
+		RuntimeError,
+		RuntimeOrigin,
+		RuntimeTask,
+		RuntimeHoldReason,
+		RuntimeFreezeReason
+	)]
+	pub struct Test;
+
+	#[runtime::pallet_index(0)]
+	pub type System = frame_system;
+	#[runtime::pallet_index(10)]
+	pub type Balances = pallet_balances;
+	#[runtime::pallet_index(20)]
+	pub type Pools = pallet_nomination_pools;
+	#[runtime::pallet_index(21)]
+	pub type Revive = pallet_revive;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountId = AccountId32;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig as pallet_balances::DefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxFreezes = VariantCountOf<RuntimeFreezeReason>;
+}
+
+parameter_types! {
+	pub static CurrentEra: EraIndex = 0;
+	pub storage BondedBalanceMap: BTreeMap<AccountId32, u64> = Default::default();
+	pub storage UnbondingBalanceMap: BTreeMap<AccountId32, Vec<(EraIndex, u64)>> =
+		Default::default();
+}
+
+/// A staking implementation that only keeps track of the bonded and unbonding balances.
+pub struct StakingMock;
+
+impl StakingMock {
+	fn unlocking(who: &AccountId32) -> u64 {
+		UnbondingBalanceMap::get()
+			.get(who)
+			.map_or(0, |chunks| chunks.iter().map(|(_, amount)| amount).sum())
+	}
+}
+
+impl sp_staking::StakingInterface for StakingMock {
+	type Balance = u64;
+	type AccountId = AccountId32;
+	type CurrencyToVote = ();
+
+	fn minimum_nominator_bond() -> Self::Balance {
+		10
+	}
+
+	fn minimum_validator_bond() -> Self::Balance {
+		10
+	}
+
+	fn stash_by_ctrl(_controller: &Self::AccountId) -> Result<Self::AccountId, DispatchError> {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn bonding_duration() -> EraIndex {
+		3
+	}
+
+	fn current_era() -> EraIndex {
+		CurrentEra::get()
+	}
+
+	fn stake(who: &Self::AccountId) -> Result<Stake<Self::Balance>, DispatchError> {
+		let active = BondedBalanceMap::get()
+			.get(who)
+			.copied()
+			.ok_or(DispatchError::Other("balance not found"))?;
+		Ok(Stake { total: active + Self::unlocking(who), active })
+	}
+
+	fn bond(who: &Self::AccountId, value: Self::Balance, _: &Self::AccountId) -> DispatchResult {
+		let mut bonded = BondedBalanceMap::get();
+		bonded.insert(who.clone(), value);
+		BondedBalanceMap::set(&bonded);
+		Ok(())
+	}
+
+	fn nominate(_: &Self::AccountId, _: Vec<Self::AccountId>) -> DispatchResult {
+		Ok(())
+	}
+
+	fn chill(_: &Self::AccountId) -> DispatchResult {
+		Ok(())
+	}
+
+	fn bond_extra(who: &Self::AccountId, extra: Self::Balance) -> DispatchResult {
+		let mut bonded = BondedBalanceMap::get();
+		if let Some(bonded) = bonded.get_mut(who) {
+			*bonded += extra;
+		}
+		BondedBalanceMap::set(&bonded);
+		Ok(())
+	}
+
+	fn unbond(who: &Self::AccountId, amount: Self::Balance) -> DispatchResult {
+		let mut bonded = BondedBalanceMap::get();
+		if let Some(bonded) = bonded.get_mut(who) {
+			*bonded = bonded.saturating_sub(amount);
+		}
+		BondedBalanceMap::set(&bonded);
+
+		let mut unbonding = UnbondingBalanceMap::get();
+		unbonding
+			.entry(who.clone())
+			.or_default()
+			.push((Self::current_era() + Self::bonding_duration(), amount));
+		UnbondingBalanceMap::set(&unbonding);
+		Ok(())
+	}
+
+	fn set_payee(_stash: &Self::AccountId, _reward_acc: &Self::AccountId) -> DispatchResult {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn withdraw_unbonded(who: Self::AccountId, _: u32) -> Result<bool, DispatchError> {
+		let unlocking_before = Self::unlocking(&who);
+		let mut unbonding = UnbondingBalanceMap::get();
+		unbonding
+			.get_mut(&who)
+			.ok_or("Nothing to unbond")?
+			.retain(|(unlocking_at, _)| *unlocking_at > Self::current_era());
+		UnbondingBalanceMap::set(&unbonding);
+		Pools::on_withdraw(&who, unlocking_before.saturating_sub(Self::unlocking(&who)));
+
+		Ok(Self::unlocking(&who).is_zero() &&
+			BondedBalanceMap::get().get(&who).copied().unwrap_or_default().is_zero())
+	}
+
+	fn desired_validator_count() -> u32 {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn election_ongoing() -> bool {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn force_unstake(_who: Self::AccountId) -> DispatchResult {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn is_exposed_in_era(_who: &Self::AccountId, _era: &EraIndex) -> bool {
+		unimplemented!("method currently not used in testing")
+	}
+
+	fn status(_: &Self::AccountId) -> Result<StakerStatus<Self::AccountId>, DispatchError> {
+		Ok(StakerStatus::Nominator(Vec::new()))
+	}
+
+	fn is_virtual_staker(_who: &Self::AccountId) -> bool {
+		false
+	}
+
+	fn slash_reward_fraction() -> Perbill {
+		unimplemented!("method currently not used in testing")
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_exposure_page_size() -> sp_staking::Page {
+		unimplemented!("method currently not used in testing")
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn add_era_stakers(
+		_current_era: &EraIndex,
+		_stash: &Self::AccountId,
+		_exposures: Vec<(Self::AccountId, Self::Balance)>,
+	) {
+		unimplemented!("method currently not used in testing")
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_current_era(_era: EraIndex) {
+		unimplemented!("method currently not used in testing")
+	}
+}
+
+pub struct BalanceToU256;
+impl Convert<u64, U256> for BalanceToU256 {
+	fn convert(n: u64) -> U256 {
+		n.into()
+	}
+}
+
+pub struct U256ToBalance;
+impl Convert<U256, u64> for U256ToBalance {
+	fn convert(n: U256) -> u64 {
+		n.try_into().unwrap()
+	}
+}
+
+parameter_types! {
+	pub const PoolsPalletId: PalletId = PalletId(*b"py/nopls");
+}
+
+#[allow(deprecated)]
+impl pallet_nomination_pools::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type RewardCounter = FixedU128;
+	type BalanceToU256 = BalanceToU256;
+	type U256ToBalance = U256ToBalance;
+	type StakeAdapter = pallet_nomination_pools::adapter::TransferStake<Self, StakingMock>;
+	type PostUnbondingPoolsWindow = ConstU32<2>;
+	type PalletId = PoolsPalletId;
+	type MaxMetadataLen = ConstU32<2>;
+	type MaxUnbonding = ConstU32<8>;
+	type MaxPointsToBalance = ConstU8<10>;
+	type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type BlockNumberProvider = System;
+	type Filter = Nothing;
+}
+
+#[derive_impl(pallet_revive::config_preludes::TestDefaultConfig)]
+impl pallet_revive::Config for Test {
+	type AddressMapper = pallet_revive::AccountId32Mapper<Self>;
+	type UploadOrigin = frame_system::EnsureSigned<Self::AccountId>;
+	type InstantiateOrigin = frame_system::EnsureSigned<Self::AccountId>;
+	type Balance = u64;
+	type Currency = Balances;
+	type Precompiles = (NominationPoolsPrecompile<Self>,);
+}
+
+/// Returns the account of the Ethereum address `H160::repeat_byte(id)`.
+pub fn account(id: u8) -> AccountId32 {
+	<Test as pallet_revive::Config>::AddressMapper::to_fallback_account_id(&H160::repeat_byte(id))
+}
+
+/// Creates pool `1` with `account(10)` as its depositor and funds the accounts `1` and `2`.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_nomination_pools::GenesisConfig::<Test> {
+		min_join_bond: 2,
+		min_create_bond: 2,
+		max_pools: Some(2),
+		max_members_per_pool: None,
+		max_members: None,
+		global_max_commission: None,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| {
+		System::set_block_number(1);
+
+		for id in [1, 2, 10] {
+			Balances::set_balance(&account(id), 100);
+		}
+		assert_ok!(Pools::create(
+			RuntimeOrigin::signed(account(10)),
+			Pools::depositor_min_bond(),
+			account(10),
+			account(10),
+			account(10),
+		));
+	});
+
+	ext
+}
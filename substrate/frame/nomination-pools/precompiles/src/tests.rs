@@ -0,0 +1,140 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+	alloy::sol_types::SolInterface,
+	mock::{account, new_test_ext, Balances, CurrentEra, Pools, RuntimeOrigin, Test},
+};
+use frame_support::{assert_ok, traits::fungible::Mutate};
+use pallet_nomination_pools::{Error as PoolsError, PoolMembers};
+use pallet_revive::{precompiles::TransactionLimits, ExecConfig, ExecReturnValue};
+use sp_runtime::DispatchError;
+
+const PRECOMPILE_ADDR: [u8; 20] =
+	<NominationPoolsPrecompile<Test> as Precompile>::MATCHER.base_address();
+
+fn address(id: u8) -> alloy::primitives::Address {
+	H160::repeat_byte(id).0.into()
+}
+
+fn call_result(from: u8, input: INominationPoolsCalls) -> Result<ExecReturnValue, DispatchError> {
+	pallet_revive::Pallet::<Test>::bare_call(
+		RuntimeOrigin::signed(account(from)),
+		PRECOMPILE_ADDR.into(),
+		0u32.into(),
+		TransactionLimits::WeightAndDeposit { weight_limit: Weight::MAX, deposit_limit: u64::MAX },
+		input.abi_encode(),
+		ExecConfig::new_substrate_tx(),
+	)
+	.result
+}
+
+/// Calls the precompile, asserting that the call succeeded.
+fn call(from: u8, input: INominationPoolsCalls) {
+	let output = call_result(from, input).unwrap();
+	assert!(!output.did_revert());
+}
+
+fn join(amount: u128, pool_id: u32) -> INominationPoolsCalls {
+	INominationPoolsCalls::join(INominationPools::joinCall { amount, poolId: pool_id })
+}
+
+fn points(id: u8) -> u64 {
+	PoolMembers::<Test>::get(account(id)).unwrap().points
+}
+
+#[test]
+fn join_and_bond_extra_work() {
+	new_test_ext().execute_with(|| {
+		call(1, join(10, 1));
+		assert_eq!(points(1), 10);
+		assert_eq!(Balances::free_balance(account(1)), 90);
+
+		call(1, INominationPoolsCalls::bondExtra(INominationPools::bondExtraCall { amount: 5 }));
+		assert_eq!(points(1), 15);
+		assert_eq!(Balances::free_balance(account(1)), 85);
+	});
+}
+
+#[test]
+fn join_rejects_invalid_input() {
+	new_test_ext().execute_with(|| {
+		// the balance of the mock is a `u64`
+		assert!(call_result(1, join(u128::from(u64::MAX) + 1, 1)).unwrap().did_revert());
+
+		assert_eq!(call_result(1, join(10, 2)), Err(PoolsError::<Test>::PoolNotFound.into()));
+		assert!(PoolMembers::<Test>::get(account(1)).is_none());
+	});
+}
+
+#[test]
+fn claim_payout_and_bond_extra_rewards_work() {
+	new_test_ext().execute_with(|| {
+		call(1, join(10, 1));
+
+		// the depositor and the member own half of the pool each
+		assert_ok!(Balances::mint_into(&Pools::generate_reward_account(1), 20));
+		call(1, INominationPoolsCalls::claimPayout(INominationPools::claimPayoutCall {}));
+		assert_eq!(Balances::free_balance(account(1)), 100);
+
+		assert_ok!(Balances::mint_into(&Pools::generate_reward_account(1), 20));
+		call(1, INominationPoolsCalls::bondExtraRewards(INominationPools::bondExtraRewardsCall {}));
+		assert_eq!(points(1), 20);
+		assert_eq!(Balances::free_balance(account(1)), 100);
+	});
+}
+
+#[test]
+fn unbond_and_withdraw_unbonded_work() {
+	new_test_ext().execute_with(|| {
+		call(1, join(10, 1));
+
+		let unbond = || {
+			INominationPoolsCalls::unbond(INominationPools::unbondCall {
+				member: address(1),
+				points: 5,
+			})
+		};
+		// only the member can partially unbond from an open pool
+		assert_eq!(
+			call_result(2, unbond()),
+			Err(PoolsError::<Test>::PartialUnbondNotAllowedPermissionlessly.into())
+		);
+
+		call(1, unbond());
+		assert_eq!(points(1), 5);
+		assert_eq!(PoolMembers::<Test>::get(account(1)).unwrap().unbonding_eras.get(&3), Some(&5));
+
+		let withdraw_unbonded = || {
+			INominationPoolsCalls::withdrawUnbonded(INominationPools::withdrawUnbondedCall {
+				member: address(1),
+				numSlashingSpans: 0,
+			})
+		};
+		// the bonding duration hasn't passed yet
+		assert_eq!(
+			call_result(1, withdraw_unbonded()),
+			Err(PoolsError::<Test>::CannotWithdrawAny.into())
+		);
+
+		CurrentEra::set(3);
+		call(1, withdraw_unbonded());
+		assert!(PoolMembers::<Test>::get(account(1)).unwrap().unbonding_eras.is_empty());
+		assert_eq!(Balances::free_balance(account(1)), 95);
+	});
+}
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS backfill_checkpoints (
+	start_block INTEGER NOT NULL PRIMARY KEY,
+	end_block INTEGER NOT NULL
+);
@@ -25,3 +25,6 @@ pub use health_api::*;
 
 mod polkadot_api;
 pub use polkadot_api::*;
+
+mod pubsub_apis;
+pub use pubsub_apis::*;
@@ -29,7 +29,7 @@ pub trait DebugRpc {
 	async fn trace_block_by_number(
 		&self,
 		block: BlockNumberOrTag,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Vec<TransactionTrace>>;
 
 	/// Returns a transaction's traces by replaying it.
@@ -41,7 +41,7 @@ pub trait DebugRpc {
 	async fn trace_transaction(
 		&self,
 		transaction_hash: H256,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Trace>;
 
 	/// Dry run a call and returns the transaction's traces.
@@ -54,13 +54,41 @@ pub trait DebugRpc {
 		&self,
 		transaction: GenericTransaction,
 		block: BlockNumberOrTagOrHash,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Trace>;
 
 	#[method(name = "debug_getAutomine")]
 	async fn get_automine(&self) -> RpcResult<bool>;
 }
 
+/// Parity style trace JSON-RPC apis.
+#[rpc(server, client)]
+pub trait TraceRpc {
+	/// Returns the flat call traces of all the transactions in the given block.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_block>
+	#[method(name = "trace_block")]
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Vec<FlatCallTrace>>;
+
+	/// Returns the flat call traces of the given transaction.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_transaction>
+	#[method(name = "trace_transaction")]
+	async fn trace_transaction(&self, transaction_hash: H256) -> RpcResult<Vec<FlatCallTrace>>;
+
+	/// Returns the flat call traces matching the given filter.
+	///
+	/// ## References
+	///
+	/// - <https://openethereum.github.io/JSONRPC-trace-module#trace_filter>
+	#[method(name = "trace_filter")]
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<FlatCallTrace>>;
+}
+
 pub struct DebugRpcServerImpl {
 	client: client::Client,
 }
@@ -94,18 +122,18 @@ impl DebugRpcServer for DebugRpcServerImpl {
 	async fn trace_block_by_number(
 		&self,
 		block: BlockNumberOrTag,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Vec<TransactionTrace>> {
-		let TracerConfig { config, timeout } = tracer_config;
+		let TracerConfig { config, timeout } = tracer_config.unwrap_or_default();
 		with_timeout(timeout, self.client.trace_block_by_number(block, config)).await
 	}
 
 	async fn trace_transaction(
 		&self,
 		transaction_hash: H256,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Trace> {
-		let TracerConfig { config, timeout } = tracer_config;
+		let TracerConfig { config, timeout } = tracer_config.unwrap_or_default();
 		with_timeout(timeout, self.client.trace_transaction(transaction_hash, config)).await
 	}
 
@@ -113,9 +141,9 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		&self,
 		transaction: GenericTransaction,
 		block: BlockNumberOrTagOrHash,
-		tracer_config: TracerConfig,
+		tracer_config: Option<TracerConfig>,
 	) -> RpcResult<Trace> {
-		let TracerConfig { config, timeout } = tracer_config;
+		let TracerConfig { config, timeout } = tracer_config.unwrap_or_default();
 		with_timeout(timeout, self.client.trace_call(transaction, block, config)).await
 	}
 
@@ -123,3 +151,28 @@ impl DebugRpcServer for DebugRpcServerImpl {
 		sc_service::Result::Ok(self.client.get_automine().await)
 	}
 }
+
+pub struct TraceRpcServerImpl {
+	client: client::Client,
+}
+
+impl TraceRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+#[async_trait]
+impl TraceRpcServer for TraceRpcServerImpl {
+	async fn trace_block(&self, block: BlockNumberOrTag) -> RpcResult<Vec<FlatCallTrace>> {
+		Ok(self.client.trace_block_flat(block).await?)
+	}
+
+	async fn trace_transaction(&self, transaction_hash: H256) -> RpcResult<Vec<FlatCallTrace>> {
+		Ok(self.client.trace_transaction_flat(transaction_hash).await?)
+	}
+
+	async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<FlatCallTrace>> {
+		Ok(self.client.trace_filter(filter).await?)
+	}
+}
@@ -0,0 +1,138 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Ethereum publish/subscribe JSON-RPC methods.
+use crate::*;
+use futures::{Stream, StreamExt};
+use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc, PendingSubscriptionSink};
+use sc_rpc::utils::{BoundedVecDeque, PendingSubscription};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// The kind of events an `eth_subscribe` subscription is notified of.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+	/// New best block headers.
+	NewHeads,
+	/// Logs included in new best blocks, matching the given filter.
+	///
+	/// The logs of blocks retracted by a reorg are sent again with `removed` set.
+	Logs,
+	/// Hashes of transactions submitted through this server.
+	NewPendingTransactions,
+}
+
+/// An item sent to an `eth_subscribe` subscriber.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum SubscriptionItem {
+	/// The header of a new best block.
+	Header(Box<BlockHeader>),
+	/// A log matching the subscription filter.
+	Log(Log),
+	/// The hash of a new pending transaction.
+	TransactionHash(H256),
+}
+
+/// Ethereum publish/subscribe JSON-RPC apis.
+#[rpc(server, client)]
+pub trait EthPubSubRpc {
+	/// Subscribes to new best blocks, logs or pending transactions.
+	///
+	/// The `filter` is only used by `logs` subscriptions, where its `address` and `topics` are
+	/// matched against the logs of each new best block. Block range criteria are ignored.
+	///
+	/// ## References
+	///
+	/// - <https://geth.ethereum.org/docs/interacting-with-geth/rpc/pubsub>
+	#[subscription(
+		name = "eth_subscribe" => "eth_subscription",
+		unsubscribe = "eth_unsubscribe",
+		item = SubscriptionItem
+	)]
+	async fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>)
+		-> SubscriptionResult;
+}
+
+pub struct EthPubSubRpcServerImpl {
+	client: client::Client,
+}
+
+impl EthPubSubRpcServerImpl {
+	pub fn new(client: client::Client) -> Self {
+		Self { client }
+	}
+}
+
+/// Turn a broadcast receiver into a stream, skipping over lagged items.
+fn broadcast_stream<T: Clone + Send + 'static>(
+	receiver: broadcast::Receiver<T>,
+) -> impl Stream<Item = T> + Send {
+	futures::stream::unfold(receiver, |mut receiver| async move {
+		loop {
+			match receiver.recv().await {
+				Ok(item) => return Some((item, receiver)),
+				Err(RecvError::Lagged(skipped)) => {
+					log::debug!(target: LOG_TARGET, "eth_subscribe subscriber lagged, skipped {skipped} items");
+				},
+				Err(RecvError::Closed) => return None,
+			}
+		}
+	})
+}
+
+#[async_trait]
+impl EthPubSubRpcServer for EthPubSubRpcServerImpl {
+	async fn subscribe(
+		&self,
+		pending: PendingSubscriptionSink,
+		kind: SubscriptionKind,
+		filter: Option<Filter>,
+	) -> SubscriptionResult {
+		log::trace!(target: LOG_TARGET, "eth_subscribe kind={kind:?} filter={filter:?}");
+
+		let stream = match kind {
+			SubscriptionKind::NewHeads => broadcast_stream(self.client.subscribe_evm_blocks())
+				.map(|notification| SubscriptionItem::Header(Box::new(notification.header.clone())))
+				.boxed(),
+			SubscriptionKind::Logs => {
+				let filter = filter.unwrap_or_default();
+				broadcast_stream(self.client.subscribe_evm_blocks())
+					.flat_map(move |notification| {
+						let logs = notification
+							.logs
+							.iter()
+							.filter(|log| filter.matches(log))
+							.cloned()
+							.map(SubscriptionItem::Log)
+							.collect::<Vec<_>>();
+						futures::stream::iter(logs)
+					})
+					.boxed()
+			},
+			SubscriptionKind::NewPendingTransactions =>
+				broadcast_stream(self.client.subscribe_pending_transactions())
+					.map(SubscriptionItem::TransactionHash)
+					.boxed(),
+		};
+
+		PendingSubscription::from(pending)
+			.pipe_from_stream(stream, BoundedVecDeque::default())
+			.await;
+		Ok(())
+	}
+}
@@ -0,0 +1,173 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Offline backfill of the receipts database.
+use crate::{
+	client::{Client, SubstrateBlockNumber},
+	BackfillBlock, ClientError, LOG_TARGET,
+};
+use futures::{StreamExt, TryStreamExt};
+use std::{
+	ops::RangeInclusive,
+	sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Backfill the receipts and logs of a range of blocks into the database.
+///
+/// The range is split into batches that are processed concurrently. Each batch is written in a
+/// single database transaction along with a checkpoint, so that an interrupted backfill resumes
+/// from the batches that were not completed yet.
+#[derive(Debug, Clone, clap::Args)]
+pub struct BackfillCmd {
+	/// The first block to index.
+	#[clap(long, default_value = "0")]
+	pub from: SubstrateBlockNumber,
+
+	/// The last block to index. Defaults to the latest finalized block.
+	#[clap(long)]
+	pub to: Option<SubstrateBlockNumber>,
+
+	/// The number of blocks written to the database in a single transaction.
+	#[clap(long, default_value = "100", value_parser = clap::value_parser!(u32).range(1..))]
+	pub batch_size: SubstrateBlockNumber,
+
+	/// The number of batches processed concurrently.
+	#[clap(long, default_value = "8")]
+	pub concurrency: usize,
+
+	/// Index again the batches that were completed by a previous run.
+	#[clap(long)]
+	pub reindex: bool,
+
+	/// Verify the batches that were completed by a previous run against the node, and index
+	/// again the blocks that are not consistent.
+	#[clap(long, conflicts_with = "reindex")]
+	pub verify: bool,
+}
+
+impl BackfillCmd {
+	/// Run the backfill against the given client.
+	pub async fn run(&self, client: &Client) -> anyhow::Result<()> {
+		let to = match self.to {
+			Some(to) => to,
+			None => client.latest_finalized_block().await.number(),
+		};
+		anyhow::ensure!(self.from <= to, "invalid block range #{}..=#{to}", self.from);
+
+		let completed = client.receipt_provider().backfill_checkpoints(self.from..=to).await?;
+		let batches = (self.from..=to)
+			.step_by(self.batch_size as usize)
+			.map(|start| start..=start.saturating_add(self.batch_size - 1).min(to))
+			.collect::<Vec<_>>();
+
+		let total = batches.len();
+		let processed = AtomicUsize::new(0);
+		let repaired = AtomicUsize::new(0);
+		log::info!(target: LOG_TARGET, "🗄️ Backfilling blocks #{}..=#{to} in {total} batches", self.from);
+
+		futures::stream::iter(batches)
+			.map(Ok)
+			.try_for_each_concurrent(self.concurrency.max(1), |batch| {
+				let is_completed = completed
+					.iter()
+					.any(|done| done.start() <= batch.start() && batch.end() <= done.end());
+				let (processed, repaired) = (&processed, &repaired);
+
+				async move {
+					match (is_completed, self.reindex, self.verify) {
+						(true, false, false) => {},
+						(true, false, true) => {
+							let count = verify_batch(client, batch.clone()).await?;
+							repaired.fetch_add(count, Ordering::Relaxed);
+						},
+						_ => index_batch(client, batch.clone()).await?,
+					}
+
+					let processed = processed.fetch_add(1, Ordering::Relaxed) + 1;
+					log::info!(target: LOG_TARGET, "🗄️ Processed blocks #{}..=#{} ({processed}/{total})", batch.start(), batch.end());
+					Ok::<_, ClientError>(())
+				}
+			})
+			.await?;
+
+		log::info!(
+			target: LOG_TARGET,
+			"🗄️ Finished backfilling blocks #{}..=#{to}, {} blocks repaired",
+			self.from,
+			repaired.load(Ordering::Relaxed)
+		);
+		Ok(())
+	}
+}
+
+/// Fetch the receipts of the given block from the node.
+async fn fetch_block(
+	client: &Client,
+	number: SubstrateBlockNumber,
+) -> Result<BackfillBlock, ClientError> {
+	let substrate_hash = client.get_block_hash(number).await?.ok_or(ClientError::BlockNotFound)?;
+	let block = client.block_by_hash(&substrate_hash).await?.ok_or(ClientError::BlockNotFound)?;
+	let ethereum_hash = client
+		.runtime_api(substrate_hash)
+		.eth_block_hash(number.into())
+		.await?
+		.ok_or(ClientError::EthereumBlockNotFound)?;
+	let receipts = client.receipt_provider().receipts_from_block(&block).await?;
+
+	Ok(BackfillBlock { number, substrate_hash, ethereum_hash, receipts })
+}
+
+/// Index all the blocks of the given batch.
+async fn index_batch(
+	client: &Client,
+	batch: RangeInclusive<SubstrateBlockNumber>,
+) -> Result<(), ClientError> {
+	let mut blocks = Vec::new();
+	for number in batch.clone() {
+		blocks.push(fetch_block(client, number).await?);
+	}
+
+	client.receipt_provider().insert_backfill_batch(&blocks, batch).await
+}
+
+/// Verify the blocks of the given batch, index again the inconsistent ones and return their
+/// count.
+async fn verify_batch(
+	client: &Client,
+	batch: RangeInclusive<SubstrateBlockNumber>,
+) -> Result<usize, ClientError> {
+	let receipt_provider = client.receipt_provider();
+	let mut inconsistent = Vec::new();
+
+	for number in batch.clone() {
+		let block = fetch_block(client, number).await?;
+		let receipts_count = receipt_provider.receipts_count_per_block(&block.substrate_hash).await;
+		let ethereum_hash = receipt_provider.get_ethereum_hash(&block.substrate_hash).await;
+
+		if receipts_count != Some(block.receipts.len()) ||
+			ethereum_hash != Some(block.ethereum_hash)
+		{
+			log::warn!(target: LOG_TARGET, "🗄️ Block #{number} is not consistent, indexing it again");
+			inconsistent.push(block);
+		}
+	}
+
+	let count = inconsistent.len();
+	if count > 0 {
+		receipt_provider.insert_backfill_batch(&inconsistent, batch).await?;
+	}
+	Ok(count)
+}
@@ -16,10 +16,12 @@
 // limitations under the License.
 //! The Ethereum JSON-RPC server.
 use crate::{
+	backfill::BackfillCmd,
 	client::{connect, Client, SubscriptionType, SubstrateBlockNumber},
-	DebugRpcServer, DebugRpcServerImpl, EthRpcServer, EthRpcServerImpl, PolkadotRpcServer,
-	PolkadotRpcServerImpl, ReceiptExtractor, ReceiptProvider, SubxtBlockInfoProvider,
-	SystemHealthRpcServer, SystemHealthRpcServerImpl, LOG_TARGET,
+	DebugRpcServer, DebugRpcServerImpl, EthPubSubRpcServer, EthPubSubRpcServerImpl, EthRpcServer,
+	EthRpcServerImpl, PolkadotRpcServer, PolkadotRpcServerImpl, ReceiptExtractor, ReceiptProvider,
+	SubxtBlockInfoProvider, SystemHealthRpcServer, SystemHealthRpcServerImpl, TraceRpcServer,
+	TraceRpcServerImpl, LOG_TARGET,
 };
 use clap::Parser;
 use futures::{future::BoxFuture, pin_mut, FutureExt};
@@ -76,6 +78,17 @@ pub struct CliCommand {
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub prometheus_params: PrometheusParams,
+
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub subcommand: Option<Subcommand>,
+}
+
+/// The available subcommands. The server is started when none is given.
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommand {
+	/// Backfill the receipts database from the node, resuming from the last run.
+	Backfill(BackfillCmd),
 }
 
 /// Initialize the logger
@@ -99,51 +112,54 @@ fn init_logger(params: &SharedParams) -> anyhow::Result<()> {
 	Ok(())
 }
 
-fn build_client(
-	tokio_handle: &tokio::runtime::Handle,
+async fn connect_client(
 	cache_size: usize,
 	earliest_receipt_block: Option<SubstrateBlockNumber>,
 	node_rpc_url: &str,
 	database_url: &str,
-	abort_signal: Signals,
 ) -> anyhow::Result<Client> {
-	let fut = async {
-		let (api, rpc_client, rpc) = connect(node_rpc_url).await?;
-		let block_provider = SubxtBlockInfoProvider::new( api.clone(), rpc.clone()).await?;
-
-		let (pool, keep_latest_n_blocks) = if database_url == IN_MEMORY_DB {
-			log::warn!( target: LOG_TARGET, "💾 Using in-memory database, keeping only {cache_size} blocks in memory");
-			// see sqlite in-memory issue: https://github.com/launchbadge/sqlx/issues/2510
-			let pool = SqlitePoolOptions::new()
-					.max_connections(1)
-					.idle_timeout(None)
-					.max_lifetime(None)
-					.connect(database_url).await?;
-
-			(pool, Some(cache_size))
-		} else {
-			(SqlitePoolOptions::new().connect(database_url).await?, None)
-		};
-
-		let receipt_extractor = ReceiptExtractor::new(
-			api.clone(),
-			earliest_receipt_block,
-		).await?;
-
-		let receipt_provider = ReceiptProvider::new(
-				pool,
-				block_provider.clone(),
-				receipt_extractor.clone(),
-				keep_latest_n_blocks,
-			)
+	let (api, rpc_client, rpc) = connect(node_rpc_url).await?;
+	let block_provider = SubxtBlockInfoProvider::new(api.clone(), rpc.clone()).await?;
+
+	let (pool, keep_latest_n_blocks) = if database_url == IN_MEMORY_DB {
+		log::warn!( target: LOG_TARGET, "💾 Using in-memory database, keeping only {cache_size} blocks in memory");
+		// see sqlite in-memory issue: https://github.com/launchbadge/sqlx/issues/2510
+		let pool = SqlitePoolOptions::new()
+			.max_connections(1)
+			.idle_timeout(None)
+			.max_lifetime(None)
+			.connect(database_url)
 			.await?;
 
-		let client =
-			Client::new(api, rpc_client, rpc, block_provider, receipt_provider).await?;
+		(pool, Some(cache_size))
+	} else {
+		(SqlitePoolOptions::new().connect(database_url).await?, None)
+	};
 
-		Ok(client)
-	}
-	.fuse();
+	let receipt_extractor = ReceiptExtractor::new(api.clone(), earliest_receipt_block).await?;
+
+	let receipt_provider = ReceiptProvider::new(
+		pool,
+		block_provider.clone(),
+		receipt_extractor.clone(),
+		keep_latest_n_blocks,
+	)
+	.await?;
+
+	let client = Client::new(api, rpc_client, rpc, block_provider, receipt_provider).await?;
+
+	Ok(client)
+}
+
+fn build_client(
+	tokio_handle: &tokio::runtime::Handle,
+	cache_size: usize,
+	earliest_receipt_block: Option<SubstrateBlockNumber>,
+	node_rpc_url: &str,
+	database_url: &str,
+	abort_signal: Signals,
+) -> anyhow::Result<Client> {
+	let fut = connect_client(cache_size, earliest_receipt_block, node_rpc_url, database_url).fuse();
 	pin_mut!(fut);
 
 	match tokio_handle.block_on(abort_signal.try_until_signal(fut)) {
@@ -164,11 +180,33 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		earliest_receipt_block,
 		index_last_n_blocks,
 		shared_params,
+		subcommand,
 		..
 	} = cmd;
 
 	#[cfg(not(test))]
 	init_logger(&shared_params)?;
+
+	if let Some(Subcommand::Backfill(backfill_cmd)) = subcommand {
+		if database_url == IN_MEMORY_DB {
+			anyhow::bail!(
+				"The backfill command requires a persistent database, see --database-url"
+			);
+		}
+
+		let tokio_runtime = sc_cli::build_runtime()?;
+		let signals = tokio_runtime.block_on(async { Signals::capture() })?;
+		let backfill = async {
+			let client =
+				connect_client(cache_size, earliest_receipt_block, &node_rpc_url, &database_url)
+					.await?;
+			backfill_cmd.run(&client).await
+		};
+		return match tokio_runtime.block_on(signals.try_until_signal(backfill.fuse())) {
+			Ok(result) => result,
+			Err(_) => anyhow::bail!("Process interrupted"),
+		};
+	}
 	let is_dev = shared_params.dev;
 	let rpc_addrs: Option<Vec<sc_service::config::RpcEndpoint>> = rpc_params
 		.rpc_addr(is_dev, false, 8545)?
@@ -189,6 +227,7 @@ pub fn run(cmd: CliCommand) -> anyhow::Result<()> {
 		rate_limit: rpc_params.rpc_rate_limit,
 		rate_limit_whitelisted_ips: rpc_params.rpc_rate_limit_whitelisted_ips,
 		rate_limit_trust_proxy_headers: rpc_params.rpc_rate_limit_trust_proxy_headers,
+		quotas: rpc_params.rpc_quotas()?,
 		request_logger_limit: if is_dev { 1024 * 1024 } else { 1024 },
 	};
 
@@ -267,12 +306,16 @@ fn rpc_module(is_dev: bool, client: Client) -> Result<RpcModule<()>, sc_service:
 
 	let health_api = SystemHealthRpcServerImpl::new(client.clone()).into_rpc();
 	let debug_api = DebugRpcServerImpl::new(client.clone()).into_rpc();
+	let trace_api = TraceRpcServerImpl::new(client.clone()).into_rpc();
+	let pubsub_api = EthPubSubRpcServerImpl::new(client.clone()).into_rpc();
 	let polkadot_api = PolkadotRpcServerImpl::new(client).into_rpc();
 
 	let mut module = RpcModule::new(());
 	module.merge(eth_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(health_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module.merge(debug_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(trace_api).map_err(|e| sc_service::Error::Application(e.into()))?;
+	module.merge(pubsub_api).map_err(|e| sc_service::Error::Application(e.into()))?;
 	module
 		.merge(polkadot_api)
 		.map_err(|e| sc_service::Error::Application(e.into()))?;
@@ -22,25 +22,31 @@ pub(crate) mod storage_api;
 
 use crate::{
 	subxt_client::{self, revive::calls::types::EthTransact, SrcChainConfig},
-	BlockInfoProvider, BlockTag, FeeHistoryProvider, ReceiptProvider, SubxtBlockInfoProvider,
+	BlockInfoProvider, BlockTag, FeeHistoryProvider, FilterChanges, FilterKind, FilterProvider,
+	PendingTransaction, PendingTransactionProvider, ReceiptProvider, SubxtBlockInfoProvider,
 	TracerType, TransactionInfo,
 };
+use futures::StreamExt;
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObjectOwned};
 use pallet_revive::{
 	evm::{
-		decode_revert_reason, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, FeeHistoryResult,
-		Filter, GenericTransaction, HashesOrTransactionInfos, Log, ReceiptInfo, SyncingProgress,
-		SyncingStatus, Trace, TransactionSigned, TransactionTrace, H256,
+		decode_revert_reason, AccountProof, Block, BlockHeader, BlockNumberOrTag,
+		BlockNumberOrTagOrHash, FeeHistoryResult, Filter, FilterResults, FlatCallTrace,
+		GenericTransaction, HashesOrTransactionInfos, Log, ReceiptInfo, SimulateCallError,
+		SimulateCallResult, SimulatePayload, SimulatedBlock, StorageProof, SyncingProgress,
+		SyncingStatus, Trace, TraceFilter, TransactionSigned, TransactionTrace, H160, H256, U256,
 	},
 	EthTransactError,
 };
 use runtime_api::RuntimeApi;
+use sc_rpc_api::state::ReadProof;
 use sp_runtime::traits::Block as BlockT;
 use sp_weights::Weight;
 use std::{ops::Range, sync::Arc, time::Duration};
 use storage_api::StorageApi;
 use subxt::{
 	backend::{
+		chain_head::ChainHeadRpcMethods,
 		legacy::{rpc_methods::SystemHealth, LegacyRpcMethods},
 		rpc::{
 			reconnecting_rpc_client::{ExponentialBackoff, RpcClient as ReconnectingRpcClient},
@@ -48,7 +54,7 @@ use subxt::{
 		},
 	},
 	config::{HashFor, Header},
-	ext::subxt_rpcs::rpc_params,
+	ext::subxt_rpcs::{methods::chain_head::TransactionStatus, rpc_params},
 	Config, OnlineClient,
 };
 use thiserror::Error;
@@ -69,6 +75,23 @@ pub type SubstrateBlockHash = HashFor<SrcChainConfig>;
 /// The runtime balance type.
 pub type Balance = u128;
 
+/// A new best block, along with the logs emitted by its transactions.
+#[derive(Debug, Clone)]
+pub struct BlockNotification {
+	/// The header of the Ethereum block.
+	pub header: BlockHeader,
+	/// The logs of the blocks retracted by a reorg, marked as removed, newest first, followed by
+	/// the logs emitted in this block.
+	pub logs: Vec<Log>,
+}
+
+/// A block of the best chain notified to `eth_subscribe` subscribers.
+struct NotifiedBlock {
+	hash: SubstrateBlockHash,
+	number: SubstrateBlockNumber,
+	logs: Vec<Log>,
+}
+
 /// The subscription type used to listen to new blocks.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SubscriptionType {
@@ -130,12 +153,57 @@ pub enum ClientError {
 	/// Receipt data length mismatch.
 	#[error("Receipt data length mismatch")]
 	ReceiptDataLengthMismatch,
+	/// The filter was not found, or has expired.
+	#[error("filter not found")]
+	FilterNotFound,
+	/// The filter criteria are not supported.
+	#[error("invalid filter: blockHash is not supported")]
+	InvalidFilter,
+	/// The maximum number of installed filters was reached.
+	#[error("too many installed filters")]
+	TooManyFilters,
+	/// The block range of a `trace_filter` request is too large.
+	#[error("block range is too large, the maximum is {MAX_TRACE_FILTER_BLOCKS} blocks")]
+	TraceFilterRangeTooLarge,
+	/// The transaction was rejected by the transaction pool of the node.
+	#[error("transaction rejected: {0}")]
+	TransactionRejected(String),
+	/// The subscription watching a submitted transaction was closed before any event.
+	#[error("transaction watch subscription closed")]
+	TransactionWatchClosed,
+	/// A transaction replacing another one does not pay a higher fee.
+	#[error("replacement transaction underpriced")]
+	ReplacementUnderpriced,
 }
 const LOG_TARGET: &str = "eth-rpc::client";
 
 const REVERT_CODE: i32 = 3;
 
+/// The error codes of transactions rejected by the transaction pool, as used by `author_*` RPCs.
+const VERIFICATION_ERROR_CODE: i32 = 1002;
+const POOL_INVALID_TX_CODE: i32 = 1010;
+const POOL_UNKNOWN_VALIDITY_CODE: i32 = POOL_INVALID_TX_CODE + 1;
+const POOL_TEMPORARILY_BANNED_CODE: i32 = POOL_INVALID_TX_CODE + 2;
+const POOL_ALREADY_IMPORTED_CODE: i32 = POOL_INVALID_TX_CODE + 3;
+const POOL_TOO_LOW_PRIORITY_CODE: i32 = POOL_INVALID_TX_CODE + 4;
+const POOL_CYCLE_DETECTED_CODE: i32 = POOL_INVALID_TX_CODE + 5;
+const POOL_IMMEDIATELY_DROPPED_CODE: i32 = POOL_INVALID_TX_CODE + 6;
+const POOL_UNACTIONABLE_CODE: i32 = POOL_INVALID_TX_CODE + 8;
+const POOL_NO_TAGS_CODE: i32 = POOL_INVALID_TX_CODE + 9;
+const POOL_INVALID_BLOCK_ID_CODE: i32 = POOL_INVALID_TX_CODE + 10;
+const POOL_FUTURE_TX_CODE: i32 = POOL_INVALID_TX_CODE + 11;
+const POOL_BUNDLES_NOT_SUPPORTED_CODE: i32 = POOL_INVALID_TX_CODE + 12;
+const POOL_INVALID_BUNDLE_CODE: i32 = POOL_INVALID_TX_CODE + 13;
+
 const NOTIFIER_CAPACITY: usize = 16;
+
+const PENDING_TX_NOTIFIER_CAPACITY: usize = 1024;
+
+/// The number of best blocks whose logs are kept to notify them as removed on reorgs.
+const NOTIFIED_BLOCKS: usize = 256;
+
+/// The maximum number of blocks traced by a single `trace_filter` request.
+const MAX_TRACE_FILTER_BLOCKS: SubstrateBlockNumber = 1000;
 impl From<ClientError> for ErrorObjectOwned {
 	fn from(err: ClientError) -> Self {
 		match err {
@@ -155,12 +223,61 @@ impl From<ClientError> for ErrorObjectOwned {
 			},
 			ClientError::TransactError(EthTransactError::Message(msg)) =>
 				ErrorObjectOwned::owned::<String>(CALL_EXECUTION_FAILED_CODE, msg, None),
+			ClientError::TransactionRejected(reason) => {
+				let (code, message) = pool_error(&reason);
+				ErrorObjectOwned::owned::<String>(code, message, None)
+			},
 			_ =>
 				ErrorObjectOwned::owned::<String>(CALL_EXECUTION_FAILED_CODE, err.to_string(), None),
 		}
 	}
 }
 
+/// Map the error of a transaction rejected by `transactionWatch_v1_submitAndWatch` to the code
+/// and message returned by `author_submitExtrinsic` for the same rejection.
+///
+/// The errors are only available as text, which `pool_errors_match_author_errors` checks against
+/// the errors of the transaction pool.
+fn pool_error(error: &str) -> (i32, String) {
+	if let Some(reason) = error.strip_prefix("Verification error: ") {
+		return (VERIFICATION_ERROR_CODE, format!("Verification Error: {reason}"))
+	}
+	if error.starts_with("Unknown transaction validity: ") {
+		return (POOL_UNKNOWN_VALIDITY_CODE, "Unknown Transaction Validity".into())
+	}
+	if let Some((old, new)) = error
+		.strip_prefix("The priority of the transaction is too low (pool ")
+		.and_then(|priorities| priorities.strip_suffix(')'))
+		.and_then(|priorities| priorities.split_once(" > current "))
+	{
+		return (POOL_TOO_LOW_PRIORITY_CODE, format!("Priority is too low: ({old} vs {new})"))
+	}
+	match error {
+		"Transaction is temporarily banned" =>
+			(POOL_TEMPORARILY_BANNED_CODE, "Transaction is temporarily banned".into()),
+		"Transaction is already imported" =>
+			(POOL_ALREADY_IMPORTED_CODE, "Transaction Already Imported".into()),
+		"The transaction contains a cyclic dependency" =>
+			(POOL_CYCLE_DETECTED_CODE, "Cycle Detected".into()),
+		"The transaction could not enter the pool because of the limit" |
+		"Extrinsic dropped from the pool due to exceeding limits" =>
+			(POOL_IMMEDIATELY_DROPPED_CODE, "Immediately Dropped".into()),
+		"Transaction cannot be propagated and the local node does not author blocks" =>
+			(POOL_UNACTIONABLE_CODE, "Unactionable".into()),
+		"Transaction does not provide any tags, so the pool cannot identify it" =>
+			(POOL_NO_TAGS_CODE, "No tags provided".into()),
+		"The provided block ID is not valid" =>
+			(POOL_INVALID_BLOCK_ID_CODE, "The provided block ID is not valid".into()),
+		"The pool is not accepting future transactions" =>
+			(POOL_FUTURE_TX_CODE, "The pool is not accepting future transactions".into()),
+		"The pool does not support transaction bundles" =>
+			(POOL_BUNDLES_NOT_SUPPORTED_CODE, "Bundles not supported".into()),
+		"Transaction bundle is empty, too large, contains duplicated transactions or transactions \
+		 requiring tags it does not provide" => (POOL_INVALID_BUNDLE_CODE, "Invalid bundle".into()),
+		_ => (POOL_INVALID_TX_CODE, "Invalid Transaction".into()),
+	}
+}
+
 /// A client connect to a node and maintains a cache of the last `CACHE_SIZE` blocks.
 #[derive(Clone)]
 pub struct Client {
@@ -170,12 +287,20 @@ pub struct Client {
 	receipt_provider: ReceiptProvider,
 	block_provider: SubxtBlockInfoProvider,
 	fee_history_provider: FeeHistoryProvider,
+	filter_provider: FilterProvider,
+	pending_transaction_provider: PendingTransactionProvider,
 	chain_id: u64,
 	max_block_weight: Weight,
 	/// Whether the node has automine enabled.
 	automine: bool,
 	/// A notifier, that informs subscribers of new best blocks.
 	block_notifier: Option<tokio::sync::broadcast::Sender<H256>>,
+	/// A notifier, that informs `eth_subscribe` subscribers of new best Ethereum blocks.
+	evm_block_notifier: tokio::sync::broadcast::Sender<Arc<BlockNotification>>,
+	/// The last best blocks notified to `eth_subscribe` subscribers, oldest first.
+	notified_blocks: Arc<Mutex<std::collections::VecDeque<NotifiedBlock>>>,
+	/// A notifier, that informs `eth_subscribe` subscribers of newly submitted transactions.
+	pending_tx_notifier: tokio::sync::broadcast::Sender<H256>,
 	/// A lock to ensure only one subscription can perform write operations at a time.
 	subscription_lock: Arc<Mutex<()>>,
 }
@@ -245,11 +370,16 @@ impl Client {
 			receipt_provider,
 			block_provider,
 			fee_history_provider: FeeHistoryProvider::default(),
+			filter_provider: FilterProvider::default(),
+			pending_transaction_provider: PendingTransactionProvider::default(),
 			chain_id,
 			max_block_weight,
 			automine,
 			block_notifier: automine
 				.then(|| tokio::sync::broadcast::channel::<H256>(NOTIFIER_CAPACITY).0),
+			evm_block_notifier: tokio::sync::broadcast::channel(NOTIFIER_CAPACITY).0,
+			notified_blocks: Default::default(),
+			pending_tx_notifier: tokio::sync::broadcast::channel(PENDING_TX_NOTIFIER_CAPACITY).0,
 			subscription_lock: Arc::new(Mutex::new(())),
 		};
 
@@ -362,6 +492,7 @@ impl Client {
 		log::info!(target: LOG_TARGET, "🔌 Subscribing to new blocks ({subscription_type:?})");
 		self.subscribe_new_blocks(subscription_type, |block| async {
 			let hash = block.hash();
+			let (number, parent_hash) = (block.number(), block.header().parent_hash);
 			let evm_block = self.runtime_api(hash).eth_block().await?;
 			let (_, receipts): (Vec<_>, Vec<_>) = self
 				.receipt_provider
@@ -373,6 +504,11 @@ impl Client {
 			self.block_provider.update_latest(Arc::new(block), subscription_type).await;
 			self.fee_history_provider.update_fee_history(&evm_block, &receipts).await;
 
+			if subscription_type == SubscriptionType::BestBlocks {
+				let logs = receipts.into_iter().flat_map(|receipt| receipt.logs).collect();
+				self.notify_evm_block(hash, number, parent_hash, evm_block, logs).await?;
+			}
+
 			// Only broadcast for best blocks to avoid duplicate notifications.
 			match (subscription_type, &self.block_notifier) {
 				(SubscriptionType::BestBlocks, Some(sender)) if sender.receiver_count() > 0 => {
@@ -385,6 +521,62 @@ impl Client {
 		.await
 	}
 
+	/// Notify `eth_subscribe` subscribers of a new best block.
+	///
+	/// The logs of the previously notified blocks that are not ancestors of the new best block
+	/// are notified again, marked as removed.
+	async fn notify_evm_block(
+		&self,
+		hash: SubstrateBlockHash,
+		number: SubstrateBlockNumber,
+		parent_hash: SubstrateBlockHash,
+		evm_block: Block,
+		logs: Vec<Log>,
+	) -> Result<(), ClientError> {
+		let mut notified_blocks = self.notified_blocks.lock().await;
+		let mut removed_logs = Vec::new();
+		let (mut ancestor, mut ancestor_number) = (parent_hash, number.saturating_sub(1));
+		while let Some(last) = notified_blocks.back() {
+			if last.number < ancestor_number {
+				// Walk the new best chain down to the last notified block, giving up on gaps
+				// larger than the notified blocks.
+				let parent =
+					if ancestor_number - last.number > NOTIFIED_BLOCKS as SubstrateBlockNumber {
+						None
+					} else {
+						self.block_provider.block_by_hash(&ancestor).await?
+					};
+				let Some(parent) = parent else {
+					notified_blocks.clear();
+					break;
+				};
+				(ancestor, ancestor_number) = (parent.header().parent_hash, ancestor_number - 1);
+				continue;
+			}
+			if last.number == ancestor_number && last.hash == ancestor {
+				break;
+			}
+			let retracted = notified_blocks.pop_back().expect("Checked above; qed");
+			removed_logs.extend(retracted.logs.into_iter().rev().map(|mut log| {
+				log.removed = true;
+				log
+			}));
+		}
+
+		if notified_blocks.len() == NOTIFIED_BLOCKS {
+			notified_blocks.pop_front();
+		}
+		notified_blocks.push_back(NotifiedBlock { hash, number, logs: logs.clone() });
+
+		if self.evm_block_notifier.receiver_count() > 0 {
+			removed_logs.extend(logs);
+			let _ = self
+				.evm_block_notifier
+				.send(Arc::new(BlockNotification { header: evm_block.into(), logs: removed_logs }));
+		}
+		Ok(())
+	}
+
 	/// Cache old blocks up to the given block number.
 	pub async fn subscribe_and_cache_blocks(
 		&self,
@@ -446,6 +638,11 @@ impl Client {
 		RuntimeApi::new(self.api.runtime_api().at(block_hash))
 	}
 
+	/// Get the receipt provider.
+	pub fn receipt_provider(&self) -> &ReceiptProvider {
+		&self.receipt_provider
+	}
+
 	/// Get the latest finalized block.
 	pub async fn latest_finalized_block(&self) -> Arc<SubstrateBlock> {
 		self.block_provider.latest_finalized_block().await
@@ -456,20 +653,107 @@ impl Client {
 		self.block_provider.latest_block().await
 	}
 
-	/// Expose the transaction API.
+	/// Submit a transaction with `transactionWatch_v1_submitAndWatch`.
+	///
+	/// Resolves once the transaction is validated by the node. The given pending transaction is
+	/// then tracked until the transaction is finalized or dropped, unless too many transactions
+	/// are tracked already.
 	pub async fn submit(
 		&self,
 		call: subxt::tx::DefaultPayload<EthTransact>,
+		mut pending_tx: Option<PendingTransaction>,
 	) -> Result<(), ClientError> {
+		if let Some(tx) = &pending_tx {
+			self.pending_transaction_provider.check_replacement(tx).await?;
+			if self.pending_transaction_provider.is_full().await {
+				log::debug!(target: LOG_TARGET, "Too many tracked transactions, not tracking {:?}", tx.hash);
+				pending_tx = None;
+			}
+		}
+
 		let ext = self.api.tx().create_unsigned(&call).map_err(ClientError::from)?;
-		let hash: H256 = self
-			.rpc_client
-			.request("author_submitExtrinsic", rpc_params![to_hex(ext.encoded())])
+		let mut events = ChainHeadRpcMethods::<SrcChainConfig>::new(self.rpc_client.clone())
+			.transactionwatch_v1_submit_and_watch(ext.encoded())
 			.await?;
-		log::debug!(target: LOG_TARGET, "Submitted transaction with substrate hash: {hash:?}");
+
+		let status = match events.next().await {
+			Some(Ok(
+				TransactionStatus::Error { error } |
+				TransactionStatus::Invalid { error } |
+				TransactionStatus::Dropped { error },
+			)) => return Err(ClientError::TransactionRejected(error)),
+			Some(Ok(status)) => status,
+			Some(Err(err)) => return Err(err.into()),
+			None => return Err(ClientError::TransactionWatchClosed),
+		};
+		log::debug!(target: LOG_TARGET, "Submitted transaction with substrate hash: {:?}", ext.hash());
+
+		if let Some(tx) = pending_tx {
+			let hash = tx.hash;
+			let provider = self.pending_transaction_provider.clone();
+			provider.insert(tx).await;
+			if provider.update(&hash, &status).await {
+				tokio::spawn(async move { provider.watch(hash, events).await });
+			}
+		}
 		Ok(())
 	}
 
+	/// Get a transaction submitted through this server, which is not yet in the receipt index.
+	pub async fn pending_transaction(&self, tx_hash: &H256) -> Option<TransactionInfo> {
+		let tx = self.pending_transaction_provider.transaction(tx_hash).await?;
+		let block_number = self.block_provider.latest_block_number().await.saturating_add(1);
+		Some(TransactionInfo {
+			block_number: block_number.into(),
+			from: tx.from,
+			hash: tx.hash,
+			transaction_signed: tx.signed_tx,
+			..Default::default()
+		})
+	}
+
+	/// Get the nonce of the next transaction of `address`, including its pending transactions.
+	pub async fn pending_nonce(&self, address: &H160, nonce: U256) -> U256 {
+		self.pending_transaction_provider.next_nonce(address, nonce).await
+	}
+
+	/// Get the pending block, made of the transactions submitted through this server which are
+	/// not yet included in the best chain, on top of the latest block.
+	pub async fn pending_evm_block(&self, hydrated_transactions: bool) -> Option<Block> {
+		let latest = self.evm_block(self.latest_block().await, false).await?;
+		let number = latest.number.saturating_add(U256::one());
+
+		let pending_txs = self.pending_transaction_provider.pending_transactions().await;
+		let transactions = if hydrated_transactions {
+			let tx_infos = pending_txs
+				.into_iter()
+				.enumerate()
+				.map(|(index, tx)| TransactionInfo {
+					block_number: number,
+					from: tx.from,
+					hash: tx.hash,
+					transaction_index: index.into(),
+					transaction_signed: tx.signed_tx,
+					..Default::default()
+				})
+				.collect();
+			HashesOrTransactionInfos::TransactionInfos(tx_infos)
+		} else {
+			HashesOrTransactionInfos::Hashes(pending_txs.into_iter().map(|tx| tx.hash).collect())
+		};
+
+		Some(Block {
+			base_fee_per_gas: latest.base_fee_per_gas,
+			gas_limit: latest.gas_limit,
+			miner: latest.miner,
+			number,
+			parent_hash: latest.hash,
+			timestamp: latest.timestamp,
+			transactions,
+			..Default::default()
+		})
+	}
+
 	/// Get an EVM transaction receipt by hash.
 	pub async fn receipt(&self, tx_hash: &H256) -> Option<ReceiptInfo> {
 		self.receipt_provider.receipt_by_hash(tx_hash).await
@@ -715,6 +999,134 @@ impl Client {
 		runtime_api.trace_call(transaction, config).await
 	}
 
+	/// Get the flat call traces of the transactions in the given block.
+	pub async fn trace_block_flat(
+		&self,
+		at: BlockNumberOrTag,
+	) -> Result<Vec<FlatCallTrace>, ClientError> {
+		if self.receipt_provider.is_before_earliest_block(&at) {
+			return Ok(vec![]);
+		}
+
+		let block_hash = self.block_hash_for_tag(at.into()).await?;
+		self.block_flat_call_traces(block_hash).await
+	}
+
+	/// Get the flat call traces of the given transaction.
+	pub async fn trace_transaction_flat(
+		&self,
+		transaction_hash: H256,
+	) -> Result<Vec<FlatCallTrace>, ClientError> {
+		let (block_hash, transaction_index) = self
+			.receipt_provider
+			.find_transaction(&transaction_hash)
+			.await
+			.ok_or(ClientError::EthExtrinsicNotFound)?;
+
+		let block = self.tracing_block(block_hash).await?;
+		let block_number = block.header.number;
+		let runtime_api = self.runtime_api(block.header.parent_hash);
+		let trace = runtime_api
+			.trace_tx(block, transaction_index as u32, TracerType::FlatCallTracer)
+			.await?;
+
+		let ethereum_hash = self.resolve_ethereum_hash(&block_hash).await.unwrap_or(block_hash);
+		Ok(flat_call_traces_with_context(
+			trace,
+			ethereum_hash,
+			block_number,
+			transaction_hash,
+			transaction_index as u32,
+		))
+	}
+
+	/// Get the flat call traces matching the given filter.
+	pub async fn trace_filter(
+		&self,
+		filter: TraceFilter,
+	) -> Result<Vec<FlatCallTrace>, ClientError> {
+		let from = self.trace_filter_block_number(filter.from_block.clone()).await?;
+		let to = self.trace_filter_block_number(filter.to_block.clone()).await?;
+		if to.saturating_sub(from) >= MAX_TRACE_FILTER_BLOCKS {
+			return Err(ClientError::TraceFilterRangeTooLarge);
+		}
+
+		let mut skip = filter.after.unwrap_or_default();
+		let count = filter.count.unwrap_or(u64::MAX);
+		let mut traces = Vec::new();
+		for number in from..=to {
+			if traces.len() as u64 >= count {
+				break;
+			}
+			if self
+				.receipt_provider
+				.is_before_earliest_block(&BlockNumberOrTag::U256(number.into()))
+			{
+				continue;
+			}
+			let Some(block_hash) = self.get_block_hash(number).await? else { continue };
+
+			for trace in self.block_flat_call_traces(block_hash).await? {
+				if traces.len() as u64 >= count {
+					break;
+				}
+				if !filter.matches(&trace) {
+					continue;
+				}
+				if skip > 0 {
+					skip -= 1;
+					continue;
+				}
+				traces.push(trace);
+			}
+		}
+
+		Ok(traces)
+	}
+
+	/// Resolve a `trace_filter` block bound, defaulting to the latest block.
+	async fn trace_filter_block_number(
+		&self,
+		block: Option<BlockNumberOrTag>,
+	) -> Result<SubstrateBlockNumber, ClientError> {
+		let block = self
+			.block_by_number_or_tag(&block.unwrap_or_default())
+			.await?
+			.ok_or(ClientError::BlockNotFound)?;
+		Ok(block.number())
+	}
+
+	/// Get the flat call traces of the Ethereum transactions in the given block.
+	async fn block_flat_call_traces(
+		&self,
+		block_hash: H256,
+	) -> Result<Vec<FlatCallTrace>, ClientError> {
+		let block = self.tracing_block(block_hash).await?;
+		let block_number = block.header.number;
+		let runtime_api = self.runtime_api(block.header.parent_hash);
+		let traces = runtime_api.trace_block(block, TracerType::FlatCallTracer).await?;
+
+		let hashes = self
+			.receipt_provider
+			.block_transaction_hashes(&block_hash)
+			.await
+			.unwrap_or_default();
+		let ethereum_hash = self.resolve_ethereum_hash(&block_hash).await.unwrap_or(block_hash);
+
+		let traces = traces.into_iter().flat_map(|(index, trace)| {
+			let Some(transaction_hash) = hashes.get(&(index as usize)) else { return vec![] };
+			flat_call_traces_with_context(
+				trace,
+				ethereum_hash,
+				block_number,
+				*transaction_hash,
+				index,
+			)
+		});
+
+		Ok(traces.collect())
+	}
+
 	/// Get the EVM block for the given Substrate block.
 	pub async fn evm_block(
 		&self,
@@ -770,6 +1182,23 @@ impl Client {
 		self.block_notifier.clone()
 	}
 
+	/// Subscribe to new best Ethereum blocks and their logs.
+	pub fn subscribe_evm_blocks(&self) -> tokio::sync::broadcast::Receiver<Arc<BlockNotification>> {
+		self.evm_block_notifier.subscribe()
+	}
+
+	/// Subscribe to the hashes of transactions submitted through this server.
+	pub fn subscribe_pending_transactions(&self) -> tokio::sync::broadcast::Receiver<H256> {
+		self.pending_tx_notifier.subscribe()
+	}
+
+	/// Notify subscribers that the transaction with the given Ethereum hash was submitted.
+	pub fn notify_pending_transaction(&self, hash: H256) {
+		if self.pending_tx_notifier.receiver_count() > 0 {
+			let _ = self.pending_tx_notifier.send(hash);
+		}
+	}
+
 	/// Get the logs matching the given filter.
 	pub async fn logs(&self, filter: Option<Filter>) -> Result<Vec<Log>, ClientError> {
 		let logs =
@@ -777,6 +1206,205 @@ impl Client {
 		Ok(logs)
 	}
 
+	/// Install a new log filter, and return its id.
+	pub async fn new_filter(&self, filter: Filter) -> Result<U256, ClientError> {
+		let current_block = self.block_provider.latest_block_number().await;
+		self.filter_provider.install(FilterKind::Logs(filter), current_block).await
+	}
+
+	/// Install a new block filter, and return its id.
+	pub async fn new_block_filter(&self) -> Result<U256, ClientError> {
+		let current_block = self.block_provider.latest_block_number().await;
+		self.filter_provider.install(FilterKind::Blocks, current_block).await
+	}
+
+	/// Install a new pending transaction filter, and return its id.
+	pub async fn new_pending_transaction_filter(&self) -> Result<U256, ClientError> {
+		let current_block = self.block_provider.latest_block_number().await;
+		let kind = FilterKind::PendingTransactions(self.subscribe_pending_transactions());
+		self.filter_provider.install(kind, current_block).await
+	}
+
+	/// Get the changes of the given filter since it was last polled.
+	pub async fn filter_changes(&self, id: &U256) -> Result<FilterResults, ClientError> {
+		let latest_block = self.block_provider.latest_block_number().await;
+		let finalized_block = self.block_provider.latest_finalized_block().await.number();
+		let results = match self.filter_provider.poll(id, latest_block, finalized_block).await? {
+			FilterChanges::Logs(filter) => FilterResults::Logs(self.logs(Some(filter)).await?),
+			FilterChanges::Blocks(range) => {
+				let mut hashes = Vec::new();
+				for block_number in range {
+					let Some(block) = self.block_provider.block_by_number(block_number).await?
+					else {
+						continue
+					};
+					if let Some(hash) = self.resolve_ethereum_hash(&block.hash()).await {
+						hashes.push(hash);
+					}
+				}
+				FilterResults::Hashes(hashes)
+			},
+			FilterChanges::PendingTransactions(hashes) => FilterResults::Hashes(hashes),
+			FilterChanges::None => FilterResults::default(),
+		};
+		Ok(results)
+	}
+
+	/// Get all the logs matching the given log filter.
+	pub async fn filter_logs(&self, id: &U256) -> Result<Vec<Log>, ClientError> {
+		let filter = self.filter_provider.log_filter(id).await?;
+		self.logs(Some(filter)).await
+	}
+
+	/// Uninstall the given filter, returns `true` if the filter existed.
+	pub async fn uninstall_filter(&self, id: &U256) -> bool {
+		self.filter_provider.uninstall(id).await
+	}
+
+	/// Simulate blocks of calls with block and state overrides (`eth_simulateV1`).
+	///
+	/// The simulated blocks are not produced, so the block and transaction hashes of the returned
+	/// logs are zero.
+	pub async fn simulate(
+		&self,
+		payload: SimulatePayload,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<Vec<SimulatedBlock>, ClientError> {
+		let hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(hash);
+		let blocks = runtime_api.simulate(payload.block_state_calls).await?;
+
+		let blocks = blocks
+			.into_iter()
+			.map(|block| {
+				let mut log_index = 0u32;
+				let mut gas_used = U256::zero();
+				let calls = block
+					.calls
+					.into_iter()
+					.enumerate()
+					.map(|(transaction_index, call)| {
+						gas_used = gas_used.saturating_add(call.eth_gas);
+						let logs = call
+							.logs
+							.into_iter()
+							.map(|log| {
+								let log = Log {
+									address: log.address,
+									block_number: block.number,
+									data: Some(log.data),
+									log_index: log_index.into(),
+									topics: log.topics,
+									transaction_index: transaction_index.into(),
+									..Default::default()
+								};
+								log_index += 1;
+								log
+							})
+							.collect();
+
+						match call.result {
+							Ok(return_data) => SimulateCallResult {
+								return_data: return_data.into(),
+								logs,
+								gas_used: call.eth_gas,
+								status: U256::one(),
+								error: None,
+							},
+							Err(err) => {
+								let data = match &err {
+									EthTransactError::Data(data) => Some(data.clone().into()),
+									EthTransactError::Message(_) => None,
+								};
+								let err = ErrorObjectOwned::from(ClientError::TransactError(err));
+								SimulateCallResult {
+									logs,
+									gas_used: call.eth_gas,
+									status: U256::zero(),
+									error: Some(SimulateCallError {
+										code: err.code(),
+										message: err.message().to_string(),
+										data,
+									}),
+									..Default::default()
+								}
+							},
+						}
+					})
+					.collect();
+
+				SimulatedBlock {
+					number: block.number,
+					timestamp: block.timestamp,
+					base_fee_per_gas: block.base_fee,
+					gas_used,
+					calls,
+				}
+			})
+			.collect();
+
+		Ok(blocks)
+	}
+
+	/// Get the EIP-1186 proof of the given account and storage slots.
+	///
+	/// The account values are read through the runtime API, the proofs are Substrate read proofs
+	/// of the top trie and of the contract child trie, see [`AccountProof`].
+	pub async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccountProof, ClientError> {
+		let hash = self.block_hash_for_tag(block).await?;
+		let runtime_api = self.runtime_api(hash);
+		let keys = storage_keys.iter().map(|key| key.to_big_endian()).collect();
+		let info = runtime_api.account_proof_info(address, keys).await?;
+
+		let account_keys = info.account_keys.iter().map(to_hex).collect::<Vec<_>>();
+		let account_proof: ReadProof<H256> = self
+			.rpc_client
+			.request("state_getReadProof", rpc_params![account_keys, hash])
+			.await?;
+
+		let mut storage_proof = Vec::with_capacity(info.storage.len());
+		for slot in info.storage {
+			let proof = match &info.child_trie_key {
+				Some(child_trie_key) => {
+					let proof: ReadProof<H256> = self
+						.rpc_client
+						.request(
+							"state_getChildReadProof",
+							rpc_params![
+								to_hex(child_trie_key),
+								vec![to_hex(&slot.hashed_key)],
+								hash
+							],
+						)
+						.await?;
+					proof.proof.into_iter().map(|node| node.0.into()).collect()
+				},
+				None => Vec::new(),
+			};
+
+			storage_proof.push(StorageProof {
+				key: U256::from_big_endian(&slot.key),
+				value: slot.value.map_or(U256::zero(), |value| U256::from_big_endian(&value)),
+				proof,
+			});
+		}
+
+		Ok(AccountProof {
+			address,
+			account_proof: account_proof.proof.into_iter().map(|node| node.0.into()).collect(),
+			balance: info.balance,
+			code_hash: info.code_hash,
+			nonce: info.nonce,
+			storage_hash: info.storage_root,
+			storage_proof,
+		})
+	}
+
 	pub async fn fee_history(
 		&self,
 		block_count: u32,
@@ -803,6 +1431,113 @@ impl Client {
 	}
 }
 
+/// Fill in the block and transaction fields of the flat call traces of a transaction.
+fn flat_call_traces_with_context(
+	trace: Trace,
+	block_hash: H256,
+	block_number: SubstrateBlockNumber,
+	transaction_hash: H256,
+	transaction_position: u32,
+) -> Vec<FlatCallTrace> {
+	let Trace::FlatCall(traces) = trace else { return vec![] };
+	traces
+		.into_iter()
+		.map(|trace| FlatCallTrace {
+			block_hash: Some(block_hash),
+			block_number: Some(block_number.into()),
+			transaction_hash: Some(transaction_hash),
+			transaction_position: Some(transaction_position),
+			..trace
+		})
+		.collect()
+}
+
 fn to_hex(bytes: impl AsRef<[u8]>) -> String {
 	format!("0x{}", hex::encode(bytes.as_ref()))
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rejected(error: &str) -> ErrorObjectOwned {
+		ClientError::TransactionRejected(error.into()).into()
+	}
+
+	#[test]
+	fn rejected_transactions_keep_author_errors() {
+		for (error, code, message) in [
+			("Invalid transaction: Transaction is outdated", 1010, "Invalid Transaction"),
+			("Invalid transaction with custom error: 3", 1010, "Invalid Transaction"),
+			("Extrinsic bytes cannot be decoded", 1010, "Invalid Transaction"),
+			("Unknown transaction validity: CannotLookup", 1011, "Unknown Transaction Validity"),
+			("Transaction is temporarily banned", 1012, "Transaction is temporarily banned"),
+			("Transaction is already imported", 1013, "Transaction Already Imported"),
+			(
+				"The priority of the transaction is too low (pool 10 > current 5)",
+				1014,
+				"Priority is too low: (10 vs 5)",
+			),
+			(
+				"The transaction could not enter the pool because of the limit",
+				1016,
+				"Immediately Dropped",
+			),
+			("Verification error: Runtime error", 1002, "Verification Error: Runtime error"),
+		] {
+			let err = rejected(error);
+			assert_eq!((err.code(), err.message()), (code, message), "{error}");
+			assert!(err.data().is_none());
+		}
+	}
+
+	#[test]
+	fn pool_errors_match_author_errors() {
+		use sc_rpc_api::author::error::Error as AuthorError;
+		use sc_rpc_spec_v2::transaction::{
+			error::Error as TransactionError,
+			event::{TransactionError as InvalidEvent, TransactionEvent},
+		};
+		use sc_transaction_pool_api::error::Error as PoolError;
+		use sp_runtime::transaction_validity::{InvalidTransaction, UnknownTransaction};
+
+		let errors: Vec<fn() -> PoolError> = vec![
+			|| PoolError::InvalidTransaction(InvalidTransaction::Stale),
+			|| PoolError::InvalidTransaction(InvalidTransaction::Payment),
+			|| PoolError::InvalidTransaction(InvalidTransaction::Custom(3)),
+			|| PoolError::UnknownTransaction(UnknownTransaction::CannotLookup),
+			|| PoolError::TemporarilyBanned,
+			|| PoolError::AlreadyImported(Box::new(H256::zero())),
+			|| PoolError::TooLowPriority { old: 10, new: 5 },
+			|| PoolError::CycleDetected,
+			|| PoolError::ImmediatelyDropped,
+			|| PoolError::Unactionable,
+			|| PoolError::NoTagsProvided,
+			|| PoolError::InvalidBlockId("0x00".into()),
+			|| PoolError::RejectedFutureTransaction,
+			|| PoolError::BundlesNotSupported,
+			|| PoolError::InvalidBundle,
+		];
+		let verification = || Box::new(std::io::Error::other("Runtime error"));
+		let cases = errors
+			.into_iter()
+			.map(|error| (AuthorError::Pool(error()), TransactionError::Pool(error())))
+			.chain([(
+				AuthorError::Verification(verification()),
+				TransactionError::Verification(verification()),
+			)]);
+		for (author, transaction) in cases {
+			let expected = ErrorObjectOwned::from(author);
+			let TransactionEvent::<H256>::Invalid(InvalidEvent { error }) = transaction.into()
+			else {
+				panic!("transaction errors are reported as invalid");
+			};
+			let err = rejected(&error);
+			assert_eq!(
+				(err.code(), err.message()),
+				(expected.code(), expected.message()),
+				"{error}"
+			);
+		}
+	}
+}
@@ -24,9 +24,9 @@ use futures::TryFutureExt;
 use pallet_revive::{
 	evm::{
 		Block as EthBlock, BlockNumberOrTagOrHash, BlockTag, GenericTransaction, ReceiptGasInfo,
-		Trace, H160, U256,
+		SimulateBlock, Trace, H160, U256,
 	},
-	DryRunConfig, EthTransactInfo,
+	AccessListInfo, AccountProofInfo, DryRunConfig, EthTransactInfo, SimulatedBlockInfo,
 };
 use sp_core::H256;
 use sp_timestamp::Timestamp;
@@ -66,6 +66,20 @@ impl RuntimeApi {
 		Ok(result)
 	}
 
+	/// Get the account values and the storage keys needed to prove the given storage slots.
+	pub async fn account_proof_info(
+		&self,
+		address: H160,
+		keys: Vec<[u8; 32]>,
+	) -> Result<AccountProofInfo, ClientError> {
+		let payload = subxt_client::apis()
+			.revive_api()
+			.account_proof_info(address, keys)
+			.unvalidated();
+		let info = self.0.call(payload).await?;
+		Ok(info.0)
+	}
+
 	/// Dry run a transaction and returns the [`EthTransactInfo`] for the transaction.
 	pub async fn dry_run(
 		&self,
@@ -121,6 +135,51 @@ impl RuntimeApi {
 		}
 	}
 
+	/// Dry run a transaction and returns its EIP-2930 [`AccessListInfo`].
+	pub async fn create_access_list(
+		&self,
+		tx: GenericTransaction,
+		block: BlockNumberOrTagOrHash,
+	) -> Result<AccessListInfo, ClientError> {
+		let timestamp_override = match block {
+			BlockNumberOrTagOrHash::BlockTag(BlockTag::Pending) =>
+				Some(Timestamp::current().as_millis()),
+			_ => None,
+		};
+
+		let payload = subxt_client::apis()
+			.revive_api()
+			.create_access_list(tx.into(), DryRunConfig::new(timestamp_override).into())
+			.unvalidated();
+
+		match self.0.call(payload).await? {
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "Create access list failed {err:?}");
+				Err(ClientError::TransactError(err.0))
+			},
+			Ok(result) => Ok(result.0),
+		}
+	}
+
+	/// Simulate blocks of calls and returns the [`SimulatedBlockInfo`] of each block.
+	pub async fn simulate(
+		&self,
+		blocks: Vec<SimulateBlock>,
+	) -> Result<Vec<SimulatedBlockInfo>, ClientError> {
+		let payload = subxt_client::apis()
+			.revive_api()
+			.simulate(blocks.into_iter().map(Into::into).collect())
+			.unvalidated();
+
+		match self.0.call(payload).await? {
+			Err(err) => {
+				log::debug!(target: LOG_TARGET, "Simulate failed {err:?}");
+				Err(ClientError::TransactError(err.0))
+			},
+			Ok(blocks) => Ok(blocks.into_iter().map(|block| block.0).collect()),
+		}
+	}
+
 	/// Get the nonce of the given address.
 	pub async fn nonce(&self, address: H160) -> Result<U256, ClientError> {
 		let address = address.0.into();
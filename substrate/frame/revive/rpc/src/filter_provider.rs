@@ -0,0 +1,342 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{client::SubstrateBlockNumber, BlockNumberOrTag, BlockTag, ClientError};
+use pallet_revive::evm::Filter;
+use sp_core::{H256, U256};
+use std::{
+	collections::HashMap,
+	ops::RangeInclusive,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use tokio::sync::{
+	broadcast::{self, error::TryRecvError},
+	Mutex,
+};
+
+const LOG_TARGET: &str = "eth-rpc::filter_provider";
+
+/// The duration after which a filter that has not been polled is removed.
+const FILTER_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// The maximum number of filters that can be installed at the same time.
+const MAX_FILTERS: usize = 1024;
+
+/// The kind of an installed filter.
+pub enum FilterKind {
+	/// A log filter, installed with `eth_newFilter`.
+	Logs(Filter),
+	/// A new block filter, installed with `eth_newBlockFilter`.
+	Blocks,
+	/// A pending transaction filter, installed with `eth_newPendingTransactionFilter`.
+	PendingTransactions(broadcast::Receiver<H256>),
+}
+
+/// The changes of a filter since it was last polled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterChanges {
+	/// The logs matching the filter, in the given block range.
+	Logs(Filter),
+	/// The new blocks in the given block range.
+	Blocks(RangeInclusive<SubstrateBlockNumber>),
+	/// The hashes of the new pending transactions.
+	PendingTransactions(Vec<H256>),
+	/// Nothing changed since the last poll.
+	None,
+}
+
+struct InstalledFilter {
+	/// The kind of the filter.
+	kind: FilterKind,
+	/// The last block number that was reported to the caller.
+	last_block: SubstrateBlockNumber,
+	/// When the filter was last installed or polled.
+	last_poll: Instant,
+}
+
+/// Manages the filters installed through the `eth_newFilter` family of methods.
+///
+/// Filters only keep a cursor to the last block they reported, the actual changes are resolved
+/// against the block cache and the receipt index by the caller. Filters that have not been polled
+/// for [`FILTER_TIMEOUT`] are removed. Filter ids are random, so that clients cannot guess the
+/// ids of the filters installed by other clients.
+#[derive(Default, Clone)]
+pub struct FilterProvider {
+	filters: Arc<Mutex<HashMap<U256, InstalledFilter>>>,
+}
+
+impl FilterProvider {
+	/// Install a new filter, starting at the given block number, and return its id.
+	pub async fn install(
+		&self,
+		kind: FilterKind,
+		current_block: SubstrateBlockNumber,
+	) -> Result<U256, ClientError> {
+		if let FilterKind::Logs(Filter { block_hash: Some(_), .. }) = kind {
+			return Err(ClientError::InvalidFilter);
+		}
+
+		let mut filters = self.filters.lock().await;
+		Self::remove_expired(&mut filters);
+		if filters.len() >= MAX_FILTERS {
+			return Err(ClientError::TooManyFilters);
+		}
+
+		let id = loop {
+			let id = U256::from(rand::random::<u128>());
+			if !id.is_zero() && !filters.contains_key(&id) {
+				break id;
+			}
+		};
+
+		log::trace!(target: LOG_TARGET, "Install filter {id:?} at block #{current_block}");
+		filters.insert(
+			id,
+			InstalledFilter { kind, last_block: current_block, last_poll: Instant::now() },
+		);
+		Ok(id)
+	}
+
+	/// Uninstall the filter with the given id, returns `true` if the filter existed.
+	pub async fn uninstall(&self, id: &U256) -> bool {
+		let mut filters = self.filters.lock().await;
+		Self::remove_expired(&mut filters);
+		filters.remove(id).is_some()
+	}
+
+	/// Get the log filter with the given id.
+	pub async fn log_filter(&self, id: &U256) -> Result<Filter, ClientError> {
+		let mut filters = self.filters.lock().await;
+		Self::remove_expired(&mut filters);
+		let filter = filters.get_mut(id).ok_or(ClientError::FilterNotFound)?;
+		filter.last_poll = Instant::now();
+		match &filter.kind {
+			FilterKind::Logs(filter) => Ok(filter.clone()),
+			_ => Err(ClientError::FilterNotFound),
+		}
+	}
+
+	/// Poll the filter with the given id, returning its changes up to the `latest_block`.
+	///
+	/// The `finalized_block` resolves the `finalized` and `safe` tags of log filters.
+	pub async fn poll(
+		&self,
+		id: &U256,
+		latest_block: SubstrateBlockNumber,
+		finalized_block: SubstrateBlockNumber,
+	) -> Result<FilterChanges, ClientError> {
+		let mut filters = self.filters.lock().await;
+		Self::remove_expired(&mut filters);
+		let filter = filters.get_mut(id).ok_or(ClientError::FilterNotFound)?;
+		filter.last_poll = Instant::now();
+
+		let from = filter.last_block.saturating_add(1);
+		// The best block can go backwards on re-orgs, wait until we are past the cursor again.
+		if from > latest_block {
+			if let FilterKind::Blocks | FilterKind::Logs(_) = filter.kind {
+				return Ok(FilterChanges::None);
+			}
+		}
+
+		let changes = match &mut filter.kind {
+			FilterKind::Blocks => {
+				filter.last_block = latest_block;
+				FilterChanges::Blocks(from..=latest_block)
+			},
+			FilterKind::Logs(log_filter) => {
+				filter.last_block = latest_block;
+
+				let as_block_number = |block: &Option<BlockNumberOrTag>| match block {
+					Some(BlockNumberOrTag::U256(n)) => Some(
+						SubstrateBlockNumber::try_from(*n).unwrap_or(SubstrateBlockNumber::MAX),
+					),
+					Some(BlockNumberOrTag::BlockTag(BlockTag::Earliest)) => Some(0),
+					Some(BlockNumberOrTag::BlockTag(BlockTag::Finalized | BlockTag::Safe)) =>
+						Some(finalized_block),
+					Some(BlockNumberOrTag::BlockTag(BlockTag::Latest | BlockTag::Pending)) |
+					None => None,
+				};
+				let from = as_block_number(&log_filter.from_block).map_or(from, |n| n.max(from));
+				let to = as_block_number(&log_filter.to_block)
+					.map_or(latest_block, |n| n.min(latest_block));
+				if from > to {
+					return Ok(FilterChanges::None);
+				}
+
+				FilterChanges::Logs(Filter {
+					from_block: Some(BlockNumberOrTag::U256(from.into())),
+					to_block: Some(BlockNumberOrTag::U256(to.into())),
+					..log_filter.clone()
+				})
+			},
+			FilterKind::PendingTransactions(receiver) => {
+				let mut hashes = Vec::new();
+				loop {
+					match receiver.try_recv() {
+						Ok(hash) => hashes.push(hash),
+						Err(TryRecvError::Lagged(skipped)) => {
+							log::debug!(target: LOG_TARGET, "Filter {id:?} lagged, skipped {skipped} transactions");
+						},
+						Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+					}
+				}
+				FilterChanges::PendingTransactions(hashes)
+			},
+		};
+
+		Ok(changes)
+	}
+
+	/// Remove the filters that have not been polled for [`FILTER_TIMEOUT`].
+	fn remove_expired(filters: &mut HashMap<U256, InstalledFilter>) {
+		filters.retain(|id, filter| {
+			let keep = filter.last_poll.elapsed() < FILTER_TIMEOUT;
+			if !keep {
+				log::debug!(target: LOG_TARGET, "Filter {id:?} expired");
+			}
+			keep
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{AddressOrAddresses, FilterTopic};
+	use pretty_assertions::assert_eq;
+	use sp_core::H160;
+
+	#[tokio::test]
+	async fn block_filter_works() {
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::Blocks, 10).await.unwrap();
+
+		assert_eq!(provider.poll(&id, 10, 0).await.unwrap(), FilterChanges::None);
+		assert_eq!(provider.poll(&id, 12, 0).await.unwrap(), FilterChanges::Blocks(11..=12));
+		assert_eq!(provider.poll(&id, 12, 0).await.unwrap(), FilterChanges::None);
+		assert_eq!(provider.poll(&id, 13, 0).await.unwrap(), FilterChanges::Blocks(13..=13));
+
+		assert!(provider.uninstall(&id).await);
+		assert!(!provider.uninstall(&id).await);
+		assert!(matches!(provider.poll(&id, 14, 0).await, Err(ClientError::FilterNotFound)));
+	}
+
+	#[tokio::test]
+	async fn log_filter_works() {
+		let filter = Filter {
+			address: Some(AddressOrAddresses::Address(H160([1u8; 20]))),
+			topics: Some(vec![FilterTopic::Single(H256([2u8; 32]))]),
+			to_block: Some(BlockNumberOrTag::U256(15.into())),
+			..Default::default()
+		};
+
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::Logs(filter.clone()), 10).await.unwrap();
+		assert_eq!(provider.log_filter(&id).await.unwrap(), filter);
+
+		assert_eq!(
+			provider.poll(&id, 12, 0).await.unwrap(),
+			FilterChanges::Logs(Filter {
+				from_block: Some(BlockNumberOrTag::U256(11.into())),
+				to_block: Some(BlockNumberOrTag::U256(12.into())),
+				..filter.clone()
+			})
+		);
+
+		assert_eq!(
+			provider.poll(&id, 20, 0).await.unwrap(),
+			FilterChanges::Logs(Filter {
+				from_block: Some(BlockNumberOrTag::U256(13.into())),
+				to_block: Some(BlockNumberOrTag::U256(15.into())),
+				..filter
+			})
+		);
+
+		assert_eq!(provider.poll(&id, 22, 0).await.unwrap(), FilterChanges::None);
+	}
+
+	#[tokio::test]
+	async fn log_filter_tags_are_resolved() {
+		let filter = Filter {
+			from_block: Some(BlockNumberOrTag::BlockTag(BlockTag::Earliest)),
+			to_block: Some(BlockNumberOrTag::BlockTag(BlockTag::Finalized)),
+			..Default::default()
+		};
+
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::Logs(filter.clone()), 10).await.unwrap();
+		assert_eq!(
+			provider.poll(&id, 14, 12).await.unwrap(),
+			FilterChanges::Logs(Filter {
+				from_block: Some(BlockNumberOrTag::U256(11.into())),
+				to_block: Some(BlockNumberOrTag::U256(12.into())),
+				..filter
+			})
+		);
+		assert_eq!(provider.poll(&id, 16, 12).await.unwrap(), FilterChanges::None);
+	}
+
+	#[tokio::test]
+	async fn filter_ids_are_random() {
+		let provider = FilterProvider::default();
+		let first = provider.install(FilterKind::Blocks, 10).await.unwrap();
+		let second = provider.install(FilterKind::Blocks, 10).await.unwrap();
+		assert_ne!(first, second);
+		assert!(first.bits() <= 128 && second.bits() <= 128);
+		assert_ne!(second.saturating_sub(first), U256::one());
+	}
+
+	#[tokio::test]
+	async fn pending_transaction_filter_works() {
+		let (sender, receiver) = broadcast::channel(16);
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::PendingTransactions(receiver), 10).await.unwrap();
+
+		sender.send(H256([1u8; 32])).unwrap();
+		sender.send(H256([2u8; 32])).unwrap();
+
+		assert_eq!(
+			provider.poll(&id, 10, 0).await.unwrap(),
+			FilterChanges::PendingTransactions(vec![H256([1u8; 32]), H256([2u8; 32])])
+		);
+		assert_eq!(
+			provider.poll(&id, 11, 0).await.unwrap(),
+			FilterChanges::PendingTransactions(vec![])
+		);
+	}
+
+	#[tokio::test]
+	async fn block_hash_log_filter_is_rejected() {
+		let provider = FilterProvider::default();
+		let filter = Filter { block_hash: Some(H256::zero()), ..Default::default() };
+		assert!(matches!(
+			provider.install(FilterKind::Logs(filter), 10).await,
+			Err(ClientError::InvalidFilter)
+		));
+	}
+
+	#[tokio::test]
+	async fn expired_filters_are_removed() {
+		let provider = FilterProvider::default();
+		let id = provider.install(FilterKind::Blocks, 10).await.unwrap();
+
+		provider.filters.lock().await.get_mut(&id).unwrap().last_poll =
+			Instant::now() - FILTER_TIMEOUT;
+
+		assert!(matches!(provider.poll(&id, 11, 0).await, Err(ClientError::FilterNotFound)));
+	}
+}
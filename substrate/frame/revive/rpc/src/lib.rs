@@ -27,6 +27,7 @@ use sp_core::{keccak_256, H160, H256, U256};
 use thiserror::Error;
 use tokio::time::Duration;
 
+pub mod backfill;
 pub mod cli;
 pub mod client;
 pub mod example;
@@ -44,6 +45,12 @@ pub use receipt_provider::*;
 mod fee_history_provider;
 pub use fee_history_provider::*;
 
+mod filter_provider;
+pub use filter_provider::*;
+
+mod pending_transaction_provider;
+pub use pending_transaction_provider::*;
+
 mod receipt_extractor;
 pub use receipt_extractor::*;
 
@@ -139,6 +146,19 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(receipt)
 	}
 
+	async fn create_access_list(
+		&self,
+		transaction: GenericTransaction,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<AccessListResult> {
+		log::trace!(target: LOG_TARGET, "create_access_list transaction={transaction:?} block={block:?}");
+		let block = block.unwrap_or_default();
+		let hash = self.client.block_hash_for_tag(block.clone()).await?;
+		let runtime_api = self.client.runtime_api(hash);
+		let info = runtime_api.create_access_list(transaction, block).await?;
+		Ok(AccessListResult { access_list: info.access_list, gas_used: info.eth_gas })
+	}
+
 	async fn estimate_gas(
 		&self,
 		transaction: GenericTransaction,
@@ -168,18 +188,21 @@ impl EthRpcServer for EthRpcServerImpl {
 	async fn send_raw_transaction(&self, transaction: Bytes) -> RpcResult<H256> {
 		let hash = H256(keccak_256(&transaction.0));
 		log::trace!(target: LOG_TARGET, "send_raw_transaction transaction: {transaction:?} ethereum_hash: {hash:?}");
+		// Transactions which can't be decoded are left to the node to reject.
+		let pending_tx = PendingTransaction::decode(&transaction.0);
 		let call = subxt_client::tx().revive().eth_transact(transaction.0);
 
 		// Subscribe to new block only when automine is enabled.
 		let receiver = self.client.block_notifier().map(|sender| sender.subscribe());
 
 		// Submit the transaction
-		self.client.submit(call).await.map_err(|err| {
+		self.client.submit(call, pending_tx).await.map_err(|err| {
 			log::trace!(target: LOG_TARGET, "send_raw_transaction ethereum_hash: {hash:?} failed: {err:?}");
 			err
 		})?;
 
 		log::trace!(target: LOG_TARGET, "send_raw_transaction with hash: {hash:?}");
+		self.client.notify_pending_transaction(hash);
 
 		// Wait for the transaction to be included in a block if automine is enabled
 		if let Some(mut receiver) = receiver {
@@ -235,7 +258,7 @@ impl EthRpcServer for EthRpcServerImpl {
 
 		if transaction.nonce.is_none() {
 			transaction.nonce =
-				Some(self.get_transaction_count(from, BlockTag::Latest.into()).await?);
+				Some(self.get_transaction_count(from, BlockTag::Pending.into()).await?);
 		}
 
 		if transaction.chain_id.is_none() {
@@ -270,6 +293,15 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(self.client.chain_id().into())
 	}
 
+	async fn simulate_v1(
+		&self,
+		payload: SimulatePayload,
+		block: Option<BlockNumberOrTagOrHash>,
+	) -> RpcResult<Vec<SimulatedBlock>> {
+		log::trace!(target: LOG_TARGET, "simulate_v1 payload={payload:?} block={block:?}");
+		Ok(self.client.simulate(payload, block.unwrap_or_default()).await?)
+	}
+
 	async fn gas_price(&self) -> RpcResult<U256> {
 		let hash = self.client.block_hash_for_tag(BlockTag::Latest.into()).await?;
 		let runtime_api = self.client.runtime_api(hash);
@@ -297,6 +329,10 @@ impl EthRpcServer for EthRpcServerImpl {
 		block_number: BlockNumberOrTag,
 		hydrated_transactions: bool,
 	) -> RpcResult<Option<Block>> {
+		if matches!(block_number, BlockNumberOrTag::BlockTag(BlockTag::Pending)) {
+			return Ok(self.client.pending_evm_block(hydrated_transactions).await);
+		}
+
 		let Some(block) = self.client.block_by_number_or_tag(&block_number).await? else {
 			return Ok(None);
 		};
@@ -343,6 +379,40 @@ impl EthRpcServer for EthRpcServerImpl {
 		Ok(FilterResults::Logs(logs))
 	}
 
+	async fn get_filter_changes(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		Ok(self.client.filter_changes(&filter_id).await?)
+	}
+
+	async fn get_filter_logs(&self, filter_id: U256) -> RpcResult<FilterResults> {
+		let logs = self.client.filter_logs(&filter_id).await?;
+		Ok(FilterResults::Logs(logs))
+	}
+
+	async fn new_filter(&self, filter: Filter) -> RpcResult<U256> {
+		Ok(self.client.new_filter(filter).await?)
+	}
+
+	async fn new_block_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_block_filter().await?)
+	}
+
+	async fn new_pending_transaction_filter(&self) -> RpcResult<U256> {
+		Ok(self.client.new_pending_transaction_filter().await?)
+	}
+
+	async fn uninstall_filter(&self, filter_id: U256) -> RpcResult<bool> {
+		Ok(self.client.uninstall_filter(&filter_id).await)
+	}
+
+	async fn get_proof(
+		&self,
+		address: H160,
+		storage_keys: Vec<U256>,
+		block: BlockNumberOrTagOrHash,
+	) -> RpcResult<AccountProof> {
+		Ok(self.client.get_proof(address, storage_keys, block).await?)
+	}
+
 	async fn get_storage_at(
 		&self,
 		address: H160,
@@ -393,7 +463,7 @@ impl EthRpcServer for EthRpcServerImpl {
 			return Ok(Some(TransactionInfo::new(&receipt, signed_tx)));
 		}
 
-		Ok(None)
+		Ok(self.client.pending_transaction(&transaction_hash).await)
 	}
 
 	async fn get_transaction_count(
@@ -401,9 +471,13 @@ impl EthRpcServer for EthRpcServerImpl {
 		address: H160,
 		block: BlockNumberOrTagOrHash,
 	) -> RpcResult<U256> {
+		let is_pending = matches!(block, BlockNumberOrTagOrHash::BlockTag(BlockTag::Pending));
 		let hash = self.client.block_hash_for_tag(block).await?;
 		let runtime_api = self.client.runtime_api(hash);
 		let nonce = runtime_api.nonce(address).await?;
+		if is_pending {
+			return Ok(self.client.pending_nonce(&address, nonce).await);
+		}
 		Ok(nonce)
 	}
 
@@ -0,0 +1,329 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::ClientError;
+use futures::{Stream, StreamExt};
+use pallet_revive::evm::{GenericTransaction, TransactionSigned};
+use sp_core::{keccak_256, H160, H256, U256};
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Arc,
+};
+use subxt::ext::subxt_rpcs::{methods::chain_head::TransactionStatus, Error as RpcError};
+use tokio::sync::Mutex;
+
+const LOG_TARGET: &str = "eth-rpc::pending_transaction_provider";
+
+/// The maximum number of tracked transactions.
+///
+/// Each tracked transaction holds a `transactionWatch_v1_submitAndWatch` subscription to the node,
+/// which limits the number of subscriptions per connection.
+const MAX_TRACKED_TRANSACTIONS: usize = 512;
+
+/// A transaction submitted through `eth_sendRawTransaction`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTransaction {
+	/// The Ethereum hash of the transaction.
+	pub hash: H256,
+	/// The sender of the transaction.
+	pub from: H160,
+	/// The nonce of the transaction.
+	pub nonce: U256,
+	/// The maximum price per gas the sender is willing to pay.
+	pub max_fee_per_gas: U256,
+	/// The signed transaction.
+	pub signed_tx: TransactionSigned,
+}
+
+impl PendingTransaction {
+	/// Decode a raw signed transaction.
+	///
+	/// Returns `None` if the payload can't be decoded or its sender can't be recovered.
+	pub fn decode(payload: &[u8]) -> Option<Self> {
+		let signed_tx = TransactionSigned::decode(payload).ok()?;
+		let from = signed_tx.recover_eth_address().ok()?;
+
+		// With an unbounded base price, the effective gas price is the maximum fee per gas.
+		let tx = GenericTransaction::from_signed(signed_tx.clone(), U256::MAX, Some(from));
+		Some(Self {
+			hash: H256(keccak_256(payload)),
+			from,
+			nonce: tx.nonce.unwrap_or_default(),
+			max_fee_per_gas: tx.gas_price.unwrap_or_default(),
+			signed_tx,
+		})
+	}
+}
+
+struct TrackedTransaction {
+	/// The tracked transaction.
+	tx: PendingTransaction,
+	/// Whether the transaction is included in a block of the best chain.
+	included: bool,
+}
+
+#[derive(Default)]
+struct Pool {
+	/// The tracked transactions, by hash.
+	transactions: HashMap<H256, TrackedTransaction>,
+	/// The hashes of the tracked transactions, by sender and nonce.
+	by_sender: BTreeMap<(H160, U256), H256>,
+}
+
+impl Pool {
+	fn remove(&mut self, hash: &H256) -> Option<TrackedTransaction> {
+		let tracked = self.transactions.remove(hash)?;
+		let key = (tracked.tx.from, tracked.tx.nonce);
+		if self.by_sender.get(&key) == Some(hash) {
+			self.by_sender.remove(&key);
+		}
+		Some(tracked)
+	}
+}
+
+/// Tracks the transactions submitted through this server until they are finalized or dropped.
+///
+/// Transactions are followed through the events of `transactionWatch_v1_submitAndWatch`. A
+/// transaction with the same sender and nonce as a tracked one replaces it, provided that it pays
+/// a higher fee.
+#[derive(Default, Clone)]
+pub struct PendingTransactionProvider {
+	pool: Arc<Mutex<Pool>>,
+}
+
+impl PendingTransactionProvider {
+	/// Check whether `tx` can be submitted, and return the hash of the transaction it replaces.
+	pub async fn check_replacement(
+		&self,
+		tx: &PendingTransaction,
+	) -> Result<Option<H256>, ClientError> {
+		let pool = self.pool.lock().await;
+		let Some(replaced) = pool
+			.by_sender
+			.get(&(tx.from, tx.nonce))
+			.and_then(|hash| pool.transactions.get(hash))
+		else {
+			return Ok(None);
+		};
+
+		// Resubmitting the same transaction is left to the node to reject.
+		if replaced.tx.hash == tx.hash {
+			return Ok(None);
+		}
+
+		if tx.max_fee_per_gas <= replaced.tx.max_fee_per_gas {
+			return Err(ClientError::ReplacementUnderpriced);
+		}
+
+		Ok(Some(replaced.tx.hash))
+	}
+
+	/// Whether no more transactions can be tracked.
+	pub async fn is_full(&self) -> bool {
+		self.pool.lock().await.transactions.len() >= MAX_TRACKED_TRANSACTIONS
+	}
+
+	/// Start tracking a transaction accepted by the node.
+	///
+	/// The tracked transaction with the same sender and nonce, if any, is replaced.
+	pub async fn insert(&self, tx: PendingTransaction) {
+		let mut pool = self.pool.lock().await;
+		if let Some(replaced) = pool.by_sender.get(&(tx.from, tx.nonce)).copied() {
+			if replaced != tx.hash {
+				log::debug!(target: LOG_TARGET, "Transaction {replaced:?} replaced by {:?}", tx.hash);
+				pool.remove(&replaced);
+			}
+		}
+
+		log::trace!(target: LOG_TARGET, "Track transaction {:?}", tx.hash);
+		pool.by_sender.insert((tx.from, tx.nonce), tx.hash);
+		pool.transactions.insert(tx.hash, TrackedTransaction { tx, included: false });
+	}
+
+	/// Stop tracking the transaction with the given hash.
+	pub async fn remove(&self, hash: &H256) {
+		self.pool.lock().await.remove(hash);
+	}
+
+	/// Update the transaction with the given hash with a status reported by the node.
+	///
+	/// Returns `false` once the transaction is no longer tracked.
+	pub async fn update(&self, hash: &H256, status: &TransactionStatus<H256>) -> bool {
+		let mut pool = self.pool.lock().await;
+		match status {
+			TransactionStatus::Validated | TransactionStatus::Broadcasted => {},
+			TransactionStatus::BestChainBlockIncluded { block } => {
+				if let Some(tracked) = pool.transactions.get_mut(hash) {
+					tracked.included = block.is_some();
+				}
+			},
+			TransactionStatus::Finalized { .. } => {
+				log::trace!(target: LOG_TARGET, "Transaction {hash:?} finalized");
+				pool.remove(hash);
+			},
+			TransactionStatus::Error { error } |
+			TransactionStatus::Invalid { error } |
+			TransactionStatus::Dropped { error } => {
+				log::debug!(target: LOG_TARGET, "Transaction {hash:?} dropped: {error}");
+				pool.remove(hash);
+			},
+		}
+		pool.transactions.contains_key(hash)
+	}
+
+	/// Follow the status of the transaction with the given hash until it is no longer tracked.
+	pub async fn watch(
+		&self,
+		hash: H256,
+		mut events: impl Stream<Item = Result<TransactionStatus<H256>, RpcError>> + Unpin,
+	) {
+		while let Some(event) = events.next().await {
+			match event {
+				Ok(status) =>
+					if !self.update(&hash, &status).await {
+						return;
+					},
+				Err(err) => {
+					log::debug!(target: LOG_TARGET, "Failed to watch transaction {hash:?}: {err:?}");
+					break;
+				},
+			}
+		}
+
+		self.remove(&hash).await;
+	}
+
+	/// Get the tracked transaction with the given hash.
+	pub async fn transaction(&self, hash: &H256) -> Option<PendingTransaction> {
+		self.pool.lock().await.transactions.get(hash).map(|tracked| tracked.tx.clone())
+	}
+
+	/// Get the tracked transactions not yet included in the best chain, by sender and nonce.
+	pub async fn pending_transactions(&self) -> Vec<PendingTransaction> {
+		let pool = self.pool.lock().await;
+		pool.by_sender
+			.values()
+			.filter_map(|hash| pool.transactions.get(hash))
+			.filter(|tracked| !tracked.included)
+			.map(|tracked| tracked.tx.clone())
+			.collect()
+	}
+
+	/// Get the next nonce of the given sender, skipping the tracked nonces which directly follow
+	/// `nonce`.
+	///
+	/// Tracked transactions after a gap in the nonces can't be included and are ignored.
+	pub async fn next_nonce(&self, from: &H160, mut nonce: U256) -> U256 {
+		let pool = self.pool.lock().await;
+		for ((_, tracked_nonce), _) in pool.by_sender.range((*from, nonce)..=(*from, U256::MAX)) {
+			if *tracked_nonce != nonce {
+				break;
+			}
+			nonce = nonce.saturating_add(U256::one());
+		}
+		nonce
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_revive::evm::{Account, TransactionLegacyUnsigned};
+	use pretty_assertions::assert_eq;
+	use subxt::ext::subxt_rpcs::methods::chain_head::TransactionBlockDetails;
+
+	fn transaction(nonce: u32, gas_price: u32) -> PendingTransaction {
+		let tx = TransactionLegacyUnsigned {
+			nonce: nonce.into(),
+			gas_price: gas_price.into(),
+			gas: 21_000u32.into(),
+			..Default::default()
+		};
+		let signed_tx = Account::default().sign_transaction(tx.into());
+		PendingTransaction::decode(&signed_tx.signed_payload()).unwrap()
+	}
+
+	#[test]
+	fn decode_works() {
+		let tx = transaction(3, 100);
+		assert_eq!(tx.from, Account::default().address());
+		assert_eq!(tx.nonce, 3.into());
+		assert_eq!(tx.max_fee_per_gas, 100.into());
+		assert_eq!(tx.hash, H256(keccak_256(&tx.signed_tx.signed_payload())));
+	}
+
+	#[tokio::test]
+	async fn replacement_works() {
+		let provider = PendingTransactionProvider::default();
+		let tx = transaction(0, 100);
+		provider.insert(tx.clone()).await;
+
+		let underpriced = transaction(0, 99);
+		assert!(matches!(
+			provider.check_replacement(&underpriced).await,
+			Err(ClientError::ReplacementUnderpriced)
+		));
+
+		let replacement = transaction(0, 101);
+		assert_eq!(provider.check_replacement(&replacement).await.unwrap(), Some(tx.hash));
+		assert_eq!(provider.check_replacement(&transaction(1, 1)).await.unwrap(), None);
+		assert_eq!(provider.check_replacement(&tx).await.unwrap(), None);
+
+		provider.insert(replacement.clone()).await;
+		assert_eq!(provider.transaction(&tx.hash).await, None);
+		assert_eq!(provider.pending_transactions().await, vec![replacement]);
+	}
+
+	#[tokio::test]
+	async fn status_updates_work() {
+		let provider = PendingTransactionProvider::default();
+		let (first, second) = (transaction(0, 100), transaction(1, 100));
+		provider.insert(first.clone()).await;
+		provider.insert(second.clone()).await;
+		assert_eq!(provider.next_nonce(&first.from, 0.into()).await, 2.into());
+		assert_eq!(provider.next_nonce(&first.from, 1.into()).await, 2.into());
+		assert_eq!(provider.next_nonce(&first.from, 3.into()).await, 3.into());
+		assert_eq!(provider.next_nonce(&H160::zero(), 0.into()).await, 0.into());
+
+		// A transaction after a gap in the nonces is not counted.
+		let future = transaction(3, 100);
+		provider.insert(future.clone()).await;
+		assert_eq!(provider.next_nonce(&first.from, 0.into()).await, 2.into());
+		provider.remove(&future.hash).await;
+
+		let block = TransactionBlockDetails { hash: H256::zero(), index: 0 };
+		let included = TransactionStatus::BestChainBlockIncluded { block: Some(block.clone()) };
+		assert!(provider.update(&first.hash, &included).await);
+		assert_eq!(provider.pending_transactions().await, vec![second.clone()]);
+		assert_eq!(provider.transaction(&first.hash).await, Some(first.clone()));
+
+		let retracted = TransactionStatus::BestChainBlockIncluded { block: None };
+		assert!(provider.update(&first.hash, &retracted).await);
+		assert_eq!(provider.pending_transactions().await, vec![first.clone(), second.clone()]);
+
+		let events = futures::stream::iter(vec![
+			Ok(TransactionStatus::Validated),
+			Ok(TransactionStatus::Finalized { block }),
+		]);
+		provider.watch(first.hash, events).await;
+		assert_eq!(provider.transaction(&first.hash).await, None);
+
+		let dropped = TransactionStatus::Dropped { error: "dropped".into() };
+		assert!(!provider.update(&second.hash, &dropped).await);
+		assert_eq!(provider.pending_transactions().await, vec![]);
+		assert_eq!(provider.next_nonce(&first.from, 0.into()).await, 0.into());
+	}
+}
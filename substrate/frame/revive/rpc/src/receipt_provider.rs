@@ -21,9 +21,10 @@ use crate::{
 };
 use pallet_revive::evm::{Filter, Log, ReceiptInfo, TransactionSigned};
 use sp_core::{H256, U256};
-use sqlx::{query, QueryBuilder, Row, Sqlite, SqlitePool};
+use sqlx::{query, QueryBuilder, Row, Sqlite, SqliteConnection, SqlitePool};
 use std::{
 	collections::{BTreeMap, HashMap},
+	ops::RangeInclusive,
 	sync::Arc,
 };
 use tokio::sync::Mutex;
@@ -77,6 +78,19 @@ impl BlockInfo for SubstrateBlock {
 	}
 }
 
+/// The receipts of a block, written by [`ReceiptProvider::insert_backfill_batch`].
+#[derive(Clone, Debug, Default)]
+pub struct BackfillBlock {
+	/// The Substrate block number.
+	pub number: SubstrateBlockNumber,
+	/// The Substrate block hash.
+	pub substrate_hash: H256,
+	/// The Ethereum block hash.
+	pub ethereum_hash: H256,
+	/// The receipts of the block.
+	pub receipts: Vec<(TransactionSigned, ReceiptInfo)>,
+}
+
 impl<B: BlockInfoProvider> ReceiptProvider<B> {
 	/// Create a new `ReceiptProvider` with the given database URL and block provider.
 	pub async fn new(
@@ -115,10 +129,75 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		Some((block_hash, transaction_index))
 	}
 
-	/// Insert a block mapping from Ethereum block hash to Substrate block hash.
-	async fn insert_block_mapping(&self, block_map: &BlockHashMap) -> Result<(), ClientError> {
+	/// Write the receipts of a block along with its Ethereum to Substrate block hash mapping.
+	///
+	/// This is shared by [`Self::insert`] and [`Self::insert_backfill_batch`], the latter running
+	/// it within a database transaction.
+	async fn write_receipts(
+		conn: &mut SqliteConnection,
+		block_number: SubstrateBlockNumber,
+		block_map: &BlockHashMap,
+		receipts: &[(TransactionSigned, ReceiptInfo)],
+	) -> Result<(), sqlx::Error> {
 		let ethereum_hash_ref = block_map.ethereum_hash.as_ref();
 		let substrate_hash_ref = block_map.substrate_hash.as_ref();
+		let block_number = block_number as i64;
+
+		for (_, receipt) in receipts {
+			let transaction_hash: &[u8] = receipt.transaction_hash.as_ref();
+			let transaction_index = receipt.transaction_index.as_u32() as i32;
+
+			query!(
+				r#"
+				INSERT OR REPLACE INTO transaction_hashes (transaction_hash, block_hash, transaction_index)
+				VALUES ($1, $2, $3)
+				"#,
+				transaction_hash,
+				substrate_hash_ref,
+				transaction_index
+			)
+			.execute(&mut *conn)
+			.await?;
+
+			for log in &receipt.logs {
+				let log_index = log.log_index.as_u32() as i32;
+				let address: &[u8] = log.address.as_ref();
+
+				let topic_0 = log.topics.first().as_ref().map(|v| &v[..]);
+				let topic_1 = log.topics.get(1).as_ref().map(|v| &v[..]);
+				let topic_2 = log.topics.get(2).as_ref().map(|v| &v[..]);
+				let topic_3 = log.topics.get(3).as_ref().map(|v| &v[..]);
+				let data = log.data.as_ref().map(|v| &v.0[..]);
+
+				query!(
+					r#"
+					INSERT INTO logs(
+						block_hash,
+						transaction_index,
+						log_index,
+						address,
+						block_number,
+						transaction_hash,
+						topic_0, topic_1, topic_2, topic_3,
+						data)
+					VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+					"#,
+					ethereum_hash_ref,
+					transaction_index,
+					log_index,
+					address,
+					block_number,
+					transaction_hash,
+					topic_0,
+					topic_1,
+					topic_2,
+					topic_3,
+					data
+				)
+				.execute(&mut *conn)
+				.await?;
+			}
+		}
 
 		query!(
 			r#"
@@ -128,7 +207,7 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 			ethereum_hash_ref,
 			substrate_hash_ref,
 		)
-		.execute(&self.pool)
+		.execute(&mut *conn)
 		.await?;
 
 		log::trace!(target: LOG_TARGET, "Insert block mapping ethereum block: {:?} -> substrate block: {:?}", block_map.ethereum_hash, block_map.substrate_hash);
@@ -309,13 +388,12 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 	) -> Result<(), ClientError> {
 		let substrate_block_hash = block.hash();
 		let substrate_hash_ref = substrate_block_hash.as_ref();
-		let block_number = block.number() as i64;
-		let ethereum_hash_ref = ethereum_hash.as_ref();
+		let block_number = block.number();
 		let block_map = BlockHashMap::new(substrate_block_hash, *ethereum_hash);
 
 		log::trace!(target: LOG_TARGET, "Insert receipts for substrate block #{block_number} {:?}", substrate_block_hash);
 
-		self.prune_blocks(block.number(), &block_map).await?;
+		self.prune_blocks(block_number, &block_map).await?;
 
 		// Check if mapping already exists (eg. added when processing best block and we are now
 		// processing finalized block)
@@ -328,65 +406,82 @@ impl<B: BlockInfoProvider> ReceiptProvider<B> {
 		// Assuming that if no mapping exists then no relevant entries in transaction_hashes and
 		// logs exist
 		if !result.exists {
-			for (_, receipt) in receipts {
-				let transaction_hash: &[u8] = receipt.transaction_hash.as_ref();
-				let transaction_index = receipt.transaction_index.as_u32() as i32;
+			let mut conn = self.pool.acquire().await?;
+			Self::write_receipts(&mut *conn, block_number, &block_map, receipts).await?;
+		}
 
-				query!(
-					r#"
-					INSERT INTO transaction_hashes (transaction_hash, block_hash, transaction_index)
-					VALUES ($1, $2, $3)
-					"#,
-					transaction_hash,
-					substrate_hash_ref,
-					transaction_index
-				)
-				.execute(&self.pool)
+		Ok(())
+	}
+
+	/// Get the completed backfill batches overlapping the given range.
+	pub async fn backfill_checkpoints(
+		&self,
+		range: RangeInclusive<SubstrateBlockNumber>,
+	) -> Result<Vec<RangeInclusive<SubstrateBlockNumber>>, ClientError> {
+		let range_end = *range.end() as i64;
+		let range_start = *range.start() as i64;
+		let rows = query!(
+			r#"
+			SELECT start_block, end_block
+			FROM backfill_checkpoints
+			WHERE start_block <= $1 AND end_block >= $2
+			ORDER BY start_block
+			"#,
+			range_end,
+			range_start
+		)
+		.fetch_all(&self.pool)
+		.await?;
+
+		Ok(rows
+			.into_iter()
+			.map(|row| {
+				row.start_block as SubstrateBlockNumber..=row.end_block as SubstrateBlockNumber
+			})
+			.collect())
+	}
+
+	/// Write the receipts of a batch of backfilled blocks, and record the batch as completed.
+	///
+	/// Existing entries of the given blocks are replaced. Everything is written in a single
+	/// database transaction, so that an interrupted backfill never leaves a partially written
+	/// batch behind.
+	pub async fn insert_backfill_batch(
+		&self,
+		blocks: &[BackfillBlock],
+		batch: RangeInclusive<SubstrateBlockNumber>,
+	) -> Result<(), ClientError> {
+		let mut tx = self.pool.begin().await?;
+
+		for block in blocks {
+			let substrate_hash_ref = block.substrate_hash.as_ref();
+			let ethereum_hash_ref = block.ethereum_hash.as_ref();
+			query!("DELETE FROM transaction_hashes WHERE block_hash = $1", substrate_hash_ref)
+				.execute(&mut *tx)
+				.await?;
+			query!("DELETE FROM logs WHERE block_hash = $1", ethereum_hash_ref)
+				.execute(&mut *tx)
 				.await?;
 
-				for log in &receipt.logs {
-					let log_index = log.log_index.as_u32() as i32;
-					let address: &[u8] = log.address.as_ref();
-
-					let topic_0 = log.topics.first().as_ref().map(|v| &v[..]);
-					let topic_1 = log.topics.get(1).as_ref().map(|v| &v[..]);
-					let topic_2 = log.topics.get(2).as_ref().map(|v| &v[..]);
-					let topic_3 = log.topics.get(3).as_ref().map(|v| &v[..]);
-					let data = log.data.as_ref().map(|v| &v.0[..]);
-
-					query!(
-						r#"
-						INSERT INTO logs(
-							block_hash,
-							transaction_index,
-							log_index,
-							address,
-							block_number,
-							transaction_hash,
-							topic_0, topic_1, topic_2, topic_3,
-							data)
-						VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-						"#,
-						ethereum_hash_ref,
-						transaction_index,
-						log_index,
-						address,
-						block_number,
-						transaction_hash,
-						topic_0,
-						topic_1,
-						topic_2,
-						topic_3,
-						data
-					)
-					.execute(&self.pool)
-					.await?;
-				}
-			}
-			// Insert block mapping from Ethereum to Substrate hash
-			self.insert_block_mapping(&block_map).await?;
+			let block_map = BlockHashMap::new(block.substrate_hash, block.ethereum_hash);
+			Self::write_receipts(&mut *tx, block.number, &block_map, &block.receipts).await?;
 		}
 
+		let start_block = *batch.start() as i64;
+		let end_block = *batch.end() as i64;
+		query!(
+			r#"
+			INSERT OR REPLACE INTO backfill_checkpoints (start_block, end_block)
+			VALUES ($1, $2)
+			"#,
+			start_block,
+			end_block
+		)
+		.execute(&mut *tx)
+		.await?;
+
+		tx.commit().await?;
+		log::trace!(target: LOG_TARGET, "Inserted backfill batch {batch:?}");
 		Ok(())
 	}
 
@@ -666,6 +761,52 @@ mod tests {
 		Ok(())
 	}
 
+	#[sqlx::test]
+	async fn test_insert_backfill_batch(pool: SqlitePool) -> anyhow::Result<()> {
+		let provider = setup_sqlite_provider(pool).await;
+		let block = BackfillBlock {
+			number: 1,
+			substrate_hash: H256::from([1_u8; 32]),
+			ethereum_hash: H256::from([2_u8; 32]),
+			receipts: vec![(
+				TransactionSigned::default(),
+				ReceiptInfo {
+					logs: vec![Log::default(), Log { log_index: 1.into(), ..Default::default() }],
+					..Default::default()
+				},
+			)],
+		};
+
+		provider.insert_backfill_batch(&[block.clone()], 0..=9).await?;
+		assert_eq!(
+			count(&provider.pool, "transaction_hashes", Some(block.substrate_hash)).await,
+			1
+		);
+		assert_eq!(count(&provider.pool, "logs", Some(block.ethereum_hash)).await, 2);
+		assert_eq!(
+			provider.get_ethereum_hash(&block.substrate_hash).await,
+			Some(block.ethereum_hash)
+		);
+
+		// Re-indexing the block replaces its entries.
+		let block = BackfillBlock {
+			receipts: vec![(TransactionSigned::default(), ReceiptInfo::default())],
+			..block
+		};
+		provider.insert_backfill_batch(&[block.clone()], 0..=9).await?;
+		assert_eq!(
+			count(&provider.pool, "transaction_hashes", Some(block.substrate_hash)).await,
+			1
+		);
+		assert_eq!(count(&provider.pool, "logs", Some(block.ethereum_hash)).await, 0);
+
+		provider.insert_backfill_batch(&[], 10..=19).await?;
+		assert_eq!(provider.backfill_checkpoints(0..=100).await?, vec![0..=9, 10..=19]);
+		assert_eq!(provider.backfill_checkpoints(15..=100).await?, vec![10..=19]);
+		assert_eq!(provider.backfill_checkpoints(20..=100).await?, vec![]);
+		Ok(())
+	}
+
 	#[sqlx::test]
 	async fn test_prune(pool: SqlitePool) -> anyhow::Result<()> {
 		let provider = setup_sqlite_provider(pool).await;
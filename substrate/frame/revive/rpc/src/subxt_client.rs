@@ -59,6 +59,22 @@ pub use subxt::config::PolkadotConfig as SrcChainConfig;
 		path = "pallet_revive::primitives::EthTransactError",
 		with = "::subxt::utils::Static<::pallet_revive::EthTransactError>"
 	),
+	substitute_type(
+		path = "pallet_revive::primitives::AccountProofInfo",
+		with = "::subxt::utils::Static<::pallet_revive::AccountProofInfo>"
+	),
+	substitute_type(
+		path = "pallet_revive::primitives::AccessListInfo",
+		with = "::subxt::utils::Static<::pallet_revive::AccessListInfo>"
+	),
+	substitute_type(
+		path = "pallet_revive::evm::api::simulate_rpc_types::SimulateBlock",
+		with = "::subxt::utils::Static<::pallet_revive::evm::SimulateBlock>"
+	),
+	substitute_type(
+		path = "pallet_revive::primitives::SimulatedBlockInfo",
+		with = "::subxt::utils::Static<::pallet_revive::SimulatedBlockInfo>"
+	),
 	substitute_type(
 		path = "pallet_revive::primitives::ExecReturnValue",
 		with = "::subxt::utils::Static<::pallet_revive::ExecReturnValue>"
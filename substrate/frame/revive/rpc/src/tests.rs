@@ -25,7 +25,7 @@ use crate::{
 	subxt_client::{
 		self, src_chain::runtime_types::pallet_revive::primitives::Code, SrcChainConfig,
 	},
-	EthRpcClient,
+	EthPubSubRpcClient, EthRpcClient, SubscriptionItem, SubscriptionKind,
 };
 use anyhow::anyhow;
 use clap::Parser;
@@ -33,7 +33,7 @@ use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use pallet_revive::{
 	create1,
 	evm::{
-		Account, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag,
+		Account, Block, BlockNumberOrTag, BlockNumberOrTagOrHash, BlockTag, FilterResults,
 		HashesOrTransactionInfos, TransactionInfo, TransactionUnsigned, H256, U256,
 	},
 };
@@ -297,6 +297,8 @@ async fn run_all_eth_rpc_tests() -> anyhow::Result<()> {
 		test_multiple_transactions_in_block,
 		test_mixed_evm_substrate_transactions,
 		test_runtime_pallets_address_upload_code,
+		test_subscribe_new_heads_and_pending_transactions,
+		test_block_and_pending_transaction_filters,
 	);
 
 	log::debug!(target: LOG_TARGET, "All tests completed successfully!");
@@ -462,6 +464,7 @@ async fn test_invalid_transaction(client: Arc<WsClient>) -> anyhow::Result<()> {
 		.unwrap_err();
 
 	let call_err = unwrap_call_err!(err.source().unwrap());
+	assert_eq!(call_err.code(), 1010);
 	assert_eq!(call_err.message(), "Invalid Transaction");
 
 	Ok(())
@@ -790,3 +793,59 @@ async fn test_runtime_pallets_address_upload_code(client: Arc<WsClient>) -> anyh
 
 	Ok(())
 }
+
+async fn test_subscribe_new_heads_and_pending_transactions(
+	client: Arc<WsClient>,
+) -> anyhow::Result<()> {
+	let mut new_heads = client.subscribe(SubscriptionKind::NewHeads, None).await?;
+	let mut pending_txs = client.subscribe(SubscriptionKind::NewPendingTransactions, None).await?;
+
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let value = 1_000_000_000_000_000_000u128.into();
+	let tx = TransactionBuilder::new(&client).value(value).to(ethan.address()).send().await?;
+	let hash = tx.hash();
+
+	let item = pending_txs.next().await.ok_or(anyhow!("Subscription ended"))??;
+	assert_eq!(item, SubscriptionItem::TransactionHash(hash));
+
+	let receipt = tx.wait_for_receipt().await?;
+	loop {
+		let item = new_heads.next().await.ok_or(anyhow!("Subscription ended"))??;
+		let SubscriptionItem::Header(block) = item else {
+			return Err(anyhow!("Expected a block header, got {item:?}"));
+		};
+		if block.number >= receipt.block_number {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+async fn test_block_and_pending_transaction_filters(client: Arc<WsClient>) -> anyhow::Result<()> {
+	let block_filter = client.new_block_filter().await?;
+	let pending_tx_filter = client.new_pending_transaction_filter().await?;
+
+	let ethan = Account::from(subxt_signer::eth::dev::ethan());
+	let value = 1_000_000_000_000_000_000u128.into();
+	let tx = TransactionBuilder::new(&client).value(value).to(ethan.address()).send().await?;
+	let receipt = tx.wait_for_receipt().await?;
+
+	let changes = client.get_filter_changes(pending_tx_filter).await?;
+	assert_eq!(changes, FilterResults::Hashes(vec![tx.hash()]));
+
+	let changes = client.get_filter_changes(block_filter).await?;
+	let FilterResults::Hashes(hashes) = changes else {
+		return Err(anyhow!("Expected block hashes, got {changes:?}"));
+	};
+	assert!(
+		hashes.contains(&receipt.block_hash),
+		"Block filter changes {hashes:?} should contain {:?}",
+		receipt.block_hash
+	);
+
+	assert!(client.uninstall_filter(block_filter).await?);
+	assert!(client.uninstall_filter(pending_tx_filter).await?);
+	assert!(!client.uninstall_filter(block_filter).await?);
+	Ok(())
+}
@@ -30,8 +30,11 @@ pub use type_id::*;
 mod debug_rpc_types;
 pub use debug_rpc_types::*;
 
+mod simulate_rpc_types;
+pub use simulate_rpc_types::*;
+
 mod rpc_types;
-pub use rpc_types::DryRunConfig;
+pub use rpc_types::{BlockHeader, DryRunConfig};
 
 mod rpc_types_gen;
 pub use rpc_types_gen::*;
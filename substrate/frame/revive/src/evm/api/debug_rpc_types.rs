@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::evm::Bytes;
+use crate::evm::{BlockNumberOrTag, Bytes};
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
 use codec::{Decode, Encode};
 use derive_more::From;
@@ -36,6 +36,16 @@ pub enum TracerType {
 
 	/// A tracer that traces the prestate.
 	PrestateTracer(Option<PrestateTracerConfig>),
+
+	/// A tracer that logs every executed instruction.
+	StructLogger(Option<StructLoggerConfig>),
+
+	/// A tracer that reports calls as a flat list, in the Parity `trace_*` format.
+	FlatCallTracer,
+
+	/// A tracer that counts the 4-byte selectors and input sizes of the calls.
+	#[serde(rename = "4byteTracer")]
+	FourByteTracer,
 }
 
 impl From<CallTracerConfig> for TracerType {
@@ -46,13 +56,16 @@ impl From<CallTracerConfig> for TracerType {
 
 impl Default for TracerType {
 	fn default() -> Self {
-		TracerType::CallTracer(Some(CallTracerConfig::default()))
+		TracerType::StructLogger(Some(StructLoggerConfig::default()))
 	}
 }
 
 /// Tracer configuration used to trace calls.
+///
+/// When no `tracer` is given, the struct logger is used and its options are read from the top
+/// level object, as done by geth.
 #[derive(TypeInfo, Debug, Clone, Default, PartialEq)]
-#[cfg_attr(feature = "std", derive(Deserialize, Serialize), serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", derive(Serialize), serde(rename_all = "camelCase"))]
 pub struct TracerConfig {
 	/// The tracer type.
 	#[cfg_attr(feature = "std", serde(flatten, default))]
@@ -63,6 +76,40 @@ pub struct TracerConfig {
 	pub timeout: Option<core::time::Duration>,
 }
 
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for TracerConfig {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		#[derive(Deserialize)]
+		#[serde(rename_all = "camelCase")]
+		struct RawTracerConfig {
+			tracer: Option<String>,
+			tracer_config: Option<serde_json::Value>,
+			#[serde(with = "humantime_serde", default)]
+			timeout: Option<core::time::Duration>,
+			#[serde(flatten)]
+			struct_logger: StructLoggerConfig,
+		}
+
+		let raw = RawTracerConfig::deserialize(deserializer)?;
+		let config = match raw.tracer {
+			None => TracerType::StructLogger(Some(raw.struct_logger)),
+			Some(tracer) => {
+				let mut map = serde_json::Map::new();
+				map.insert("tracer".into(), tracer.into());
+				if let Some(tracer_config) = raw.tracer_config {
+					map.insert("tracerConfig".into(), tracer_config);
+				}
+				TracerType::deserialize(serde_json::Value::Object(map)).map_err(Error::custom)?
+			},
+		};
+
+		Ok(TracerConfig { config, timeout: raw.timeout })
+	}
+}
+
 /// The configuration for the call tracer.
 #[derive(Clone, Debug, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
 #[serde(default, rename_all = "camelCase")]
@@ -100,6 +147,23 @@ impl Default for PrestateTracerConfig {
 	}
 }
 
+/// The configuration for the struct logger.
+#[derive(Clone, Debug, Default, Decode, Serialize, Deserialize, Encode, PartialEq, TypeInfo)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+	/// Whether to exclude the stack from the trace.
+	pub disable_stack: bool,
+
+	/// Whether to include the memory in the trace.
+	pub enable_memory: bool,
+
+	/// Whether to exclude the storage from the trace.
+	pub disable_storage: bool,
+
+	/// The maximum number of logs to record, `0` means no limit.
+	pub limit: u64,
+}
+
 /// Serialization should support the following JSON format:
 ///
 /// ```json
@@ -137,6 +201,32 @@ fn test_tracer_config_serialization() {
 				timeout: Some(core::time::Duration::from_millis(10)),
 			},
 		),
+		(
+			r#"{"tracer": "flatCallTracer"}"#,
+			TracerConfig { config: TracerType::FlatCallTracer, timeout: None },
+		),
+		(
+			r#"{"tracer": "4byteTracer"}"#,
+			TracerConfig { config: TracerType::FourByteTracer, timeout: None },
+		),
+		(
+			r#"{}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(Default::default())),
+				timeout: None,
+			},
+		),
+		(
+			r#"{"enableMemory": true, "disableStorage": true, "timeout": "10ms"}"#,
+			TracerConfig {
+				config: TracerType::StructLogger(Some(StructLoggerConfig {
+					enable_memory: true,
+					disable_storage: true,
+					..Default::default()
+				})),
+				timeout: Some(core::time::Duration::from_millis(10)),
+			},
+		),
 	];
 
 	for (json_data, expected) in tracers {
@@ -144,6 +234,8 @@ fn test_tracer_config_serialization() {
 			serde_json::from_str(json_data).expect("Deserialization should succeed");
 		assert_eq!(result, expected);
 	}
+
+	assert!(serde_json::from_str::<TracerConfig>(r#"{"tracer": "unknownTracer"}"#).is_err());
 }
 
 /// The type of call that was executed.
@@ -175,6 +267,12 @@ pub enum Trace {
 	Call(CallTrace),
 	/// A prestate trace.
 	Prestate(PrestateTrace),
+	/// A struct logger trace.
+	StructLogger(StructLoggerTrace),
+	/// A flat call trace.
+	FlatCall(Vec<FlatCallTrace>),
+	/// A 4byte trace.
+	FourByte(FourByteTrace),
 }
 
 /// A prestate Trace
@@ -378,6 +476,251 @@ pub struct CallLog {
 	pub position: u32,
 }
 
+/// A struct logger trace, listing every instruction executed by a transaction.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLoggerTrace {
+	/// Amount of gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// Return data of the transaction.
+	pub return_value: Bytes,
+	/// The executed instructions.
+	pub struct_logs: Vec<StructLog>,
+}
+
+/// A single instruction executed by a contract.
+///
+/// For PVM contracts, `op` is the name of the PolkaVM instruction, `stack` holds the registers and
+/// `memory` is always empty.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// The program counter.
+	pub pc: u64,
+	/// The name of the instruction.
+	pub op: String,
+	/// Gas left before the instruction is executed.
+	pub gas: u64,
+	/// Gas consumed by the instruction, including the gas used by sub-calls.
+	pub gas_cost: u64,
+	/// The call depth, starting at 1.
+	pub depth: u32,
+	/// The error raised by the instruction, if any.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The stack before the instruction is executed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// The memory before the instruction is executed, in 32 bytes words.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<Bytes>>,
+	/// The storage slots of the current contract accessed by the instruction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<Bytes, Bytes>>,
+}
+
+/// A call, in the flat Parity `trace_*` format.
+///
+/// The block and transaction fields are not known when tracing, they are filled in by the RPC
+/// server.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallTrace {
+	/// The call action.
+	pub action: FlatCallAction,
+	/// The hash of the block containing the transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub block_hash: Option<H256>,
+	/// The number of the block containing the transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub block_number: Option<u64>,
+	/// The error message if the call failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// The call result, if the call succeeded.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub result: Option<FlatCallResult>,
+	/// Number of direct sub-calls.
+	pub subtraces: u32,
+	/// Position of the call in the call tree.
+	pub trace_address: Vec<u32>,
+	/// The hash of the transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub transaction_hash: Option<H256>,
+	/// The index of the transaction in the block.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub transaction_position: Option<u32>,
+	/// The type of the action.
+	#[serde(rename = "type")]
+	pub action_type: FlatCallType,
+}
+
+/// The type of a [`FlatCallTrace`].
+#[derive(
+	Default, TypeInfo, Encode, Decode, Serialize, Deserialize, Eq, PartialEq, Clone, Debug,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum FlatCallType {
+	/// A call.
+	#[default]
+	Call,
+	/// A contract creation.
+	Create,
+	/// A selfdestruct.
+	Suicide,
+}
+
+/// The action of a [`FlatCallTrace`].
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum FlatCallAction {
+	/// A call.
+	Call(FlatCallActionCall),
+	/// A contract creation.
+	Create(FlatCallActionCreate),
+	/// A selfdestruct.
+	Suicide(FlatCallActionSuicide),
+}
+
+impl Default for FlatCallAction {
+	fn default() -> Self {
+		FlatCallAction::Call(Default::default())
+	}
+}
+
+/// A call action.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallActionCall {
+	/// The type of call, in lowercase.
+	pub call_type: String,
+	/// Address of the sender.
+	pub from: H160,
+	/// Amount of gas provided for the call.
+	pub gas: U256,
+	/// Call input data.
+	pub input: Bytes,
+	/// Address of the receiver.
+	pub to: H160,
+	/// Amount of value transferred.
+	pub value: U256,
+}
+
+/// A contract creation action.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallActionCreate {
+	/// Either `create` or `create2`.
+	pub creation_method: String,
+	/// Address of the sender.
+	pub from: H160,
+	/// Amount of gas provided for the creation.
+	pub gas: U256,
+	/// The init code, followed by the constructor input.
+	pub init: Bytes,
+	/// Amount of value transferred.
+	pub value: U256,
+}
+
+/// A selfdestruct action.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct FlatCallActionSuicide {
+	/// Address of the destroyed contract.
+	pub address: H160,
+	/// Address receiving the remaining balance.
+	pub refund_address: H160,
+	/// The remaining balance.
+	pub balance: U256,
+}
+
+/// The result of a [`FlatCallTrace`].
+#[derive(TypeInfo, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum FlatCallResult {
+	/// The result of a contract creation.
+	Create {
+		/// Address of the created contract.
+		address: H160,
+		/// The code of the created contract.
+		code: Bytes,
+		/// Amount of gas used.
+		#[serde(rename = "gasUsed")]
+		gas_used: U256,
+	},
+	/// The result of a call.
+	Call {
+		/// Amount of gas used.
+		#[serde(rename = "gasUsed")]
+		gas_used: U256,
+		/// Return data.
+		output: Bytes,
+	},
+}
+
+/// A 4byte trace, mapping `<selector>-<input size>` to the number of calls.
+///
+/// The input size does not include the 4 bytes of the selector.
+#[derive(
+	TypeInfo, Default, Encode, Decode, Serialize, Deserialize, Clone, Debug, Eq, PartialEq,
+)]
+pub struct FourByteTrace(pub BTreeMap<String, u32>);
+
+/// The filter of `trace_filter`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TraceFilter {
+	/// The first block to trace, defaults to the latest block.
+	pub from_block: Option<BlockNumberOrTag>,
+	/// The last block to trace, defaults to the latest block.
+	pub to_block: Option<BlockNumberOrTag>,
+	/// Only include the calls made by these addresses.
+	pub from_address: Option<Vec<H160>>,
+	/// Only include the calls made to these addresses.
+	pub to_address: Option<Vec<H160>>,
+	/// The number of matching traces to skip.
+	pub after: Option<u64>,
+	/// The maximum number of traces to return.
+	pub count: Option<u64>,
+}
+
+impl TraceFilter {
+	/// Whether the given trace matches the address criteria of the filter.
+	pub fn matches(&self, trace: &FlatCallTrace) -> bool {
+		let (from, to) = match (&trace.action, &trace.result) {
+			(FlatCallAction::Call(call), _) => (call.from, Some(call.to)),
+			(FlatCallAction::Create(create), Some(FlatCallResult::Create { address, .. })) =>
+				(create.from, Some(*address)),
+			(FlatCallAction::Create(create), _) => (create.from, None),
+			(FlatCallAction::Suicide(suicide), _) =>
+				(suicide.address, Some(suicide.refund_address)),
+		};
+
+		let from_matches = self
+			.from_address
+			.as_ref()
+			.map_or(true, |addresses| addresses.is_empty() || addresses.contains(&from));
+		let to_matches = self.to_address.as_ref().map_or(true, |addresses| {
+			addresses.is_empty() || to.is_some_and(|to| addresses.contains(&to))
+		});
+		from_matches && to_matches
+	}
+}
+
 /// A transaction trace
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -388,3 +731,26 @@ pub struct TransactionTrace {
 	#[serde(rename = "result")]
 	pub trace: Trace,
 }
+
+#[test]
+fn trace_filter_matches_works() {
+	let call = |from: H160, to: H160| FlatCallTrace {
+		action: FlatCallAction::Call(FlatCallActionCall { from, to, ..Default::default() }),
+		..Default::default()
+	};
+	let (a, b, c) = (H160::repeat_byte(1), H160::repeat_byte(2), H160::repeat_byte(3));
+
+	assert!(TraceFilter::default().matches(&call(a, b)));
+
+	let filter = TraceFilter { from_address: Some(vec![a]), ..Default::default() };
+	assert!(filter.matches(&call(a, b)));
+	assert!(!filter.matches(&call(b, a)));
+
+	let filter = TraceFilter {
+		from_address: Some(vec![a]),
+		to_address: Some(vec![c]),
+		..Default::default()
+	};
+	assert!(!filter.matches(&call(a, b)));
+	assert!(filter.matches(&call(a, c)));
+}
@@ -412,19 +412,18 @@ impl Decodable for Transaction7702Signed {
 					nonce: rlp.val_at(1)?,
 					max_priority_fee_per_gas: rlp.val_at(2)?,
 					max_fee_per_gas: rlp.val_at(3)?,
-					gas_price: rlp.val_at(4)?,
-					gas: rlp.val_at(5)?,
-					to: rlp.val_at(6)?,
-					value: rlp.val_at(7)?,
-					input: Bytes(rlp.val_at(8)?),
-					access_list: rlp.list_at(9)?,
-					authorization_list: rlp.list_at(10)?,
-					r#type: Default::default(),
+					gas: rlp.val_at(4)?,
+					to: rlp.val_at(5)?,
+					value: rlp.val_at(6)?,
+					input: Bytes(rlp.val_at(7)?),
+					access_list: rlp.list_at(8)?,
+					authorization_list: rlp.list_at(9)?,
+					..Default::default()
 				}
 			},
-			y_parity: rlp.val_at(11)?,
-			r: rlp.val_at(12)?,
-			s: rlp.val_at(13)?,
+			y_parity: rlp.val_at(10)?,
+			r: rlp.val_at(11)?,
+			s: rlp.val_at(12)?,
 			v: None,
 		})
 	}
@@ -20,6 +20,7 @@ use alloc::vec::Vec;
 use codec::{Decode, Encode};
 use frame_support::DefaultNoBound;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_core::{H160, U256};
 
 /// Configuration specific to a dry-run execution.
@@ -128,6 +129,114 @@ impl ReceiptInfo {
 		bloom.into()
 	}
 }
+
+impl Filter {
+	/// Returns `true` if the log matches the address and topics criteria of this filter.
+	///
+	/// The block range (`from_block`, `to_block` and `block_hash`) is not checked.
+	pub fn matches(&self, log: &Log) -> bool {
+		let address_matches = match &self.address {
+			None => true,
+			Some(AddressOrAddresses::Address(addr)) => *addr == log.address,
+			Some(AddressOrAddresses::Addresses(addrs)) =>
+				addrs.is_empty() || addrs.contains(&log.address),
+		};
+
+		if !address_matches {
+			return false;
+		}
+
+		let Some(topics) = &self.topics else { return true };
+		topics.iter().enumerate().all(|(i, topic)| match (topic, log.topics.get(i)) {
+			(FilterTopic::Multiple(hashes), _) if hashes.is_empty() => true,
+			(_, None) => false,
+			(FilterTopic::Single(hash), Some(log_topic)) => hash == log_topic,
+			(FilterTopic::Multiple(hashes), Some(log_topic)) => hashes.contains(log_topic),
+		})
+	}
+}
+
+/// The header of a [`Block`], as sent to `newHeads` subscribers.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeader {
+	/// Base fee per gas
+	pub base_fee_per_gas: U256,
+	/// Blob gas used
+	pub blob_gas_used: U256,
+	/// Difficulty
+	pub difficulty: U256,
+	/// Excess blob gas
+	pub excess_blob_gas: U256,
+	/// Extra data
+	pub extra_data: Bytes,
+	/// Gas limit
+	pub gas_limit: U256,
+	/// Gas used
+	pub gas_used: U256,
+	/// Hash
+	pub hash: H256,
+	/// Bloom filter
+	pub logs_bloom: Bytes256,
+	/// Coinbase
+	pub miner: Address,
+	/// Mix hash
+	pub mix_hash: H256,
+	/// Nonce
+	pub nonce: Bytes8,
+	/// Number
+	pub number: U256,
+	/// Parent Beacon Block Root
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub parent_beacon_block_root: Option<H256>,
+	/// Parent block hash
+	pub parent_hash: H256,
+	/// Receipts root
+	pub receipts_root: H256,
+	/// Requests root
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub requests_hash: Option<H256>,
+	/// Ommers hash
+	pub sha_3_uncles: H256,
+	/// State root
+	pub state_root: H256,
+	/// Timestamp
+	pub timestamp: U256,
+	/// Transactions root
+	pub transactions_root: H256,
+	/// Withdrawals root
+	pub withdrawals_root: H256,
+}
+
+impl From<Block> for BlockHeader {
+	fn from(block: Block) -> Self {
+		Self {
+			base_fee_per_gas: block.base_fee_per_gas,
+			blob_gas_used: block.blob_gas_used,
+			difficulty: block.difficulty,
+			excess_blob_gas: block.excess_blob_gas,
+			extra_data: block.extra_data,
+			gas_limit: block.gas_limit,
+			gas_used: block.gas_used,
+			hash: block.hash,
+			logs_bloom: block.logs_bloom,
+			miner: block.miner,
+			mix_hash: block.mix_hash,
+			nonce: block.nonce,
+			number: block.number,
+			parent_beacon_block_root: block.parent_beacon_block_root,
+			parent_hash: block.parent_hash,
+			receipts_root: block.receipts_root,
+			requests_hash: block.requests_hash,
+			sha_3_uncles: block.sha_3_uncles,
+			state_root: block.state_root,
+			timestamp: block.timestamp,
+			transactions_root: block.transactions_root,
+			withdrawals_root: block.withdrawals_root,
+		}
+	}
+}
+
 /// Specialised Bloom filter that sets three bits out of 2048, given an
 /// arbitrary byte sequence.
 ///
@@ -224,6 +333,92 @@ fn logs_bloom_works() {
 	assert_eq!(receipt.logs_bloom, ReceiptInfo::logs_bloom(&receipt.logs));
 }
 
+#[test]
+fn filter_matches_works() {
+	let log = Log {
+		address: H160([1u8; 20]),
+		topics: vec![H256([1u8; 32]), H256([2u8; 32])],
+		..Default::default()
+	};
+
+	let cases = [
+		("empty filter", Filter::default(), true),
+		(
+			"matching address",
+			Filter { address: Some(H160([1u8; 20]).into()), ..Default::default() },
+			true,
+		),
+		(
+			"other address",
+			Filter { address: Some(H160([2u8; 20]).into()), ..Default::default() },
+			false,
+		),
+		(
+			"one of addresses",
+			Filter {
+				address: Some(vec![H160([2u8; 20]), H160([1u8; 20])].into()),
+				..Default::default()
+			},
+			true,
+		),
+		(
+			"matching topics",
+			Filter {
+				topics: Some(vec![H256([1u8; 32]).into(), H256([2u8; 32]).into()]),
+				..Default::default()
+			},
+			true,
+		),
+		(
+			"wildcard first topic",
+			Filter {
+				topics: Some(vec![FilterTopic::Multiple(vec![]), H256([2u8; 32]).into()]),
+				..Default::default()
+			},
+			true,
+		),
+		(
+			"one of topics",
+			Filter {
+				topics: Some(vec![FilterTopic::Multiple(vec![H256([3u8; 32]), H256([1u8; 32])])]),
+				..Default::default()
+			},
+			true,
+		),
+		(
+			"other topic",
+			Filter { topics: Some(vec![H256([2u8; 32]).into()]), ..Default::default() },
+			false,
+		),
+		(
+			"too many topics",
+			Filter {
+				topics: Some(vec![
+					H256([1u8; 32]).into(),
+					H256([2u8; 32]).into(),
+					H256([3u8; 32]).into(),
+				]),
+				..Default::default()
+			},
+			false,
+		),
+	];
+
+	for (name, filter, expected) in cases {
+		assert_eq!(filter.matches(&log), expected, "{name}");
+	}
+}
+
+#[test]
+fn block_header_omits_block_body() {
+	let block = Block { number: 42u64.into(), uncles: vec![H256([1u8; 32])], ..Default::default() };
+	let header = serde_json::to_value(BlockHeader::from(block)).unwrap();
+	assert_eq!(header["number"], "0x2a");
+	for field in ["transactions", "uncles", "withdrawals", "size"] {
+		assert!(header.get(field).is_none(), "{field}");
+	}
+}
+
 impl GenericTransaction {
 	/// Create a new [`GenericTransaction`] from a signed transaction.
 	pub fn from_signed(tx: TransactionSigned, base_gas_price: U256, from: Option<H160>) -> Self {
@@ -1105,6 +1105,59 @@ pub struct FeeHistoryResult {
 	pub reward: Vec<Vec<U256>>,
 }
 
+/// Account proof, as returned by `eth_getProof` (EIP-1186).
+///
+/// pallet-revive state lives in a Substrate trie, not in an Ethereum Merkle-Patricia trie, so the
+/// proofs are Substrate read proofs that must be verified against the `stateRoot` of the
+/// Substrate block header:
+///
+/// - `accountProof` is a read proof of the top trie for the `System::Account` entry (nonce and
+///   balance), the `Revive::AccountInfoOf` entry (dust and code hash) and, for contracts, the child
+///   trie root entry, whose value is `storageHash`.
+/// - `storageHash` is the root of the contract child trie, or zero if the account has no code.
+/// - Each `storageProof` entry is a read proof of the contract child trie, rooted at `storageHash`,
+///   for the `blake2_256` hash of the storage slot.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProof {
+	/// The address of the account.
+	pub address: Address,
+	/// The trie nodes proving the account values.
+	pub account_proof: Vec<Bytes>,
+	/// The balance of the account.
+	pub balance: U256,
+	/// The hash of the account code.
+	pub code_hash: H256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The root of the account storage trie.
+	pub storage_hash: H256,
+	/// The proofs of the requested storage slots.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+/// Storage slot proof, part of an [`AccountProof`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageProof {
+	/// The requested storage slot.
+	pub key: U256,
+	/// The value of the storage slot.
+	pub value: U256,
+	/// The trie nodes proving the storage value.
+	pub proof: Vec<Bytes>,
+}
+
+/// Access list result, returned by `eth_createAccessList`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+	/// The accounts and storage slots accessed by the transaction.
+	pub access_list: AccessList,
+	/// The amount of gas used by the transaction.
+	pub gas_used: U256,
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
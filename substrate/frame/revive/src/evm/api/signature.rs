@@ -17,6 +17,7 @@
 //! Ethereum signature utilities
 
 use super::*;
+use alloc::vec::Vec;
 use sp_core::{H160, U256};
 use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
 
@@ -173,6 +174,64 @@ impl TransactionSigned {
 	}
 }
 
+impl AuthorizationListEntry {
+	/// The magic byte prefixed to the RLP encoded authorization before signing it.
+	/// See https://eips.ethereum.org/EIPS/eip-7702
+	pub const MAGIC: u8 = 0x05;
+
+	/// Half of the secp256k1 curve order. Signatures with a greater `s` are rejected.
+	const SECP256K1N_HALF: U256 =
+		U256([0xdfe92f46681b20a0, 0x5d576e7357a4501d, 0xffffffffffffffff, 0x7fffffffffffffff]);
+
+	/// Get the payload signed by the authority.
+	pub fn unsigned_payload(&self) -> Vec<u8> {
+		let mut s = rlp::RlpStream::new_list(3);
+		s.append(&self.chain_id);
+		s.append(&self.address);
+		s.append(&self.nonce);
+		let mut payload = alloc::vec![Self::MAGIC];
+		payload.extend_from_slice(&s.out());
+		payload
+	}
+
+	/// Recover the Ethereum address of the authority that signed the authorization.
+	pub fn recover_authority(&self) -> Result<H160, ()> {
+		if self.s > Self::SECP256K1N_HALF || self.y_parity > U256::one() {
+			return Err(());
+		}
+
+		let mut signature = [0u8; 65];
+		self.r.write_as_big_endian(signature[0..32].as_mut());
+		self.s.write_as_big_endian(signature[32..64].as_mut());
+		signature[64] = self.y_parity.try_into().map_err(|_| ())?;
+
+		let hash = keccak_256(&self.unsigned_payload());
+		let mut addr = H160::default();
+		let pk = secp256k1_ecdsa_recover(&signature, &hash).map_err(|_| ())?;
+		addr.assign_from_slice(&keccak_256(&pk[..])[12..]);
+		Ok(addr)
+	}
+}
+
+#[test]
+fn sign_and_recover_authorization_work() {
+	let account = Account::default();
+	let authorization = account.sign_authorization(1.into(), H160::repeat_byte(0x42), 7.into());
+
+	assert_eq!(authorization.address, H160::repeat_byte(0x42));
+	assert_eq!(authorization.recover_authority(), Ok(account.address()));
+
+	let tampered = AuthorizationListEntry { nonce: 8.into(), ..authorization.clone() };
+	assert_ne!(tampered.recover_authority(), Ok(account.address()));
+
+	let high_s = AuthorizationListEntry { s: U256::MAX, ..authorization.clone() };
+	assert_eq!(high_s.recover_authority(), Err(()));
+
+	let y_parity =
+		AuthorizationListEntry { y_parity: authorization.y_parity + 27, ..authorization };
+	assert_eq!(y_parity.recover_authority(), Err(()));
+}
+
 #[test]
 fn sign_and_recover_work() {
 	use crate::evm::TransactionUnsigned;
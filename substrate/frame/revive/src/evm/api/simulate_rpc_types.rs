@@ -0,0 +1,183 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Types used by `eth_simulateV1`.
+
+use crate::evm::{Bytes, GenericTransaction, Log};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
+use sp_core::{H160, H256, U256};
+
+/// The overrides applied to the state before simulating calls, keyed by account address.
+pub type StateOverrides = BTreeMap<H160, AccountOverride>;
+
+/// The overrides applied to a single account.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Overrides the balance of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub balance: Option<U256>,
+	/// Overrides the nonce of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub nonce: Option<U256>,
+	/// Overrides the code of the account, turning it into a contract if needed.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub code: Option<Bytes>,
+	/// Replaces the whole storage of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Overrides individual storage slots of the account.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// The overrides applied to the environment of a simulated block.
+///
+/// Only the block number and timestamp can be overridden.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+	/// Overrides the block number.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub number: Option<U256>,
+	/// Overrides the block timestamp, in seconds.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub time: Option<U256>,
+}
+
+/// A block of calls to simulate, along with the overrides applied before executing them.
+#[derive(
+	Debug, Default, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize, Eq, PartialEq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+	/// The block environment overrides.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub block_overrides: Option<BlockOverrides>,
+	/// The state overrides.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub state_overrides: Option<StateOverrides>,
+	/// The calls to execute, in order.
+	#[serde(default)]
+	pub calls: Vec<GenericTransaction>,
+}
+
+/// The `eth_simulateV1` payload.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+	/// The blocks to simulate, in order.
+	pub block_state_calls: Vec<SimulateBlock>,
+}
+
+/// A simulated block, returned by `eth_simulateV1`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+	/// The block number.
+	pub number: U256,
+	/// The block timestamp, in seconds.
+	pub timestamp: U256,
+	/// The base fee per gas.
+	pub base_fee_per_gas: U256,
+	/// The gas used by all the calls of the block.
+	pub gas_used: U256,
+	/// The results of the calls, in order.
+	pub calls: Vec<SimulateCallResult>,
+}
+
+/// The result of a simulated call.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallResult {
+	/// The data returned by the call.
+	pub return_data: Bytes,
+	/// The logs emitted by the call.
+	pub logs: Vec<Log>,
+	/// The gas used by the call.
+	pub gas_used: U256,
+	/// `1` if the call succeeded, `0` otherwise.
+	pub status: U256,
+	/// The error of a failed call.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub error: Option<SimulateCallError>,
+}
+
+/// The error of a failed simulated call.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallError {
+	/// The JSON-RPC error code.
+	pub code: i32,
+	/// The error message.
+	pub message: String,
+	/// The revert data, if the call reverted.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub data: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn simulate_payload_serialization_works() {
+		let payload = serde_json::json!({
+			"blockStateCalls": [{
+				"blockOverrides": { "number": "0x10", "time": "0x20" },
+				"stateOverrides": {
+					"0x0101010101010101010101010101010101010101": {
+						"balance": "0x1",
+						"stateDiff": {
+							"0x0000000000000000000000000000000000000000000000000000000000000001":
+							"0x0000000000000000000000000000000000000000000000000000000000000002"
+						}
+					}
+				},
+				"calls": [{ "to": "0x0202020202020202020202020202020202020202" }]
+			}]
+		});
+
+		let payload: SimulatePayload = serde_json::from_value(payload).unwrap();
+		let block = &payload.block_state_calls[0];
+		assert_eq!(
+			block.block_overrides,
+			Some(BlockOverrides { number: Some(16.into()), time: Some(32.into()) })
+		);
+		assert_eq!(
+			block.state_overrides,
+			Some(StateOverrides::from([(
+				H160::repeat_byte(1),
+				AccountOverride {
+					balance: Some(1.into()),
+					state_diff: Some(BTreeMap::from([(
+						H256::from_low_u64_be(1),
+						H256::from_low_u64_be(2)
+					)])),
+					..Default::default()
+				}
+			)]))
+		);
+		assert_eq!(block.calls[0].to, Some(H160::repeat_byte(2)));
+	}
+}
@@ -35,7 +35,8 @@ use sp_runtime::{transaction_validity::InvalidTransaction, SaturatedConversion};
 pub struct CallInfo<T: Config> {
 	/// The dispatchable call with the correct weights assigned.
 	///
-	/// This will be either `eth_call` or `eth_instantiate_with_code`.
+	/// This will be either `eth_call`, `eth_call_with_authorizations` or
+	/// `eth_instantiate_with_code`.
 	pub call: CallOf<T>,
 	/// The weight that was set inside [`Self::call`].
 	pub weight_limit: Weight,
@@ -122,6 +123,15 @@ impl GenericTransaction {
 
 		let value = self.value.unwrap_or_default();
 		let data = self.input.to_vec();
+		let authorization_list = self.authorization_list;
+
+		// See https://eips.ethereum.org/EIPS/eip-7702
+		if !authorization_list.is_empty() &&
+			self.to.map_or(true, |dest| dest == RUNTIME_PALLETS_ADDR)
+		{
+			log::debug!(target: LOG_TARGET, "Authorizations require a contract call");
+			return Err(InvalidTransaction::Call);
+		}
 
 		let mut call = if let Some(dest) = self.to {
 			if dest == RUNTIME_PALLETS_ADDR {
@@ -139,7 +149,7 @@ impl GenericTransaction {
 
 				crate::Call::eth_substrate_call::<T> { call: Box::new(call), transaction_encoded }
 					.into()
-			} else {
+			} else if authorization_list.is_empty() {
 				let call = crate::Call::eth_call::<T> {
 					dest,
 					value,
@@ -152,6 +162,20 @@ impl GenericTransaction {
 				}
 				.into();
 				call
+			} else {
+				let call = crate::Call::eth_call_with_authorizations::<T> {
+					dest,
+					value,
+					weight_limit: Zero::zero(),
+					eth_gas_limit: gas,
+					data,
+					transaction_encoded,
+					effective_gas_price,
+					encoded_len,
+					authorization_list,
+				}
+				.into();
+				call
 			}
 		} else {
 			let (code, data) = if data.starts_with(&polkavm_common::program::BLOB_MAGIC) {
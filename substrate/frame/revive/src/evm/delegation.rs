@@ -0,0 +1,187 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Set-code delegations of externally owned accounts.
+//!
+//! See <https://eips.ethereum.org/EIPS/eip-7702>
+
+use crate::{
+	address::AddressMapper,
+	evm::{
+		fees::{compute_max_integer_quotient, InfoT},
+		AuthorizationListEntry,
+	},
+	exec::is_precompile,
+	weights::WeightInfo,
+	AccountInfo, BalanceOf, Config, ContractBlob, DelegationOf, HoldReason, LOG_TARGET,
+};
+use alloc::vec::Vec;
+use frame_support::{
+	traits::{fungible::MutateHold, tokens::Precision},
+	weights::Weight,
+};
+use frame_system::Pallet as System;
+use sp_core::{Get, H160, U256};
+use sp_runtime::{SaturatedConversion, Saturating};
+
+/// The prefix of the code reported for an account that delegates its code.
+pub const DELEGATION_INDICATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// Returns the code reported for an account that delegates its code to `address`.
+pub fn delegation_indicator(address: &H160) -> Vec<u8> {
+	let mut code = DELEGATION_INDICATOR_PREFIX.to_vec();
+	code.extend_from_slice(address.as_bytes());
+	code
+}
+
+/// The gas charged for every authorization of a transaction.
+pub const PER_EMPTY_ACCOUNT_COST: u64 = 25_000;
+
+/// The gas an authorization costs if its authority already exists.
+///
+/// The difference to [`PER_EMPTY_ACCOUNT_COST`] is refunded after applying the authorization.
+pub const PER_AUTH_BASE_COST: u64 = 12_500;
+
+/// The weight charged for processing an authorization list with `len` entries.
+///
+/// Includes [`PER_EMPTY_ACCOUNT_COST`] for every entry, converted to weight at
+/// `effective_gas_price`.
+pub(crate) fn authorization_list_weight<T: Config>(
+	len: usize,
+	effective_gas_price: U256,
+) -> Weight {
+	T::WeightInfo::ecdsa_recover()
+		.saturating_add(T::DbWeight::get().reads_writes(4, 4))
+		.saturating_add(gas_to_weight::<T>(PER_EMPTY_ACCOUNT_COST, effective_gas_price))
+		.saturating_mul(len as u64)
+}
+
+/// The deposit held from an authority for storing its delegation in [`DelegationOf`].
+pub(crate) fn delegation_deposit<T: Config>() -> BalanceOf<T> {
+	// each entry stores the address of the authority and the address it delegates to
+	T::DepositPerByte::get()
+		.saturating_mul(40u32.into())
+		.saturating_add(T::DepositPerItem::get())
+}
+
+/// Convert an amount of Ethereum gas into the weight that costs the same at `effective_gas_price`.
+fn gas_to_weight<T: Config>(gas: u64, effective_gas_price: U256) -> Weight {
+	let fee = effective_gas_price.saturating_mul(gas.into()) / T::NativeToEthRatio::get();
+	let fee = compute_max_integer_quotient(
+		T::FeeInfo::next_fee_multiplier(),
+		fee.saturated_into::<BalanceOf<T>>(),
+	);
+	T::FeeInfo::fee_to_weight(fee)
+}
+
+/// Process the authorization list of a transaction.
+///
+/// Each valid authorization sets the delegation of its authority and increments the nonce of the
+/// authority. Invalid authorizations are skipped without failing the transaction.
+///
+/// Returns the weight to refund from what [`authorization_list_weight`] charged.
+pub(crate) fn apply_authorizations<T: Config>(
+	authorization_list: &[AuthorizationListEntry],
+	effective_gas_price: U256,
+) -> Weight {
+	let mut refund = 0u64;
+	for authorization in authorization_list {
+		match apply_authorization::<T>(authorization) {
+			Ok((authority, gas_refund)) => {
+				log::debug!(
+					target: LOG_TARGET,
+					"Account {authority:?} delegated its code to {:?}",
+					authorization.address
+				);
+				refund.saturating_accrue(gas_refund);
+			},
+			Err(reason) =>
+				log::debug!(target: LOG_TARGET, "Skipping authorization {authorization:?}: {reason}"),
+		}
+	}
+	gas_to_weight::<T>(refund, effective_gas_price)
+}
+
+/// Apply a single authorization and return its authority and the gas to refund.
+///
+/// Setting a delegation holds [`delegation_deposit`] from the authority. As the authority needs a
+/// balance to pay for it, it already exists and is refunded the difference to
+/// [`PER_AUTH_BASE_COST`]. Clearing a delegation releases the deposit and refunds the whole
+/// [`PER_EMPTY_ACCOUNT_COST`].
+fn apply_authorization<T: Config>(
+	authorization: &AuthorizationListEntry,
+) -> Result<(H160, u64), &'static str> {
+	if !authorization.chain_id.is_zero() && authorization.chain_id != T::ChainId::get().into() {
+		return Err("invalid chain id");
+	}
+
+	let nonce: u64 = authorization.nonce.try_into().map_err(|_| "invalid nonce")?;
+	if nonce == u64::MAX {
+		return Err("invalid nonce");
+	}
+
+	let authority = authorization.recover_authority().map_err(|_| "invalid signature")?;
+
+	if AccountInfo::<T>::has_code(&authority) || is_precompile::<T, ContractBlob<T>>(&authority) {
+		return Err("authority is a contract");
+	}
+
+	let account_id = T::AddressMapper::to_account_id(&authority);
+	if System::<T>::account_nonce(&account_id).saturated_into::<u64>() != nonce {
+		return Err("nonce mismatch");
+	}
+
+	let delegated = DelegationOf::<T>::contains_key(&authority);
+	let refund = if authorization.address.is_zero() {
+		if delegated {
+			T::Currency::release_all(
+				&HoldReason::Delegation.into(),
+				&account_id,
+				Precision::BestEffort,
+			)
+			.map_err(|_| "failed to release the deposit")?;
+			DelegationOf::<T>::remove(&authority);
+		}
+		PER_EMPTY_ACCOUNT_COST
+	} else {
+		if !delegated {
+			T::Currency::hold(
+				&HoldReason::Delegation.into(),
+				&account_id,
+				delegation_deposit::<T>(),
+			)
+			.map_err(|_| "insufficient balance for the deposit")?;
+		}
+		DelegationOf::<T>::insert(&authority, authorization.address);
+		PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST
+	};
+	System::<T>::inc_account_nonce(&account_id);
+
+	Ok((authority, refund))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn delegation_indicator_works() {
+		let indicator = delegation_indicator(&H160::repeat_byte(0x42));
+		assert_eq!(indicator.len(), 23);
+		assert_eq!(indicator[..3], DELEGATION_INDICATOR_PREFIX);
+		assert_eq!(indicator[3..], [0x42; 20]);
+	}
+}
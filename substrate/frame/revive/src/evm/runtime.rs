@@ -45,7 +45,8 @@ use sp_runtime::{
 	Debug, OpaqueExtrinsic, Weight,
 };
 
-/// Used to set the weight limit argument of a `eth_call` or `eth_instantiate_with_code` call.
+/// Used to set the weight limit argument of a `eth_call`, `eth_call_with_authorizations` or
+/// `eth_instantiate_with_code` call.
 pub trait SetWeightLimit {
 	/// Set the weight limit of this call.
 	///
@@ -290,9 +291,11 @@ pub trait EthExtra {
 			crate::evm::api::TransactionSigned::TransactionLegacySigned(_) => {
 				// Supported transaction types, continue processing
 			},
-			crate::evm::api::TransactionSigned::Transaction7702Signed(_) => {
-				log::debug!(target: LOG_TARGET, "EIP-7702 transactions are not supported");
-				return Err(InvalidTransaction::Call);
+			crate::evm::api::TransactionSigned::Transaction7702Signed(tx) => {
+				if tx.transaction_7702_unsigned.authorization_list.is_empty() {
+					log::debug!(target: LOG_TARGET, "EIP-7702 transaction without authorizations");
+					return Err(InvalidTransaction::Call);
+				}
 			},
 			crate::evm::api::TransactionSigned::Transaction4844Signed(_) => {
 				log::debug!(target: LOG_TARGET, "EIP-4844 transactions are not supported");
@@ -601,6 +604,40 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn check_eth_transact_7702_works() {
+		let authorization = Account::from_secret_key([1u8; 32]).sign_authorization(
+			<Test as Config>::ChainId::get().into(),
+			H160::from([2u8; 20]),
+			0u32.into(),
+		);
+		let base_fee =
+			ExtBuilder::default().build().execute_with(|| Pallet::<Test>::evm_base_fee());
+		let mut builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		builder.tx.r#type = Some(TYPE_EIP7702.into());
+		builder.tx.max_fee_per_gas = Some(base_fee);
+		builder.tx.authorization_list = vec![authorization.clone()];
+		let (_, call, _, _, _, _) = builder.check().unwrap();
+
+		match call {
+			RuntimeCall::Contracts(crate::Call::eth_call_with_authorizations::<Test> {
+				authorization_list,
+				..
+			}) => {
+				assert_eq!(authorization_list, vec![authorization]);
+			},
+			_ => panic!("Call does not match."),
+		}
+
+		// EIP-7702 transactions must carry at least one authorization.
+		let mut builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
+		builder.tx.r#type = Some(TYPE_EIP7702.into());
+		assert_eq!(
+			builder.check(),
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Call))
+		);
+	}
+
 	#[test]
 	fn check_eth_transact_nonce_works() {
 		let builder = UncheckedExtrinsicBuilder::call_with(H160::from([1u8; 20]));
@@ -15,7 +15,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use crate::{
-	evm::{CallTrace, Trace},
+	evm::{CallTrace, FourByteTrace, StructLoggerTrace, Trace},
 	tracing::Tracing,
 	Config,
 };
@@ -26,6 +26,18 @@ pub use call_tracing::*;
 mod prestate_tracing;
 pub use prestate_tracing::*;
 
+mod struct_logger;
+pub use struct_logger::*;
+
+mod flat_call_tracing;
+pub use flat_call_tracing::*;
+
+mod four_byte_tracing;
+pub use four_byte_tracing::*;
+
+mod access_list_tracing;
+pub use access_list_tracing::*;
+
 /// A composite tracer.
 #[derive(derive_more::From, Debug)]
 pub enum Tracer<T> {
@@ -33,6 +45,12 @@ pub enum Tracer<T> {
 	CallTracer(CallTracer),
 	/// A tracer that traces the prestate.
 	PrestateTracer(PrestateTracer<T>),
+	/// A tracer that logs every executed instruction.
+	StructLogger(StructLogger),
+	/// A tracer that reports calls as a flat list.
+	FlatCallTracer(FlatCallTracer),
+	/// A tracer that counts the 4-byte selectors of the calls.
+	FourByteTracer(FourByteTracer),
 }
 
 impl<T: Config> Tracer<T>
@@ -44,6 +62,9 @@ where
 		match self {
 			Tracer::CallTracer(_) => CallTrace::default().into(),
 			Tracer::PrestateTracer(tracer) => tracer.empty_trace().into(),
+			Tracer::StructLogger(_) => StructLoggerTrace::default().into(),
+			Tracer::FlatCallTracer(_) => Trace::FlatCall(Default::default()),
+			Tracer::FourByteTracer(_) => FourByteTrace::default().into(),
 		}
 	}
 
@@ -52,6 +73,9 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner as &mut dyn Tracing,
 			Tracer::PrestateTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::StructLogger(inner) => inner as &mut dyn Tracing,
+			Tracer::FlatCallTracer(inner) => inner as &mut dyn Tracing,
+			Tracer::FourByteTracer(inner) => inner as &mut dyn Tracing,
 		}
 	}
 
@@ -60,6 +84,9 @@ where
 		match self {
 			Tracer::CallTracer(inner) => inner.collect_trace().map(Trace::Call),
 			Tracer::PrestateTracer(inner) => Some(inner.collect_trace().into()),
+			Tracer::StructLogger(inner) => Some(inner.collect_trace().into()),
+			Tracer::FlatCallTracer(inner) => inner.collect_trace().map(Trace::FlatCall),
+			Tracer::FourByteTracer(inner) => Some(inner.collect_trace().into()),
 		}
 	}
 }
@@ -0,0 +1,201 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{AccessList, AccessListEntry},
+	tracing::Tracing,
+	Code, DispatchError, ExecReturnValue, Key,
+};
+use alloc::{
+	collections::{BTreeMap, BTreeSet},
+	vec::Vec,
+};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that records the accounts and storage slots accessed by a transaction, used to build
+/// an EIP-2930 access list.
+///
+/// Contracts created by the transaction are not part of the collected list. Neither are addresses
+/// that are excluded (typically the sender and the recipient), unless their storage was accessed.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AccessListTracer {
+	/// The addresses that are only part of the access list along with their storage slots.
+	excluded: BTreeSet<H160>,
+	/// The accessed addresses along with their accessed storage slots.
+	accessed: BTreeMap<H160, BTreeSet<H256>>,
+	/// Stack of the addresses owning the storage of each active call frame.
+	calls: Vec<H160>,
+	/// Whether the next call frame is a contract creation.
+	is_create: bool,
+	/// List of created contracts addresses.
+	created_addrs: BTreeSet<H160>,
+}
+
+impl AccessListTracer {
+	/// Create a new [`AccessListTracer`] instance.
+	///
+	/// The entries of `access_list` are always part of the collected list, even if they are not
+	/// accessed by the transaction.
+	pub fn new(excluded: impl IntoIterator<Item = H160>, access_list: AccessList) -> Self {
+		let accessed = access_list
+			.into_iter()
+			.map(|entry| (entry.address, entry.storage_keys.into_iter().collect()))
+			.collect();
+		Self { excluded: excluded.into_iter().collect(), accessed, ..Default::default() }
+	}
+
+	/// Collect the access list and return it.
+	pub fn collect_access_list(self) -> AccessList {
+		let Self { excluded, accessed, created_addrs, .. } = self;
+		accessed
+			.into_iter()
+			// Excluded addresses are warm anyway, but their storage slots are not.
+			.filter(|(address, storage_keys)| {
+				!created_addrs.contains(address) &&
+					!(excluded.contains(address) && storage_keys.is_empty())
+			})
+			.map(|(address, storage_keys)| AccessListEntry {
+				address,
+				storage_keys: storage_keys.into_iter().collect(),
+			})
+			.collect()
+	}
+
+	fn current_addr(&self) -> H160 {
+		self.calls.last().copied().unwrap_or_default()
+	}
+
+	fn access_address(&mut self, address: H160) {
+		self.accessed.entry(address).or_default();
+	}
+
+	fn access_storage(&mut self, key: &Key) {
+		// Only fixed sized keys can be expressed in an access list.
+		let Key::Fix(key) = key else { return };
+		let address = self.current_addr();
+		self.accessed.entry(address).or_default().insert(H256(*key));
+	}
+}
+
+impl Tracing for AccessListTracer {
+	fn instantiate_code(&mut self, _code: &Code, _salt: Option<&[u8; 32]>) {
+		self.is_create = true;
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_limit: U256,
+	) {
+		if core::mem::take(&mut self.is_create) {
+			self.created_addrs.insert(to);
+		}
+
+		if let Some(delegate_call) = delegate_call {
+			self.calls.push(self.current_addr());
+			self.access_address(delegate_call);
+		} else {
+			self.calls.push(to);
+			self.access_address(to);
+		}
+	}
+
+	fn exit_child_span(&mut self, _output: &ExecReturnValue, _gas_used: U256) {
+		self.calls.pop();
+	}
+
+	fn exit_child_span_with_error(&mut self, _error: DispatchError, _gas_used: U256) {
+		self.calls.pop();
+	}
+
+	fn terminate(
+		&mut self,
+		_contract_address: H160,
+		beneficiary_address: H160,
+		_gas_left: U256,
+		_value: U256,
+	) {
+		self.access_address(beneficiary_address);
+	}
+
+	fn balance_read(&mut self, addr: &H160, _value: U256) {
+		self.access_address(*addr);
+	}
+
+	fn storage_read(&mut self, key: &Key, _value: Option<&[u8]>) {
+		self.access_storage(key);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, _new_value: Option<&[u8]>) {
+		self.access_storage(key);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	#[test]
+	fn collect_access_list_works() {
+		let sender = H160::repeat_byte(1);
+		let contract = H160::repeat_byte(2);
+		let library = H160::repeat_byte(3);
+		let created = H160::repeat_byte(4);
+		let listed = H160::repeat_byte(5);
+
+		let mut tracer = AccessListTracer::new(
+			[sender],
+			vec![AccessListEntry { address: listed, storage_keys: vec![H256::repeat_byte(9)] }],
+		);
+
+		let output = ExecReturnValue::default();
+		tracer.enter_child_span(sender, contract, None, false, 0.into(), &[], 0.into());
+		tracer.storage_read(&Key::Fix([1u8; 32]), None);
+
+		// The storage accessed by a delegate call belongs to the caller.
+		tracer.enter_child_span(contract, contract, Some(library), false, 0.into(), &[], 0.into());
+		tracer.storage_write(&Key::Fix([2u8; 32]), None, Some(&[1u8]));
+		tracer.exit_child_span(&output, 0.into());
+
+		tracer.instantiate_code(&Code::Existing(Default::default()), None);
+		tracer.enter_child_span(contract, created, None, false, 0.into(), &[], 0.into());
+		tracer.storage_write(&Key::Fix([3u8; 32]), None, Some(&[1u8]));
+		tracer.exit_child_span(&output, 0.into());
+
+		tracer.balance_read(&sender, 0.into());
+		tracer.exit_child_span(&output, 0.into());
+
+		let expected = vec![
+			AccessListEntry {
+				address: contract,
+				storage_keys: vec![H256::repeat_byte(1), H256::repeat_byte(2)],
+			},
+			AccessListEntry { address: library, storage_keys: vec![] },
+			AccessListEntry { address: listed, storage_keys: vec![H256::repeat_byte(9)] },
+		];
+		assert_eq!(tracer.clone().collect_access_list(), expected);
+
+		// Excluding the called contract keeps its storage slots.
+		tracer.excluded.insert(contract);
+		assert_eq!(tracer.collect_access_list(), expected);
+	}
+}
@@ -0,0 +1,232 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{
+		CallTrace, CallTracer, CallTracerConfig, CallType, FlatCallAction, FlatCallActionCall,
+		FlatCallActionCreate, FlatCallActionSuicide, FlatCallResult, FlatCallTrace, FlatCallType,
+	},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	Code, DispatchError,
+};
+use alloc::{string::ToString, vec::Vec};
+use sp_core::{H160, H256, U256};
+
+/// A tracer that reports calls as a flat list, in the Parity `trace_*` format.
+///
+/// The nested calls are recorded with a [`CallTracer`] and flattened once the transaction is
+/// done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatCallTracer(CallTracer);
+
+impl Default for FlatCallTracer {
+	fn default() -> Self {
+		Self(CallTracer::new(CallTracerConfig { with_logs: false, only_top_call: false }))
+	}
+}
+
+impl FlatCallTracer {
+	/// Collect the traces and return them.
+	pub fn collect_trace(self) -> Option<Vec<FlatCallTrace>> {
+		let trace = self.0.collect_trace()?;
+		let mut traces = Vec::new();
+		flatten(trace, Vec::new(), &mut traces);
+		Some(traces)
+	}
+}
+
+/// Append `trace` and its sub-calls, in depth-first order, to `traces`.
+fn flatten(trace: CallTrace, trace_address: Vec<u32>, traces: &mut Vec<FlatCallTrace>) {
+	let CallTrace {
+		from, gas, gas_used, to, input, output, error, calls, value, call_type, ..
+	} = trace;
+	let value = value.unwrap_or_default();
+
+	let (action_type, action, result) = match call_type {
+		CallType::Create | CallType::Create2 => (
+			FlatCallType::Create,
+			FlatCallAction::Create(FlatCallActionCreate {
+				creation_method: if call_type == CallType::Create2 { "create2" } else { "create" }
+					.to_string(),
+				from,
+				gas,
+				init: input,
+				value,
+			}),
+			FlatCallResult::Create { address: to, code: output, gas_used },
+		),
+		CallType::Selfdestruct => (
+			FlatCallType::Suicide,
+			FlatCallAction::Suicide(FlatCallActionSuicide {
+				address: from,
+				refund_address: to,
+				balance: value,
+			}),
+			FlatCallResult::Call { gas_used, output },
+		),
+		CallType::Call | CallType::StaticCall | CallType::DelegateCall => {
+			let call_type = match call_type {
+				CallType::StaticCall => "staticcall",
+				CallType::DelegateCall => "delegatecall",
+				_ => "call",
+			};
+			(
+				FlatCallType::Call,
+				FlatCallAction::Call(FlatCallActionCall {
+					call_type: call_type.to_string(),
+					from,
+					gas,
+					input,
+					to,
+					value,
+				}),
+				FlatCallResult::Call { gas_used, output },
+			)
+		},
+	};
+
+	let result = match action_type {
+		FlatCallType::Suicide => None,
+		_ if error.is_some() => None,
+		_ => Some(result),
+	};
+
+	traces.push(FlatCallTrace {
+		action,
+		error,
+		result,
+		subtraces: calls.len() as u32,
+		trace_address: trace_address.clone(),
+		action_type,
+		..Default::default()
+	});
+
+	for (index, call) in calls.into_iter().enumerate() {
+		let mut child_address = trace_address.clone();
+		child_address.push(index as u32);
+		flatten(call, child_address, traces);
+	}
+}
+
+// Forward the hooks used by the call tracer.
+impl Tracing for FlatCallTracer {
+	fn instantiate_code(&mut self, code: &Code, salt: Option<&[u8; 32]>) {
+		self.0.instantiate_code(code, salt)
+	}
+
+	fn terminate(
+		&mut self,
+		contract_address: H160,
+		beneficiary_address: H160,
+		gas_left: U256,
+		value: U256,
+	) {
+		self.0.terminate(contract_address, beneficiary_address, gas_left, value)
+	}
+
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		is_read_only: bool,
+		value: U256,
+		input: &[u8],
+		gas_limit: U256,
+	) {
+		self.0
+			.enter_child_span(from, to, delegate_call, is_read_only, value, input, gas_limit)
+	}
+
+	fn log_event(&mut self, address: H160, topics: &[H256], data: &[u8]) {
+		self.0.log_event(address, topics, data)
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: U256) {
+		self.0.exit_child_span(output, gas_used)
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: U256) {
+		self.0.exit_child_span_with_error(error, gas_used)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::evm::Bytes;
+	use alloc::vec;
+
+	#[test]
+	fn flatten_works() {
+		let trace = CallTrace {
+			from: H160::repeat_byte(1),
+			to: H160::repeat_byte(2),
+			input: Bytes(vec![1, 2, 3, 4]),
+			call_type: CallType::Call,
+			calls: vec![
+				CallTrace {
+					from: H160::repeat_byte(2),
+					to: H160::repeat_byte(3),
+					call_type: CallType::Create2,
+					..Default::default()
+				},
+				CallTrace {
+					from: H160::repeat_byte(2),
+					to: H160::repeat_byte(4),
+					call_type: CallType::StaticCall,
+					error: Some("execution reverted".to_string()),
+					calls: vec![CallTrace {
+						from: H160::repeat_byte(4),
+						to: H160::repeat_byte(5),
+						call_type: CallType::DelegateCall,
+						..Default::default()
+					}],
+					..Default::default()
+				},
+			],
+			..Default::default()
+		};
+
+		let mut traces = Vec::new();
+		flatten(trace, Vec::new(), &mut traces);
+
+		let summary = traces
+			.iter()
+			.map(|trace| (trace.action_type.clone(), trace.trace_address.clone(), trace.subtraces))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			summary,
+			vec![
+				(FlatCallType::Call, vec![], 2),
+				(FlatCallType::Create, vec![0], 0),
+				(FlatCallType::Call, vec![1], 1),
+				(FlatCallType::Call, vec![1, 0], 0),
+			]
+		);
+
+		assert!(matches!(
+			&traces[1].action,
+			FlatCallAction::Create(FlatCallActionCreate { creation_method, .. }) if creation_method == "create2"
+		));
+		assert_eq!(traces[2].result, None);
+		assert!(matches!(
+			&traces[3].action,
+			FlatCallAction::Call(FlatCallActionCall { call_type, .. }) if call_type == "delegatecall"
+		));
+	}
+}
@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{CallTrace, CallTracer, CallTracerConfig, CallType, FourByteTrace},
+	primitives::ExecReturnValue,
+	tracing::Tracing,
+	Code, DispatchError,
+};
+use alloc::format;
+use alloy_core::hex;
+use sp_core::{H160, U256};
+
+/// A tracer that counts the 4-byte selectors and input sizes of the calls made by a transaction.
+///
+/// The calls are recorded with a [`CallTracer`], contract creations are not counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FourByteTracer(CallTracer);
+
+impl Default for FourByteTracer {
+	fn default() -> Self {
+		Self(CallTracer::new(CallTracerConfig { with_logs: false, only_top_call: false }))
+	}
+}
+
+impl FourByteTracer {
+	/// Collect the trace and return it.
+	pub fn collect_trace(self) -> FourByteTrace {
+		let mut trace = FourByteTrace::default();
+		if let Some(call) = self.0.collect_trace() {
+			count_selectors(&call, &mut trace);
+		}
+		trace
+	}
+}
+
+/// Count the selector of `call` and its sub-calls in `trace`.
+fn count_selectors(call: &CallTrace, trace: &mut FourByteTrace) {
+	let is_call =
+		matches!(call.call_type, CallType::Call | CallType::StaticCall | CallType::DelegateCall);
+	if is_call && call.input.0.len() >= 4 {
+		let (selector, data) = call.input.0.split_at(4);
+		let key = format!("0x{}-{}", hex::encode(selector), data.len());
+		*trace.0.entry(key).or_default() += 1;
+	}
+
+	for call in &call.calls {
+		count_selectors(call, trace);
+	}
+}
+
+// Forward the hooks used by the call tracer.
+impl Tracing for FourByteTracer {
+	fn instantiate_code(&mut self, code: &Code, salt: Option<&[u8; 32]>) {
+		self.0.instantiate_code(code, salt)
+	}
+
+	fn enter_child_span(
+		&mut self,
+		from: H160,
+		to: H160,
+		delegate_call: Option<H160>,
+		is_read_only: bool,
+		value: U256,
+		input: &[u8],
+		gas_limit: U256,
+	) {
+		self.0
+			.enter_child_span(from, to, delegate_call, is_read_only, value, input, gas_limit)
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: U256) {
+		self.0.exit_child_span(output, gas_used)
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: U256) {
+		self.0.exit_child_span_with_error(error, gas_used)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::evm::Bytes;
+	use alloc::{collections::BTreeMap, string::ToString, vec};
+
+	#[test]
+	fn count_selectors_works() {
+		let call = CallTrace {
+			input: Bytes(vec![0xa9, 0x05, 0x9c, 0xbb, 0, 0]),
+			call_type: CallType::Call,
+			calls: vec![
+				CallTrace {
+					input: Bytes(vec![0xa9, 0x05, 0x9c, 0xbb, 1, 1]),
+					call_type: CallType::StaticCall,
+					..Default::default()
+				},
+				CallTrace {
+					input: Bytes(vec![0xa9, 0x05, 0x9c, 0xbb]),
+					call_type: CallType::Create,
+					..Default::default()
+				},
+				CallTrace { input: Bytes(vec![1, 2]), ..Default::default() },
+			],
+			..Default::default()
+		};
+
+		let mut trace = FourByteTrace::default();
+		count_selectors(&call, &mut trace);
+		assert_eq!(trace.0, BTreeMap::from([("0xa9059cbb-2".to_string(), 2)]));
+	}
+}
@@ -0,0 +1,175 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use crate::{
+	evm::{Bytes, StructLog, StructLoggerConfig, StructLoggerTrace},
+	primitives::ExecReturnValue,
+	tracing::{StepInfo, Tracing},
+	DispatchError, Key,
+};
+use alloc::{
+	collections::BTreeMap,
+	format,
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
+use sp_core::{H160, U256};
+
+/// A tracer that records every executed instruction, like the default geth struct logger.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct StructLogger {
+	/// The tracer configuration.
+	config: StructLoggerConfig,
+	/// The recorded logs.
+	logs: Vec<StructLog>,
+	/// The index of the last recorded log of each active call frame.
+	last_logs: Vec<Option<usize>>,
+	/// Amount of gas used by the transaction.
+	gas_used: u64,
+	/// Whether the transaction failed.
+	failed: bool,
+	/// Return data of the transaction.
+	return_value: Vec<u8>,
+}
+
+impl StructLogger {
+	/// Create a new [`StructLogger`] instance.
+	pub fn new(config: StructLoggerConfig) -> Self {
+		Self { config, ..Default::default() }
+	}
+
+	/// Collect the trace and return it.
+	pub fn collect_trace(self) -> StructLoggerTrace {
+		StructLoggerTrace {
+			gas: self.gas_used,
+			failed: self.failed,
+			return_value: self.return_value.into(),
+			struct_logs: self.logs,
+		}
+	}
+
+	/// Record a storage access of the current frame on its last log.
+	///
+	/// Only the slots accessed by the instruction of the log are recorded on it.
+	fn record_storage(&mut self, key: &Key, value: Option<&[u8]>) {
+		if self.config.disable_storage {
+			return
+		}
+
+		let Some(Some(index)) = self.last_logs.last() else { return };
+		let Some(log) = self.logs.get_mut(*index) else { return };
+
+		let value = value.map_or_else(|| vec![0u8; 32], |value| value.to_vec());
+		log.storage
+			.get_or_insert_with(BTreeMap::new)
+			.insert(key.unhashed().to_vec().into(), value.into());
+	}
+
+	/// Leave the current frame, recording the error on its last log.
+	fn exit(&mut self, gas_used: U256, failed: bool, error: Option<String>) {
+		let Some(last_log) = self.last_logs.pop() else { return };
+
+		if let (Some(error), Some(index)) = (error, last_log) {
+			if let Some(log) = self.logs.get_mut(index) {
+				log.error = Some(error);
+			}
+		}
+
+		if self.last_logs.is_empty() {
+			self.gas_used = gas_used.try_into().unwrap_or(u64::MAX);
+			self.failed = failed;
+		}
+	}
+}
+
+impl Tracing for StructLogger {
+	fn is_step_tracing_enabled(&self) -> bool {
+		true
+	}
+
+	fn step(&mut self, step: &StepInfo) {
+		if self.config.limit != 0 && self.logs.len() as u64 >= self.config.limit {
+			return
+		}
+
+		// The cost of the previous instruction of this frame is only known now, as it includes
+		// the gas used by the sub-calls it made.
+		if let Some(Some(index)) = self.last_logs.last() {
+			if let Some(log) = self.logs.get_mut(*index) {
+				log.gas_cost = log.gas.saturating_sub(step.gas_left);
+			}
+		}
+
+		let memory = self
+			.config
+			.enable_memory
+			.then(|| step.memory.chunks(32).map(|word| Bytes(word.to_vec())).collect::<Vec<_>>());
+
+		self.logs.push(StructLog {
+			pc: step.pc,
+			op: step.op.to_string(),
+			gas: step.gas_left,
+			gas_cost: 0,
+			depth: self.last_logs.len() as u32,
+			error: None,
+			stack: (!self.config.disable_stack).then(|| step.stack.to_vec()),
+			memory,
+			storage: None,
+		});
+
+		if let Some(last_log) = self.last_logs.last_mut() {
+			*last_log = Some(self.logs.len() - 1);
+		}
+	}
+
+	fn enter_child_span(
+		&mut self,
+		_from: H160,
+		_to: H160,
+		_delegate_call: Option<H160>,
+		_is_read_only: bool,
+		_value: U256,
+		_input: &[u8],
+		_gas_limit: U256,
+	) {
+		self.last_logs.push(None);
+	}
+
+	fn storage_read(&mut self, key: &Key, value: Option<&[u8]>) {
+		self.record_storage(key, value);
+	}
+
+	fn storage_write(&mut self, key: &Key, _old_value: Option<Vec<u8>>, new_value: Option<&[u8]>) {
+		self.record_storage(key, new_value);
+	}
+
+	fn exit_child_span(&mut self, output: &ExecReturnValue, gas_used: U256) {
+		if self.last_logs.len() == 1 {
+			self.return_value = output.data.clone();
+		}
+		self.exit(gas_used, output.did_revert(), None);
+	}
+
+	fn exit_child_span_with_error(&mut self, error: DispatchError, gas_used: U256) {
+		let error = match error {
+			DispatchError::Module(sp_runtime::ModuleError { message, .. }) =>
+				message.unwrap_or_default().to_string(),
+			_ => format!("{:?}", error),
+		};
+		self.exit(gas_used, true, Some(error));
+	}
+}
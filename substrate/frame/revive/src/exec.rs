@@ -17,7 +17,7 @@
 
 use crate::{
 	address::{self, AddressMapper},
-	evm::{block_storage, transfer_with_dust},
+	evm::{block_storage, delegation_indicator, transfer_with_dust},
 	limits,
 	metering::{ChargedAmount, Diff, FrameMeter, ResourceMeter, State, Token, TransactionMeter},
 	precompiles::{All as AllPrecompiles, Instance as PrecompileInstance, Precompiles},
@@ -760,6 +760,11 @@ enum CachedContract<T: Config> {
 	Invalidated,
 	/// The frame is associated with pre-compile that has no contract info.
 	None,
+	/// The frame runs the code an account without code of its own delegates to (EIP-7702).
+	///
+	/// The contract info only exists in memory and is never persisted. Its storage can be read
+	/// but not written.
+	Delegated(ContractInfo<T>),
 }
 
 impl<T: Config> Frame<T> {
@@ -840,10 +845,18 @@ impl<T: Config> CachedContract<T> {
 
 	/// Return the cached contract_info.
 	fn get(&mut self, account_id: &T::AccountId) -> &mut ContractInfo<T> {
+		if let CachedContract::Delegated(contract) = self {
+			return contract;
+		}
 		self.load(account_id);
 		get_cached_or_panic_after_load!(self)
 	}
 
+	/// Return true if the frame runs the code of a delegation (EIP-7702).
+	fn is_delegated(&self) -> bool {
+		matches!(self, CachedContract::Delegated(_))
+	}
+
 	/// Set the status to invalidate if is cached.
 	fn invalidate(&mut self) {
 		if matches!(self, CachedContract::Cached(_)) {
@@ -1066,6 +1079,10 @@ where
 					(None, None) =>
 						if let Some(info) = AccountInfo::<T>::load_contract(&address) {
 							CachedContract::Cached(info)
+						} else if let Some(code_hash) = Self::delegated_code_hash(&address) {
+							let nonce = <System<T>>::account_nonce(&dest);
+							let info = ContractInfo::new(&address, nonce, code_hash)?;
+							CachedContract::Delegated(info)
 						} else {
 							return Ok(None);
 						},
@@ -1096,11 +1113,13 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let Some(info) = AccountInfo::<T>::load_contract(&delegated_call.callee)
+						let Some(code_hash) =
+							AccountInfo::<T>::code_hash_of(&delegated_call.callee)
+								.or_else(|| Self::delegated_code_hash(&delegated_call.callee))
 						else {
 							return Ok(None);
 						};
-						let executable = E::from_storage(info.code_hash, meter)?;
+						let executable = E::from_storage(code_hash, meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				} else {
@@ -1110,13 +1129,7 @@ where
 							_phantom: Default::default(),
 						}
 					} else {
-						let executable = E::from_storage(
-							contract
-								.as_contract()
-								.expect("When not a precompile the contract was loaded above; qed")
-								.code_hash,
-							meter,
-						)?;
+						let executable = E::from_storage(contract.get(&dest).code_hash, meter)?;
 						ExecutableOrPrecompile::Executable(executable)
 					}
 				};
@@ -1173,6 +1186,14 @@ where
 		Ok(Some((frame, executable)))
 	}
 
+	/// The code hash of the code `address` delegates to (EIP-7702).
+	///
+	/// `None` if `address` has no delegation or the delegation points to an account without code.
+	fn delegated_code_hash(address: &H160) -> Option<H256> {
+		AccountInfo::<T>::load_delegation(address)
+			.and_then(|delegation| AccountInfo::<T>::code_hash_of(&delegation))
+	}
+
 	/// Create a subsequent nested frame.
 	fn push_frame(
 		&mut self,
@@ -1854,13 +1875,15 @@ where
 		*self.last_frame_output_mut() = Default::default();
 
 		let top_frame = self.top_frame_mut();
-		let contract_info = top_frame.contract_info().clone();
+		// the contract info of a delegation is not cached as it is reconstructed on load
+		let cached_info =
+			(!top_frame.contract_info.is_delegated()).then(|| top_frame.contract_info().clone());
 		let account_id = top_frame.account_id.clone();
 		let value = top_frame.value_transferred;
 		if let Some(executable) = self.push_frame(
 			FrameArgs::Call {
 				dest: account_id,
-				cached_info: Some(contract_info),
+				cached_info,
 				delegated_call: Some(DelegateInfo {
 					caller: self.caller().clone(),
 					callee: address,
@@ -1929,12 +1952,15 @@ where
 		}
 
 		// Immutable is read from contract code being executed
-		let address = self
-			.top_frame()
-			.delegate
-			.as_ref()
-			.map(|d| d.callee)
-			.unwrap_or(T::AddressMapper::to_address(self.account_id()));
+		let frame = self.top_frame();
+		let address = frame.delegate.as_ref().map(|d| d.callee).unwrap_or_else(|| {
+			let address = T::AddressMapper::to_address(&frame.account_id);
+			if frame.contract_info.is_delegated() {
+				AccountInfo::<T>::load_delegation(&address).unwrap_or(address)
+			} else {
+				address
+			}
+		});
 		Ok(<ImmutableDataOf<T>>::get(address).ok_or_else(|| Error::<T>::InvalidImmutableAccess)?)
 	}
 
@@ -2208,14 +2234,16 @@ where
 			return sp_io::hashing::keccak_256(code).into()
 		}
 
-		<AccountInfo<T>>::load_contract(&address)
-			.map(|contract| contract.code_hash)
-			.unwrap_or_else(|| {
-				if System::<T>::account_exists(&T::AddressMapper::to_account_id(address)) {
-					return EMPTY_CODE_HASH;
-				}
-				H256::zero()
-			})
+		if let Some(delegation) = <AccountInfo<T>>::load_delegation(address) {
+			return sp_io::hashing::keccak_256(&delegation_indicator(&delegation)).into()
+		}
+
+		<AccountInfo<T>>::code_hash_of(&address).unwrap_or_else(|| {
+			if System::<T>::account_exists(&T::AddressMapper::to_account_id(address)) {
+				return EMPTY_CODE_HASH;
+			}
+			H256::zero()
+		})
 	}
 
 	fn code_size(&self, address: &H160) -> u64 {
@@ -2223,8 +2251,12 @@ where
 			return code.len() as u64
 		}
 
-		<AccountInfo<T>>::load_contract(&address)
-			.and_then(|contract| CodeInfoOf::<T>::get(contract.code_hash))
+		if let Some(delegation) = <AccountInfo<T>>::load_delegation(address) {
+			return delegation_indicator(&delegation).len() as u64
+		}
+
+		<AccountInfo<T>>::code_hash_of(&address)
+			.and_then(CodeInfoOf::<T>::get)
 			.map(|info| info.code_len())
 			.unwrap_or_default()
 	}
@@ -2363,8 +2395,10 @@ where
 			return;
 		}
 
-		let code_hash = self.code_hash(address);
-		let code = crate::PristineCode::<T>::get(&code_hash).unwrap_or_default();
+		let code = match <AccountInfo<T>>::load_delegation(address) {
+			Some(delegation) => delegation_indicator(&delegation),
+			None => crate::PristineCode::<T>::get(&self.code_hash(address)).unwrap_or_default(),
+		};
 
 		let len = len.min(code.len().saturating_sub(code_offset));
 		if len > 0 {
@@ -2379,6 +2413,7 @@ where
 		let parent = self.frames_mut().nth(1).ok_or_else(|| Error::<T>::ContractNotFound)?;
 		ensure!(parent.entry_point == ExportedFunction::Call, Error::<T>::TerminatedInConstructor);
 		ensure!(parent.delegate.is_none(), Error::<T>::PrecompileDelegateDenied);
+		ensure!(!parent.contract_info.is_delegated(), Error::<T>::DelegatedAccountTerminated);
 
 		let info = parent.contract_info();
 		let trie_id = info.trie_id.clone();
@@ -2436,6 +2471,7 @@ where
 	) -> Result<WriteOutcome, DispatchError> {
 		assert!(self.has_contract_info());
 		let frame = self.top_frame_mut();
+		ensure!(!frame.contract_info.is_delegated(), Error::<T>::DelegatedStorageWrite);
 		frame.contract_info.get(&frame.account_id).write(
 			key.into(),
 			value,
@@ -49,8 +49,10 @@ pub mod weights;
 use crate::{
 	evm::{
 		block_hash::EthereumBlockBuilderIR, block_storage, fees::InfoT as FeeInfo,
-		runtime::SetWeightLimit, CallTracer, CreateCallMode, GenericTransaction, PrestateTracer,
-		Trace, Tracer, TracerType, TYPE_EIP1559,
+		runtime::SetWeightLimit, AccessList, AccessListTracer, AccountOverride,
+		AuthorizationListEntry, BlockOverrides, CallLog, CallTrace, CallTracer, CallTracerConfig,
+		CreateCallMode, FlatCallTracer, FourByteTracer, GenericTransaction, PrestateTracer,
+		SimulateBlock, StructLogger, Trace, Tracer, TracerType, TYPE_EIP1559,
 	},
 	exec::{AccountIdOf, ExecError, ReentrancyProtection, Stack as ExecStack},
 	storage::{AccountType, DeletionQueueManager},
@@ -623,6 +625,12 @@ pub mod pallet {
 		/// Some pre-compile functions will trap the caller context if being delegate
 		/// called or if their caller was being delegate called.
 		PrecompileDelegateDenied = 0x40,
+		/// Tried to write the storage of an account that runs delegated code (EIP-7702).
+		///
+		/// Accounts without code of their own have no contract storage.
+		DelegatedStorageWrite = 0x41,
+		/// Tried to terminate an account that runs delegated code (EIP-7702).
+		DelegatedAccountTerminated = 0x42,
 		/// Benchmarking only error.
 		#[cfg(feature = "runtime-benchmarks")]
 		BenchmarkingError = 0xFF,
@@ -637,6 +645,8 @@ pub mod pallet {
 		StorageDepositReserve,
 		/// Deposit for creating an address mapping in [`OriginalAccount`].
 		AddressMapping,
+		/// Deposit for a code delegation in [`DelegationOf`].
+		Delegation,
 	}
 
 	#[derive(
@@ -662,6 +672,12 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type AccountInfoOf<T: Config> = StorageMap<_, Identity, H160, AccountInfo<T>>;
 
+	/// The address an externally owned account delegates its code to.
+	///
+	/// Set by the EIP-7702 authorizations of a transaction.
+	#[pallet::storage]
+	pub(crate) type DelegationOf<T: Config> = StorageMap<_, Identity, H160, H160>;
+
 	/// The immutable data associated with a given account.
 	#[pallet::storage]
 	pub(crate) type ImmutableDataOf<T: Config> = StorageMap<_, Identity, H160, ImmutableData>;
@@ -1356,11 +1372,7 @@ pub mod pallet {
 			effective_gas_price: U256,
 			encoded_len: u32,
 		) -> DispatchResultWithPostInfo {
-			let signer = Self::ensure_eth_signed(origin)?;
-			let origin = OriginFor::<T>::signed(signer.clone());
-
-			Self::ensure_non_contract_if_signed(&origin)?;
-			let mut call = Call::<T>::eth_call {
+			let call = Call::<T>::eth_call {
 				dest,
 				value,
 				weight_limit,
@@ -1371,34 +1383,76 @@ pub mod pallet {
 				encoded_len,
 			}
 			.into();
-			let info = T::FeeInfo::dispatch_info(&call);
-			let base_info = T::FeeInfo::base_dispatch_info(&mut call);
-			drop(call);
-
-			block_storage::with_ethereum_context::<T>(transaction_encoded, || {
-				let extra_weight = base_info.total_weight();
-				let output = Self::bare_call(
-					origin,
-					dest,
-					value,
-					TransactionLimits::EthereumGas {
-						eth_gas_limit: eth_gas_limit.saturated_into(),
-						maybe_weight_limit: Some(weight_limit),
-						eth_tx_info: EthTxInfo::new(encoded_len, extra_weight),
-					},
-					data,
-					ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight),
-				);
+			Self::do_eth_call(
+				origin,
+				call,
+				dest,
+				value,
+				weight_limit,
+				eth_gas_limit,
+				data,
+				transaction_encoded,
+				effective_gas_price,
+				encoded_len,
+				&[],
+			)
+		}
 
-				block_storage::EthereumCallResult::new::<T>(
-					signer,
-					output,
-					base_info.call_weight,
-					encoded_len,
-					&info,
-					effective_gas_price,
-				)
-			})
+		/// Same as [`Self::eth_call`], but applies the authorizations of an EIP-7702
+		/// transaction before executing the call.
+		///
+		/// # Parameters
+		///
+		/// Same as [`Self::eth_call`] with the addition of:
+		///
+		/// * `authorization_list`: The EIP-7702 authorizations applied before executing the call.
+		#[pallet::call_index(13)]
+		#[pallet::weight(
+			T::WeightInfo::eth_call(Pallet::<T>::has_dust(*value).into())
+			.saturating_add(*weight_limit)
+			.saturating_add(T::WeightInfo::on_finalize_block_per_tx(transaction_encoded.len() as u32))
+			.saturating_add(crate::evm::authorization_list_weight::<T>(
+				authorization_list.len(),
+				*effective_gas_price,
+			))
+		)]
+		pub fn eth_call_with_authorizations(
+			origin: OriginFor<T>,
+			dest: H160,
+			value: U256,
+			weight_limit: Weight,
+			eth_gas_limit: U256,
+			data: Vec<u8>,
+			transaction_encoded: Vec<u8>,
+			effective_gas_price: U256,
+			encoded_len: u32,
+			authorization_list: Vec<AuthorizationListEntry>,
+		) -> DispatchResultWithPostInfo {
+			let call = Call::<T>::eth_call_with_authorizations {
+				dest,
+				value,
+				weight_limit,
+				eth_gas_limit,
+				data: data.clone(),
+				transaction_encoded: transaction_encoded.clone(),
+				effective_gas_price,
+				encoded_len,
+				authorization_list: authorization_list.clone(),
+			}
+			.into();
+			Self::do_eth_call(
+				origin,
+				call,
+				dest,
+				value,
+				weight_limit,
+				eth_gas_limit,
+				data,
+				transaction_encoded,
+				effective_gas_price,
+				encoded_len,
+				&authorization_list,
+			)
 		}
 
 		/// Executes a Substrate runtime call from an Ethereum transaction.
@@ -1820,6 +1874,7 @@ impl<T: Config> Pallet<T> {
 		let input = tx.input.clone().to_vec();
 		let from = tx.from;
 		let to = tx.to;
+		let authorization_list = tx.authorization_list.clone();
 
 		// we need to parse the weight from the transaction so that it is run
 		// using the exact weight limit passed by the eth wallet
@@ -1890,6 +1945,10 @@ impl<T: Config> Pallet<T> {
 
 					Default::default()
 				} else {
+					// Like on-chain, the authorizations are applied after the nonce of the sender
+					// was bumped by `prepare_dry_run`. This allows self-sponsored authorizations.
+					crate::evm::apply_authorizations::<T>(&authorization_list, effective_gas_price);
+
 					// Dry run the call.
 					let result = crate::Pallet::<T>::bare_call(
 						OriginFor::<T>::signed(origin),
@@ -2139,6 +2198,256 @@ impl<T: Config> Pallet<T> {
 			.into()
 	}
 
+	/// Dry-run an Ethereum transaction and return the EIP-2930 access list of the accounts and
+	/// storage slots it accesses.
+	///
+	/// The sender, the recipient and the pre-compiles are not part of the returned list, while
+	/// the entries of the access list of `tx` are always kept.
+	///
+	/// Accesses are not priced warm or cold as in EIP-2929: the access list only changes the gas
+	/// of a transaction through the length of its encoding. The transaction is run again with the
+	/// access list it produced until the list settles, so that the returned gas includes the
+	/// length fee of the transaction carrying it.
+	pub fn create_access_list(
+		tx: GenericTransaction,
+		dry_run_config: DryRunConfig<<<T as Config>::Time as Time>::Moment>,
+	) -> Result<AccessListInfo, EthTransactError>
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		use frame_support::storage::{
+			transactional::with_transaction_opaque_err, TransactionOutcome,
+		};
+		use precompiles::{All, Precompiles};
+
+		/// Upper bound on the number of runs needed for the access list to settle.
+		const MAX_RUNS: usize = 8;
+
+		let excluded: Vec<_> = [tx.from.unwrap_or_default()].into_iter().chain(tx.to).collect();
+		let mut tx = tx;
+		let mut access_list = tx.access_list.clone().unwrap_or_default();
+		for _ in 0..MAX_RUNS {
+			let mut tracer = AccessListTracer::new(excluded.iter().copied(), access_list.clone());
+			// Every run starts from the same state, so that runs only differ by the access list
+			// they were given.
+			let info = with_transaction_opaque_err(|| {
+				TransactionOutcome::Rollback(crate::tracing::trace(&mut tracer, || {
+					Self::dry_run_eth_transact(tx.clone(), dry_run_config.clone())
+				}))
+			})
+			.map_err(|_| EthTransactError::Message("Transactional layer limit reached".into()))??;
+
+			let new_access_list: AccessList = tracer
+				.collect_access_list()
+				.into_iter()
+				.filter(|entry| <All<T>>::code(entry.address.as_fixed_bytes()).is_none())
+				.collect();
+
+			// The call was executed with exactly the access list it produced, so `eth_gas`
+			// includes the length fee of the transaction carrying it.
+			if tx.access_list.as_ref() == Some(&new_access_list) {
+				return Ok(AccessListInfo { access_list: new_access_list, eth_gas: info.eth_gas });
+			}
+			access_list = new_access_list.clone();
+			tx.access_list = Some(new_access_list);
+		}
+
+		Err(EthTransactError::Message("Access list did not settle".into()))
+	}
+
+	/// Simulate blocks of Ethereum calls on top of the current state.
+	///
+	/// Each block is executed as a pending block following the previous one, once its block and
+	/// state overrides are applied. The state changes of a call are visible to the calls that
+	/// follow it. This is meant to be called from a runtime API, whose state changes are discarded.
+	pub fn simulate(blocks: Vec<SimulateBlock>) -> Result<Vec<SimulatedBlockInfo>, EthTransactError>
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		let mut number = <frame_system::Pallet<T>>::block_number();
+		let mut timestamp = T::Time::now();
+		let mut results = Vec::with_capacity(blocks.len());
+
+		for SimulateBlock { block_overrides, state_overrides, calls } in blocks {
+			let BlockOverrides { number: number_override, time } =
+				block_overrides.unwrap_or_default();
+
+			let parent_number = number;
+			number = match number_override {
+				Some(number) => BlockNumberFor::<T>::try_from(number).map_err(|_| {
+					EthTransactError::Message(format!("Invalid block number override {number:?}"))
+				})?,
+				None => number.saturating_add(1u32.into()),
+			};
+			if number <= parent_number {
+				return Err(EthTransactError::Message(format!(
+					"Block number {number:?} must be greater than {parent_number:?}"
+				)));
+			}
+
+			// The calls never run before one second after the current timestamp, see
+			// `DryRunConfig::timestamp_override`.
+			timestamp = match time {
+				Some(time) =>
+					u64::try_from(time).unwrap_or(u64::MAX).saturating_mul(1000).saturated_into(),
+				None => timestamp.saturating_add(1000u32.into()),
+			};
+			timestamp = timestamp.max(T::Time::now().saturating_add(1000u32.into()));
+
+			// The calls are executed in the pending block following the block number set here.
+			<frame_system::Pallet<T>>::set_block_number(number.saturating_sub(1u32.into()));
+			for (address, account_override) in state_overrides.unwrap_or_default() {
+				Self::apply_account_override(address, account_override)?;
+			}
+
+			let calls = calls
+				.into_iter()
+				.map(|tx| Self::simulate_call(tx, DryRunConfig::new(Some(timestamp))))
+				.collect();
+
+			results.push(SimulatedBlockInfo {
+				number: number.into(),
+				timestamp: (timestamp / 1000u32.into()).into(),
+				base_fee: Self::evm_base_fee(),
+				calls,
+			});
+		}
+
+		Ok(results)
+	}
+
+	/// Dry-run a single call of [`Self::simulate`], recording the logs it emits.
+	///
+	/// The call runs with the nonce it declares, or the sender's current nonce otherwise. The
+	/// dry-run bumps it, so that the calls that follow see the nonce they would see in a block.
+	fn simulate_call(
+		mut tx: GenericTransaction,
+		dry_run_config: DryRunConfig<<<T as Config>::Time as Time>::Moment>,
+	) -> SimulatedCallInfo
+	where
+		T::Nonce: Into<U256>,
+		CallOf<T>: SetWeightLimit,
+	{
+		let sender = T::AddressMapper::to_account_id(&tx.from.unwrap_or_default());
+		match tx.nonce {
+			Some(nonce) => {
+				let nonce = u64::try_from(nonce).unwrap_or(u64::MAX).saturated_into();
+				frame_system::Account::<T>::mutate(&sender, |info| info.nonce = nonce);
+			},
+			None => tx.nonce = Some(<System<T>>::account_nonce(&sender).into()),
+		}
+
+		let mut tracer =
+			CallTracer::new(CallTracerConfig { with_logs: true, only_top_call: false });
+		let result =
+			crate::tracing::trace(&mut tracer, || Self::dry_run_eth_transact(tx, dry_run_config));
+		let trace = tracer.collect_trace();
+
+		match result {
+			Ok(info) => {
+				let mut logs = Vec::new();
+				if let Some(trace) = &trace {
+					Self::collect_logs(trace, &mut logs);
+				}
+				SimulatedCallInfo { result: Ok(info.data), logs, eth_gas: info.eth_gas }
+			},
+			Err(err) => SimulatedCallInfo {
+				result: Err(err),
+				logs: Vec::new(),
+				eth_gas: trace.map(|trace| trace.gas_used).unwrap_or_default(),
+			},
+		}
+	}
+
+	/// Collect the logs of a call trace in emission order, skipping the failed frames.
+	fn collect_logs(trace: &CallTrace, logs: &mut Vec<CallLog>) {
+		if trace.error.is_some() {
+			return
+		}
+
+		// The position of a log is the number of sub-calls made before it was emitted.
+		let mut own_logs = trace.logs.iter().peekable();
+		for (index, call) in trace.calls.iter().enumerate() {
+			while let Some(log) = own_logs.next_if(|log| log.position as usize <= index) {
+				logs.push(log.clone());
+			}
+			Self::collect_logs(call, logs);
+		}
+		logs.extend(own_logs.cloned());
+	}
+
+	/// Apply the state overrides of a single account of [`Self::simulate`].
+	///
+	/// Code reference counts and storage deposits are not maintained, as the simulated state is
+	/// never persisted.
+	fn apply_account_override(
+		address: H160,
+		account_override: AccountOverride,
+	) -> Result<(), EthTransactError> {
+		use frame_support::storage::child;
+
+		let AccountOverride { balance, nonce, code, state, state_diff } = account_override;
+		let account_id = T::AddressMapper::to_account_id(&address);
+		let override_error = |what: &str, err: &dyn core::fmt::Debug| {
+			EthTransactError::Message(format!(
+				"Failed to override the {what} of {address:?}: {err:?}"
+			))
+		};
+
+		if let Some(nonce) = nonce {
+			let nonce = u64::try_from(nonce).unwrap_or(u64::MAX).saturated_into();
+			frame_system::Account::<T>::mutate(&account_id, |info| info.nonce = nonce);
+		}
+
+		if let Some(code) = code {
+			let blob = if code.0.starts_with(&polkavm_common::program::BLOB_MAGIC) {
+				ContractBlob::<T>::from_pvm_code(code.0.clone(), Self::account_id())
+			} else {
+				ContractBlob::<T>::from_evm_runtime_code(code.0.clone(), account_id.clone())
+			}
+			.map_err(|err| override_error("code", &err))?;
+
+			let code_hash = *blob.code_hash();
+			let contract = match AccountInfo::<T>::load_contract(&address) {
+				Some(mut contract) => {
+					contract.code_hash = code_hash;
+					contract
+				},
+				None => ContractInfo::<T>::new(&address, 0u32.into(), code_hash)
+					.map_err(|err| override_error("code", &err))?,
+			};
+
+			AccountInfo::<T>::insert_contract(&address, contract);
+			<PristineCode<T>>::insert(code_hash, code.0);
+			<CodeInfoOf<T>>::insert(code_hash, blob.code_info().clone());
+		}
+
+		if state.is_some() || state_diff.is_some() {
+			let contract = AccountInfo::<T>::load_contract(&address)
+				.ok_or_else(|| override_error("storage", &ContractAccessError::DoesntExist))?;
+
+			if state.is_some() {
+				let _ = child::clear_storage(&contract.child_trie_info(), None, None);
+			}
+
+			for (key, value) in state.into_iter().chain(state_diff).flatten() {
+				let value = (!value.is_zero()).then(|| value.0.to_vec());
+				contract
+					.write(&Key::from_fixed(key.0), value, None, false)
+					.map_err(|err| override_error("storage", &err))?;
+			}
+		}
+
+		if let Some(balance) = balance {
+			Self::set_evm_balance(&address, balance)
+				.map_err(|err| override_error("balance", &err))?;
+		}
+
+		Ok(())
+	}
+
 	/// Build an EVM tracer from the given tracer type.
 	pub fn evm_tracer(tracer_type: TracerType) -> Tracer<T>
 	where
@@ -2148,6 +2457,10 @@ impl<T: Config> Pallet<T> {
 			TracerType::CallTracer(config) => CallTracer::new(config.unwrap_or_default()).into(),
 			TracerType::PrestateTracer(config) =>
 				PrestateTracer::new(config.unwrap_or_default()).into(),
+			TracerType::StructLogger(config) =>
+				StructLogger::new(config.unwrap_or_default()).into(),
+			TracerType::FlatCallTracer => FlatCallTracer::default().into(),
+			TracerType::FourByteTracer => FourByteTracer::default().into(),
 		}
 	}
 
@@ -2231,6 +2544,64 @@ impl<T: Config> Pallet<T> {
 		Ok(maybe_value)
 	}
 
+	/// Get the account values and storage keys needed to build an EIP-1186 proof.
+	///
+	/// The balance and nonce are read from `System::Account`, which assumes the runtime uses
+	/// `frame_system` as the account store of its currency. The storage slots are read from the
+	/// contract child trie and are empty for accounts that are not contracts.
+	pub fn account_proof_info(address: H160, keys: Vec<[u8; 32]>) -> AccountProofInfo {
+		use frame_support::storage::child;
+		use precompiles::{All, Precompiles};
+
+		let account_id = T::AddressMapper::to_account_id(&address);
+		let contract = AccountInfo::<T>::load_contract(&address);
+		let child_info = contract.as_ref().map(|contract| contract.child_trie_info());
+		let child_trie_key =
+			child_info.as_ref().map(|info| info.prefixed_storage_key().into_inner());
+
+		let code_hash = if let Some(code) = <All<T>>::code(address.as_fixed_bytes()) {
+			sp_io::hashing::keccak_256(code).into()
+		} else {
+			contract.as_ref().map_or(exec::EMPTY_CODE_HASH, |contract| contract.code_hash)
+		};
+
+		let storage_root = child_info
+			.as_ref()
+			.map(|info| {
+				let version = <T as frame_system::Config>::Version::get().state_version();
+				child::root(info, version)
+			})
+			.filter(|root| root.len() == 32)
+			.map(|root| H256::from_slice(&root))
+			.unwrap_or_default();
+
+		let mut account_keys = vec![
+			frame_system::Account::<T>::hashed_key_for(&account_id),
+			AccountInfoOf::<T>::hashed_key_for(&address),
+		];
+		account_keys.extend(child_trie_key.clone());
+
+		let storage = keys
+			.into_iter()
+			.map(|key| {
+				let hashed_key = Key::from_fixed(key).hash();
+				let value =
+					contract.as_ref().and_then(|contract| contract.read(&Key::from_fixed(key)));
+				StorageProofInfo { key, hashed_key, value }
+			})
+			.collect();
+
+		AccountProofInfo {
+			balance: Self::evm_balance(&address),
+			nonce: System::<T>::account_nonce(&account_id).saturated_into::<u64>().into(),
+			code_hash,
+			storage_root,
+			account_keys,
+			child_trie_key,
+			storage,
+		}
+	}
+
 	/// Convert a native balance to EVM balance.
 	pub fn convert_native_to_evm(value: impl Into<BalanceWithDust<BalanceOf<T>>>) -> U256 {
 		let (value, dust) = value.into().deconstruct();
@@ -2309,14 +2680,17 @@ impl<T: Config> Pallet<T> {
 
 	/// Returns the code at `address`.
 	///
-	/// This takes pre-compiles into account.
+	/// This takes pre-compiles and code delegations into account.
 	pub fn code(address: &H160) -> Vec<u8> {
 		use precompiles::{All, Precompiles};
 		if let Some(code) = <All<T>>::code(address.as_fixed_bytes()) {
 			return code.into()
 		}
-		AccountInfo::<T>::load_contract(&address)
-			.and_then(|contract| <PristineCode<T>>::get(contract.code_hash))
+		if let Some(delegation) = AccountInfo::<T>::load_delegation(address) {
+			return crate::evm::delegation_indicator(&delegation)
+		}
+		AccountInfo::<T>::code_hash_of(address)
+			.and_then(<PristineCode<T>>::get)
 			.map(|code| code.into())
 			.unwrap_or_default()
 	}
@@ -2502,6 +2876,62 @@ impl<T: Config> Pallet<T> {
 		<frame_system::Pallet<T>>::deposit_event(<T as Config>::RuntimeEvent::from(event))
 	}
 
+	/// The implementation of [`Self::eth_call`] and [`Self::eth_call_with_authorizations`].
+	///
+	/// `call` is the dispatched call. It is used to compute the fees of the transaction.
+	#[allow(clippy::too_many_arguments)]
+	fn do_eth_call(
+		origin: OriginFor<T>,
+		mut call: CallOf<T>,
+		dest: H160,
+		value: U256,
+		weight_limit: Weight,
+		eth_gas_limit: U256,
+		data: Vec<u8>,
+		transaction_encoded: Vec<u8>,
+		effective_gas_price: U256,
+		encoded_len: u32,
+		authorization_list: &[AuthorizationListEntry],
+	) -> DispatchResultWithPostInfo {
+		let signer = Self::ensure_eth_signed(origin)?;
+		let origin = OriginFor::<T>::signed(signer.clone());
+
+		Self::ensure_non_contract_if_signed(&origin)?;
+		let info = T::FeeInfo::dispatch_info(&call);
+		let mut base_info = T::FeeInfo::base_dispatch_info(&mut call);
+		drop(call);
+
+		// Authorizations are applied even if the call itself fails. Hence they are applied outside
+		// of the storage transaction the call is executed in.
+		let refund = crate::evm::apply_authorizations::<T>(authorization_list, effective_gas_price);
+		base_info.call_weight.saturating_reduce(refund);
+
+		block_storage::with_ethereum_context::<T>(transaction_encoded, || {
+			let extra_weight = base_info.total_weight();
+			let output = Self::bare_call(
+				origin,
+				dest,
+				value,
+				TransactionLimits::EthereumGas {
+					eth_gas_limit: eth_gas_limit.saturated_into(),
+					maybe_weight_limit: Some(weight_limit),
+					eth_tx_info: EthTxInfo::new(encoded_len, extra_weight),
+				},
+				data,
+				ExecConfig::new_eth_tx(effective_gas_price, encoded_len, extra_weight),
+			);
+
+			block_storage::EthereumCallResult::new::<T>(
+				signer,
+				output,
+				base_info.call_weight,
+				encoded_len,
+				&info,
+				effective_gas_price,
+			)
+		})
+	}
+
 	// Returns Ok with the account that signed the eth transaction.
 	fn ensure_eth_signed(origin: OriginFor<T>) -> Result<AccountIdOf<T>, DispatchError> {
 		match <T as Config>::RuntimeOrigin::from(origin).into() {
@@ -2512,7 +2942,7 @@ impl<T: Config> Pallet<T> {
 
 	/// Ensure that the origin is neither a pre-compile nor a contract.
 	///
-	/// This enforces EIP-3607.
+	/// This enforces EIP-3607. Accounts that delegate their code (EIP-7702) are allowed.
 	fn ensure_non_contract_if_signed(origin: &OriginFor<T>) -> DispatchResult {
 		if DebugSettings::bypass_eip_3607::<T>() {
 			return Ok(())
@@ -2525,7 +2955,7 @@ impl<T: Config> Pallet<T> {
 			return Ok(())
 		};
 		if exec::is_precompile::<T, ContractBlob<T>>(&address) ||
-			<AccountInfo<T>>::is_contract(&address)
+			<AccountInfo<T>>::has_code(&address)
 		{
 			log::debug!(
 				target: crate::LOG_TARGET,
@@ -2682,6 +3112,19 @@ sp_api::decl_runtime_apis! {
 		/// See eth-rpc `debug_traceCall` for usage.
 		fn trace_call(tx: GenericTransaction, config: TracerType) -> Result<Trace, EthTransactError>;
 
+		/// Dry run the given call and return its EIP-2930 access list.
+		///
+		/// See [`crate::Pallet::create_access_list`].
+		fn create_access_list(
+			tx: GenericTransaction,
+			config: DryRunConfig<Moment>,
+		) -> Result<AccessListInfo, EthTransactError>;
+
+		/// Simulate blocks of calls with block and state overrides.
+		///
+		/// See [`crate::Pallet::simulate`].
+		fn simulate(blocks: Vec<SimulateBlock>) -> Result<Vec<SimulatedBlockInfo>, EthTransactError>;
+
 		/// The address of the validator that produced the current block.
 		fn block_author() -> H160;
 
@@ -2699,6 +3142,11 @@ sp_api::decl_runtime_apis! {
 
 		/// Construct the new balance and dust components of this EVM balance.
 		fn new_balance_with_dust(balance: U256) -> Result<(Balance, u32), BalanceConversionError>;
+
+		/// Get the account values and storage keys needed to build an EIP-1186 proof.
+		///
+		/// See [`crate::Pallet::account_proof_info`].
+		fn account_proof_info(address: H160, keys: Vec<[u8; 32]>) -> AccountProofInfo;
 	}
 }
 
@@ -2727,6 +3175,7 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 				match self {
 					Self::$Revive(
 						ReviveCall::eth_call{ weight_limit, .. } |
+						ReviveCall::eth_call_with_authorizations{ weight_limit, .. } |
 						ReviveCall::eth_instantiate_with_code{ weight_limit, .. }
 					) => {
 						let old = *weight_limit;
@@ -2951,6 +3400,19 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 					}
 				}
 
+				fn create_access_list(
+					tx: $crate::evm::GenericTransaction,
+					config: $crate::DryRunConfig<__ReviveMacroMoment>,
+				) -> Result<$crate::AccessListInfo, $crate::EthTransactError> {
+					$crate::Pallet::<Self>::create_access_list(tx, config)
+				}
+
+				fn simulate(
+					blocks: Vec<$crate::evm::SimulateBlock>,
+				) -> Result<Vec<$crate::SimulatedBlockInfo>, $crate::EthTransactError> {
+					$crate::Pallet::<Self>::simulate(blocks)
+				}
+
 				fn runtime_pallets_address() -> $crate::H160 {
 					$crate::RUNTIME_PALLETS_ADDR
 				}
@@ -2967,6 +3429,13 @@ macro_rules! impl_runtime_apis_plus_revive_traits {
 				fn new_balance_with_dust(balance: $crate::U256) -> Result<(Balance, u32), $crate::BalanceConversionError> {
 					$crate::Pallet::<Self>::new_balance_with_dust(balance)
 				}
+
+				fn account_proof_info(
+					address: $crate::H160,
+					keys: Vec<[u8; 32]>,
+				) -> $crate::AccountProofInfo {
+					$crate::Pallet::<Self>::account_proof_info(address, keys)
+				}
 			}
 		}
 	};
@@ -176,7 +176,23 @@ pub mod substrate_execution {
 	/// Converts the remaining weight and deposit into their gas-equivalents (via `FeeInfo`) and
 	/// returns the sum. Returns `None` if either component does not have enough left.
 	pub fn gas_left<T: Config, S: State>(meter: &ResourceMeter<T, S>) -> Option<SignedGas<T>> {
-		match (weight_left(meter), deposit_left(meter)) {
+		gas_left_with_weight(meter, meter.weight.weight_consumed())
+	}
+
+	/// Same as [`gas_left`], with the given weight consumed by the frame.
+	pub fn gas_left_with_weight<T: Config, S: State>(
+		meter: &ResourceMeter<T, S>,
+		weight_consumed: Weight,
+	) -> Option<SignedGas<T>> {
+		let weight_left = meter
+			.weight
+			.weight_limit
+			.expect(
+				"Weight limits are always defined for `ResourceMeter` in Substrate \
+				execution mode (i.e., when its `transaction_limits` are `WeightAndDeposit`); qed",
+			)
+			.checked_sub(&weight_consumed);
+		match (weight_left, deposit_left(meter)) {
 			(Some(weight_left), Some(deposit_left)) => {
 				let weight_gas_left = SignedGas::<T>::from_weight_fee(
 					T::FeeInfo::weight_to_fee_average(&weight_left),
@@ -430,7 +446,15 @@ pub mod ethereum_execution {
 		meter: &ResourceMeter<T, S>,
 		eth_tx_info: &EthTxInfo<T>,
 	) -> Option<SignedGas<T>> {
-		let self_consumed_weight = meter.weight.weight_consumed();
+		gas_left_with_weight(meter, eth_tx_info, meter.weight.weight_consumed())
+	}
+
+	/// Same as [`gas_left`], with the given weight consumed by the frame.
+	pub fn gas_left_with_weight<T: Config, S: State>(
+		meter: &ResourceMeter<T, S>,
+		eth_tx_info: &EthTxInfo<T>,
+		self_consumed_weight: Weight,
+	) -> Option<SignedGas<T>> {
 		let self_consumed_deposit = meter.deposit.consumed();
 
 		let total_consumed_weight =
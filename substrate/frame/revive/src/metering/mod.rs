@@ -361,6 +361,23 @@ impl<T: Config, S: State> ResourceMeter<T, S> {
 		gas_left.to_ethereum_gas()
 	}
 
+	/// Get remaining ethereum gas equivalent, including the fuel consumed by the PolkaVM
+	/// executor since the last [`Self::sync_from_executor`].
+	///
+	/// Unlike syncing from the executor, this doesn't modify the meter, so reading the gas left
+	/// while the executor runs doesn't change the metering of the execution.
+	pub fn eth_gas_left_with_executor(&self, engine_fuel: polkavm::Gas) -> Option<BalanceOf<T>> {
+		let weight_consumed = self.weight.weight_consumed_with_executor(engine_fuel);
+		let gas_left = match &self.transaction_limits {
+			TransactionLimits::EthereumGas { eth_tx_info, .. } =>
+				math::ethereum_execution::gas_left_with_weight(self, eth_tx_info, weight_consumed),
+			TransactionLimits::WeightAndDeposit { .. } =>
+				math::substrate_execution::gas_left_with_weight(self, weight_consumed),
+		}?;
+
+		gas_left.to_ethereum_gas()
+	}
+
 	/// Get remaining weight available.
 	///
 	/// Computes remaining computational capacity:
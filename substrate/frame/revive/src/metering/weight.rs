@@ -51,9 +51,17 @@ impl<T: Config> EngineMeter<T> {
 	/// Set the fuel left to the given value.
 	/// Returns the amount of Weight consumed since the last update.
 	fn set_fuel(&mut self, fuel: u64) -> Weight {
-		let consumed = self.fuel.saturating_sub(fuel).saturating_mul(Self::ref_time_per_fuel());
+		let consumed = self.consumed_since(fuel);
 		self.fuel = fuel;
-		Weight::from_parts(consumed, 0)
+		consumed
+	}
+
+	/// Returns the amount of Weight consumed since the last update if `fuel` is left.
+	fn consumed_since(&self, fuel: u64) -> Weight {
+		Weight::from_parts(
+			self.fuel.saturating_sub(fuel).saturating_mul(Self::ref_time_per_fuel()),
+			0,
+		)
 	}
 
 	/// Charge the given amount of ref time.
@@ -272,6 +280,15 @@ impl<T: Config> WeightMeter<T> {
 		self.weight_consumed
 	}
 
+	/// Returns how much weight was spent, including the fuel consumed by the executor since the
+	/// last [`Self::sync_from_executor`], without syncing.
+	pub fn weight_consumed_with_executor(&self, engine_fuel: polkavm::Gas) -> Weight {
+		let fuel = engine_fuel.try_into().unwrap_or_default();
+		self.weight_consumed
+			.saturating_add(self.engine_meter.consumed_since(fuel))
+			.min(self.effective_weight_limit)
+	}
+
 	pub fn consume_all(&mut self) {
 		self.weight_consumed = self.effective_weight_limit;
 	}
@@ -102,6 +102,41 @@ pub struct EthTransactInfo<Balance> {
 	pub data: Vec<u8>,
 }
 
+/// The EIP-2930 access list generated by dry-running a transaction.
+///
+/// See [`crate::Pallet::create_access_list`].
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode, Debug, TypeInfo)]
+pub struct AccessListInfo {
+	/// The accounts and storage slots accessed by the transaction.
+	pub access_list: crate::evm::AccessList,
+	/// The weight and deposit equivalent in EVM Gas.
+	pub eth_gas: U256,
+}
+
+/// The result of a block simulated by [`crate::Pallet::simulate`].
+#[derive(Clone, Eq, PartialEq, Default, Encode, Decode, Debug, TypeInfo)]
+pub struct SimulatedBlockInfo {
+	/// The number of the simulated block.
+	pub number: U256,
+	/// The timestamp of the simulated block, in seconds.
+	pub timestamp: U256,
+	/// The base fee per gas.
+	pub base_fee: U256,
+	/// The results of the simulated calls, in order.
+	pub calls: Vec<SimulatedCallInfo>,
+}
+
+/// The result of a call simulated by [`crate::Pallet::simulate`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct SimulatedCallInfo {
+	/// The data returned by the call, or the error of a failed call.
+	pub result: Result<Vec<u8>, EthTransactError>,
+	/// The logs emitted by the call. Logs of reverted frames are not included.
+	pub logs: Vec<crate::evm::CallLog>,
+	/// The EVM gas used by the call.
+	pub eth_gas: U256,
+}
+
 /// Error type of a `eth_transact` call.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
 pub enum EthTransactError {
@@ -199,6 +234,45 @@ pub enum ContractAccessError {
 	StorageWriteFailed(DispatchError),
 }
 
+/// The information needed to build an EIP-1186 (`eth_getProof`) response for an account.
+///
+/// The runtime cannot produce trie proofs itself. Instead it returns the account values along
+/// with the storage keys they are read from, so that a client can request the matching read
+/// proofs from the node (`state_getReadProof` and `state_getChildReadProof`).
+///
+/// See [`crate::Pallet::account_proof_info`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct AccountProofInfo {
+	/// The balance of the account, using EVM decimals.
+	pub balance: U256,
+	/// The nonce of the account.
+	pub nonce: U256,
+	/// The keccak256 hash of the account code.
+	pub code_hash: sp_core::H256,
+	/// The root of the contract child trie, or zero if the account has no child trie.
+	pub storage_root: sp_core::H256,
+	/// The keys of the top trie that hold the account values.
+	///
+	/// These are the `System::Account` entry (nonce and balance), the `Revive::AccountInfoOf`
+	/// entry (dust and code hash) and, for contracts, the entry holding the child trie root.
+	pub account_keys: Vec<Vec<u8>>,
+	/// The prefixed storage key of the contract child trie, if the account is a contract.
+	pub child_trie_key: Option<Vec<u8>>,
+	/// The requested storage slots.
+	pub storage: Vec<StorageProofInfo>,
+}
+
+/// The information needed to prove a single storage slot of a contract.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug, TypeInfo)]
+pub struct StorageProofInfo {
+	/// The storage slot, as requested.
+	pub key: [u8; 32],
+	/// The key under which the slot is stored in the contract child trie.
+	pub hashed_key: Vec<u8>,
+	/// The value of the slot, if any.
+	pub value: Option<Vec<u8>>,
+}
+
 /// Output of a contract call or instantiation which ran to completion.
 #[derive(Clone, PartialEq, Eq, Encode, Decode, Debug, TypeInfo, Default)]
 pub struct ExecReturnValue {
@@ -23,8 +23,8 @@ use crate::{
 	metering::FrameMeter,
 	tracing::if_tracing,
 	weights::WeightInfo,
-	AccountInfoOf, BalanceOf, BalanceWithDust, Config, DeletionQueue, DeletionQueueCounter, Error,
-	TrieId, SENTINEL,
+	AccountInfoOf, BalanceOf, BalanceWithDust, Config, DelegationOf, DeletionQueue,
+	DeletionQueueCounter, Error, TrieId, SENTINEL,
 };
 use alloc::vec::Vec;
 use codec::{Decode, Encode, MaxEncodedLen};
@@ -175,6 +175,26 @@ impl<T: Config> AccountInfo<T> {
 		Some(contract_info)
 	}
 
+	/// Loads the code hash of the contract at the given address.
+	///
+	/// Returns `None` for accounts without code of their own. This includes pre-compiles that only
+	/// hold a contract info to store their state.
+	pub fn code_hash_of(address: &H160) -> Option<sp_core::H256> {
+		Self::load_contract(address)
+			.map(|contract| contract.code_hash)
+			.filter(|code_hash| !code_hash.is_zero())
+	}
+
+	/// Returns true if the account at the given address has code of its own.
+	pub fn has_code(address: &H160) -> bool {
+		Self::code_hash_of(address).is_some()
+	}
+
+	/// Loads the address the account at the given address delegates its code to (EIP-7702).
+	pub fn load_delegation(address: &H160) -> Option<H160> {
+		<DelegationOf<T>>::get(address)
+	}
+
 	/// Insert a contract, existing dust if any will be unchanged.
 	pub fn insert_contract(address: &H160, contract: ContractInfo<T>) {
 		AccountInfoOf::<T>::mutate(address, |account| {
@@ -17,9 +17,11 @@
 
 use super::{deposit_limit, ETH_GAS_LIMIT, WEIGHT_LIMIT};
 use crate::{
-	address::AddressMapper, evm::TransactionSigned, metering::TransactionLimits, AccountIdOf,
-	BalanceOf, Code, Config, ContractResult, ExecConfig, ExecReturnValue, InstantiateReturnValue,
-	OriginFor, Pallet, Weight, U256,
+	address::AddressMapper,
+	evm::{AuthorizationListEntry, TransactionSigned},
+	metering::TransactionLimits,
+	AccountIdOf, BalanceOf, Code, Config, ContractResult, ExecConfig, ExecReturnValue,
+	InstantiateReturnValue, OriginFor, Pallet, Weight, U256,
 };
 use alloc::{vec, vec::Vec};
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
@@ -275,6 +277,37 @@ builder!(
 	}
 );
 
+builder!(
+	eth_call_with_authorizations(
+		origin: OriginFor<T>,
+		dest: H160,
+		value: U256,
+		weight_limit: Weight,
+		eth_gas_limit: U256,
+		data: Vec<u8>,
+		transaction_encoded: Vec<u8>,
+		effective_gas_price: U256,
+		encoded_len: u32,
+		authorization_list: Vec<AuthorizationListEntry>,
+	) -> DispatchResultWithPostInfo;
+
+	/// Create a [`EthCallWithAuthorizationsBuilder`] with default values.
+	pub fn eth_call_with_authorizations(origin: OriginFor<T>, dest: H160) -> Self {
+		Self {
+			origin,
+			dest,
+			value: 0u32.into(),
+			weight_limit: WEIGHT_LIMIT,
+			eth_gas_limit: ETH_GAS_LIMIT.into(),
+			data: vec![],
+			transaction_encoded: TransactionSigned::TransactionLegacySigned(Default::default()).signed_payload(),
+			effective_gas_price: 0u32.into(),
+			encoded_len: 0,
+			authorization_list: vec![],
+		}
+	}
+);
+
 builder!(
 	eth_instantiate_with_code(
 			origin: OriginFor<T>,
@@ -249,6 +249,13 @@ pub(crate) mod builder {
 		EthCallBuilder::<Test>::eth_call(crate::Origin::<Test>::EthTransaction(ALICE).into(), dest)
 	}
 
+	pub fn eth_call_with_authorizations(dest: H160) -> EthCallWithAuthorizationsBuilder<Test> {
+		EthCallWithAuthorizationsBuilder::<Test>::eth_call_with_authorizations(
+			crate::Origin::<Test>::EthTransaction(ALICE).into(),
+			dest,
+		)
+	}
+
 	pub fn eth_instantiate_with_code(code: Vec<u8>) -> EthInstantiateWithCodeBuilder<Test> {
 		EthInstantiateWithCodeBuilder::<Test>::eth_instantiate_with_code(
 			crate::Origin::<Test>::EthTransaction(ALICE).into(),
@@ -424,6 +431,7 @@ impl SetWeightLimit for RuntimeCall {
 		match self {
 			Self::Contracts(
 				Call::eth_call { weight_limit, .. } |
+				Call::eth_call_with_authorizations { weight_limit, .. } |
 				Call::eth_instantiate_with_code { weight_limit, .. },
 			) => {
 				let old = *weight_limit;
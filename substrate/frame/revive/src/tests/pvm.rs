@@ -43,6 +43,7 @@ use crate::{
 	weights::WeightInfo,
 	AccountInfo, AccountInfoOf, BalanceWithDust, Code, Config, ContractInfo, DebugSettings,
 	DeletionQueueCounter, Error, ExecConfig, HoldReason, Origin, Pallet, StorageDeposit,
+	StorageProofInfo,
 };
 use assert_matches::assert_matches;
 use codec::Encode;
@@ -4142,6 +4143,36 @@ fn create_call_tracing_works() {
 	});
 }
 
+#[test]
+fn struct_logger_works_for_pvm() {
+	use crate::evm::*;
+	let (code, _) = compile_module("dummy").unwrap();
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let untraced = builder::bare_call(addr).build();
+		let mut tracer = StructLogger::new(Default::default());
+		let traced = trace(&mut tracer, || builder::bare_call(addr).build());
+
+		// Tracing must not change the metering of the execution.
+		assert!(traced.result.is_ok());
+		assert_eq!(traced.weight_consumed, untraced.weight_consumed);
+
+		let trace = tracer.collect_trace();
+		assert!(!trace.failed);
+		assert!(!trace.struct_logs.is_empty());
+		for log in &trace.struct_logs {
+			assert_eq!(log.depth, 1);
+			assert!(!log.op.is_empty());
+			assert_eq!(log.stack.as_ref().map(|stack| stack.len()), Some(13));
+			assert_eq!(log.memory, None);
+		}
+		assert!(trace.struct_logs.windows(2).all(|logs| logs[0].gas >= logs[1].gas));
+	});
+}
+
 #[test]
 fn prestate_tracing_works() {
 	use crate::evm::*;
@@ -5088,6 +5119,203 @@ fn get_set_storage_var_key_works() {
 	});
 }
 
+#[test]
+fn account_proof_info_works() {
+	let (code, code_hash) = compile_module("dummy").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		Pallet::<Test>::set_storage(addr, [1; 32], Some(vec![1, 2, 3])).unwrap();
+
+		let info = Pallet::<Test>::account_proof_info(addr, vec![[1; 32], [2; 32]]);
+		let contract = AccountInfo::<Test>::load_contract(&addr).unwrap();
+		let child_info = contract.child_trie_info();
+
+		assert_eq!(info.balance, Pallet::<Test>::evm_balance(&addr));
+		assert_eq!(info.code_hash, code_hash);
+		assert_ne!(info.storage_root, H256::zero());
+		assert_eq!(info.child_trie_key, Some(child_info.prefixed_storage_key().into_inner()));
+		assert_eq!(info.account_keys.len(), 3);
+		assert_eq!(
+			info.storage,
+			vec![
+				StorageProofInfo {
+					key: [1; 32],
+					hashed_key: blake2_256(&[1; 32]).to_vec(),
+					value: Some(vec![1, 2, 3]),
+				},
+				StorageProofInfo {
+					key: [2; 32],
+					hashed_key: blake2_256(&[2; 32]).to_vec(),
+					value: None,
+				},
+			]
+		);
+
+		// Accounts without code have no child trie.
+		let info = Pallet::<Test>::account_proof_info(ALICE_ADDR, vec![[1; 32]]);
+		assert_eq!(info.code_hash, crate::exec::EMPTY_CODE_HASH);
+		assert_eq!(info.storage_root, H256::zero());
+		assert_eq!(info.child_trie_key, None);
+		assert_eq!(info.account_keys.len(), 2);
+		assert_eq!(info.storage[0].value, None);
+	});
+}
+
+#[test]
+fn access_list_tracing_works() {
+	use crate::evm::{AccessListEntry, AccessListTracer};
+	let (code, _) = compile_module("storage").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let min_balance = Contracts::min_balance();
+		let Contract { addr, .. } = builder::bare_instantiate(Code::Upload(code))
+			.native_value(min_balance * 100)
+			.build_and_unwrap_contract();
+
+		let mut tracer = AccessListTracer::new([ALICE_ADDR], vec![]);
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+		assert_eq!(
+			tracer.collect_access_list(),
+			vec![AccessListEntry { address: addr, storage_keys: vec![H256([1u8; 32])] }]
+		);
+
+		// The storage slots of excluded addresses are still part of the access list.
+		let mut tracer = AccessListTracer::new([ALICE_ADDR, addr], vec![]);
+		trace(&mut tracer, || builder::bare_call(addr).build_and_unwrap_result());
+		assert_eq!(
+			tracer.collect_access_list(),
+			vec![AccessListEntry { address: addr, storage_keys: vec![H256([1u8; 32])] }]
+		);
+	});
+}
+
+#[test]
+fn simulate_applies_overrides() {
+	use crate::evm::{AccountOverride, BlockOverrides, SimulateBlock, StateOverrides};
+	let (code, _) = compile_module("dummy").unwrap();
+	let (other_code, other_code_hash) = compile_module("storage").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		Pallet::<Test>::set_storage(addr, [1; 32], Some(vec![1; 32])).unwrap();
+		Pallet::<Test>::set_storage(addr, [2; 32], Some(vec![2; 32])).unwrap();
+
+		let number = System::block_number();
+		let blocks = vec![
+			SimulateBlock {
+				state_overrides: Some(StateOverrides::from([
+					(
+						addr,
+						AccountOverride {
+							code: Some(other_code.into()),
+							state: Some([(H256([3; 32]), H256([3; 32]))].into()),
+							..Default::default()
+						},
+					),
+					(
+						BOB_ADDR,
+						AccountOverride {
+							balance: Some(1_000_000_000u64.into()),
+							nonce: Some(5u64.into()),
+							..Default::default()
+						},
+					),
+				])),
+				..Default::default()
+			},
+			SimulateBlock {
+				block_overrides: Some(BlockOverrides {
+					number: Some((number + 10).into()),
+					..Default::default()
+				}),
+				state_overrides: Some(StateOverrides::from([(
+					addr,
+					AccountOverride {
+						state_diff: Some([(H256([3; 32]), H256::zero())].into()),
+						..Default::default()
+					},
+				)])),
+				..Default::default()
+			},
+		];
+
+		let results = Pallet::<Test>::simulate(blocks).unwrap();
+		assert_eq!(
+			results.iter().map(|block| block.number).collect::<Vec<_>>(),
+			vec![U256::from(number + 1), U256::from(number + 10)]
+		);
+
+		let contract = AccountInfo::<Test>::load_contract(&addr).unwrap();
+		assert_eq!(contract.code_hash, other_code_hash);
+		assert_eq!(Pallet::<Test>::get_storage(addr, [1; 32]), Ok(None));
+		assert_eq!(Pallet::<Test>::get_storage(addr, [3; 32]), Ok(None));
+		let (balance, _) = Pallet::<Test>::new_balance_with_dust(1_000_000_000u64.into()).unwrap();
+		assert_eq!(<Test as Config>::Currency::total_balance(&BOB), balance);
+		assert_eq!(System::account_nonce(&BOB), 5);
+
+		// Block numbers must increase.
+		let blocks = vec![SimulateBlock {
+			block_overrides: Some(BlockOverrides {
+				number: Some(number.into()),
+				..Default::default()
+			}),
+			..Default::default()
+		}];
+		assert!(Pallet::<Test>::simulate(blocks).is_err());
+	});
+}
+
+#[test]
+fn simulate_carries_state_and_nonce_over() {
+	use crate::evm::{GenericTransaction, SimulateBlock};
+	let (code, _) = compile_module("event_size").unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000_000_000);
+		let nonce = System::account_nonce(&ALICE);
+		let addr = create1(&ALICE_ADDR, nonce.into());
+
+		let deploy =
+			GenericTransaction { from: Some(ALICE_ADDR), input: code.into(), ..Default::default() };
+		let call = GenericTransaction {
+			from: Some(ALICE_ADDR),
+			to: Some(addr),
+			input: 4u32.encode().into(),
+			..Default::default()
+		};
+		let blocks = vec![
+			SimulateBlock { calls: vec![deploy, call.clone()], ..Default::default() },
+			SimulateBlock {
+				calls: vec![call.clone(), GenericTransaction { nonce: Some(10u64.into()), ..call }],
+				..Default::default()
+			},
+		];
+
+		let results = Pallet::<Test>::simulate(blocks).unwrap();
+		let calls = results.iter().flat_map(|block| block.calls.iter()).collect::<Vec<_>>();
+		assert_eq!(calls.len(), 4);
+		assert!(calls.iter().all(|call| call.result.is_ok()));
+
+		// The contract deployed by the first call exists for the calls that follow it.
+		assert!(calls[0].logs.is_empty());
+		for call in &calls[1..] {
+			assert_eq!(call.logs.len(), 1);
+			assert_eq!(call.logs[0].address, addr);
+			assert_eq!(call.logs[0].data.0, vec![0u8; 4]);
+		}
+
+		// Each call bumps the nonce, starting from the one it declares.
+		assert_eq!(System::account_nonce(&ALICE), 11);
+	});
+}
+
 #[test]
 fn get_set_immutables_works() {
 	let (code, _code_hash) = compile_module("immutable_data").unwrap();
@@ -5365,6 +5593,9 @@ fn self_destruct_by_syscall_tracing_works() {
 			let trace_wrapped = match trace {
 				crate::evm::Trace::Call(ct) => Trace::Call(ct),
 				crate::evm::Trace::Prestate(pt) => Trace::Prestate(pt),
+				crate::evm::Trace::StructLogger(st) => Trace::StructLogger(st),
+				crate::evm::Trace::FlatCall(ft) => Trace::FlatCall(ft),
+				crate::evm::Trace::FourByte(bt) => Trace::FourByte(bt),
 			};
 
 			assert_eq!(trace_wrapped, expected_trace, "Trace mismatch for: {}", description);
@@ -5446,3 +5677,121 @@ fn call_with_gas_limit() {
 			.build());
 	});
 }
+
+#[test]
+fn eip7702_delegation_works() {
+	use crate::evm::{delegation_deposit, delegation_indicator, Account};
+	use frame_support::traits::fungible::InspectHold;
+	let (code, _) = compile_module("store_call").unwrap();
+	let (checker_code, _) = compile_module("extcodesize").unwrap();
+
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 1_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+		let Contract { addr: checker_addr, .. } =
+			builder::bare_instantiate(Code::Upload(checker_code)).build_and_unwrap_contract();
+
+		let authority = Account::from_secret_key([1u8; 32]);
+		let authority_addr = authority.address();
+		let authority_id = <Test as Config>::AddressMapper::to_account_id(&authority_addr);
+		let _ = <Test as Config>::Currency::set_balance(&authority_id, 1_000_000);
+		let chain_id = <Test as Config>::ChainId::get().into();
+		let held = || {
+			<Test as Config>::Currency::balance_on_hold(
+				&HoldReason::Delegation.into(),
+				&authority_id,
+			)
+		};
+
+		// The authority delegates its code and gets called in the same transaction. The delegated
+		// code cannot write storage as the authority has none. The authorization is applied even
+		// though the call fails.
+		let authorization = authority.sign_authorization(chain_id, addr, 0u32.into());
+		assert_ok!(builder::eth_call_with_authorizations(authority_addr)
+			.authorization_list(vec![authorization.clone()])
+			.data(16u32.encode())
+			.build());
+		assert_eq!(AccountInfo::<Test>::load_delegation(&authority_addr), Some(addr));
+		assert_eq!(System::account_nonce(&authority_id), 1);
+		assert_eq!(held(), delegation_deposit::<Test>());
+
+		// The authority does not become a contract.
+		assert!(!AccountInfo::<Test>::is_contract(&authority_addr));
+		let mut key = [0u8; 32];
+		key[0] = 1;
+		assert_eq!(
+			Pallet::<Test>::get_storage(authority_addr, key),
+			Err(crate::ContractAccessError::DoesntExist)
+		);
+		assert_err!(
+			builder::bare_call(authority_addr).data(16u32.encode()).build().result,
+			<Error<Test>>::DelegatedStorageWrite
+		);
+
+		// The code of the authority is the delegation indicator.
+		assert_eq!(Pallet::<Test>::code(&authority_addr), delegation_indicator(&addr));
+		let result = builder::bare_call(checker_addr)
+			.data((authority_addr, 23u64).encode())
+			.build_and_unwrap_result();
+		assert!(!result.did_revert());
+
+		// The authority can still send transactions.
+		assert_ok!(Pallet::<Test>::ensure_non_contract_if_signed(&RuntimeOrigin::signed(
+			authority_id.clone()
+		)));
+
+		// A replayed authorization is skipped.
+		assert_ok!(builder::eth_call_with_authorizations(authority_addr)
+			.authorization_list(vec![authorization])
+			.build());
+		assert_eq!(System::account_nonce(&authority_id), 1);
+		assert_eq!(held(), delegation_deposit::<Test>());
+
+		// Delegating to the zero address clears the delegation and releases the deposit.
+		let authorization = authority.sign_authorization(chain_id, H160::zero(), 1u32.into());
+		assert_ok!(builder::eth_call_with_authorizations(authority_addr)
+			.authorization_list(vec![authorization])
+			.build());
+		assert_eq!(AccountInfo::<Test>::load_delegation(&authority_addr), None);
+		assert_eq!(Pallet::<Test>::code(&authority_addr), Vec::<u8>::new());
+		assert_eq!(held(), 0);
+	});
+}
+
+#[test]
+fn eip7702_self_sponsored_dry_run_works() {
+	use crate::evm::{Account, GenericTransaction, TYPE_EIP7702};
+
+	ExtBuilder::default().existential_deposit(200).build().execute_with(|| {
+		let authority = Account::from_secret_key([1u8; 32]);
+		let authority_addr = authority.address();
+		let authority_id = <Test as Config>::AddressMapper::to_account_id(&authority_addr);
+		let _ = <Test as Config>::Currency::set_balance(&authority_id, 1_000_000);
+		let chain_id = <Test as Config>::ChainId::get().into();
+		let delegate = H160::repeat_byte(0x42);
+
+		// The nonce of the sender is bumped before the authorizations are applied. A
+		// self-sponsored authorization has to sign the nonce that follows the one of the
+		// transaction.
+		let nonce = System::account_nonce(&authority_id);
+		let tx = |authorization_nonce: u32| GenericTransaction {
+			from: Some(authority_addr),
+			to: Some(BOB_ADDR),
+			r#type: Some(TYPE_EIP7702.into()),
+			authorization_list: vec![authority.sign_authorization(
+				chain_id,
+				delegate,
+				authorization_nonce.into(),
+			)],
+			..Default::default()
+		};
+
+		assert_ok!(Pallet::<Test>::dry_run_eth_transact(tx(nonce as u32), Default::default()));
+		assert_eq!(AccountInfo::<Test>::load_delegation(&authority_addr), None);
+
+		let nonce = System::account_nonce(&authority_id);
+		assert_ok!(Pallet::<Test>::dry_run_eth_transact(tx(nonce as u32 + 1), Default::default()));
+		assert_eq!(AccountInfo::<Test>::load_delegation(&authority_addr), Some(delegate));
+	});
+}
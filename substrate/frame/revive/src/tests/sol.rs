@@ -108,6 +108,40 @@ fn basic_evm_flow_works() {
 	});
 }
 
+#[test]
+fn struct_logger_works_for_evm() {
+	use crate::{
+		evm::{Bytes, StructLogger, StructLoggerConfig},
+		tracing::trace,
+	};
+	let (code, _) = compile_module_with_type("Fibonacci", FixtureType::Solc).unwrap();
+
+	ExtBuilder::default().build().execute_with(|| {
+		let _ = <Test as Config>::Currency::set_balance(&ALICE, 100_000_000_000);
+		let Contract { addr, .. } =
+			builder::bare_instantiate(Code::Upload(code)).build_and_unwrap_contract();
+
+		let mut tracer =
+			StructLogger::new(StructLoggerConfig { enable_memory: true, ..Default::default() });
+		let result = trace(&mut tracer, || {
+			builder::bare_call(addr)
+				.data(Fibonacci::FibonacciCalls::fib(Fibonacci::fibCall { n: 3u64 }).abi_encode())
+				.build_and_unwrap_result()
+		});
+
+		let trace = tracer.collect_trace();
+		assert!(!trace.failed);
+		assert_eq!(trace.return_value, Bytes(result.data));
+
+		let first = &trace.struct_logs[0];
+		assert_eq!((first.pc, first.op.as_str(), first.depth), (0, "PUSH1", 1));
+		assert_eq!(first.stack, Some(vec![]));
+		assert_eq!(first.memory, Some(vec![]));
+		assert_eq!(trace.struct_logs.last().unwrap().op, "RETURN");
+		assert!(trace.struct_logs.windows(2).all(|logs| logs[0].gas >= logs[1].gas));
+	});
+}
+
 #[test]
 fn basic_evm_flow_tracing_works() {
 	use crate::{
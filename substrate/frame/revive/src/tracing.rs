@@ -40,8 +40,34 @@ pub(crate) fn if_tracing<R, F: FnOnce(&mut (dyn Tracing + 'static)) -> R>(f: F)
 	tracer::with(f)
 }
 
+/// The state of the interpreter before an instruction is executed.
+///
+/// See [`Tracing::step`].
+pub struct StepInfo<'a> {
+	/// The program counter.
+	pub pc: u64,
+	/// The name of the instruction.
+	pub op: &'static str,
+	/// The gas left before the instruction is executed, in eth gas units.
+	pub gas_left: u64,
+	/// The EVM stack, or the registers for PVM contracts.
+	pub stack: &'a [U256],
+	/// The EVM memory, empty for PVM contracts.
+	pub memory: &'a [u8],
+}
+
 /// Defines methods to trace contract interactions.
 pub trait Tracing {
+	/// Whether [`Self::step`] should be called for every executed instruction.
+	///
+	/// This is only checked once before a contract starts executing.
+	fn is_step_tracing_enabled(&self) -> bool {
+		false
+	}
+
+	/// Called before an instruction is executed.
+	fn step(&mut self, _step: &StepInfo) {}
+
 	/// Register an address that should be traced.
 	fn watch_address(&mut self, _addr: &H160) {}
 
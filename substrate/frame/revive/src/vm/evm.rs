@@ -17,13 +17,17 @@
 use crate::{
 	debug::DebugSettings,
 	precompiles::Token,
+	tracing::{if_tracing, StepInfo},
 	vm::{evm::instructions::exec_instruction, BytecodeType, ExecResult, Ext},
 	weights::WeightInfo,
 	AccountIdOf, CodeInfo, Config, ContractBlob, DispatchError, Error, Weight, H256, LOG_TARGET,
 };
 use alloc::vec::Vec;
 use core::{convert::Infallible, ops::ControlFlow};
-use revm::{bytecode::Bytecode, primitives::Bytes};
+use revm::{
+	bytecode::{opcode::OpCode, Bytecode},
+	primitives::Bytes,
+};
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod instructions;
@@ -129,7 +133,9 @@ impl<T: Config> ContractBlob<T> {
 /// Calls the EVM interpreter with the provided bytecode and inputs.
 pub fn call<E: Ext>(bytecode: Bytecode, ext: &mut E, input: Vec<u8>) -> ExecResult {
 	let mut interpreter = Interpreter::new(ExtBytecode::new(bytecode), input, ext);
-	let ControlFlow::Break(halt) = run_plain(&mut interpreter);
+	let step_tracing = if_tracing(|tracer| tracer.is_step_tracing_enabled()).unwrap_or(false);
+	let ControlFlow::Break(halt) =
+		if step_tracing { run_traced(&mut interpreter) } else { run_plain(&mut interpreter) };
 	halt.into()
 }
 
@@ -140,3 +146,21 @@ fn run_plain<E: Ext>(interpreter: &mut Interpreter<E>) -> ControlFlow<Halt, Infa
 		exec_instruction(interpreter, opcode)?;
 	}
 }
+
+/// Same as [`run_plain`], but reports every instruction to the tracer before executing it.
+fn run_traced<E: Ext>(interpreter: &mut Interpreter<E>) -> ControlFlow<Halt, Infallible> {
+	loop {
+		let opcode = interpreter.bytecode.opcode();
+		if_tracing(|tracer| {
+			tracer.step(&StepInfo {
+				pc: interpreter.bytecode.pc() as u64,
+				op: OpCode::new(opcode).map_or("INVALID", |op| op.as_str()),
+				gas_left: interpreter.ext.gas_left(),
+				stack: interpreter.stack.as_slice(),
+				memory: interpreter.memory.slice(0..interpreter.memory.size()),
+			})
+		});
+		interpreter.bytecode.relative_jump(1);
+		exec_instruction(interpreter, opcode)?;
+	}
+}
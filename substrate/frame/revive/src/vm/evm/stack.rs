@@ -80,6 +80,11 @@ impl<T: Config> Stack<T> {
 		self.stack.len()
 	}
 
+	/// Get the stack items, from bottom to top
+	pub fn as_slice(&self) -> &[U256] {
+		&self.stack
+	}
+
 	/// Check if stack is empty
 	#[cfg(test)]
 	pub fn is_empty(&self) -> bool {
@@ -28,6 +28,7 @@ use crate::{
 	metering::ChargedAmount,
 	precompiles::{All as AllPrecompiles, Precompiles},
 	primitives::ExecReturnValue,
+	tracing::{if_tracing, StepInfo},
 	Code, Config, Error, Pallet, ReentrancyProtection, RuntimeCosts, LOG_TARGET, SENTINEL,
 };
 use alloc::{vec, vec::Vec};
@@ -36,7 +37,7 @@ use core::{fmt, marker::PhantomData, mem};
 use frame_support::{ensure, weights::Weight};
 use pallet_revive_uapi::{CallFlags, ReturnErrorCode, ReturnFlags, StorageFlags};
 use sp_core::{H160, H256, U256};
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, SaturatedConversion};
 
 /// Extracts the code and data from a given program blob.
 pub fn extract_code_and_data(data: &[u8]) -> Option<(Vec<u8>, Vec<u8>)> {
@@ -818,12 +819,17 @@ pub struct PreparedCall<'a, E: Ext> {
 	module: polkavm::Module,
 	instance: polkavm::RawInstance,
 	runtime: Runtime<'a, E, polkavm::RawInstance>,
+	/// The parsed program, only set when every instruction is reported to the tracer.
+	step_tracing: Option<polkavm::ProgramBlob>,
 }
 
 impl<'a, E: Ext> PreparedCall<'a, E> {
 	pub fn call(mut self) -> ExecResult {
 		let exec_result = loop {
 			let interrupt = self.instance.run();
+			if let Ok(polkavm::InterruptKind::Step) = interrupt {
+				self.trace_step();
+			}
 			if let Some(exec_result) =
 				self.runtime.handle_interrupt(interrupt, &self.module, &mut self.instance)
 			{
@@ -834,6 +840,31 @@ impl<'a, E: Ext> PreparedCall<'a, E> {
 		exec_result
 	}
 
+	/// Report the instruction about to be executed to the tracer.
+	fn trace_step(&mut self) {
+		let (Some(blob), Some(pc)) = (&self.step_tracing, self.instance.program_counter()) else {
+			return
+		};
+		let op = blob
+			.instructions_bounded_at(pc)
+			.next()
+			.map_or("invalid", |instruction| instruction.kind.opcode().name());
+
+		// The meter is not synced, so that tracing doesn't change the metering of the execution.
+		let gas_left = self
+			.runtime
+			.ext()
+			.frame_meter()
+			.eth_gas_left_with_executor(self.instance.gas())
+			.unwrap_or_default()
+			.saturated_into::<u64>();
+
+		let registers = polkavm::Reg::ALL.map(|reg| U256::from(self.instance.reg(reg))).to_vec();
+		if_tracing(|tracer| {
+			tracer.step(&StepInfo { pc: pc.0.into(), op, gas_left, stack: &registers, memory: &[] })
+		});
+	}
+
 	/// The guest memory address at which the aux data is located.
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn aux_data_base(&self) -> u32 {
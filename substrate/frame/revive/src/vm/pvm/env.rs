@@ -66,10 +66,16 @@ impl<T: Config> ContractBlob<T> {
 				interpreter is available on all platforms; qed",
 		);
 
+		let step_tracing = crate::tracing::if_tracing(|tracer| tracer.is_step_tracing_enabled())
+			.unwrap_or(false)
+			.then(|| polkavm::ProgramBlob::parse(self.code[..].into()).ok())
+			.flatten();
+
 		let mut module_config = polkavm::ModuleConfig::new();
 		module_config.set_page_size(limits::PAGE_SIZE);
 		module_config.set_gas_metering(Some(polkavm::GasMeteringKind::Sync));
 		module_config.set_aux_data_size(aux_data_size);
+		module_config.set_step_tracing(step_tracing.is_some());
 		let module =
 			polkavm::Module::new(&engine, &module_config, self.code.into()).map_err(|err| {
 				log::debug!(target: LOG_TARGET, "failed to create polkavm module: {err:?}");
@@ -100,7 +106,7 @@ impl<T: Config> ContractBlob<T> {
 			.map_err(|_| Error::<T>::CodeRejected)?;
 		instance.prepare_call_untyped(entry_program_counter, &[]);
 
-		Ok(PreparedCall { module, instance, runtime })
+		Ok(PreparedCall { module, instance, runtime, step_tracing })
 	}
 }
 
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT
+// OpenZeppelin Contracts (last updated v5.1.0) (token/ERC1155/IERC1155.sol)
+// https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC1155/IERC1155.sol
+pragma solidity ^0.8.20;
+
+///
+/// @dev Required interface of an ERC-1155 compliant contract.
+///
+/// The functions of the optional metadata URI extension and of ERC-165 are part of this interface.
+///
+interface IERC1155 {
+     /// @dev Emitted when `value` amount of tokens of type `id` are transferred from `from` to `to` by `operator`.
+    event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+
+     /// @dev Equivalent to multiple {TransferSingle} events, where `operator`, `from` and `to` are the same for all
+     /// transfers.
+    event TransferBatch(
+        address indexed operator,
+        address indexed from,
+        address indexed to,
+        uint256[] ids,
+        uint256[] values
+    );
+
+     /// @dev Emitted when `account` grants or revokes permission to `operator` to transfer their tokens, according to
+     /// `approved`.
+    event ApprovalForAll(address indexed account, address indexed operator, bool approved);
+
+     /// @dev Returns true if this contract implements the interface defined by
+     /// `interfaceId`.
+    function supportsInterface(bytes4 interfaceId) external view returns (bool);
+
+     /// @dev Returns the value of tokens of token type `id` owned by `account`.
+    function balanceOf(address account, uint256 id) external view returns (uint256);
+
+     /// @dev Batched version of {balanceOf}.
+     ///
+     /// Requirements:
+     ///
+     /// - `accounts` and `ids` must have the same length.
+    function balanceOfBatch(
+        address[] calldata accounts,
+        uint256[] calldata ids
+    ) external view returns (uint256[] memory);
+
+     /// @dev Grants or revokes permission to `operator` to transfer the caller's tokens, according to `approved`,
+     ///
+     /// Emits an {ApprovalForAll} event.
+    function setApprovalForAll(address operator, bool approved) external;
+
+     /// @dev Returns true if `operator` is approved to transfer ``account``'s tokens.
+    function isApprovedForAll(address account, address operator) external view returns (bool);
+
+     /// @dev Transfers a `value` amount of tokens of type `id` from `from` to `to`.
+     ///
+     /// Requirements:
+     ///
+     /// - `to` cannot be the zero address.
+     /// - If the caller is not `from`, it must have been approved to spend ``from``'s tokens via {setApprovalForAll}.
+     /// - `from` must have a balance of tokens of type `id` of at least `value` amount.
+     /// - If `to` refers to a smart contract, it must implement {IERC1155Receiver-onERC1155Received} and return the
+     ///   acceptance magic value.
+     ///
+     /// Emits a {TransferSingle} event.
+    function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes calldata data) external;
+
+     /// @dev Batched version of {safeTransferFrom}.
+     ///
+     /// Requirements:
+     ///
+     /// - `ids` and `values` must have the same length.
+     /// - If `to` refers to a smart contract, it must implement {IERC1155Receiver-onERC1155BatchReceived} and return the
+     ///   acceptance magic value.
+     ///
+     /// Emits a {TransferBatch} event.
+    function safeBatchTransferFrom(
+        address from,
+        address to,
+        uint256[] calldata ids,
+        uint256[] calldata values,
+        bytes calldata data
+    ) external;
+
+     /// @dev Returns the URI for token type `id`.
+    function uri(uint256 id) external view returns (string memory);
+}
+
+///
+/// @dev Interface that must be implemented by smart contracts in order to receive
+/// ERC-1155 token transfers.
+///
+interface IERC1155Receiver {
+     /// @dev Handles the receipt of a single ERC-1155 token type.
+     ///
+     /// It must return its Solidity selector to accept the transfer.
+    function onERC1155Received(
+        address operator,
+        address from,
+        uint256 id,
+        uint256 value,
+        bytes calldata data
+    ) external returns (bytes4);
+
+     /// @dev Handles the receipt of multiple ERC-1155 token types.
+     ///
+     /// It must return its Solidity selector to accept the transfer.
+    function onERC1155BatchReceived(
+        address operator,
+        address from,
+        uint256[] calldata ids,
+        uint256[] calldata values,
+        bytes calldata data
+    ) external returns (bytes4);
+}
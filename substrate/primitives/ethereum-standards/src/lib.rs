@@ -19,4 +19,6 @@
 
 #![no_std]
 
+alloy_core::sol!("src/IERC1155.sol");
 alloy_core::sol!("src/IERC20.sol");
+alloy_core::sol!("src/IERC721.sol");
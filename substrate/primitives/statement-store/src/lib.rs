@@ -47,7 +47,8 @@ pub const MAX_TOPICS: usize = 4;
 
 #[cfg(feature = "std")]
 pub use store_api::{
-	Error, InvalidReason, RejectionReason, Result, StatementSource, StatementStore, SubmitResult,
+	Error, InvalidReason, RejectionReason, Result, StatementSource, StatementStore,
+	StatementStream, SubmitResult, TopicFilter,
 };
 
 #[cfg(feature = "std")]
@@ -16,7 +16,7 @@
 // limitations under the License.
 
 pub use crate::runtime_api::StatementSource;
-use crate::{Hash, Statement, Topic};
+use crate::{DecryptionKey, Hash, Statement, Topic, MAX_TOPICS};
 
 /// Statement store error.
 #[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
@@ -100,6 +100,35 @@ pub enum SubmitResult {
 	InternalError(Error),
 }
 
+/// Topic filter of a statement subscription.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum TopicFilter {
+	/// Match statements regardless of their topics.
+	Any,
+	/// Match statements which include all of the given topics.
+	MatchAll(Vec<Topic>),
+	/// Match statements which include at least one of the given topics.
+	MatchAny(Vec<Topic>),
+}
+
+impl TopicFilter {
+	/// Check if the topics of `statement` satisfy the filter.
+	pub fn matches(&self, statement: &Statement) -> bool {
+		let has_topic =
+			|topic: &Topic| (0..MAX_TOPICS).map_while(|i| statement.topic(i)).any(|t| t == *topic);
+		match self {
+			TopicFilter::Any => true,
+			TopicFilter::MatchAll(topics) => topics.iter().all(has_topic),
+			TopicFilter::MatchAny(topics) => topics.iter().any(has_topic),
+		}
+	}
+}
+
+/// Stream of statements delivered to a subscriber.
+pub type StatementStream = futures::stream::BoxStream<'static, Statement>;
+
 /// Result type for `Error`
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -160,4 +189,19 @@ pub trait StatementStore: Send + Sync {
 
 	/// Remove all statements authored by `who`.
 	fn remove_by(&self, who: [u8; 32]) -> Result<()>;
+
+	/// Subscribe to statements added to the store from now on.
+	///
+	/// Only statements matching `filter` are delivered. If `dest` is `None`, only statements
+	/// without a `DecryptionKey` field are delivered, otherwise only statements whose decryption
+	/// key is identified as `dest`.
+	///
+	/// The default implementation returns a stream which never delivers any statement.
+	fn subscribe_statements(
+		&self,
+		_filter: TopicFilter,
+		_dest: Option<DecryptionKey>,
+	) -> StatementStream {
+		Box::pin(futures::stream::empty())
+	}
 }
@@ -439,6 +439,10 @@ pub use pallet_contracts_uapi;
 #[cfg(feature = "pallet-conviction-voting")]
 pub use pallet_conviction_voting;
 
+/// Provides precompiles for `pallet-conviction-voting`.
+#[cfg(feature = "pallet-conviction-voting-precompiles")]
+pub use pallet_conviction_voting_precompiles;
+
 /// Logic as per the description of The Fellowship for core Polkadot technology.
 #[cfg(feature = "pallet-core-fellowship")]
 pub use pallet_core_fellowship;
@@ -551,6 +555,10 @@ pub use pallet_nft_fractionalization;
 #[cfg(feature = "pallet-nfts")]
 pub use pallet_nfts;
 
+/// Provides precompiles for `pallet-nfts`.
+#[cfg(feature = "pallet-nfts-precompiles")]
+pub use pallet_nfts_precompiles;
+
 /// Runtime API for the FRAME NFTs pallet.
 #[cfg(feature = "pallet-nfts-runtime-api")]
 pub use pallet_nfts_runtime_api;
@@ -571,6 +579,10 @@ pub use pallet_nomination_pools;
 #[cfg(feature = "pallet-nomination-pools-benchmarking")]
 pub use pallet_nomination_pools_benchmarking;
 
+/// Provides precompiles for `pallet-nomination-pools`.
+#[cfg(feature = "pallet-nomination-pools-precompiles")]
+pub use pallet_nomination_pools_precompiles;
+
 /// Runtime API for nomination-pools FRAME pallet.
 #[cfg(feature = "pallet-nomination-pools-runtime-api")]
 pub use pallet_nomination_pools_runtime_api;